@@ -0,0 +1,59 @@
+//! Adding a custom editor for a type, which is used wherever a value of the type is edited.
+
+use aether_spyglass::tabs::entities::editors::EditorStates;
+use aether_spyglass::tabs::entities::ReprEditors;
+use aether_spyglass::{SpyglassAppExt, SpyglassPlugin};
+use bevy::prelude::*;
+use bevy_egui::egui::Ui;
+
+fn main() {
+    App::new()
+        .add_plugins((DefaultPlugins, CustomEditorPlugin))
+        .run();
+}
+
+pub struct CustomEditorPlugin;
+
+impl Plugin for CustomEditorPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Tint>()
+            .add_plugins(SpyglassPlugin::default())
+            .register_spyglass_editor(rgb_editor)
+            .add_systems(Startup, setup);
+    }
+}
+
+#[derive(Clone, Copy, Default, Reflect)]
+struct Rgb {
+    r: f32,
+    g: f32,
+    b: f32,
+}
+
+#[derive(Component, Default, Reflect)]
+#[reflect(Component, Default)]
+pub struct Tint {
+    color: Rgb,
+    strength: f32,
+}
+
+fn rgb_editor(ui: &mut Ui, value: &mut Rgb, _: &mut World, _: &ReprEditors, _: &mut EditorStates) {
+    let mut rgb = [value.r, value.g, value.b];
+    if ui.color_edit_button_rgb(&mut rgb).changed() {
+        [value.r, value.g, value.b] = rgb;
+    }
+}
+
+fn setup(mut c: Commands) {
+    c.spawn((
+        Name::new("Tinted"),
+        Tint {
+            color: Rgb {
+                r: 1.0,
+                g: 0.5,
+                b: 0.0,
+            },
+            strength: 0.5,
+        },
+    ));
+}
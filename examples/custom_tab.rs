@@ -0,0 +1,49 @@
+//! Adding a custom tab to the inspector.
+
+use aether_spyglass::{Spyglass, SpyglassPlugin, Tab};
+use bevy::prelude::*;
+use bevy_egui::egui::Ui;
+
+fn main() {
+    App::new()
+        .add_plugins((DefaultPlugins, CustomTabPlugin))
+        .run();
+}
+
+pub struct CustomTabPlugin;
+
+impl Plugin for CustomTabPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(SpyglassPlugin::default())
+            .init_resource::<Score>();
+
+        // Tabs are placed by their `Tab::order`, after the builtin tabs by default. The list can
+        // also be edited directly to insert a tab anywhere.
+        let mut spyglass = app.world.resource_mut::<Spyglass>();
+        spyglass.add_tab(Box::new(ScoreTab));
+    }
+}
+
+#[derive(Default, Resource)]
+pub struct Score(pub u32);
+
+struct ScoreTab;
+
+impl Tab for ScoreTab {
+    fn name(&self) -> &str {
+        "Score"
+    }
+
+    fn draw(&mut self, ui: &mut Ui, world: &mut World) {
+        let mut score = world.resource_mut::<Score>();
+        ui.horizontal(|ui| {
+            ui.label(format!("score: {}", score.0));
+            if ui.button("+1").clicked() {
+                score.0 += 1;
+            }
+            if ui.button("reset").clicked() {
+                score.0 = 0;
+            }
+        });
+    }
+}
@@ -0,0 +1,39 @@
+//! An app with multiple windows. The inspector is drawn in the primary window.
+
+use aether_spyglass::SpyglassPlugin;
+use bevy::prelude::*;
+use bevy::render::camera::RenderTarget;
+use bevy::window::WindowRef;
+
+fn main() {
+    App::new()
+        .add_plugins((DefaultPlugins, MultiWindowPlugin))
+        .run();
+}
+
+pub struct MultiWindowPlugin;
+
+impl Plugin for MultiWindowPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(SpyglassPlugin::default())
+            .add_systems(Startup, setup);
+    }
+}
+
+fn setup(mut c: Commands) {
+    c.spawn(Camera3dBundle::default());
+
+    let second = c
+        .spawn(Window {
+            title: "Second window".to_string(),
+            ..default()
+        })
+        .id();
+    c.spawn(Camera3dBundle {
+        camera: Camera {
+            target: RenderTarget::Window(WindowRef::Entity(second)),
+            ..default()
+        },
+        ..default()
+    });
+}
@@ -0,0 +1,70 @@
+//! Inspecting a game running in another process. `cargo run --example remote` runs the game,
+//! which serves its world without any inspector UI. `cargo run --example remote -- client` runs
+//! the inspector; connect to `127.0.0.1:7878` in its Remote tab.
+
+use aether_spyglass::remote::SpyglassServerPlugin;
+use aether_spyglass::tabs::remote::RemoteTabPlugin;
+use aether_spyglass::SpyglassPlugin;
+use bevy::prelude::*;
+
+fn main() {
+    let mut app = App::new();
+    app.add_plugins(DefaultPlugins);
+    if std::env::args().any(|arg| arg == "client") {
+        app.add_plugins(RemoteClientPlugin);
+    } else {
+        app.add_plugins(RemoteGamePlugin {
+            address: "127.0.0.1:7878".to_string(),
+        });
+    }
+    app.run();
+}
+
+/// The game, which only adds the server.
+pub struct RemoteGamePlugin {
+    pub address: String,
+}
+
+impl Plugin for RemoteGamePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(SpyglassServerPlugin {
+            address: self.address.clone(),
+        })
+        .register_type::<Spin>()
+        .add_systems(Startup, spawn_spinner)
+        .add_systems(Update, spin);
+    }
+}
+
+/// The inspector. Types must be registered on both sides to be edited.
+pub struct RemoteClientPlugin;
+
+impl Plugin for RemoteClientPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins((SpyglassPlugin::default(), RemoteTabPlugin))
+            .register_type::<Spin>();
+    }
+}
+
+#[derive(Component, Default, Reflect)]
+#[reflect(Component)]
+pub struct Spin {
+    pub speed: f32,
+    pub angle: f32,
+}
+
+fn spawn_spinner(mut commands: Commands) {
+    commands.spawn((
+        Name::new("Spinner"),
+        Spin {
+            speed: 1.0,
+            angle: 0.0,
+        },
+    ));
+}
+
+fn spin(mut q: Query<&mut Spin>, time: Res<Time>) {
+    for mut spin in &mut q {
+        spin.angle += spin.speed * time.delta_seconds();
+    }
+}
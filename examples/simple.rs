@@ -9,7 +9,7 @@ fn main() {
         .register_type::<Vec<i32>>()
         .register_type::<HashMap<String, i32>>()
         .add_plugins(DefaultPlugins)
-        .add_plugins(SpyglassPlugin)
+        .add_plugins(SpyglassPlugin::default())
         .add_systems(Startup, setup)
         .run();
 }
@@ -0,0 +1,64 @@
+//! A huge world, to check that the inspector stays usable with many entities and archetypes.
+
+use aether_spyglass::SpyglassPlugin;
+use bevy::prelude::*;
+
+/// The number of entities spawned.
+pub const ENTITIES: usize = 10_000;
+
+fn main() {
+    App::new().add_plugins((DefaultPlugins, StressPlugin)).run();
+}
+
+pub struct StressPlugin;
+
+impl Plugin for StressPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Health>()
+            .register_type::<Team>()
+            .register_type::<Spin>()
+            .add_plugins(SpyglassPlugin::default())
+            .add_systems(Startup, spawn)
+            .add_systems(Update, spin);
+    }
+}
+
+#[derive(Component, Default, Reflect)]
+#[reflect(Component, Default)]
+struct Health(f32);
+
+#[derive(Component, Default, Reflect)]
+#[reflect(Component, Default)]
+enum Team {
+    #[default]
+    Red,
+    Blue,
+}
+
+#[derive(Component, Default, Reflect)]
+#[reflect(Component, Default)]
+struct Spin {
+    speed: f32,
+}
+
+fn spawn(mut c: Commands) {
+    for i in 0..ENTITIES {
+        let mut entity = c.spawn((
+            Name::new(format!("entity {i}")),
+            Transform::from_xyz(i as f32, 0.0, 0.0),
+            Health(100.0),
+        ));
+        // Fragment the entities over a few archetypes.
+        match i % 3 {
+            0 => entity.insert(Team::Red),
+            1 => entity.insert((Team::Blue, Spin { speed: 1.0 })),
+            _ => entity.insert(Spin { speed: 2.0 }),
+        };
+    }
+}
+
+fn spin(time: Res<Time>, mut q: Query<(&mut Transform, &Spin)>) {
+    for (mut transform, spin) in &mut q {
+        transform.rotate_y(spin.speed * time.delta_seconds());
+    }
+}
@@ -0,0 +1,133 @@
+//! The capture module. Takes screenshots of the primary window, optionally annotated with the
+//! state of the inspector, so captures shared in bug threads describe what they show.
+
+use std::path::PathBuf;
+
+use bevy::core::FrameCount;
+use bevy::prelude::*;
+use bevy::render::view::screenshot::ScreenshotManager;
+use bevy::window::PrimaryWindow;
+
+use crate::compat::egui::{self, Ui};
+use crate::compat::{Egui, EguiBackend};
+use crate::tabs::entities::{Popups, SelectedEntity};
+
+/// A function that produces an extra line of annotation for a screenshot, if it has anything to
+/// add.
+pub type Annotator = dyn Fn(&World) -> Option<String> + Send + Sync;
+
+/// The resource that configures screenshot captures.
+#[derive(Resource)]
+pub struct Capture {
+    /// The directory screenshots are saved to.
+    pub directory: PathBuf,
+    /// Whether to overlay the frame number, the selected entity and the lines of the
+    /// [`annotators`](Self::annotators) onto screenshots.
+    pub annotate: bool,
+    /// Extra annotations, added below the builtin ones.
+    pub annotators: Vec<Box<Annotator>>,
+    requested: bool,
+}
+
+impl Default for Capture {
+    fn default() -> Self {
+        Self {
+            directory: PathBuf::from("screenshots"),
+            annotate: true,
+            annotators: vec![],
+            requested: false,
+        }
+    }
+}
+
+impl Capture {
+    /// Request a screenshot of the primary window at the end of this frame, or the next one if
+    /// the inspector was already drawn this frame. Screenshots are saved as PNG, which requires
+    /// bevy's `png` feature.
+    pub fn request(&mut self) {
+        self.requested = true;
+    }
+
+    fn annotations(&self, world: &World) -> Vec<String> {
+        let mut lines = vec![];
+        if let Some(frame) = world.get_resource::<FrameCount>() {
+            lines.push(format!("frame {}", frame.0));
+        }
+        if let Some(selected) = world.get_resource::<SelectedEntity>() {
+            lines.push(format!("selected: {} ({:?})", selected.name, selected.id));
+        }
+        lines.extend(
+            self.annotators
+                .iter()
+                .filter_map(|annotator| annotator(world)),
+        );
+        lines
+    }
+}
+
+/// Draw the capture controls, if screenshots can be taken.
+pub(crate) fn capture_controls(ui: &mut Ui, world: &mut World) {
+    if !world.contains_resource::<ScreenshotManager>() {
+        return;
+    }
+    let Some(mut capture) = world.get_resource_mut::<Capture>() else {
+        return;
+    };
+
+    ui.horizontal(|ui| {
+        if ui.button("screenshot").clicked() {
+            capture.request();
+        }
+        ui.checkbox(&mut capture.annotate, "annotate");
+    });
+}
+
+/// Take a requested screenshot. The annotations are drawn the same frame, so they are part of the
+/// rendered image.
+pub(crate) fn take_requested_screenshot(world: &mut World) {
+    let Some(mut capture) = world.remove_resource::<Capture>() else {
+        return;
+    };
+
+    if std::mem::take(&mut capture.requested) {
+        if let Err(err) = screenshot(world, &capture) {
+            if let Some(mut popups) = world.get_resource_mut::<Popups>() {
+                popups.error(format!("failed to take screenshot: {err}"));
+            }
+        }
+    }
+
+    world.insert_resource(capture);
+}
+
+fn screenshot(world: &mut World, capture: &Capture) -> Result<(), String> {
+    let window = world
+        .query_filtered::<Entity, With<PrimaryWindow>>()
+        .get_single(world)
+        .map_err(|_| "there is no primary window".to_string())?;
+    let ctx = Egui::context(world, window).ok_or("the primary window has no egui context")?;
+
+    if capture.annotate {
+        let lines = capture.annotations(world);
+        egui::Area::new("spyglass_capture_annotations")
+            .anchor(egui::Align2::LEFT_BOTTOM, egui::vec2(8.0, -8.0))
+            .order(egui::Order::Tooltip)
+            .show(&ctx, |ui| {
+                egui::Frame::popup(ui.style()).show(ui, |ui| {
+                    for line in lines {
+                        ui.label(line);
+                    }
+                });
+            });
+    }
+
+    let frame = world
+        .get_resource::<FrameCount>()
+        .map_or(0, |frame| frame.0);
+    std::fs::create_dir_all(&capture.directory).map_err(|e| e.to_string())?;
+    let path = capture.directory.join(format!("spyglass-{frame}.png"));
+    world
+        .resource_mut::<ScreenshotManager>()
+        .save_screenshot_to_disk(window, path)
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,247 @@
+//! The command palette, opened with Ctrl+P (Cmd+P on macOS) over the inspector. It fuzzily
+//! searches commands by name and runs the chosen one, so everything is reachable from the
+//! keyboard. Besides the commands registered in [`SpyglassCommands`], it lists commands to open
+//! every tab and to select every named entity.
+
+use bevy::prelude::*;
+
+use crate::compat::egui::{self, Key, Modifiers};
+use crate::compat::{Egui, EguiBackend};
+use crate::tabs::entities::selection::SelectEntity;
+use crate::{Spyglass, SpyglassContext};
+
+/// The most matching commands listed in the palette.
+const MAX_RESULTS: usize = 12;
+
+/// The most named entities the palette offers to select, so huge worlds stay responsive.
+const MAX_ENTITY_COMMANDS: usize = 1000;
+
+/// A function that runs a command.
+pub type SpyglassCommandFn = dyn Fn(&mut World) + Send + Sync;
+
+/// A command that can be run from the command palette.
+pub struct SpyglassCommand {
+    /// The name the command is searched by, e.g. "pause time".
+    pub name: String,
+    /// Invoked with the world when the command is chosen.
+    pub run: Box<SpyglassCommandFn>,
+}
+
+/// The resource that contains the commands tabs and apps contribute to the command palette.
+#[derive(Default, Resource)]
+pub struct SpyglassCommands {
+    /// The commands, in the order they are listed when their scores tie.
+    pub commands: Vec<SpyglassCommand>,
+}
+
+impl SpyglassCommands {
+    /// Add a command with the given name.
+    pub fn add(
+        &mut self,
+        name: impl Into<String>,
+        run: impl Fn(&mut World) + Send + Sync + 'static,
+    ) {
+        self.commands.push(SpyglassCommand {
+            name: name.into(),
+            run: Box::new(run),
+        });
+    }
+
+    /// Run the first command named `name`. Returns whether there was one.
+    pub fn run(world: &mut World, name: &str) -> bool {
+        let index = world
+            .get_resource::<Self>()
+            .and_then(|commands| commands.commands.iter().position(|c| c.name == name));
+        if let Some(index) = index {
+            Self::run_index(world, index);
+        }
+        index.is_some()
+    }
+
+    /// Run the command at `index`. The resource is removed while the command runs.
+    fn run_index(world: &mut World, index: usize) {
+        let Some(commands) = world.remove_resource::<Self>() else {
+            return;
+        };
+        if let Some(command) = commands.commands.get(index) {
+            (command.run)(world);
+        }
+        world.insert_resource(commands);
+    }
+}
+
+/// Score how well `query` matches `candidate`, ignoring case and whitespace in the query, or
+/// `None` if the characters of the query do not all appear in order in the candidate. Higher is
+/// better: consecutive characters and characters at the start of words score more, and gaps
+/// between them score less.
+pub fn fuzzy_score(query: &str, candidate: &str) -> Option<i32> {
+    let candidate = candidate.to_lowercase().chars().collect::<Vec<_>>();
+    let mut score = 0;
+    let mut next = 0;
+    let mut previous = None;
+    for c in query.to_lowercase().chars().filter(|c| !c.is_whitespace()) {
+        let found = (next..candidate.len()).find(|&i| candidate[i] == c)?;
+        score += 1;
+        if previous.is_some_and(|previous| previous + 1 == found) {
+            score += 5;
+        }
+        if found == 0 || !candidate[found - 1].is_alphanumeric() {
+            score += 3;
+        }
+        score -= (found - next) as i32;
+        previous = Some(found);
+        next = found + 1;
+    }
+    Some(score)
+}
+
+/// The state of the command palette.
+#[derive(Default, Resource)]
+struct CommandPalette {
+    open: bool,
+    query: String,
+    highlighted: usize,
+}
+
+/// What a listed command does.
+#[derive(Clone, Copy)]
+enum Action {
+    Registered(usize),
+    OpenTab(usize),
+    Select(Entity),
+}
+
+/// List every command the palette offers.
+fn entries(world: &mut World) -> Vec<(String, Action)> {
+    let mut entries = vec![];
+    if let Some(commands) = world.get_resource::<SpyglassCommands>() {
+        entries.extend(
+            commands
+                .commands
+                .iter()
+                .enumerate()
+                .map(|(i, command)| (command.name.clone(), Action::Registered(i))),
+        );
+    }
+    if let Some(spyglass) = world.get_resource::<Spyglass>() {
+        entries.extend(
+            spyglass
+                .tabs
+                .iter()
+                .enumerate()
+                .map(|(i, tab)| (format!("open {} tab", tab.name()), Action::OpenTab(i))),
+        );
+    }
+    let mut named = world.query::<(Entity, &Name)>();
+    entries.extend(
+        named
+            .iter(world)
+            .take(MAX_ENTITY_COMMANDS)
+            .map(|(entity, name)| (format!("select entity {name}"), Action::Select(entity))),
+    );
+    entries
+}
+
+fn run(world: &mut World, action: Action) {
+    match action {
+        Action::Registered(i) => SpyglassCommands::run_index(world, i),
+        Action::OpenTab(i) => world.resource_mut::<Spyglass>().selected = Some(i),
+        Action::Select(entity) => {
+            world.send_event(SelectEntity(Some(entity)));
+            world
+                .resource_mut::<Spyglass>()
+                .select_tab_by_name("Entities");
+        }
+    }
+}
+
+pub(crate) fn command_palette(world: &mut World) {
+    let Some(window) = SpyglassContext::window(world) else {
+        return;
+    };
+    let Some(ctx) = Egui::context(world, window) else {
+        return;
+    };
+    let mut palette = world
+        .remove_resource::<CommandPalette>()
+        .unwrap_or_default();
+    if ctx.input_mut(|input| input.consume_key(Modifiers::COMMAND, Key::P)) {
+        palette = CommandPalette {
+            open: !palette.open,
+            ..default()
+        };
+    }
+    if palette.open {
+        if let Some(action) = draw_palette(&ctx, world, &mut palette) {
+            palette.open = false;
+            run(world, action);
+        }
+    }
+    world.insert_resource(palette);
+}
+
+/// Draw the palette, and return the action to run if one was chosen.
+fn draw_palette(
+    ctx: &egui::Context,
+    world: &mut World,
+    palette: &mut CommandPalette,
+) -> Option<Action> {
+    let mut matches = entries(world)
+        .into_iter()
+        .filter_map(|(name, action)| Some((fuzzy_score(&palette.query, &name)?, name, action)))
+        .collect::<Vec<_>>();
+    matches.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.len().cmp(&b.1.len())));
+    matches.truncate(MAX_RESULTS);
+
+    let (up, down, enter, escape) = ctx.input_mut(|input| {
+        (
+            input.consume_key(Modifiers::NONE, Key::ArrowUp),
+            input.consume_key(Modifiers::NONE, Key::ArrowDown),
+            input.key_pressed(Key::Enter),
+            input.consume_key(Modifiers::NONE, Key::Escape),
+        )
+    });
+    if escape {
+        palette.open = false;
+        return None;
+    }
+    if up {
+        palette.highlighted = palette.highlighted.saturating_sub(1);
+    }
+    if down {
+        palette.highlighted += 1;
+    }
+    palette.highlighted = palette.highlighted.min(matches.len().saturating_sub(1));
+
+    let mut chosen = enter
+        .then(|| matches.get(palette.highlighted))
+        .flatten()
+        .map(|(_, _, action)| *action);
+    egui::Window::new("command palette")
+        .title_bar(false)
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_TOP, egui::vec2(0.0, 40.0))
+        .show(ctx, |ui| {
+            let query = egui::TextEdit::singleline(&mut palette.query)
+                .hint_text("Type a command")
+                .desired_width(320.0);
+            let response = ui.add(query);
+            response.request_focus();
+            if response.changed() {
+                palette.highlighted = 0;
+            }
+            if matches.is_empty() {
+                ui.weak("no matching commands");
+            }
+            for (i, (_, name, action)) in matches.iter().enumerate() {
+                if ui
+                    .selectable_label(i == palette.highlighted, name)
+                    .clicked()
+                {
+                    chosen = Some(*action);
+                }
+            }
+        });
+    chosen
+}
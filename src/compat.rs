@@ -0,0 +1,54 @@
+//! Isolates the few bevy_egui APIs the inspector uses behind the [`EguiBackend`] trait, and
+//! re-exports the egui release that goes with them, so that supporting another bevy_egui release
+//! only requires another implementation and re-export, selected with a `bevy_egui_*` feature,
+//! instead of changes all over the crate.
+
+use bevy::prelude::*;
+
+/// The egui crate of the selected bevy_egui release. The rest of the crate imports egui from here.
+#[cfg(feature = "bevy_egui_0_23")]
+pub(crate) use bevy_egui::egui;
+
+#[cfg(not(feature = "bevy_egui_0_23"))]
+compile_error!("aether_spyglass requires one of its `bevy_egui_*` features to be enabled");
+
+/// The bevy_egui APIs the inspector depends on.
+pub(crate) trait EguiBackend {
+    /// Add the bevy_egui plugin, unless the app already added it for its own UI.
+    fn add_plugin(app: &mut App);
+
+    /// Get the egui context of a window. Contexts are reference counted, so the returned handle
+    /// can be drawn into while the world is borrowed mutably.
+    fn context(world: &mut World, window: Entity) -> Option<egui::Context>;
+
+    /// Register an image as an egui texture, so that it can be drawn with `Ui::image`. The image
+    /// is registered with a weak handle, so it is not kept loaded.
+    fn image_texture(world: &mut World, image: AssetId<Image>) -> Option<egui::TextureId>;
+}
+
+/// The backend for bevy_egui 0.23.
+#[cfg(feature = "bevy_egui_0_23")]
+pub(crate) struct BevyEgui023;
+
+#[cfg(feature = "bevy_egui_0_23")]
+impl EguiBackend for BevyEgui023 {
+    fn add_plugin(app: &mut App) {
+        if !app.is_plugin_added::<bevy_egui::EguiPlugin>() {
+            app.add_plugins(bevy_egui::EguiPlugin);
+        }
+    }
+
+    fn context(world: &mut World, window: Entity) -> Option<egui::Context> {
+        let mut ctx = world.get_mut::<bevy_egui::EguiContext>(window)?;
+        Some(ctx.get_mut().clone())
+    }
+
+    fn image_texture(world: &mut World, image: AssetId<Image>) -> Option<egui::TextureId> {
+        let mut textures = world.get_resource_mut::<bevy_egui::EguiUserTextures>()?;
+        Some(textures.add_image(Handle::Weak(image)))
+    }
+}
+
+/// The backend selected by features.
+#[cfg(feature = "bevy_egui_0_23")]
+pub(crate) type Egui = BevyEgui023;
@@ -0,0 +1,66 @@
+//! Dumps of the logical state of the inspector. These are serializable, so they can be persisted,
+//! and comparable, so integration tests can assert on inspector behavior after simulated frames.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::tabs::entities::editors::EditorStates;
+use crate::tabs::entities::{EntitySearch, SelectedEntity};
+use crate::Spyglass;
+
+/// A dump of the logical state of the inspector, captured with [`SpyglassDump::capture`].
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct SpyglassDump {
+    /// The name of the selected tab, if any.
+    pub tab: Option<String>,
+    /// The entity selected in the entities tab, if any.
+    pub selected_entity: Option<Entity>,
+    /// The search text of the entities tab.
+    pub entity_search: String,
+    /// The paths of values whose editors were expanded in the last drawn frame,
+    /// e.g. `Transform.translation`.
+    pub expanded: Vec<String>,
+    /// The paths and text of uncommitted text edits in the last drawn frame.
+    pub pending_edits: Vec<(String, String)>,
+    /// The type names of the components of the selected entity whose edits were held back,
+    /// because a game system changed them too, sorted.
+    pub conflicts: Vec<String>,
+}
+
+impl SpyglassDump {
+    /// Capture the current state of the inspector. Should be called outside of the
+    /// [`SpyglassWindow`](crate::SpyglassWindow) system set, as the inspector's resources are
+    /// temporarily removed from the world while it is drawn.
+    pub fn capture(world: &World) -> Self {
+        let tab = world.get_resource::<Spyglass>().and_then(|spyglass| {
+            let tab = spyglass.tabs.get(spyglass.selected?)?;
+            Some(tab.name().to_string())
+        });
+        let states = world.get_resource::<EditorStates>();
+
+        Self {
+            tab,
+            selected_entity: world
+                .get_resource::<SelectedEntity>()
+                .map(|selected| selected.id),
+            entity_search: world
+                .get_resource::<EntitySearch>()
+                .map(|search| search.0.clone())
+                .unwrap_or_default(),
+            expanded: states
+                .map(|states| states.expanded().to_vec())
+                .unwrap_or_default(),
+            pending_edits: states
+                .map(|states| states.pending().to_vec())
+                .unwrap_or_default(),
+            conflicts: world
+                .get_resource::<SelectedEntity>()
+                .map(|selected| {
+                    let mut conflicts = selected.conflicts().map(String::from).collect::<Vec<_>>();
+                    conflicts.sort_unstable();
+                    conflicts
+                })
+                .unwrap_or_default(),
+        }
+    }
+}
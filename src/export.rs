@@ -0,0 +1,104 @@
+//! Exports that run on the [`AsyncComputeTaskPool`], so that serializing and writing large dumps
+//! does not block the frame. Running exports are listed with a spinner at the top of the
+//! inspector, and a [`Popup`](crate::tabs::entities::Popup) reports where they were written to
+//! once they finish.
+//!
+//! Without bevy's `multi-threaded` feature, tasks run to completion as soon as they are spawned,
+//! so exports still block the frame they are started in.
+
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use bevy::prelude::*;
+use bevy::tasks::AsyncComputeTaskPool;
+
+use crate::compat::egui::Ui;
+use crate::tabs::entities::Popups;
+
+type ExportResult = Arc<Mutex<Option<Result<PathBuf, String>>>>;
+
+struct Export {
+    label: String,
+    // The task is detached and reports back through here, as the type of task depends on whether
+    // bevy's task pools are multi-threaded.
+    result: ExportResult,
+}
+
+/// The resource that tracks running exports.
+#[derive(Default, Resource)]
+pub struct Exports {
+    exports: Vec<Export>,
+}
+
+impl Exports {
+    /// Run an export on the [`AsyncComputeTaskPool`]. The export should only capture owned data,
+    /// such as clones of the values to export, a clone of the [`AppTypeRegistry`], and the
+    /// format from [`SpyglassFormats::current_shared`](crate::formats::SpyglassFormats::current_shared).
+    pub fn spawn(
+        &mut self,
+        label: impl Into<String>,
+        export: impl FnOnce() -> Result<PathBuf, String> + Send + 'static,
+    ) {
+        let result = ExportResult::default();
+        let shared_result = result.clone();
+        AsyncComputeTaskPool::get()
+            .spawn(async move {
+                *shared_result.lock().unwrap() = Some(export());
+            })
+            .detach();
+        self.exports.push(Export {
+            label: label.into(),
+            result,
+        });
+    }
+
+    /// Iterate over the labels of the running exports.
+    pub fn running(&self) -> impl Iterator<Item = &str> {
+        self.exports.iter().map(|export| export.label.as_str())
+    }
+}
+
+/// Write the bytes of an export to a file, creating its parent directories.
+pub fn write_export(path: &Path, bytes: &[u8]) -> Result<(), String> {
+    if let Some(parent) = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    std::fs::write(path, bytes).map_err(|e| e.to_string())
+}
+
+pub(crate) fn finish_exports(mut exports: ResMut<Exports>, mut popups: ResMut<Popups>) {
+    let mut finished = vec![];
+    exports
+        .exports
+        .retain(|export| match export.result.lock().unwrap().take() {
+            Some(result) => {
+                finished.push((export.label.clone(), result));
+                false
+            }
+            None => true,
+        });
+
+    for (label, result) in finished {
+        match result {
+            Ok(path) => popups.info(format!("exported {label} to `{}`", path.display())),
+            Err(err) => popups.error(format!("failed to export {label}: {err}")),
+        }
+    }
+}
+
+/// Draw the running exports. Serializers do not report how far along they are, so exports are
+/// shown with a spinner rather than a progress bar.
+pub(crate) fn export_status(ui: &mut Ui, world: &World) {
+    let Some(exports) = world.get_resource::<Exports>() else {
+        return;
+    };
+    for label in exports.running() {
+        ui.horizontal(|ui| {
+            ui.spinner();
+            ui.label(format!("exporting {label}"));
+        });
+    }
+}
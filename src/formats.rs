@@ -0,0 +1,309 @@
+//! Serialization formats used by everything in the inspector that exports or imports data, such
+//! as snapshots. Formats are abstracted over the [`SpyglassFormat`] trait, so that teams can pick
+//! the format their other tools consume. RON and JSON are built in. Tabular data, such as
+//! query results, can be exported to CSV with [`entities_to_csv`].
+
+use std::fmt::Display;
+use std::sync::Arc;
+
+use bevy::prelude::*;
+use bevy::reflect::serde::{ReflectSerializer, UntypedReflectDeserializer};
+use bevy::reflect::{GetPath, TypeRegistry, TypeRegistryArc};
+use bevy::scene::serde::{SceneDeserializer, SceneSerializer};
+use bevy::scene::DynamicScene;
+use bevy::utils::get_short_name;
+use serde::de::DeserializeSeed;
+
+use crate::compat::egui::{self, Ui};
+
+/// An error produced while serializing or deserializing with a [`SpyglassFormat`].
+#[derive(Debug)]
+pub struct FormatError(pub String);
+
+impl FormatError {
+    /// Create a format error from anything that can be displayed.
+    pub fn new(err: impl Display) -> Self {
+        Self(err.to_string())
+    }
+}
+
+impl Display for FormatError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for FormatError {}
+
+/// A serialization format for exported data. Implemented over type-erased serde, so any format
+/// with a serde implementation (e.g. MessagePack) can be added to [`SpyglassFormats`].
+pub trait SpyglassFormat: Send + Sync {
+    /// The name of the format, as displayed in the inspector.
+    fn name(&self) -> &str;
+
+    /// The file extension used for files of this format, without the leading dot.
+    fn extension(&self) -> &str;
+
+    /// Serialize a value to bytes.
+    fn serialize(&self, value: &dyn erased_serde::Serialize) -> Result<Vec<u8>, FormatError>;
+
+    /// Create a deserializer for the given bytes and pass it to `f`.
+    fn deserialize(
+        &self,
+        bytes: &[u8],
+        f: &mut dyn FnMut(&mut dyn erased_serde::Deserializer) -> Result<(), erased_serde::Error>,
+    ) -> Result<(), FormatError>;
+}
+
+/// The [RON](https://github.com/ron-rs/ron) format. This is the format bevy uses for scenes.
+pub struct RonFormat;
+
+impl SpyglassFormat for RonFormat {
+    fn name(&self) -> &str {
+        "RON"
+    }
+
+    fn extension(&self) -> &str {
+        "ron"
+    }
+
+    fn serialize(&self, value: &dyn erased_serde::Serialize) -> Result<Vec<u8>, FormatError> {
+        ron::ser::to_string_pretty(value, ron::ser::PrettyConfig::default())
+            .map(String::into_bytes)
+            .map_err(FormatError::new)
+    }
+
+    fn deserialize(
+        &self,
+        bytes: &[u8],
+        f: &mut dyn FnMut(&mut dyn erased_serde::Deserializer) -> Result<(), erased_serde::Error>,
+    ) -> Result<(), FormatError> {
+        let mut de = ron::Deserializer::from_bytes(bytes).map_err(FormatError::new)?;
+        f(&mut <dyn erased_serde::Deserializer>::erase(&mut de)).map_err(FormatError::new)?;
+        de.end().map_err(FormatError::new)
+    }
+}
+
+/// The JSON format.
+pub struct JsonFormat;
+
+impl SpyglassFormat for JsonFormat {
+    fn name(&self) -> &str {
+        "JSON"
+    }
+
+    fn extension(&self) -> &str {
+        "json"
+    }
+
+    fn serialize(&self, value: &dyn erased_serde::Serialize) -> Result<Vec<u8>, FormatError> {
+        serde_json::to_vec_pretty(value).map_err(FormatError::new)
+    }
+
+    fn deserialize(
+        &self,
+        bytes: &[u8],
+        f: &mut dyn FnMut(&mut dyn erased_serde::Deserializer) -> Result<(), erased_serde::Error>,
+    ) -> Result<(), FormatError> {
+        let mut de = serde_json::Deserializer::from_slice(bytes);
+        f(&mut <dyn erased_serde::Deserializer>::erase(&mut de)).map_err(FormatError::new)?;
+        de.end().map_err(FormatError::new)
+    }
+}
+
+/// The resource that contains the available [`SpyglassFormat`]s, and which one is used for
+/// exports. Formats are shared so that exports can serialize on worker threads.
+#[derive(Resource)]
+pub struct SpyglassFormats {
+    /// The available formats.
+    pub formats: Vec<Arc<dyn SpyglassFormat>>,
+    /// The index of the format used for exports.
+    pub selected: usize,
+}
+
+impl Default for SpyglassFormats {
+    fn default() -> Self {
+        Self {
+            formats: vec![Arc::new(RonFormat), Arc::new(JsonFormat)],
+            selected: 0,
+        }
+    }
+}
+
+impl SpyglassFormats {
+    /// Get the format currently selected for exports.
+    pub fn current(&self) -> &dyn SpyglassFormat {
+        self.formats
+            .get(self.selected)
+            .or(self.formats.first())
+            .map(Arc::as_ref)
+            .unwrap_or(&RonFormat)
+    }
+
+    /// Get a shared handle to the format currently selected for exports, which can be moved to
+    /// another thread.
+    pub fn current_shared(&self) -> Arc<dyn SpyglassFormat> {
+        self.formats
+            .get(self.selected)
+            .or(self.formats.first())
+            .cloned()
+            .unwrap_or_else(|| Arc::new(RonFormat))
+    }
+
+    /// Find the format that uses a given file extension, for imports.
+    pub fn for_extension(&self, extension: &str) -> Option<&dyn SpyglassFormat> {
+        self.formats
+            .iter()
+            .find(|format| format.extension().eq_ignore_ascii_case(extension))
+            .map(Arc::as_ref)
+    }
+
+    /// Draw a combo box that selects the format used for exports.
+    pub fn selector(&mut self, ui: &mut Ui) {
+        let current = self.current().name().to_string();
+        egui::ComboBox::from_id_source("spyglass_format")
+            .selected_text(current)
+            .show_ui(ui, |ui| {
+                for (i, format) in self.formats.iter().enumerate() {
+                    ui.selectable_value(&mut self.selected, i, format.name());
+                }
+            });
+    }
+}
+
+/// Serialize a reflected value with the given format.
+pub fn serialize_reflect(
+    format: &dyn SpyglassFormat,
+    value: &dyn Reflect,
+    registry: &TypeRegistry,
+) -> Result<Vec<u8>, FormatError> {
+    format.serialize(&ReflectSerializer::new(value, registry))
+}
+
+/// Deserialize a reflected value with the given format.
+pub fn deserialize_reflect(
+    format: &dyn SpyglassFormat,
+    bytes: &[u8],
+    registry: &TypeRegistry,
+) -> Result<Box<dyn Reflect>, FormatError> {
+    let mut value = None;
+    format.deserialize(bytes, &mut |de| {
+        value = Some(UntypedReflectDeserializer::new(registry).deserialize(de)?);
+        Ok(())
+    })?;
+    value.ok_or_else(|| FormatError::new("no value was deserialized"))
+}
+
+/// Serialize a scene with the given format.
+pub fn serialize_scene(
+    format: &dyn SpyglassFormat,
+    scene: &DynamicScene,
+    registry: &TypeRegistryArc,
+) -> Result<Vec<u8>, FormatError> {
+    format.serialize(&SceneSerializer::new(scene, registry))
+}
+
+/// Deserialize a scene with the given format.
+pub fn deserialize_scene(
+    format: &dyn SpyglassFormat,
+    bytes: &[u8],
+    registry: &TypeRegistry,
+) -> Result<DynamicScene, FormatError> {
+    let mut scene = None;
+    format.deserialize(bytes, &mut |de| {
+        scene = Some(
+            SceneDeserializer {
+                type_registry: registry,
+            }
+            .deserialize(de)?,
+        );
+        Ok(())
+    })?;
+    scene.ok_or_else(|| FormatError::new("no scene was deserialized"))
+}
+
+/// A column of a CSV export: a reflected field of a component.
+pub struct CsvColumn {
+    /// The type path of the component.
+    pub component: String,
+    /// The reflect path of the field within the component, e.g. `translation.y`. An empty path
+    /// exports the whole component.
+    pub path: String,
+}
+
+/// Export the given columns of the given entities to CSV, with one row per entity. The first two
+/// columns are the entity id and its [`Name`], if any. Cells of entities that do not have the
+/// component of a column, or whose component is not reflected, are left empty.
+pub fn entities_to_csv(world: &World, entities: &[Entity], columns: &[CsvColumn]) -> String {
+    let registry = world.resource::<AppTypeRegistry>().read();
+    let reflect = columns
+        .iter()
+        .map(|column| {
+            registry
+                .get_with_type_path(&column.component)?
+                .data::<ReflectComponent>()
+        })
+        .collect::<Vec<_>>();
+
+    let mut csv = String::new();
+    let header = ["entity".to_string(), "name".to_string()]
+        .into_iter()
+        .chain(columns.iter().map(|column| {
+            let component = get_short_name(&column.component);
+            match column.path.is_empty() {
+                true => component,
+                false => format!("{component}.{}", column.path.trim_start_matches('.')),
+            }
+        }));
+    push_csv_row(&mut csv, header);
+
+    for &entity in entities {
+        let Some(entity_ref) = world.get_entity(entity) else {
+            continue;
+        };
+
+        let name = entity_ref
+            .get::<Name>()
+            .map(|name| name.to_string())
+            .unwrap_or_default();
+        let cells = columns.iter().zip(reflect.iter()).map(|(column, reflect)| {
+            let Some(component) = reflect.and_then(|reflect| reflect.reflect(entity_ref)) else {
+                return String::new();
+            };
+            let value = match column.path.is_empty() {
+                true => Some(component),
+                false => component.reflect_path(column.path.as_str()).ok(),
+            };
+            value.map(csv_value).unwrap_or_default()
+        });
+        push_csv_row(
+            &mut csv,
+            [format!("{entity:?}"), name].into_iter().chain(cells),
+        );
+    }
+
+    csv
+}
+
+fn csv_value(value: &dyn Reflect) -> String {
+    match value.downcast_ref::<String>() {
+        Some(string) => string.clone(),
+        None => format!("{value:?}"),
+    }
+}
+
+fn push_csv_row(csv: &mut String, cells: impl Iterator<Item = String>) {
+    for (i, cell) in cells.enumerate() {
+        if i > 0 {
+            csv.push(',');
+        }
+        if cell.contains([',', '"', '\n', '\r']) {
+            csv.push('"');
+            csv.push_str(&cell.replace('"', "\"\""));
+            csv.push('"');
+        } else {
+            csv.push_str(&cell);
+        }
+    }
+    csv.push('\n');
+}
@@ -1,25 +1,190 @@
 #![forbid(missing_docs, rustdoc::broken_intra_doc_links)]
 #![doc = include_str!("../README.md")]
 
+pub mod capture;
+pub mod commands;
+mod compat;
+pub mod dump;
+pub mod export;
+pub mod formats;
+pub mod persistence;
+#[cfg(feature = "profiler")]
+pub mod profiler;
+#[cfg(feature = "inventory")]
+pub mod registration;
+pub mod remote;
+pub mod script;
 pub mod tabs;
+pub mod telemetry;
+pub mod widgets;
 
+use bevy::core::FrameCount;
 use bevy::prelude::*;
+use bevy::time::Virtual;
+use bevy::utils::{HashMap, HashSet};
 use bevy::window::PrimaryWindow;
-use bevy_egui::egui::{ScrollArea, Ui};
-use bevy_egui::{egui, EguiContext, EguiPlugin};
-use tabs::entities::EntitiesTabPlugin;
+use capture::Capture;
+use commands::SpyglassCommands;
+use compat::egui::{self, ScrollArea, Ui};
+use compat::{Egui, EguiBackend};
+use export::Exports;
+use formats::SpyglassFormats;
+use script::SpyglassScript;
+use tabs::entities::editors::EditorStates;
+use tabs::entities::groups::EntityGroupBy;
+use tabs::entities::{
+    ApplyMode, ComponentRefresh, EntitiesTabPlugin, EntitySort, NotificationStyle, ReprEditors,
+};
+use tabs::settings::SettingsTabPlugin;
+use tabs::world_stats::WorldStatsTabPlugin;
+use widgets::{AssetFolder, ImagePreview};
 
 /// The main plugin used to add the spyglass inspector to an app.
-/// Automatically adds the [`EguiPlugin`], creates the [`Spyglass`] resource,
-/// the [`SpyglassWindow`] system set, and inserts the [`EntitiesTabPlugin`].
-pub struct SpyglassPlugin;
+/// Adds the [`EguiPlugin`](bevy_egui::EguiPlugin) unless the app already added it, creates the
+/// [`Spyglass`] resource, the [`SpyglassWindow`] system set, and inserts the
+/// [`EntitiesTabPlugin`], [`WorldStatsTabPlugin`] and [`SettingsTabPlugin`]. With the `inventory`
+/// feature, it also inserts the editors exported by other crates with `spyglass_editors!`.
+///
+/// Apps that use egui themselves should add the `EguiPlugin` before this plugin, as adding it
+/// twice panics. Apps that add it later should use [`SpyglassPlugin::without_egui_plugin`].
+///
+/// With the `disabled` feature, this plugin and every other plugin of the crate add nothing to the
+/// app: no egui plugin, systems or resources. Apps can keep them in their plugin list, and strip
+/// the inspector from shipping builds by enabling the feature there.
+pub struct SpyglassPlugin {
+    /// Whether to add the `EguiPlugin` if the app has not added it yet. Defaults to `true`.
+    pub add_egui_plugin: bool,
+}
+
+impl Default for SpyglassPlugin {
+    fn default() -> Self {
+        Self {
+            add_egui_plugin: true,
+        }
+    }
+}
+
+impl SpyglassPlugin {
+    /// A plugin that never adds the `EguiPlugin`, for apps that add it themselves after this
+    /// plugin.
+    pub fn without_egui_plugin() -> Self {
+        Self {
+            add_egui_plugin: false,
+        }
+    }
+}
 
 impl Plugin for SpyglassPlugin {
     fn build(&self, app: &mut bevy::prelude::App) {
-        app.add_plugins(EguiPlugin)
-            .init_resource::<Spyglass>()
+        if cfg!(feature = "disabled") {
+            return;
+        }
+
+        if self.add_egui_plugin {
+            Egui::add_plugin(app);
+        }
+        app.init_resource::<Spyglass>()
+            .init_resource::<SpyglassFormats>()
+            .init_resource::<AssetFolder>()
+            .init_resource::<TimeStep>()
+            .init_resource::<Capture>()
+            .init_resource::<SpyglassScript>()
+            .init_resource::<SpyglassContext>()
+            .init_resource::<SpyglassSettings>()
+            .init_resource::<Exports>()
+            .init_resource::<ImagePreview>()
+            .insert_resource(default_commands())
+            .add_systems(First, advance_tab_refresh)
+            .add_systems(PreUpdate, finish_time_step)
+            .add_systems(Update, spawn_dedicated_window.before(SpyglassWindow))
+            .add_event::<FocusTab>()
+            .add_systems(
+                Update,
+                (focus_requested_tabs, select_tab_by_hotkey).before(SpyglassWindow),
+            )
+            .add_systems(
+                Update,
+                collect_selected_tab
+                    .after(focus_requested_tabs)
+                    .after(select_tab_by_hotkey)
+                    .after(script::run_script)
+                    .before(SpyglassWindow),
+            )
             .add_systems(Update, spyglass_window.in_set(SpyglassWindow))
-            .add_plugins(EntitiesTabPlugin);
+            .add_systems(
+                Update,
+                script::run_script
+                    .after(tabs::entities::collect_entity_state)
+                    .before(SpyglassWindow),
+            )
+            .add_systems(
+                Update,
+                capture::take_requested_screenshot.after(SpyglassWindow),
+            )
+            .add_systems(Update, export::finish_exports.after(SpyglassWindow))
+            .add_systems(Update, commands::command_palette.after(SpyglassWindow))
+            .add_systems(Update, widgets::image_preview_window.after(SpyglassWindow))
+            .add_plugins((EntitiesTabPlugin, WorldStatsTabPlugin, SettingsTabPlugin));
+
+        #[cfg(feature = "inventory")]
+        registration::register_exported_editors(&mut app.world.resource_mut::<ReprEditors>());
+    }
+}
+
+/// The commands of the command palette that are not specific to a tab.
+fn default_commands() -> SpyglassCommands {
+    let mut commands = SpyglassCommands::default();
+    commands.add("pause time", |world| {
+        if let Some(mut time) = world.get_resource_mut::<Time<Virtual>>() {
+            time.pause();
+        }
+    });
+    commands.add("resume time", |world| {
+        if let Some(mut time) = world.get_resource_mut::<Time<Virtual>>() {
+            time.unpause();
+        }
+    });
+    commands.add("take screenshot", |world| {
+        world.resource_mut::<Capture>().request();
+    });
+    commands.add("toggle internal components", |world| {
+        let mut settings = world.resource_mut::<SpyglassSettings>();
+        settings.hide_internal_components = !settings.hide_internal_components;
+    });
+    commands.add("toggle read-only", |world| {
+        let mut settings = world.resource_mut::<SpyglassSettings>();
+        settings.read_only = !settings.read_only;
+    });
+    commands
+}
+
+/// Extension methods on [`App`] for registering spyglass extensions.
+pub trait SpyglassAppExt {
+    /// Register an editor for the type `T`. See [`ReprEditors::insert_typed`].
+    fn register_spyglass_editor<T: FromReflect>(
+        &mut self,
+        editor: impl Fn(&mut Ui, &mut T, &mut World, &ReprEditors, &mut EditorStates)
+            + Send
+            + Sync
+            + 'static,
+    ) -> &mut Self;
+}
+
+impl SpyglassAppExt for App {
+    fn register_spyglass_editor<T: FromReflect>(
+        &mut self,
+        editor: impl Fn(&mut Ui, &mut T, &mut World, &ReprEditors, &mut EditorStates)
+            + Send
+            + Sync
+            + 'static,
+    ) -> &mut Self {
+        if cfg!(feature = "disabled") {
+            return self;
+        }
+        self.world
+            .get_resource_or_insert_with(ReprEditors::default)
+            .insert_typed(editor);
+        self
     }
 }
 
@@ -28,19 +193,345 @@ pub trait Tab: Send + Sync {
     /// Returns the name of the tab, which will be displayed in the inspector.
     fn name(&self) -> &str;
 
-    /// Draw the tab.
+    /// Collect the data the tab shows from the world. Called before the inspector is drawn, while
+    /// the tab is selected, as often as its [`Spyglass::refresh`] rate allows, so tabs that draw
+    /// from collected data hold the world for less time in [`draw`](Self::draw), and can be
+    /// throttled. Does nothing by default.
+    fn collect(&mut self, world: &mut World) {
+        let _ = world;
+    }
+
+    /// Draw the tab. Tabs that edit the world may still access it here, but should read what they
+    /// only show in [`collect`](Self::collect).
     fn draw(&mut self, ui: &mut Ui, world: &mut World);
+
+    /// Where [`Spyglass::add_tab`] places the tab: tabs are listed in ascending order, and tabs
+    /// with the same order in the order they were added. The builtin tabs use 0, so a negative
+    /// order places a tab before them, and a positive one after them.
+    fn order(&self) -> i32 {
+        0
+    }
+
+    /// A short text shown next to the name of the tab, usually a count of things that need
+    /// attention, such as errors. Called every frame, so it should be cheap.
+    fn badge(&self, world: &World) -> Option<String> {
+        let _ = world;
+        None
+    }
+
+    /// The salt of the [`egui::Id`] scope the tab is drawn in, so the ids of its widgets, and
+    /// with them their state and the [`EditorStates`] keyed by them, stay the same when tabs are
+    /// reordered, and differ from those of other tabs. Derived from the [`name`](Self::name) by
+    /// default.
+    fn id_salt(&self) -> egui::Id {
+        egui::Id::new(("spyglass_tab", self.name()))
+    }
 }
 
 /// The resource for managing the spyglass inspector.
 #[derive(Default, Resource)]
 pub struct Spyglass {
     /// Contains the ordered list of tabs to display.
-    /// May be modified at any time to alter what is displayed.
+    /// May be modified at any time to alter what is displayed. Tabs are usually added with
+    /// [`add_tab`](Self::add_tab), which respects their [`order`](Tab::order).
     pub tabs: Vec<Box<dyn Tab>>,
     /// Contains the index of what tab is selected, if any.
     /// May be altered at any time, for example as an implementation of hotkeys.
     pub selected: Option<usize>,
+    /// The initial position and size of the inspector window. Only applies until the window has
+    /// been shown, as egui remembers where it was moved to afterwards.
+    pub default_rect: Option<egui::Rect>,
+    /// How often tabs collect the data they show from the world, by tab name. Tabs that are not
+    /// listed refresh every frame. Throttling the heavy tabs keeps the inspector from inflating
+    /// frame times while profiling.
+    pub refresh: HashMap<String, ComponentRefresh>,
+    /// The tabs whose refresh was requested, which refresh in the next frame.
+    refresh_requested: HashSet<String>,
+    /// The tabs that refresh this frame because it was requested.
+    refresh_forced: HashSet<String>,
+    /// The current frame, for [`ComponentRefresh::EveryNFrames`].
+    frame: u32,
+    /// The name of the tab that collected its data last, so a newly selected tab collects its
+    /// data right away.
+    collected: Option<String>,
+}
+
+impl Spyglass {
+    /// Select the tab named `name`. Returns whether there is one; if not, the selection is kept.
+    pub fn select_tab_by_name(&mut self, name: &str) -> bool {
+        let index = self.tabs.iter().position(|tab| tab.name() == name);
+        if index.is_some() {
+            self.selected = index;
+        }
+        index.is_some()
+    }
+
+    /// Add a tab after the tabs whose [`order`](Tab::order) is lower or equal, so the position
+    /// of tabs does not depend on the order plugins are added in. The selected tab stays
+    /// selected.
+    pub fn add_tab(&mut self, tab: Box<dyn Tab>) {
+        let index = self
+            .tabs
+            .iter()
+            .position(|other| other.order() > tab.order())
+            .unwrap_or(self.tabs.len());
+        self.tabs.insert(index, tab);
+        if let Some(selected) = self
+            .selected
+            .as_mut()
+            .filter(|selected| **selected >= index)
+        {
+            *selected += 1;
+        }
+    }
+
+    /// Request that the tab named `tab` collects its data in the next frame, even if its
+    /// [`refresh`](Self::refresh) rate would skip it.
+    pub fn request_refresh(&mut self, tab: &str) {
+        self.refresh_requested.insert(tab.to_string());
+    }
+
+    /// Whether the tab named `tab` collects its data this frame. Checked by the data collection
+    /// systems of tabs, so they cost nothing in the frames their tab skips.
+    pub fn refreshes(&self, tab: &str) -> bool {
+        if self.refresh_forced.contains(tab) {
+            return true;
+        }
+        match self.refresh.get(tab).copied().unwrap_or_default() {
+            ComponentRefresh::EveryFrame => true,
+            ComponentRefresh::EveryNFrames(n) => self.frame.is_multiple_of(n.max(1)),
+            ComponentRefresh::Manual => false,
+        }
+    }
+
+    /// Whether the tab named `tab` collects its data this frame, or `true` if there is no
+    /// [`Spyglass`] resource.
+    pub(crate) fn tab_refreshes(world: &World, tab: &str) -> bool {
+        world
+            .get_resource::<Self>()
+            .is_none_or(|spyglass| spyglass.refreshes(tab))
+    }
+}
+
+/// Let the selected tab [`collect`](Tab::collect) its data, if the inspector is shown and the tab
+/// refreshes this frame.
+fn collect_selected_tab(world: &mut World) {
+    if SpyglassContext::window(world).is_none() {
+        return;
+    }
+    let mut spyglass = world.remove_resource::<Spyglass>().unwrap();
+    if let Some(tab) = spyglass.selected.and_then(|i| spyglass.tabs.get(i)) {
+        let name = tab.name().to_string();
+        if spyglass.collected.as_ref() != Some(&name) || spyglass.refreshes(&name) {
+            let selected = spyglass.selected.unwrap();
+            spyglass.tabs[selected].collect(world);
+            spyglass.collected = Some(name);
+        }
+    }
+    world.insert_resource(spyglass);
+}
+
+/// Advance the frame of the tab refresh rates, and apply the requested refreshes.
+fn advance_tab_refresh(mut spyglass: ResMut<Spyglass>, frame: Option<Res<FrameCount>>) {
+    spyglass.frame = frame.map_or(0, |frame| frame.0);
+    spyglass.refresh_forced = std::mem::take(&mut spyglass.refresh_requested);
+}
+
+/// The egui context the inspector is drawn into. Can be inserted before adding the
+/// [`SpyglassPlugin`], or changed at any time.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Resource)]
+pub enum SpyglassContext {
+    /// Draw the inspector as an egui window in the context of the primary window, shared with
+    /// the game's own egui UI.
+    #[default]
+    PrimaryWindow,
+    /// Draw the inspector in a dedicated window with its own egui context, so that the fonts,
+    /// styles and memory of the game's egui UI are not affected, and ids cannot clash. The window
+    /// is spawned when this is selected; closing it hides the inspector until it is selected
+    /// again.
+    DedicatedWindow,
+}
+
+impl SpyglassContext {
+    /// Find the window entity whose [`EguiContext`](bevy_egui::EguiContext) the inspector is
+    /// drawn into, if it exists.
+    pub fn window(world: &mut World) -> Option<Entity> {
+        match world
+            .get_resource::<SpyglassContext>()
+            .copied()
+            .unwrap_or_default()
+        {
+            SpyglassContext::PrimaryWindow => world
+                .query_filtered::<Entity, With<PrimaryWindow>>()
+                .get_single(world)
+                .ok(),
+            SpyglassContext::DedicatedWindow => world
+                .query_filtered::<Entity, With<DedicatedSpyglassWindow>>()
+                .get_single(world)
+                .ok(),
+        }
+    }
+}
+
+/// How the inspector is placed in the primary window, set in [`SpyglassSettings::layout`]. The
+/// dedicated window is always filled by the inspector.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Reflect)]
+pub enum InspectorLayout {
+    /// A floating egui window, which can be moved over the game.
+    #[default]
+    Window,
+    /// A resizable panel docked to the left of the window, which never overlaps the game's
+    /// egui windows.
+    LeftPanel,
+    /// A resizable panel docked to the right of the window.
+    RightPanel,
+}
+
+/// The egui visuals of the inspector, set in [`SpyglassSettings::theme`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Reflect)]
+pub enum SpyglassTheme {
+    /// The visuals of the egui context the inspector draws into, so it matches the app's UI.
+    #[default]
+    Inherit,
+    /// egui's dark visuals.
+    Dark,
+    /// egui's light visuals.
+    Light,
+}
+
+impl SpyglassTheme {
+    /// The visuals of the theme, or `None` to keep those of the egui context.
+    pub fn visuals(self) -> Option<egui::Visuals> {
+        match self {
+            Self::Inherit => None,
+            Self::Dark => Some(egui::Visuals::dark()),
+            Self::Light => Some(egui::Visuals::light()),
+        }
+    }
+}
+
+/// The resource that configures the inspector. May be changed at any time, including from the
+/// settings tab added by the [`SettingsTabPlugin`].
+#[derive(Clone, Reflect, Resource)]
+#[reflect(Resource)]
+pub struct SpyglassSettings {
+    /// Whether components whose type paths start with one of the
+    /// [`internal_components`](Self::internal_components) prefixes are hidden from the entities
+    /// tab.
+    pub hide_internal_components: bool,
+    /// Type path prefixes of engine-internal components, which are rarely useful to inspect.
+    /// Extend it to hide the internals of other crates too.
+    pub internal_components: Vec<String>,
+    /// How often the components with the given type names are refreshed while they are shown.
+    /// Components that are not listed are refreshed every frame.
+    pub component_refresh: HashMap<String, ComponentRefresh>,
+    /// The order the entities tab lists entities in.
+    pub entity_sort: EntitySort,
+    /// How the entities tab buckets entities.
+    pub entity_group_by: EntityGroupBy,
+    /// How recoverable errors, such as edits that fail to parse, are displayed.
+    pub recoverable_errors: NotificationStyle,
+    /// How edits of components are written back to the world.
+    pub apply_mode: ApplyMode,
+    /// Keys that select the tab with the given name when pressed, unless an egui text field has
+    /// keyboard focus. None are bound by default, e.g. bind [`KeyCode::F1`] to `"Entities"`.
+    pub tab_hotkeys: Vec<(KeyCode, String)>,
+    /// Whether the inspector floats over the game or is docked to a side of its window.
+    pub layout: InspectorLayout,
+    /// The visuals the inspector is drawn with.
+    pub theme: SpyglassTheme,
+    /// Whether the editors only display values, so the inspector can be used without risking
+    /// accidental edits, e.g. while watching a playtest. The settings stay editable.
+    pub read_only: bool,
+}
+
+impl Default for SpyglassSettings {
+    fn default() -> Self {
+        Self {
+            hide_internal_components: true,
+            internal_components: [
+                "aether_spyglass::",
+                "bevy_egui::",
+                "bevy_render::view::visibility::InheritedVisibility",
+                "bevy_render::view::visibility::ViewVisibility",
+                "bevy_render::view::visibility::VisibleEntities",
+                "bevy_render::camera::camera::CameraRenderGraph",
+                "bevy_render::camera::camera::ExtractedCamera",
+            ]
+            .map(String::from)
+            .to_vec(),
+            component_refresh: HashMap::default(),
+            entity_sort: EntitySort::default(),
+            entity_group_by: EntityGroupBy::default(),
+            recoverable_errors: NotificationStyle::default(),
+            apply_mode: ApplyMode::default(),
+            tab_hotkeys: vec![],
+            layout: InspectorLayout::default(),
+            theme: SpyglassTheme::default(),
+            read_only: false,
+        }
+    }
+}
+
+impl SpyglassSettings {
+    /// Whether the component with the given type path is listed as internal.
+    pub fn is_internal(&self, type_path: &str) -> bool {
+        self.internal_components
+            .iter()
+            .any(|prefix| type_path.starts_with(prefix.as_str()))
+    }
+
+    /// Set how often components of type `C` are refreshed while they are shown.
+    pub fn set_component_refresh<C: Component>(&mut self, refresh: ComponentRefresh) {
+        self.component_refresh
+            .insert(std::any::type_name::<C>().to_string(), refresh);
+    }
+
+    /// Whether the component with the given type path should be hidden.
+    pub fn is_hidden(&self, type_path: &str) -> bool {
+        self.hide_internal_components && self.is_internal(type_path)
+    }
+
+    /// Whether the settings of `world` make the editors read-only. `false` if there are none.
+    pub fn is_read_only(world: &World) -> bool {
+        world
+            .get_resource::<Self>()
+            .is_some_and(|settings| settings.read_only)
+    }
+}
+
+/// Marks the window spawned for [`SpyglassContext::DedicatedWindow`].
+#[derive(Component)]
+struct DedicatedSpyglassWindow;
+
+fn spawn_dedicated_window(
+    mut c: Commands,
+    context: Res<SpyglassContext>,
+    windows: Query<Entity, With<DedicatedSpyglassWindow>>,
+    mut previous: Local<Option<SpyglassContext>>,
+) {
+    if *previous == Some(*context) {
+        return;
+    }
+    *previous = Some(*context);
+
+    match *context {
+        SpyglassContext::PrimaryWindow => {
+            for window in &windows {
+                c.entity(window).despawn();
+            }
+        }
+        SpyglassContext::DedicatedWindow if windows.is_empty() => {
+            c.spawn((
+                Window {
+                    title: "Spyglass".to_string(),
+                    ..default()
+                },
+                DedicatedSpyglassWindow,
+            ));
+        }
+        SpyglassContext::DedicatedWindow => (),
+    }
 }
 
 /// The system set that draws the spyglass window. A good anchor point if there are
@@ -49,48 +540,224 @@ pub struct Spyglass {
 pub struct SpyglassWindow;
 
 fn spyglass_window(world: &mut World) {
-    let Ok(primary_window) = world
-        .query_filtered::<Entity, With<PrimaryWindow>>()
-        .get_single(world)
-        else { return };
-
-    let Some(mut ctx) = world.entity_mut(primary_window).take::<EguiContext>() else { return };
+    let Some(window) = SpyglassContext::window(world) else {
+        return;
+    };
+    let Some(ctx) = Egui::context(world, window) else {
+        return;
+    };
 
     let mut state = world.remove_resource::<Spyglass>().unwrap();
 
-    egui::Window::new("Spyglass").show(ctx.get_mut(), |ui| {
-        egui::menu::bar(ui, |ui| {
-            let mut selected = state.selected;
-            for (i, tab) in state.tabs.iter().enumerate() {
-                if ui
-                    .selectable_label(selected == Some(i), tab.name())
-                    .clicked()
-                {
-                    selected = if selected == Some(i) { None } else { Some(i) };
+    let layout = world
+        .get_resource::<SpyglassSettings>()
+        .map_or(InspectorLayout::Window, |settings| settings.layout);
+    // The frames are drawn with the visuals of the theme too, not only the contents.
+    let mut style = (*ctx.style()).clone();
+    let theme = world
+        .get_resource::<SpyglassSettings>()
+        .and_then(|settings| settings.theme.visuals());
+    if let Some(visuals) = theme {
+        style.visuals = visuals;
+    }
+    match *world.resource::<SpyglassContext>() {
+        SpyglassContext::PrimaryWindow => match layout {
+            InspectorLayout::Window => {
+                let mut window = egui::Window::new("Spyglass").frame(egui::Frame::window(&style));
+                if let Some(rect) = state.default_rect {
+                    window = window.default_rect(rect);
                 }
+                window.show(&ctx, |ui| draw_spyglass(ui, world, &mut state));
+            }
+            InspectorLayout::LeftPanel | InspectorLayout::RightPanel => {
+                let panel = match layout {
+                    InspectorLayout::LeftPanel => egui::SidePanel::left("spyglass_panel"),
+                    _ => egui::SidePanel::right("spyglass_panel"),
+                };
+                panel
+                    .frame(egui::Frame::side_top_panel(&style))
+                    .resizable(true)
+                    .default_width(state.default_rect.map_or(360.0, |rect| rect.width()))
+                    .show(&ctx, |ui| draw_spyglass(ui, world, &mut state));
             }
-            state.selected = selected;
-        });
+        },
+        SpyglassContext::DedicatedWindow => {
+            let panel = egui::CentralPanel::default().frame(egui::Frame::central_panel(&style));
+            panel.show(&ctx, |ui| draw_spyglass(ui, world, &mut state));
+        }
+    }
 
-        ui.separator();
+    world.insert_resource(state);
+}
 
-        match state.selected {
-            Some(selected) => {
-                let Some(tab) = state.tabs.get_mut(selected) else {
-                    state.selected = None;
-                    return;
-                };
+fn draw_spyglass(ui: &mut Ui, world: &mut World, state: &mut Spyglass) {
+    let theme = world
+        .get_resource::<SpyglassSettings>()
+        .and_then(|settings| settings.theme.visuals());
+    if let Some(visuals) = theme {
+        *ui.visuals_mut() = visuals;
+    }
+
+    egui::menu::bar(ui, |ui| {
+        let mut selected = state.selected;
+        for (i, tab) in state.tabs.iter().enumerate() {
+            if ui
+                .selectable_label(selected == Some(i), tab.name())
+                .clicked()
+            {
+                selected = if selected == Some(i) { None } else { Some(i) };
+            }
+            if let Some(badge) = tab.badge(world) {
+                let color = ui.visuals().warn_fg_color;
+                ui.label(egui::RichText::new(badge).small().strong().color(color));
+            }
+        }
+        state.selected = selected;
+    });
 
+    ui.separator();
+
+    time_controls(ui, world);
+    capture::capture_controls(ui, world);
+    script::record::record_controls(ui, world);
+    export::export_status(ui, world);
+
+    ui.separator();
+
+    match state.selected {
+        Some(selected) => {
+            let Some(tab) = state.tabs.get_mut(selected) else {
+                state.selected = None;
+                return;
+            };
+            let name = tab.name().to_string();
+            refresh_controls(ui, state, &name);
+            let tab = &mut state.tabs[selected];
+
+            ui.push_id(tab.id_salt(), |ui| {
                 ScrollArea::new([true, true]).show(ui, |ui| {
                     tab.draw(ui, world);
                 });
-            }
-            None => {
-                ui.heading("Please select a tab to inspect.");
+            });
+        }
+        None => {
+            ui.heading("Please select a tab to inspect.");
+        }
+    }
+}
+
+/// Draw the refresh rate of the tab named `tab`, and a button to refresh it if it is throttled.
+fn refresh_controls(ui: &mut Ui, state: &mut Spyglass, tab: &str) {
+    let mut refresh = state.refresh.get(tab).copied().unwrap_or_default();
+    ui.horizontal(|ui| {
+        egui::ComboBox::from_id_source("spyglass_tab_refresh")
+            .selected_text(match refresh {
+                ComponentRefresh::EveryFrame => "refresh every frame",
+                ComponentRefresh::EveryNFrames(_) => "refresh every n frames",
+                ComponentRefresh::Manual => "refresh on demand",
+            })
+            .show_ui(ui, |ui| {
+                ui.selectable_value(&mut refresh, ComponentRefresh::EveryFrame, "every frame");
+                if ui
+                    .selectable_label(
+                        matches!(refresh, ComponentRefresh::EveryNFrames(_)),
+                        "every n frames",
+                    )
+                    .clicked()
+                {
+                    refresh = ComponentRefresh::EveryNFrames(10);
+                }
+                ui.selectable_value(&mut refresh, ComponentRefresh::Manual, "on demand");
+            });
+        if let ComponentRefresh::EveryNFrames(n) = &mut refresh {
+            ui.add(egui::DragValue::new(n).clamp_range(1..=u32::MAX));
+        }
+        if refresh != ComponentRefresh::EveryFrame && ui.button("refresh now").clicked() {
+            state.request_refresh(tab);
+        }
+    });
+    match refresh {
+        ComponentRefresh::EveryFrame => state.refresh.remove(tab),
+        refresh => state.refresh.insert(tab.to_string(), refresh),
+    };
+}
+
+/// Tracks whether virtual time was unpaused to step a single frame.
+#[derive(Default, Resource)]
+struct TimeStep {
+    stepping: bool,
+}
+
+fn time_controls(ui: &mut Ui, world: &mut World) {
+    let Some(mut time) = world.get_resource_mut::<Time<Virtual>>() else {
+        return;
+    };
+
+    let mut step = false;
+    ui.horizontal(|ui| {
+        let paused = time.is_paused();
+        if ui.button(if paused { "resume" } else { "pause" }).clicked() {
+            if paused {
+                time.unpause();
+            } else {
+                time.pause();
             }
         }
+        step = ui.add_enabled(paused, egui::Button::new("step")).clicked();
+
+        let mut speed = time.relative_speed();
+        if ui
+            .add(egui::Slider::new(&mut speed, 0.0..=4.0).text("time scale"))
+            .changed()
+        {
+            time.set_relative_speed(speed);
+        }
     });
 
-    world.insert_resource(state);
-    world.entity_mut(primary_window).insert(ctx);
+    if step {
+        time.unpause();
+        world.resource_mut::<TimeStep>().stepping = true;
+    }
+}
+
+/// An event that selects the tab with the given name before the inspector is drawn. Lets tabs
+/// switch to another tab while they are drawn, when the [`Spyglass`] resource is not available.
+#[derive(Clone, Debug, Event)]
+pub struct FocusTab(pub String);
+
+fn focus_requested_tabs(mut events: EventReader<FocusTab>, mut spyglass: ResMut<Spyglass>) {
+    for FocusTab(name) in events.read() {
+        spyglass.select_tab_by_name(name);
+    }
+}
+
+/// Select the tab bound to a key in [`SpyglassSettings::tab_hotkeys`] when it is pressed.
+fn select_tab_by_hotkey(world: &mut World) {
+    let (Some(keys), Some(settings)) = (
+        world.get_resource::<Input<KeyCode>>(),
+        world.get_resource::<SpyglassSettings>(),
+    ) else {
+        return;
+    };
+    let Some(name) = settings
+        .tab_hotkeys
+        .iter()
+        .find(|(key, _)| keys.just_pressed(*key))
+        .map(|(_, name)| name.clone())
+    else {
+        return;
+    };
+    let typing = SpyglassContext::window(world)
+        .and_then(|window| Egui::context(world, window))
+        .is_some_and(|ctx| ctx.wants_keyboard_input());
+    if !typing {
+        world.resource_mut::<Spyglass>().select_tab_by_name(&name);
+    }
+}
+
+fn finish_time_step(mut step: ResMut<TimeStep>, time: Option<ResMut<Time<Virtual>>>) {
+    if let (true, Some(mut time)) = (step.stepping, time) {
+        step.stepping = false;
+        time.pause();
+    }
 }
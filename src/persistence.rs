@@ -0,0 +1,232 @@
+//! Persistence of the inspector layout across runs. [`SpyglassPersistencePlugin`] restores the
+//! selected tab, the position and size of the inspector window and which collapsing headers of
+//! the entities tab are open on startup, and saves them again when the app exits.
+
+use std::path::PathBuf;
+
+use bevy::app::AppExit;
+use bevy::prelude::*;
+use bevy::utils::HashSet;
+use serde::{Deserialize, Serialize};
+
+use crate::compat::egui;
+use crate::compat::{Egui, EguiBackend};
+use crate::formats::{FormatError, SpyglassFormat, SpyglassFormats};
+use crate::tabs::entities::editors::EditorStates;
+use crate::{Spyglass, SpyglassContext, SpyglassWindow};
+
+/// The plugin that persists the inspector layout. Must be added after the
+/// [`SpyglassPlugin`](crate::SpyglassPlugin).
+pub struct SpyglassPersistencePlugin;
+
+impl Plugin for SpyglassPersistencePlugin {
+    fn build(&self, app: &mut App) {
+        if cfg!(feature = "disabled") {
+            return;
+        }
+
+        app.init_resource::<SpyglassPersistence>()
+            .add_systems(PostStartup, restore_layout)
+            .add_systems(Update, expand_open_headers.before(SpyglassWindow))
+            .add_systems(Update, record_window_rect.after(SpyglassWindow))
+            .add_systems(Last, save_layout);
+    }
+}
+
+/// The position and size of the inspector window.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct WindowLayout {
+    /// The x coordinate of the top left corner, in egui points.
+    pub x: f32,
+    /// The y coordinate of the top left corner, in egui points.
+    pub y: f32,
+    /// The width of the window, in egui points.
+    pub width: f32,
+    /// The height of the window, in egui points.
+    pub height: f32,
+}
+
+/// The persisted layout of the inspector.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct SpyglassLayout {
+    /// The name of the selected tab, if any.
+    pub tab: Option<String>,
+    /// The position and size of the inspector window, if it was shown.
+    pub window: Option<WindowLayout>,
+    /// The paths of values whose collapsing headers are open, e.g. `Transform.translation`.
+    pub open_headers: Vec<String>,
+}
+
+impl SpyglassLayout {
+    /// Serialize the layout with the given format.
+    pub fn serialize(&self, format: &dyn SpyglassFormat) -> Result<Vec<u8>, FormatError> {
+        format.serialize(self)
+    }
+
+    /// Deserialize a layout with the given format.
+    pub fn deserialize(format: &dyn SpyglassFormat, bytes: &[u8]) -> Result<Self, FormatError> {
+        let mut layout = None;
+        format.deserialize(bytes, &mut |de| {
+            layout = Some(erased_serde::deserialize(de)?);
+            Ok(())
+        })?;
+        layout.ok_or_else(|| FormatError::new("no layout was deserialized"))
+    }
+}
+
+/// The resource that configures where the layout is persisted, and tracks it while the app runs.
+#[derive(Resource)]
+pub struct SpyglassPersistence {
+    /// The path the layout is saved to, without an extension. The extension of the current
+    /// [`SpyglassFormats`] format is added.
+    pub path: PathBuf,
+    window: Option<WindowLayout>,
+    open_headers: HashSet<String>,
+}
+
+impl Default for SpyglassPersistence {
+    fn default() -> Self {
+        Self {
+            path: PathBuf::from("spyglass"),
+            window: None,
+            open_headers: HashSet::default(),
+        }
+    }
+}
+
+impl SpyglassPersistence {
+    fn file(&self, format: &dyn SpyglassFormat) -> PathBuf {
+        self.path.with_extension(format.extension())
+    }
+
+    /// Load the persisted layout, if there is one.
+    pub fn load(&self, formats: &SpyglassFormats) -> Result<Option<SpyglassLayout>, String> {
+        let format = formats.current();
+        let bytes = match std::fs::read(self.file(format)) {
+            Ok(bytes) => bytes,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err.to_string()),
+        };
+        SpyglassLayout::deserialize(format, &bytes)
+            .map(Some)
+            .map_err(|e| e.to_string())
+    }
+
+    /// Save a layout, replacing the persisted one.
+    pub fn save(&self, formats: &SpyglassFormats, layout: &SpyglassLayout) -> Result<(), String> {
+        let format = formats.current();
+        let bytes = layout.serialize(format).map_err(|e| e.to_string())?;
+        let path = self.file(format);
+        if let Some(parent) = path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+        {
+            std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        std::fs::write(path, bytes).map_err(|e| e.to_string())
+    }
+
+    /// Capture the current layout of the inspector.
+    pub fn capture(&self, spyglass: &Spyglass) -> SpyglassLayout {
+        let mut open_headers = self.open_headers.iter().cloned().collect::<Vec<_>>();
+        open_headers.sort_unstable();
+
+        SpyglassLayout {
+            tab: spyglass
+                .selected
+                .and_then(|selected| spyglass.tabs.get(selected))
+                .map(|tab| tab.name().to_string()),
+            window: self.window,
+            open_headers,
+        }
+    }
+
+    /// Apply a layout to the inspector.
+    pub fn apply(&mut self, spyglass: &mut Spyglass, layout: SpyglassLayout) {
+        if let Some(tab) = layout.tab {
+            spyglass.selected = spyglass.tabs.iter().position(|t| t.name() == tab);
+        }
+        if let Some(window) = layout.window {
+            spyglass.default_rect = Some(egui::Rect::from_min_size(
+                egui::pos2(window.x, window.y),
+                egui::vec2(window.width, window.height),
+            ));
+            self.window = Some(window);
+        }
+        self.open_headers = layout.open_headers.into_iter().collect();
+    }
+}
+
+fn restore_layout(
+    mut persistence: ResMut<SpyglassPersistence>,
+    mut spyglass: ResMut<Spyglass>,
+    formats: Res<SpyglassFormats>,
+) {
+    match persistence.load(&formats) {
+        Ok(Some(layout)) => persistence.apply(&mut spyglass, layout),
+        Ok(None) => (),
+        Err(err) => warn!("failed to load the spyglass layout: {err}"),
+    }
+}
+
+/// Update the open headers from the last drawn frame, then keep the open ones open. Headers the
+/// user closed were drawn without being expanded, so they are forgotten.
+fn expand_open_headers(
+    mut persistence: ResMut<SpyglassPersistence>,
+    mut states: ResMut<EditorStates>,
+) {
+    let expanded = states.expanded().iter().collect::<HashSet<_>>();
+    let closed = states
+        .headers()
+        .iter()
+        .filter(|header| !expanded.contains(header))
+        .cloned()
+        .collect::<Vec<_>>();
+    let opened = expanded.into_iter().cloned().collect::<Vec<_>>();
+
+    for path in closed {
+        persistence.open_headers.remove(&path);
+    }
+    persistence.open_headers.extend(opened);
+
+    for path in persistence.open_headers.iter() {
+        states.request_expand(path.clone());
+    }
+}
+
+fn record_window_rect(world: &mut World) {
+    if *world.resource::<SpyglassContext>() != SpyglassContext::PrimaryWindow {
+        return;
+    }
+    let Some(window) = SpyglassContext::window(world) else {
+        return;
+    };
+    let Some(ctx) = Egui::context(world, window) else {
+        return;
+    };
+    let rect = ctx.memory(|memory| memory.area_rect(egui::Id::new("Spyglass")));
+    if let Some(rect) = rect {
+        world.resource_mut::<SpyglassPersistence>().window = Some(WindowLayout {
+            x: rect.min.x,
+            y: rect.min.y,
+            width: rect.width(),
+            height: rect.height(),
+        });
+    }
+}
+
+fn save_layout(
+    persistence: Res<SpyglassPersistence>,
+    spyglass: Res<Spyglass>,
+    formats: Res<SpyglassFormats>,
+    mut exit: EventReader<AppExit>,
+) {
+    if exit.read().next().is_none() {
+        return;
+    }
+
+    let layout = persistence.capture(&spyglass);
+    if let Err(err) = persistence.save(&formats, &layout) {
+        error!("failed to save the spyglass layout: {err}");
+    }
+}
@@ -0,0 +1,184 @@
+//! The profiler module. Times every system and schedule run through the spans that bevy's ECS
+//! emits with its `trace` feature, which the `profiler` feature enables. The spans are timed by
+//! the [`SpyglassProfilerLayer`], and the [`ProfilerPlugin`] publishes the timings of each frame
+//! in the [`Profiler`] resource, which the profiler tab shows.
+//!
+//! Tracing subscribers are global, and bevy's [`LogPlugin`](bevy::log::LogPlugin) installs one
+//! without a way to add layers to it. The [`ProfilerPlugin`] installs a subscriber with the
+//! layer if there is none yet; apps that keep the `LogPlugin` should disable it and install the
+//! layer in their own subscriber instead.
+
+use std::fmt::Debug;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use bevy::prelude::*;
+use bevy::utils::tracing::field::{Field, Visit};
+use bevy::utils::tracing::span::{Attributes, Id};
+use bevy::utils::tracing::{subscriber, Subscriber};
+use bevy::utils::HashMap;
+use tracing_subscriber::layer::{Context, Layer, SubscriberExt};
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::Registry;
+
+/// How much each frame contributes to the averages of the [`Profiler`].
+const AVERAGE_WEIGHT: f64 = 0.05;
+
+/// The timings of the frame in progress. Global, as the layer is not tied to an app.
+static CURRENT: Mutex<Option<FrameTimings>> = Mutex::new(None);
+
+/// The plugin that publishes the timings of each frame in the [`Profiler`], and installs a
+/// subscriber with the [`SpyglassProfilerLayer`] if no subscriber was installed yet. Can be added
+/// with or without the [`SpyglassPlugin`](crate::SpyglassPlugin).
+pub struct ProfilerPlugin;
+
+impl Plugin for ProfilerPlugin {
+    fn build(&self, app: &mut App) {
+        if cfg!(feature = "disabled") {
+            return;
+        }
+
+        let installed =
+            subscriber::set_global_default(Registry::default().with(SpyglassProfilerLayer)).is_ok();
+        app.insert_resource(Profiler {
+            installed,
+            ..default()
+        })
+        .add_systems(First, finish_profiled_frame);
+    }
+}
+
+/// The time spent in systems and schedules during a frame.
+#[derive(Clone, Debug, Default)]
+pub struct FrameTimings {
+    /// The total run time of each system, by name. Systems that run several times in a frame,
+    /// like those in `FixedUpdate`, are summed.
+    pub systems: HashMap<String, Duration>,
+    /// The total run time of each schedule, by name, in the order they first ran.
+    pub schedules: Vec<(String, Duration)>,
+}
+
+impl FrameTimings {
+    fn add(&mut self, kind: SpanKind, name: &str, elapsed: Duration) {
+        match kind {
+            SpanKind::System => *self.systems.entry_ref(name).or_default() += elapsed,
+            SpanKind::Schedule => match self.schedules.iter_mut().find(|(n, _)| n == name) {
+                Some((_, total)) => *total += elapsed,
+                None => self.schedules.push((name.to_string(), elapsed)),
+            },
+        }
+    }
+}
+
+/// The resource that contains the timings the [`SpyglassProfilerLayer`] measured.
+#[derive(Default, Resource)]
+pub struct Profiler {
+    /// The timings of the last complete frame.
+    pub last_frame: FrameTimings,
+    /// The average run time of each system, smoothed over recent frames.
+    pub averages: HashMap<String, Duration>,
+    installed: bool,
+}
+
+impl Profiler {
+    /// Whether the [`ProfilerPlugin`] installed the subscriber with the layer. If not, the layer
+    /// only receives spans if the app installed it in its own subscriber.
+    pub fn installed(&self) -> bool {
+        self.installed
+    }
+}
+
+fn finish_profiled_frame(mut profiler: ResMut<Profiler>) {
+    let frame = CURRENT
+        .lock()
+        .ok()
+        .and_then(|mut current| current.replace(FrameTimings::default()))
+        .unwrap_or_default();
+    let profiler = &mut *profiler;
+    for (name, &time) in frame.systems.iter() {
+        let average = profiler.averages.entry_ref(name.as_str()).or_insert(time);
+        *average = average.mul_f64(1.0 - AVERAGE_WEIGHT) + time.mul_f64(AVERAGE_WEIGHT);
+    }
+    profiler.last_frame = frame;
+}
+
+/// The kinds of spans the layer times.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum SpanKind {
+    System,
+    Schedule,
+}
+
+/// The state of a timed span, kept in its extensions.
+struct TimedSpan {
+    kind: SpanKind,
+    name: String,
+    entered: Option<Instant>,
+}
+
+/// Records the `name` field of a span.
+#[derive(Default)]
+struct NameVisitor(Option<String>);
+
+impl Visit for NameVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "name" {
+            self.0 = Some(value.to_string());
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        if field.name() == "name" {
+            self.0 = Some(format!("{value:?}"));
+        }
+    }
+}
+
+/// The tracing layer that times the `system` and `schedule` spans of bevy's ECS, for the
+/// [`Profiler`].
+pub struct SpyglassProfilerLayer;
+
+impl<S: Subscriber + for<'a> LookupSpan<'a>> Layer<S> for SpyglassProfilerLayer {
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let kind = match attrs.metadata().name() {
+            "system" => SpanKind::System,
+            "schedule" => SpanKind::Schedule,
+            _ => return,
+        };
+        let mut name = NameVisitor::default();
+        attrs.record(&mut name);
+        if let (Some(span), Some(name)) = (ctx.span(id), name.0) {
+            span.extensions_mut().insert(TimedSpan {
+                kind,
+                name,
+                entered: None,
+            });
+        }
+    }
+
+    fn on_enter(&self, id: &Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else { return };
+        let mut extensions = span.extensions_mut();
+        if let Some(timed) = extensions.get_mut::<TimedSpan>() {
+            timed.entered = Some(Instant::now());
+        }
+    }
+
+    fn on_exit(&self, id: &Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else { return };
+        let mut extensions = span.extensions_mut();
+        let Some(timed) = extensions.get_mut::<TimedSpan>() else {
+            return;
+        };
+        let Some(entered) = timed.entered.take() else {
+            return;
+        };
+        if let Ok(mut current) = CURRENT.lock() {
+            current.get_or_insert_with(FrameTimings::default).add(
+                timed.kind,
+                &timed.name,
+                entered.elapsed(),
+            );
+        }
+    }
+}
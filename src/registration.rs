@@ -0,0 +1,47 @@
+//! Compile-time registration of editors. Crates can export editors for their own types with
+//! [`spyglass_editors!`](crate::spyglass_editors), and the [`SpyglassPlugin`](crate::SpyglassPlugin)
+//! inserts them into [`ReprEditors`] when it is added, so users of those crates get the editors
+//! without any wiring. Only available with the `inventory` feature.
+
+use crate::tabs::entities::ReprEditors;
+
+#[doc(hidden)]
+pub use inventory;
+
+/// An exported registration of editors, collected by the [`SpyglassPlugin`](crate::SpyglassPlugin).
+/// Created with [`spyglass_editors!`](crate::spyglass_editors).
+pub struct EditorRegistration {
+    /// The function that inserts the exported editors.
+    pub register: fn(&mut ReprEditors),
+}
+
+inventory::collect!(EditorRegistration);
+
+/// Export a function that inserts editors into [`ReprEditors`]. Every exported function linked
+/// into the app is called when the [`SpyglassPlugin`](crate::SpyglassPlugin) is added, before any
+/// editors the app registers itself, so apps can still override them.
+///
+/// ```ignore
+/// fn register(editors: &mut ReprEditors) {
+///     editors.insert_typed::<Health>(|ui, health, _, _, _| {
+///         ui.add(egui::Slider::new(&mut health.0, 0.0..=100.0));
+///     });
+/// }
+///
+/// aether_spyglass::spyglass_editors!(register);
+/// ```
+#[macro_export]
+macro_rules! spyglass_editors {
+    ($register:path) => {
+        $crate::registration::inventory::submit! {
+            $crate::registration::EditorRegistration { register: $register }
+        }
+    };
+}
+
+/// Insert every exported editor into `editors`, in no particular order.
+pub fn register_exported_editors(editors: &mut ReprEditors) {
+    for registration in inventory::iter::<EditorRegistration> {
+        (registration.register)(editors);
+    }
+}
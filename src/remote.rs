@@ -0,0 +1,395 @@
+//! Remote inspection over TCP. [`SpyglassServerPlugin`] exposes the reflected world of a game,
+//! for example one running on a console or a dedicated server, without drawing any UI. The
+//! [`RemoteTabPlugin`](crate::tabs::remote::RemoteTabPlugin) connects to it from a separate app
+//! running the inspector, and sends edits back as [`RemoteRequest`]s.
+//!
+//! Messages are framed by a big-endian `u32` length and serialized with the current
+//! [`SpyglassFormats`] format, so both apps must select the same format (RON by default).
+//! Reflected values are serialized as text in the same format, so the client must register the
+//! types it wants to edit in its own type registry. Worlds exposed through the Bevy Remote
+//! Protocol can be inspected through the [`brp`] adapter instead.
+
+pub mod brp;
+
+use std::io::{ErrorKind, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::panic::AssertUnwindSafe;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::formats::{
+    deserialize_reflect, serialize_reflect, FormatError, SpyglassFormat, SpyglassFormats,
+};
+
+/// A request sent from the inspector to a [`SpyglassServer`].
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum RemoteRequest {
+    /// List every entity, answered with [`RemoteResponse::Entities`].
+    ListEntities,
+    /// Get the components of an entity, answered with [`RemoteResponse::Entity`].
+    GetEntity(Entity),
+    /// Apply a serialized reflected value to a component of an entity. Answered with
+    /// [`RemoteResponse::Entity`] with the updated components.
+    SetComponent {
+        /// The entity to edit.
+        entity: Entity,
+        /// The type path of the component.
+        component: String,
+        /// The serialized reflected value.
+        value: String,
+    },
+}
+
+/// A response sent from a [`SpyglassServer`] to the inspector.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum RemoteResponse {
+    /// Every entity of the world.
+    Entities(Vec<RemoteEntity>),
+    /// The components of an entity.
+    Entity {
+        /// The entity.
+        entity: Entity,
+        /// The components of the entity, sorted by type path.
+        components: Vec<RemoteComponent>,
+    },
+    /// A request failed.
+    Error(String),
+}
+
+/// An entity of a remote world.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RemoteEntity {
+    /// The entity.
+    pub entity: Entity,
+    /// The [`Name`] of the entity, if any.
+    pub name: Option<String>,
+}
+
+/// A component of an entity in a remote world.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RemoteComponent {
+    /// The type path of the component.
+    pub type_path: String,
+    /// The serialized reflected value, if the component is reflected.
+    pub value: Option<String>,
+}
+
+/// Serialize a message with the given format.
+pub fn serialize_message(
+    format: &dyn SpyglassFormat,
+    message: &impl Serialize,
+) -> Result<Vec<u8>, FormatError> {
+    format.serialize(&message as &dyn erased_serde::Serialize)
+}
+
+/// Deserialize a message with the given format.
+pub fn deserialize_message<T: for<'de> Deserialize<'de>>(
+    format: &dyn SpyglassFormat,
+    bytes: &[u8],
+) -> Result<T, FormatError> {
+    let mut message = None;
+    format.deserialize(bytes, &mut |de| {
+        message = Some(erased_serde::deserialize(de)?);
+        Ok(())
+    })?;
+    message.ok_or_else(|| FormatError::new("no message was deserialized"))
+}
+
+/// The longest message a [`RemoteConnection`] receives. Longer frames close the connection, so
+/// a peer can't make it buffer without bound.
+pub const MAX_MESSAGE_LEN: usize = 16 * 1024 * 1024;
+
+/// The most bytes a [`RemoteConnection`] queues for a peer that does not read them, after which
+/// sending fails and the connection is closed.
+const MAX_QUEUED_LEN: usize = 4 * MAX_MESSAGE_LEN;
+
+/// A non-blocking TCP connection that sends and receives length-framed messages.
+pub struct RemoteConnection {
+    stream: TcpStream,
+    buffer: Vec<u8>,
+    /// The bytes queued to be sent, which the socket did not accept yet.
+    outgoing: Vec<u8>,
+}
+
+impl RemoteConnection {
+    /// Connect to a [`SpyglassServer`].
+    pub fn connect(address: impl ToSocketAddrs) -> std::io::Result<Self> {
+        Self::new(TcpStream::connect(address)?)
+    }
+
+    fn new(stream: TcpStream) -> std::io::Result<Self> {
+        stream.set_nonblocking(true)?;
+        stream.set_nodelay(true)?;
+        Ok(Self {
+            stream,
+            buffer: vec![],
+            outgoing: vec![],
+        })
+    }
+
+    /// The address of the other end of the connection.
+    pub fn peer_addr(&self) -> std::io::Result<SocketAddr> {
+        self.stream.peer_addr()
+    }
+
+    /// Queue a message, and write as much of the queue as the socket accepts without blocking.
+    /// The rest is written by later calls to [`send`](Self::send) or
+    /// [`receive`](Self::receive).
+    pub fn send(&mut self, message: &[u8]) -> std::io::Result<()> {
+        if message.len() > MAX_MESSAGE_LEN {
+            return Err(std::io::Error::new(
+                ErrorKind::InvalidInput,
+                "message is too long",
+            ));
+        }
+        if self.outgoing.len() + message.len() > MAX_QUEUED_LEN {
+            return Err(std::io::Error::new(
+                ErrorKind::WouldBlock,
+                "the peer is not reading its messages",
+            ));
+        }
+        self.outgoing
+            .extend_from_slice(&(message.len() as u32).to_be_bytes());
+        self.outgoing.extend_from_slice(message);
+        self.flush()
+    }
+
+    /// Write as much of the queued bytes as the socket accepts without blocking.
+    fn flush(&mut self) -> std::io::Result<()> {
+        let mut written = 0;
+        while written < self.outgoing.len() {
+            match self.stream.write(&self.outgoing[written..]) {
+                Ok(0) => return Err(ErrorKind::WriteZero.into()),
+                Ok(n) => written += n,
+                Err(err) if err.kind() == ErrorKind::WouldBlock => break,
+                Err(err) if err.kind() == ErrorKind::Interrupted => continue,
+                Err(err) => return Err(err),
+            }
+        }
+        self.outgoing.drain(..written);
+        Ok(())
+    }
+
+    /// Receive every complete message that has arrived, without blocking, and write queued
+    /// messages. Returns an error once the connection is closed, or if the peer announces a
+    /// message longer than [`MAX_MESSAGE_LEN`].
+    pub fn receive(&mut self) -> std::io::Result<Vec<Vec<u8>>> {
+        self.flush()?;
+        let mut chunk = [0; 4096];
+        loop {
+            match self.stream.read(&mut chunk) {
+                Ok(0) => return Err(ErrorKind::ConnectionAborted.into()),
+                Ok(n) => self.buffer.extend_from_slice(&chunk[..n]),
+                Err(err) if err.kind() == ErrorKind::WouldBlock => break,
+                Err(err) if err.kind() == ErrorKind::Interrupted => continue,
+                Err(err) => return Err(err),
+            }
+        }
+
+        let mut messages = vec![];
+        while let Some(len) = self.buffer.get(..4) {
+            let len = u32::from_be_bytes(len.try_into().unwrap()) as usize;
+            if len > MAX_MESSAGE_LEN {
+                return Err(std::io::Error::new(
+                    ErrorKind::InvalidData,
+                    format!("message of {len} bytes is too long"),
+                ));
+            }
+            if self.buffer.len() < 4 + len {
+                break;
+            }
+            messages.push(self.buffer[4..4 + len].to_vec());
+            self.buffer.drain(..4 + len);
+        }
+        Ok(messages)
+    }
+}
+
+/// The plugin that serves the world to remote inspectors. Does not need the
+/// [`SpyglassPlugin`](crate::SpyglassPlugin), so it can be added to builds without any UI.
+pub struct SpyglassServerPlugin {
+    /// The address to listen on, e.g. `0.0.0.0:7878`.
+    pub address: String,
+}
+
+impl Plugin for SpyglassServerPlugin {
+    fn build(&self, app: &mut App) {
+        if cfg!(feature = "disabled") {
+            return;
+        }
+
+        app.init_resource::<SpyglassFormats>();
+
+        match SpyglassServer::bind(&self.address) {
+            Ok(server) => {
+                info!("spyglass server listening on {}", self.address);
+                app.insert_resource(server)
+                    .add_systems(Update, serve_remote_requests);
+            }
+            Err(err) => error!(
+                "failed to start the spyglass server on {}: {err}",
+                self.address
+            ),
+        }
+    }
+}
+
+/// The resource that accepts remote inspector connections and answers their requests.
+#[derive(Resource)]
+pub struct SpyglassServer {
+    listener: TcpListener,
+    connections: Vec<RemoteConnection>,
+}
+
+impl SpyglassServer {
+    /// Listen for connections on an address.
+    pub fn bind(address: impl ToSocketAddrs) -> std::io::Result<Self> {
+        let listener = TcpListener::bind(address)?;
+        listener.set_nonblocking(true)?;
+        Ok(Self {
+            listener,
+            connections: vec![],
+        })
+    }
+
+    /// The address the server is listening on.
+    pub fn local_addr(&self) -> std::io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// The number of connected inspectors.
+    pub fn connections(&self) -> usize {
+        self.connections.len()
+    }
+}
+
+fn serve_remote_requests(world: &mut World) {
+    world.resource_scope(|world, mut server: Mut<SpyglassServer>| {
+        while let Ok((stream, _)) = server.listener.accept() {
+            match RemoteConnection::new(stream) {
+                Ok(connection) => server.connections.push(connection),
+                Err(err) => warn!("failed to accept a spyglass connection: {err}"),
+            }
+        }
+
+        server.connections.retain_mut(|connection| {
+            let messages = match connection.receive() {
+                Ok(messages) => messages,
+                Err(_) => return false,
+            };
+            for message in messages {
+                let format = world.resource::<SpyglassFormats>().current();
+                let response = match deserialize_message(format, &message) {
+                    Ok(request) => handle_request(world, request),
+                    Err(err) => RemoteResponse::Error(format!("invalid request: {err}")),
+                };
+
+                let format = world.resource::<SpyglassFormats>().current();
+                let sent = serialize_message(format, &response)
+                    .map_err(|err| std::io::Error::new(ErrorKind::InvalidData, err))
+                    .and_then(|bytes| connection.send(&bytes));
+                if sent.is_err() {
+                    return false;
+                }
+            }
+            true
+        });
+    });
+}
+
+/// Answer a request against the world.
+pub fn handle_request(world: &mut World, request: RemoteRequest) -> RemoteResponse {
+    match request {
+        RemoteRequest::ListEntities => RemoteResponse::Entities(
+            world
+                .iter_entities()
+                .map(|entity| RemoteEntity {
+                    entity: entity.id(),
+                    name: entity.get::<Name>().map(|name| name.to_string()),
+                })
+                .collect(),
+        ),
+        RemoteRequest::GetEntity(entity) => entity_response(world, entity),
+        RemoteRequest::SetComponent {
+            entity,
+            component,
+            value,
+        } => match set_component(world, entity, &component, &value) {
+            Ok(()) => entity_response(world, entity),
+            Err(err) => {
+                RemoteResponse::Error(format!("failed to set `{component}` of {entity:?}: {err}"))
+            }
+        },
+    }
+}
+
+fn entity_response(world: &World, entity: Entity) -> RemoteResponse {
+    let Some(entity_ref) = world.get_entity(entity) else {
+        return RemoteResponse::Error(format!("{entity:?} does not exist"));
+    };
+
+    let format = world.resource::<SpyglassFormats>().current();
+    let registry = world.resource::<AppTypeRegistry>().read();
+    let mut components = entity_ref
+        .archetype()
+        .components()
+        .filter_map(|comp| world.components().get_info(comp))
+        .map(|info| {
+            let reflected = info
+                .type_id()
+                .and_then(|id| registry.get(id)?.data::<ReflectComponent>())
+                .and_then(|reflect| reflect.reflect(entity_ref));
+            RemoteComponent {
+                type_path: reflected
+                    .map(|value| value.reflect_type_path().to_string())
+                    .unwrap_or_else(|| info.name().to_string()),
+                value: reflected
+                    .and_then(|value| serialize_reflect(format, value, &registry).ok())
+                    .and_then(|bytes| String::from_utf8(bytes).ok()),
+            }
+        })
+        .collect::<Vec<_>>();
+    components.sort_unstable_by(|a, b| a.type_path.cmp(&b.type_path));
+
+    RemoteResponse::Entity { entity, components }
+}
+
+fn set_component(
+    world: &mut World,
+    entity: Entity,
+    component: &str,
+    value: &str,
+) -> Result<(), String> {
+    let format = world.resource::<SpyglassFormats>().current();
+    let registry = world.resource::<AppTypeRegistry>().read();
+    let registration = registry
+        .get_with_type_path(component)
+        .ok_or("the component is not registered")?;
+    let reflect = registration
+        .data::<ReflectComponent>()
+        .cloned()
+        .ok_or("the component is not registered as reflected")?;
+    let type_id = registration.type_id();
+    let value =
+        deserialize_reflect(format, value.as_bytes(), &registry).map_err(|e| e.to_string())?;
+    drop(registry);
+    // Applying a value of another type panics.
+    match value.get_represented_type_info() {
+        Some(info) if info.type_id() == type_id => {}
+        Some(info) => return Err(format!("the value is a `{}`", info.type_path())),
+        None => return Err("the value does not name its type".to_string()),
+    }
+
+    let entity_ref = world
+        .get_entity(entity)
+        .ok_or("the entity does not exist")?;
+    if reflect.reflect(entity_ref).is_none() {
+        return Err("the entity does not have the component".to_string());
+    }
+    let mut entity = world.entity_mut(entity);
+    std::panic::catch_unwind(AssertUnwindSafe(|| {
+        reflect.apply(&mut entity, value.as_ref());
+    }))
+    .map_err(|_| "the value does not match the component".to_string())
+}
@@ -0,0 +1,241 @@
+//! An adapter for worlds exposed through the Bevy Remote Protocol (BRP), the JSON-RPC over HTTP
+//! endpoint of newer bevy versions. [`BrpConnection`] translates [`RemoteRequest`]s into BRP
+//! method calls and their results back into [`RemoteResponse`]s, so the remote tab can browse
+//! and edit such worlds like it does a [`SpyglassServer`](super::SpyglassServer).
+//!
+//! Component values are exchanged as JSON, wrapped as `{"type::Path": value}` so they can be read
+//! with [`deserialize_reflect`](crate::formats::deserialize_reflect) and the
+//! [`JsonFormat`](crate::formats::JsonFormat).
+
+use std::io::{Read, Write};
+use std::net::{SocketAddr, TcpStream, ToSocketAddrs};
+use std::sync::mpsc::{channel, Sender};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use bevy::prelude::*;
+use serde_json::{json, Map, Value};
+
+use super::{RemoteComponent, RemoteEntity, RemoteRequest, RemoteResponse};
+
+const NAME_PATH: &str = "bevy_core::name::Name";
+
+/// How long connecting to the endpoint, and each read or write of a BRP call, may take before the
+/// call fails.
+pub const BRP_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The endpoint of a [`BrpConnection`]: the address used in the `Host` header, and where it
+/// resolved to.
+struct Endpoint {
+    address: String,
+    resolved: SocketAddr,
+}
+
+/// A connection to a BRP HTTP endpoint. As BRP calls are blocking HTTP requests, requests are
+/// queued to a worker thread, which runs them in order, and their responses are collected with
+/// [`receive`](Self::receive). The worker stops when the connection is dropped.
+pub struct BrpConnection {
+    requests: Sender<RemoteRequest>,
+    responses: Arc<Mutex<Vec<RemoteResponse>>>,
+}
+
+impl BrpConnection {
+    /// Connect to a BRP endpoint, e.g. `127.0.0.1:15702`. Checks that the endpoint is reachable.
+    pub fn connect(address: impl Into<String>) -> std::io::Result<Self> {
+        let address = address.into();
+        let resolved = address.to_socket_addrs()?.next().ok_or_else(|| {
+            std::io::Error::new(std::io::ErrorKind::InvalidInput, "unresolved address")
+        })?;
+        TcpStream::connect_timeout(&resolved, BRP_TIMEOUT)?;
+
+        let endpoint = Endpoint { address, resolved };
+        let (requests, queue) = channel();
+        let responses = Arc::<Mutex<Vec<_>>>::default();
+        let shared = responses.clone();
+        std::thread::Builder::new()
+            .name("spyglass BRP".to_string())
+            .spawn(move || {
+                for request in queue {
+                    let response = match handle_request(&endpoint, request) {
+                        Ok(response) => response,
+                        Err(err) => RemoteResponse::Error(format!("BRP request failed: {err}")),
+                    };
+                    shared.lock().unwrap().push(response);
+                }
+            })?;
+
+        Ok(Self {
+            requests,
+            responses,
+        })
+    }
+
+    /// Send a request. It is queued to the worker thread, which translates it into one or more
+    /// BRP calls.
+    pub fn send(&self, request: RemoteRequest) {
+        // The worker only stops once the connection is dropped.
+        let _ = self.requests.send(request);
+    }
+
+    /// Receive every response that has arrived, without blocking.
+    pub fn receive(&self) -> Vec<RemoteResponse> {
+        std::mem::take(&mut self.responses.lock().unwrap())
+    }
+}
+
+fn handle_request(address: &Endpoint, request: RemoteRequest) -> Result<RemoteResponse, String> {
+    match request {
+        RemoteRequest::ListEntities => {
+            let result = call(
+                address,
+                "bevy/query",
+                json!({ "data": { "option": [NAME_PATH] } }),
+            )?;
+            let entities = result
+                .as_array()
+                .ok_or("expected a list of entities")?
+                .iter()
+                .filter_map(|row| {
+                    Some(RemoteEntity {
+                        entity: Entity::from_bits(row.get("entity")?.as_u64()?),
+                        name: row
+                            .get("components")
+                            .and_then(|comps| comps.get(NAME_PATH))
+                            .and_then(name_from_json),
+                    })
+                })
+                .collect();
+            Ok(RemoteResponse::Entities(entities))
+        }
+        RemoteRequest::GetEntity(entity) => get_entity(address, entity),
+        RemoteRequest::SetComponent {
+            entity,
+            component,
+            value,
+        } => {
+            let value = serde_json::from_str::<Value>(&value).map_err(|e| e.to_string())?;
+            let value = value
+                .get(&component)
+                .cloned()
+                .ok_or("values must be serialized as JSON")?;
+            call(
+                address,
+                "bevy/insert",
+                json!({ "entity": entity.to_bits(), "components": { component: value } }),
+            )?;
+            get_entity(address, entity)
+        }
+    }
+}
+
+fn get_entity(address: &Endpoint, entity: Entity) -> Result<RemoteResponse, String> {
+    let list = call(address, "bevy/list", json!({ "entity": entity.to_bits() }))?;
+    let paths = list
+        .as_array()
+        .ok_or("expected a list of components")?
+        .iter()
+        .filter_map(|path| path.as_str().map(str::to_string))
+        .collect::<Vec<_>>();
+    let result = call(
+        address,
+        "bevy/get",
+        json!({ "entity": entity.to_bits(), "components": paths }),
+    )?;
+    // Non-strict `bevy/get` returns values and errors separately; older versions return only
+    // the values.
+    let values = result.get("components").unwrap_or(&result);
+
+    let mut components = paths
+        .into_iter()
+        .map(|path| {
+            let value = values.get(&path).map(|value| {
+                let mut wrapped = Map::new();
+                wrapped.insert(path.clone(), value.clone());
+                Value::Object(wrapped).to_string()
+            });
+            RemoteComponent {
+                type_path: path,
+                value,
+            }
+        })
+        .collect::<Vec<_>>();
+    components.sort_unstable_by(|a, b| a.type_path.cmp(&b.type_path));
+
+    Ok(RemoteResponse::Entity { entity, components })
+}
+
+fn name_from_json(value: &Value) -> Option<String> {
+    match value {
+        Value::String(name) => Some(name.clone()),
+        Value::Object(fields) => fields.get("name")?.as_str().map(str::to_string),
+        _ => None,
+    }
+}
+
+/// Call a BRP method and return its result.
+fn call(address: &Endpoint, method: &str, params: Value) -> Result<Value, String> {
+    let body = json!({ "jsonrpc": "2.0", "id": 0, "method": method, "params": params }).to_string();
+    let response = post(address, &body).map_err(|e| e.to_string())?;
+    let mut response = serde_json::from_slice::<Value>(&response).map_err(|e| e.to_string())?;
+    if let Some(error) = response.get("error") {
+        let message = error.get("message").and_then(Value::as_str);
+        return Err(format!(
+            "`{method}`: {}",
+            message.unwrap_or("unknown error")
+        ));
+    }
+    response
+        .get_mut("result")
+        .map(Value::take)
+        .ok_or_else(|| format!("`{method}` returned no result"))
+}
+
+/// Make an HTTP POST request and return the response body. Fails if connecting, or any read or
+/// write, takes longer than [`BRP_TIMEOUT`].
+fn post(endpoint: &Endpoint, body: &str) -> std::io::Result<Vec<u8>> {
+    let invalid = |msg: &str| std::io::Error::new(std::io::ErrorKind::InvalidData, msg.to_string());
+
+    let mut stream = TcpStream::connect_timeout(&endpoint.resolved, BRP_TIMEOUT)?;
+    stream.set_read_timeout(Some(BRP_TIMEOUT))?;
+    stream.set_write_timeout(Some(BRP_TIMEOUT))?;
+    write!(
+        stream,
+        "POST / HTTP/1.1\r\nHost: {}\r\nContent-Type: application/json\r\n\
+        Content-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        endpoint.address,
+        body.len()
+    )?;
+    let mut response = vec![];
+    stream.read_to_end(&mut response)?;
+
+    let split = response
+        .windows(4)
+        .position(|window| window == b"\r\n\r\n")
+        .ok_or_else(|| invalid("malformed HTTP response"))?;
+    let head = String::from_utf8_lossy(&response[..split]).to_ascii_lowercase();
+    let body = &response[split + 4..];
+
+    let status = head.split_whitespace().nth(1).unwrap_or("");
+    if status != "200" {
+        return Err(invalid(&format!("HTTP status {status}")));
+    }
+    match head.contains("transfer-encoding: chunked") {
+        true => dechunk(body).ok_or_else(|| invalid("malformed chunked body")),
+        false => Ok(body.to_vec()),
+    }
+}
+
+fn dechunk(mut body: &[u8]) -> Option<Vec<u8>> {
+    let mut out = vec![];
+    loop {
+        let line_end = body.windows(2).position(|window| window == b"\r\n")?;
+        let size = std::str::from_utf8(&body[..line_end]).ok()?;
+        let size = usize::from_str_radix(size.split(';').next()?.trim(), 16).ok()?;
+        body = &body[line_end + 2..];
+        if size == 0 {
+            return Some(out);
+        }
+        out.extend_from_slice(body.get(..size)?);
+        body = body.get(size + 2..)?;
+    }
+}
@@ -0,0 +1,239 @@
+//! The automation module. Inspector actions can be queued in a [`SpyglassScript`], which runs them
+//! over the following frames. This allows reproducible demos and tutorials, and automated tests
+//! of the inspector itself. Scripts can also be recorded from inspector interactions with the
+//! [`record`] module.
+
+pub mod record;
+
+use std::collections::VecDeque;
+
+use bevy::prelude::*;
+use bevy::reflect::GetPath;
+
+use crate::tabs::entities::editors::{type_path, EditorStates};
+use crate::tabs::entities::{Popups, SelectedEntity};
+use crate::Spyglass;
+
+/// An action of a [`SpyglassScript`]. Usually created through the helper structs in this
+/// module, such as [`SelectEntityByName`].
+pub enum ScriptAction {
+    /// Select the tab with the given name.
+    SelectTab(String),
+    /// Select the first entity with the given [`Name`], and switch to the `Entities` tab.
+    SelectEntityByName(String),
+    /// Select the given entity, and switch to the `Entities` tab.
+    SelectEntity(Entity),
+    /// Go back to the entity list.
+    Deselect,
+    /// Expand the editor of a component of the selected entity, by its short type name. If the
+    /// entity has several components with that short name, it is followed by the full type name in
+    /// parentheses, as in the component header, e.g. `Health (game::player::Health)`.
+    ExpandComponent(String),
+    /// Set a field of the selected entity. The path starts with the name of the component as in
+    /// [`ExpandComponent`](Self::ExpandComponent), unless it is a field of the last expanded
+    /// component, e.g. `Transform.scale.x`.
+    SetField(String, Box<dyn Reflect>),
+    /// Do nothing for the given number of frames.
+    Wait(usize),
+}
+
+impl Clone for ScriptAction {
+    fn clone(&self) -> Self {
+        match self {
+            Self::SelectTab(name) => Self::SelectTab(name.clone()),
+            Self::SelectEntityByName(name) => Self::SelectEntityByName(name.clone()),
+            Self::SelectEntity(entity) => Self::SelectEntity(*entity),
+            Self::Deselect => Self::Deselect,
+            Self::ExpandComponent(name) => Self::ExpandComponent(name.clone()),
+            Self::SetField(path, value) => Self::SetField(path.clone(), value.clone_value()),
+            Self::Wait(frames) => Self::Wait(*frames),
+        }
+    }
+}
+
+/// Select the tab with the given name. See [`ScriptAction::SelectTab`].
+pub struct SelectTab<S>(pub S);
+
+impl<S: Into<String>> From<SelectTab<S>> for ScriptAction {
+    fn from(SelectTab(name): SelectTab<S>) -> Self {
+        Self::SelectTab(name.into())
+    }
+}
+
+/// Select an entity by name. See [`ScriptAction::SelectEntityByName`].
+pub struct SelectEntityByName<S>(pub S);
+
+impl<S: Into<String>> From<SelectEntityByName<S>> for ScriptAction {
+    fn from(SelectEntityByName(name): SelectEntityByName<S>) -> Self {
+        Self::SelectEntityByName(name.into())
+    }
+}
+
+/// Expand a component. See [`ScriptAction::ExpandComponent`].
+pub struct ExpandComponent<S>(pub S);
+
+impl<S: Into<String>> From<ExpandComponent<S>> for ScriptAction {
+    fn from(ExpandComponent(name): ExpandComponent<S>) -> Self {
+        Self::ExpandComponent(name.into())
+    }
+}
+
+/// Set a field. See [`ScriptAction::SetField`].
+pub struct SetField<S, T>(pub S, pub T);
+
+impl<S: Into<String>, T: Reflect> From<SetField<S, T>> for ScriptAction {
+    fn from(SetField(path, value): SetField<S, T>) -> Self {
+        Self::SetField(path.into(), Box::new(value))
+    }
+}
+
+/// Wait a number of frames. See [`ScriptAction::Wait`].
+pub struct WaitFrames(pub usize);
+
+impl From<WaitFrames> for ScriptAction {
+    fn from(WaitFrames(frames): WaitFrames) -> Self {
+        Self::Wait(frames)
+    }
+}
+
+/// The resource that runs queued [`ScriptAction`]s, one per frame. If an action fails, a
+/// [`Popup`](crate::tabs::entities::Popup) describes why and the rest of the script is dropped.
+#[derive(Default, Resource)]
+pub struct SpyglassScript {
+    actions: VecDeque<ScriptAction>,
+    component: Option<String>,
+}
+
+impl SpyglassScript {
+    /// Queue an action after the already queued ones.
+    pub fn push(&mut self, action: impl Into<ScriptAction>) -> &mut Self {
+        self.actions.push_back(action.into());
+        self
+    }
+
+    /// Returns whether every queued action has run.
+    pub fn is_finished(&self) -> bool {
+        self.actions.is_empty()
+    }
+
+    /// Drop every queued action.
+    pub fn clear(&mut self) {
+        self.actions.clear();
+    }
+
+    fn run(&mut self, world: &mut World, action: ScriptAction) -> Result<(), String> {
+        match action {
+            ScriptAction::SelectTab(name) => select_tab(world, &name),
+            ScriptAction::SelectEntityByName(name) => {
+                let mut names = world.query::<(Entity, &Name)>();
+                let entity = names
+                    .iter(world)
+                    .find(|(_, entity_name)| entity_name.as_str() == name)
+                    .map(|(entity, _)| entity)
+                    .ok_or_else(|| format!("no entity is named `{name}`"))?;
+                self.select(world, entity)
+            }
+            ScriptAction::SelectEntity(entity) => self.select(world, entity),
+            ScriptAction::Deselect => {
+                world.remove_resource::<SelectedEntity>();
+                self.component = None;
+                Ok(())
+            }
+            ScriptAction::ExpandComponent(name) => {
+                let selected = world
+                    .get_resource::<SelectedEntity>()
+                    .ok_or("no entity is selected")?;
+                selected
+                    .repr_name(&name)
+                    .ok_or_else(|| format!("the selected entity has no editable `{name}`"))?;
+                world.resource_mut::<EditorStates>().request_expand(&name);
+                self.component = Some(name);
+                Ok(())
+            }
+            ScriptAction::SetField(path, value) => {
+                let mut selected = world
+                    .remove_resource::<SelectedEntity>()
+                    .ok_or("no entity is selected")?;
+                let result = self.set_field(world, &mut selected, &path, &*value);
+                world.insert_resource(selected);
+                result
+            }
+            ScriptAction::Wait(frames) => {
+                if frames > 1 {
+                    self.actions.push_front(ScriptAction::Wait(frames - 1));
+                }
+                Ok(())
+            }
+        }
+    }
+
+    fn set_field(
+        &self,
+        world: &World,
+        selected: &mut SelectedEntity,
+        path: &str,
+        value: &dyn Reflect,
+    ) -> Result<(), String> {
+        let (component, field) = match path.split_once('.') {
+            Some((component, field)) if selected.repr_name(component).is_some() => {
+                (component.to_string(), field)
+            }
+            _ => (
+                self.component
+                    .clone()
+                    .ok_or_else(|| format!("`{path}` does not start with a component"))?,
+                path,
+            ),
+        };
+        let repr = selected
+            .repr_mut(world, &component)
+            .ok_or_else(|| format!("the selected entity has no editable `{component}`"))?;
+        let target = repr
+            .as_mut()
+            .reflect_path_mut(field)
+            .map_err(|err| format!("invalid path `{path}`: {err}"))?;
+        if type_path(target) != type_path(value) {
+            return Err(format!(
+                "`{path}` is a `{}`, not a `{}`",
+                type_path(target),
+                type_path(value)
+            ));
+        }
+        target.apply(value);
+        Ok(())
+    }
+
+    fn select(&mut self, world: &mut World, entity: Entity) -> Result<(), String> {
+        if world.get_entity(entity).is_none() {
+            return Err(format!("{entity:?} does not exist"));
+        }
+        let selected = SelectedEntity::new(world, entity);
+        world.insert_resource(selected);
+        self.component = None;
+        select_tab(world, "Entities")
+    }
+}
+
+fn select_tab(world: &mut World, name: &str) -> Result<(), String> {
+    match world.resource_mut::<Spyglass>().select_tab_by_name(name) {
+        true => Ok(()),
+        false => Err(format!("there is no `{name}` tab")),
+    }
+}
+
+pub(crate) fn run_script(world: &mut World) {
+    let Some(mut script) = world.remove_resource::<SpyglassScript>() else {
+        return;
+    };
+
+    if let Some(action) = script.actions.pop_front() {
+        if let Err(err) = script.run(world, action) {
+            script.clear();
+            if let Some(mut popups) = world.get_resource_mut::<Popups>() {
+                popups.error(format!("spyglass script failed: {err}"));
+            }
+        }
+    }
+
+    world.insert_resource(script);
+}
@@ -0,0 +1,282 @@
+//! Recording of inspector interactions. While recording, the [`ScriptRecorder`] watches the
+//! inspector for tab and entity selections, expanded components and edited fields, and turns
+//! them into [`ScriptAction`]s. Recordings can be replayed as a [`SpyglassScript`], or exported
+//! as Rust code for tutorials and repro steps.
+
+use std::fmt::Write;
+
+use bevy::prelude::*;
+use bevy::utils::{get_short_name, HashMap, HashSet};
+
+use super::{run_script, ScriptAction, SpyglassScript};
+use crate::compat::egui::Ui;
+use crate::tabs::entities::editors::EditorStates;
+use crate::tabs::entities::history::diff;
+use crate::tabs::entities::SelectedEntity;
+use crate::{Spyglass, SpyglassWindow};
+
+/// The plugin that adds the [`ScriptRecorder`], and its controls to the inspector. Must be added
+/// after the [`SpyglassPlugin`](crate::SpyglassPlugin).
+pub struct ScriptRecorderPlugin;
+
+impl Plugin for ScriptRecorderPlugin {
+    fn build(&self, app: &mut App) {
+        if cfg!(feature = "disabled") {
+            return;
+        }
+
+        app.init_resource::<ScriptRecorder>().add_systems(
+            Update,
+            (
+                snapshot_selection.after(run_script).before(SpyglassWindow),
+                record_interactions.after(SpyglassWindow),
+            ),
+        );
+    }
+}
+
+/// The resource that records inspector interactions as [`ScriptAction`]s.
+#[derive(Default, Resource)]
+pub struct ScriptRecorder {
+    recording: bool,
+    actions: Vec<ScriptAction>,
+    idle_frames: usize,
+    tab: Option<String>,
+    selected: Option<Entity>,
+    expanded: HashSet<String>,
+    before_entity: Option<Entity>,
+    before: HashMap<String, Box<dyn Reflect>>,
+}
+
+impl ScriptRecorder {
+    /// Start a new recording, dropping the previous one.
+    pub fn start(&mut self, world: &World) {
+        *self = Self {
+            recording: true,
+            tab: current_tab(world),
+            selected: world.get_resource::<SelectedEntity>().map(|s| s.id),
+            ..default()
+        };
+    }
+
+    /// Stop recording, keeping the recorded actions.
+    pub fn stop(&mut self) {
+        self.recording = false;
+        self.before_entity = None;
+        self.before.clear();
+    }
+
+    /// Whether the recorder is recording.
+    pub fn is_recording(&self) -> bool {
+        self.recording
+    }
+
+    /// The recorded actions, in order.
+    pub fn actions(&self) -> &[ScriptAction] {
+        &self.actions
+    }
+
+    /// Create a script that replays the recorded actions.
+    pub fn to_script(&self) -> SpyglassScript {
+        let mut script = SpyglassScript::default();
+        for action in self.actions.iter() {
+            script.push(action.clone());
+        }
+        script
+    }
+
+    /// Export the recorded actions as Rust code that queues them on a [`SpyglassScript`]. Values
+    /// of edited fields that are not primitives or strings are written with their debug
+    /// representation, which may need adjusting to compile.
+    pub fn to_rust(&self) -> String {
+        let mut code = "world\n    .resource_mut::<SpyglassScript>()\n".to_string();
+        for action in self.actions.iter() {
+            let action = match action {
+                ScriptAction::SelectTab(name) => format!("SelectTab({name:?})"),
+                ScriptAction::SelectEntityByName(name) => format!("SelectEntityByName({name:?})"),
+                ScriptAction::SelectEntity(entity) => {
+                    format!(
+                        "ScriptAction::SelectEntity(Entity::from_bits({}))",
+                        entity.to_bits()
+                    )
+                }
+                ScriptAction::Deselect => "ScriptAction::Deselect".to_string(),
+                ScriptAction::ExpandComponent(name) => format!("ExpandComponent({name:?})"),
+                ScriptAction::SetField(path, value) => {
+                    format!("SetField({path:?}, {})", rust_value(value.as_ref()))
+                }
+                ScriptAction::Wait(frames) => format!("WaitFrames({frames})"),
+            };
+            let _ = writeln!(code, "    .push({action})");
+        }
+        code.push_str("    ;\n");
+        code
+    }
+
+    fn push(&mut self, action: ScriptAction) {
+        // Dragging a value edits it every frame, which only needs its final value.
+        if let (ScriptAction::SetField(path, _), Some(ScriptAction::SetField(last, _))) =
+            (&action, self.actions.last())
+        {
+            if path == last && self.idle_frames <= 1 {
+                self.actions.pop();
+            }
+        }
+
+        if self.idle_frames > 1 {
+            self.actions.push(ScriptAction::Wait(self.idle_frames - 1));
+        }
+        self.actions.push(action);
+        self.idle_frames = 0;
+    }
+}
+
+fn rust_value(value: &dyn Reflect) -> String {
+    macro_rules! suffixed {
+        ($($ty:ty),*) => {
+            $(if let Some(value) = value.downcast_ref::<$ty>() {
+                return format!("{value:?}{}", stringify!($ty));
+            })*
+        };
+    }
+    suffixed!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize, f32, f64);
+
+    if let Some(value) = value.downcast_ref::<String>() {
+        return format!("String::from({value:?})");
+    }
+    format!("{value:?}")
+}
+
+fn current_tab(world: &World) -> Option<String> {
+    let spyglass = world.get_resource::<Spyglass>()?;
+    let tab = spyglass.tabs.get(spyglass.selected?)?;
+    Some(tab.name().to_string())
+}
+
+/// Draw the recording controls, if the [`ScriptRecorderPlugin`] was added.
+pub(crate) fn record_controls(ui: &mut Ui, world: &mut World) {
+    let Some(mut recorder) = world.remove_resource::<ScriptRecorder>() else {
+        return;
+    };
+
+    ui.horizontal(|ui| {
+        if recorder.is_recording() {
+            if ui.button("stop recording").clicked() {
+                recorder.stop();
+            }
+            ui.weak(format!("{} actions", recorder.actions().len()));
+        } else {
+            if ui.button("record").clicked() {
+                recorder.start(world);
+            }
+            if !recorder.actions().is_empty() {
+                if ui.button("replay").clicked() {
+                    world.insert_resource(recorder.to_script());
+                }
+                if ui.button("copy as code").clicked() {
+                    let code = recorder.to_rust();
+                    ui.output_mut(|output| output.copied_text = code);
+                }
+            }
+        }
+    });
+
+    world.insert_resource(recorder);
+}
+
+fn snapshot_selection(mut recorder: ResMut<ScriptRecorder>, selected: Option<Res<SelectedEntity>>) {
+    recorder.before.clear();
+    recorder.before_entity = None;
+    let (true, Some(selected)) = (recorder.recording, selected) else {
+        return;
+    };
+    recorder.before_entity = Some(selected.id);
+    recorder.before = selected
+        .reprs()
+        .map(|(name, repr)| (name.to_string(), repr.clone_value()))
+        .collect();
+}
+
+fn record_interactions(world: &mut World) {
+    let Some(mut recorder) = world.remove_resource::<ScriptRecorder>() else {
+        return;
+    };
+    if !recorder.recording {
+        world.insert_resource(recorder);
+        return;
+    }
+    recorder.idle_frames += 1;
+
+    let selected = world.get_resource::<SelectedEntity>();
+    let id = selected.map(|selected| selected.id);
+    if id != recorder.selected {
+        recorder.selected = id;
+        recorder.expanded.clear();
+        match id {
+            Some(id) => {
+                let action = match world.get::<Name>(id) {
+                    Some(name) => ScriptAction::SelectEntityByName(name.to_string()),
+                    None => ScriptAction::SelectEntity(id),
+                };
+                recorder.push(action);
+                // Selecting an entity switches to the entities tab.
+                recorder.tab = Some("Entities".to_string());
+            }
+            None => recorder.push(ScriptAction::Deselect),
+        }
+    }
+
+    let tab = current_tab(world);
+    if tab != recorder.tab {
+        if let Some(tab) = &tab {
+            recorder.push(ScriptAction::SelectTab(tab.clone()));
+        }
+        recorder.tab = tab;
+    }
+
+    if let Some(states) = world.get_resource::<EditorStates>() {
+        let components = states
+            .expanded()
+            .iter()
+            .filter(|path| !path.contains(['.', '[']))
+            .cloned()
+            .collect::<HashSet<_>>();
+        let mut opened = components
+            .difference(&recorder.expanded)
+            .cloned()
+            .collect::<Vec<_>>();
+        opened.sort_unstable();
+        for component in opened {
+            recorder.push(ScriptAction::ExpandComponent(component));
+        }
+        recorder.expanded = components;
+    }
+
+    let before = std::mem::take(&mut recorder.before);
+    if let Some(selected) = selected {
+        if recorder.before_entity == Some(selected.id) {
+            let mut edits = vec![];
+            for (name, now) in selected.reprs() {
+                let Some(past) = before.get(name) else {
+                    continue;
+                };
+                let mut diffs = vec![];
+                diff(String::new(), past.as_ref(), now, &mut diffs);
+                // A changed root can not be addressed as a field, so it is not recorded.
+                edits.extend(diffs.into_iter().filter(|(path, ..)| !path.is_empty()).map(
+                    |(path, _, now)| {
+                        ScriptAction::SetField(
+                            format!("{}{path}", get_short_name(name)),
+                            now.clone_value(),
+                        )
+                    },
+                ));
+            }
+            for edit in edits {
+                recorder.push(edit);
+            }
+        }
+    }
+
+    world.insert_resource(recorder);
+}
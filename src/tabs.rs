@@ -1,3 +1,31 @@
 //! A collection of builtin tabs that come with Spyglass.
 
+#[cfg(feature = "animation")]
+pub mod animation;
+pub mod assets;
+#[cfg(feature = "audio")]
+pub mod audio;
+pub mod camera;
+pub mod console;
 pub mod entities;
+pub mod input;
+#[cfg(any(feature = "rapier", feature = "xpbd"))]
+pub mod physics;
+#[cfg(feature = "profiler")]
+pub mod profiler;
+pub mod query;
+pub mod reflection;
+pub mod remote;
+pub mod render_errors;
+#[cfg(feature = "rhai")]
+pub mod scripted;
+pub mod settings;
+pub mod snapshots;
+pub mod spikes;
+pub mod telemetry;
+pub mod time;
+#[cfg(feature = "ui")]
+pub mod ui;
+pub mod visibility;
+pub mod watches;
+pub mod world_stats;
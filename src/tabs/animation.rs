@@ -0,0 +1,218 @@
+//! The animation tab module. Lists the entities with an [`AnimationPlayer`], with the clip they
+//! play, its elapsed time and speed, and controls to pause, seek, repeat or switch clips, so
+//! animations can be tuned without recompiling. Only available with the `animation` feature.
+
+use bevy::animation::RepeatAnimation;
+use bevy::prelude::*;
+
+use crate::compat::egui::{self, Ui};
+use crate::{Spyglass, SpyglassSettings, Tab};
+
+/// The plugin that adds the animation tab to the end of the [`Spyglass`] tab list.
+pub struct AnimationTabPlugin;
+
+impl Plugin for AnimationTabPlugin {
+    fn build(&self, app: &mut App) {
+        if cfg!(feature = "disabled") {
+            return;
+        }
+
+        let mut spyglass = app.world.resource_mut::<Spyglass>();
+        spyglass.add_tab(Box::new(AnimationTab));
+    }
+}
+
+/// A loaded clip, as listed in the clip dropdown.
+struct Clip {
+    id: AssetId<AnimationClip>,
+    name: String,
+    duration: f32,
+}
+
+/// The loaded clips, named by their asset path if they have one, sorted by name.
+fn clips(world: &World) -> Vec<Clip> {
+    let Some(assets) = world.get_resource::<Assets<AnimationClip>>() else {
+        return vec![];
+    };
+    let server = world.resource::<AssetServer>();
+    let mut clips = assets
+        .iter()
+        .map(|(id, clip)| Clip {
+            id,
+            name: server
+                .get_path(id)
+                .map_or_else(|| format!("{id:?}"), |path| path.to_string()),
+            duration: clip.duration(),
+        })
+        .collect::<Vec<_>>();
+    clips.sort_by(|a, b| a.name.cmp(&b.name));
+    clips
+}
+
+struct AnimationTab;
+
+impl Tab for AnimationTab {
+    fn name(&self) -> &str {
+        "Animation"
+    }
+
+    fn draw(&mut self, ui: &mut Ui, world: &mut World) {
+        let clips = clips(world);
+        let read_only = SpyglassSettings::is_read_only(world);
+        let mut picked = None;
+
+        let mut players = world.query::<(Entity, Option<&Name>, &mut AnimationPlayer)>();
+        let mut any = false;
+        for (entity, name, mut player) in players.iter_mut(world) {
+            any = true;
+            ui.group(|ui| {
+                ui.add_enabled_ui(!read_only, |ui| {
+                    if let Some(clip) = draw_player(ui, entity, name, &mut player, &clips) {
+                        picked = Some((entity, clip));
+                    }
+                });
+            });
+        }
+        if !any {
+            ui.weak("no animation players");
+        }
+
+        if let Some((entity, clip)) = picked {
+            // Clips loaded from a path are kept loaded by a strong handle, others are assumed to
+            // be kept loaded by whoever added them.
+            let handle = world
+                .resource::<AssetServer>()
+                .get_id_handle(clip)
+                .unwrap_or(Handle::Weak(clip));
+            if let Some(mut player) = world.get_mut::<AnimationPlayer>(entity) {
+                player.start(handle);
+            }
+        }
+    }
+}
+
+/// Draw the state and controls of a player. The player is only borrowed mutably when a control
+/// is used, so drawing it doesn't mark it changed. Returns the clip to start, if one was picked.
+fn draw_player(
+    ui: &mut Ui,
+    entity: Entity,
+    name: Option<&Name>,
+    player: &mut Mut<AnimationPlayer>,
+    clips: &[Clip],
+) -> Option<AssetId<AnimationClip>> {
+    let current = player.animation_clip().id();
+    let clip = clips.iter().find(|clip| clip.id == current);
+    let mut picked = None;
+
+    ui.horizontal(|ui| {
+        match name {
+            Some(name) => ui.strong(format!("{name} ({entity:?})")),
+            None => ui.strong(format!("{entity:?}")),
+        };
+        let state = match (player.is_finished(), player.is_paused()) {
+            (true, _) => "finished",
+            (false, true) => "paused",
+            (false, false) => "playing",
+        };
+        ui.label(state);
+    });
+
+    ui.horizontal(|ui| {
+        ui.label("clip");
+        egui::ComboBox::from_id_source(("animation_clip", entity))
+            .selected_text(clip.map_or("none", |clip| clip.name.as_str()))
+            .show_ui(ui, |ui| {
+                for clip in clips {
+                    let label = format!("{} ({:.2}s)", clip.name, clip.duration);
+                    if ui.selectable_label(clip.id == current, label).clicked() {
+                        picked = Some(clip.id);
+                    }
+                }
+            });
+    });
+
+    ui.horizontal(|ui| {
+        if player.is_paused() {
+            if ui.button("play").clicked() {
+                player.resume();
+            }
+        } else if ui.button("pause").clicked() {
+            player.pause();
+        }
+        if ui
+            .button("replay")
+            .on_hover_text("Restart the clip from the beginning")
+            .clicked()
+        {
+            player.replay();
+        }
+    });
+
+    egui::Grid::new(("animation_player", entity)).show(ui, |ui| {
+        ui.label("elapsed");
+        ui.label(format!("{:.2}s", player.elapsed()));
+        ui.end_row();
+
+        let duration = clip.map_or(0.0, |clip| clip.duration);
+        let mut seek = player.seek_time();
+        ui.label("time");
+        if ui
+            .add_enabled(
+                duration > 0.0,
+                egui::Slider::new(&mut seek, 0.0..=duration).suffix("s"),
+            )
+            .changed()
+        {
+            player.seek_to(seek);
+        }
+        ui.end_row();
+
+        let mut speed = player.speed();
+        ui.label("speed");
+        if ui
+            .add(egui::Slider::new(&mut speed, -4.0..=4.0))
+            .on_hover_text("Negative speeds play the clip backwards")
+            .changed()
+        {
+            player.set_speed(speed);
+        }
+        ui.end_row();
+
+        ui.label("repeat");
+        ui.horizontal(|ui| {
+            let mut repeat = player.repeat_mode();
+            let mut count = match repeat {
+                RepeatAnimation::Count(count) => count,
+                _ => 1,
+            };
+            let mut changed = false;
+            for (mode, label) in [
+                (RepeatAnimation::Never, "never"),
+                (RepeatAnimation::Count(count), "count"),
+                (RepeatAnimation::Forever, "forever"),
+            ] {
+                let selected = std::mem::discriminant(&repeat) == std::mem::discriminant(&mode);
+                if ui.selectable_label(selected, label).clicked() && !selected {
+                    repeat = mode;
+                    changed = true;
+                }
+            }
+            if let RepeatAnimation::Count(_) = repeat {
+                if ui
+                    .add(egui::DragValue::new(&mut count).clamp_range(1..=u32::MAX))
+                    .changed()
+                {
+                    repeat = RepeatAnimation::Count(count);
+                    changed = true;
+                }
+                ui.weak(format!("{} done", player.completions()));
+            }
+            if changed {
+                player.set_repeat(repeat);
+            }
+        });
+        ui.end_row();
+    });
+
+    picked
+}
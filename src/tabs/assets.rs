@@ -0,0 +1,405 @@
+//! The assets tab module. Lists the loaded scenes and spawns instances of them at a chosen
+//! transform, which speeds up iterating on content. Scenes loaded from glTF files are listed as
+//! their labelled sub-assets, such as `model.gltf#Scene0`.
+//!
+//! Also shows the load state of every asset loaded by the [`AssetServer`] that is either stored
+//! or referenced by a component, with the reason failed assets failed, and reloads them. Images
+//! can be opened in the [`ImagePreview`] window, and any file in the [`AssetFolder`] can be
+//! loaded by path.
+
+use bevy::asset::{
+    AssetPath, LoadState, LoadedUntypedAsset, ReflectAsset, ReflectHandle, UntypedAssetId,
+};
+use bevy::prelude::*;
+use bevy::utils::{get_short_name, HashMap, HashSet};
+
+use crate::compat::egui::{self, Ui};
+use crate::tabs::entities::editors::EditorStates;
+use crate::tabs::entities::selection::SelectEntity;
+use crate::tabs::entities::ReprEditors;
+use crate::widgets::{asset_path_picker, AssetFolder, ImagePreview};
+use crate::{Spyglass, Tab};
+
+/// The plugin that adds the assets tab to the end of the [`Spyglass`] tab list.
+pub struct AssetsTabPlugin;
+
+impl Plugin for AssetsTabPlugin {
+    fn build(&self, app: &mut App) {
+        if cfg!(feature = "disabled") {
+            return;
+        }
+
+        let mut spyglass = app.world.resource_mut::<Spyglass>();
+        spyglass.add_tab(Box::new(AssetsTab::default()));
+    }
+}
+
+/// A scene asset that can be spawned, see [`spawn_scene`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SceneAsset {
+    /// A [`Scene`], e.g. loaded from a glTF file.
+    Scene(AssetId<Scene>),
+    /// A [`DynamicScene`], e.g. loaded from a `.scn.ron` file.
+    Dynamic(AssetId<DynamicScene>),
+}
+
+impl SceneAsset {
+    /// The path the scene was loaded from, if it was loaded by the [`AssetServer`].
+    pub fn path(self, world: &World) -> Option<AssetPath<'static>> {
+        let server = world.get_resource::<AssetServer>()?;
+        match self {
+            Self::Scene(id) => server.get_path(id),
+            Self::Dynamic(id) => server.get_path(id),
+        }
+        .map(AssetPath::into_owned)
+    }
+
+    /// The number of entities in the scene, or `None` if the asset is missing.
+    fn entities(self, world: &World) -> Option<usize> {
+        match self {
+            Self::Scene(id) => world
+                .get_resource::<Assets<Scene>>()?
+                .get(id)
+                .map(|scene| scene.world.entities().len() as usize),
+            Self::Dynamic(id) => world
+                .get_resource::<Assets<DynamicScene>>()?
+                .get(id)
+                .map(|scene| scene.entities.len()),
+        }
+    }
+}
+
+/// Spawn an instance of `scene` at `transform`, and return the root entity the scene is spawned
+/// under. The root is named after the path of the scene, if it has one.
+///
+/// The scene spawner keeps a handle to the scene. If the asset was loaded by the
+/// [`AssetServer`], that handle is strong, and the instance keeps the scene loaded. Otherwise it
+/// is weak, and the scene must be kept alive by another handle until the instance is spawned.
+pub fn spawn_scene(world: &mut World, scene: SceneAsset, transform: Transform) -> Entity {
+    let name = scene
+        .path(world)
+        .map_or_else(|| "scene instance".to_string(), |path| path.to_string());
+    let server = world.get_resource::<AssetServer>();
+    match scene {
+        SceneAsset::Scene(id) => {
+            let scene = server
+                .and_then(|server| server.get_id_handle(id))
+                .unwrap_or(Handle::Weak(id));
+            world.spawn((
+                Name::new(name),
+                SceneBundle {
+                    scene,
+                    transform,
+                    ..default()
+                },
+            ))
+        }
+        SceneAsset::Dynamic(id) => {
+            let scene = server
+                .and_then(|server| server.get_id_handle(id))
+                .unwrap_or(Handle::Weak(id));
+            world.spawn((
+                Name::new(name),
+                DynamicSceneBundle {
+                    scene,
+                    transform,
+                    ..default()
+                },
+            ))
+        }
+    }
+    .id()
+}
+
+/// Find out why the asset at `path` failed to load. The asset server only logs the errors of
+/// failed loads, so this checks the common causes again: a missing asset source, a missing file,
+/// and a missing loader. If none of them apply, the loader itself failed.
+pub fn failure_reason(server: &AssetServer, path: &AssetPath) -> String {
+    bevy::tasks::block_on(async {
+        let source = match server.get_source(path.source()) {
+            Ok(source) => source,
+            Err(err) => return err.to_string(),
+        };
+        if let Err(err) = source.reader().read(path.path()).await {
+            return err.to_string();
+        }
+        if let Err(err) = server.get_path_asset_loader(path).await {
+            return err.to_string();
+        }
+        "the loader failed, its error is in the log".to_string()
+    })
+}
+
+/// Every asset stored in the [`Assets`] of a reflected asset type, or referenced by a reflected
+/// handle component.
+fn asset_ids(world: &World) -> HashSet<UntypedAssetId> {
+    let mut ids = HashSet::new();
+    let registry = world.resource::<AppTypeRegistry>().read();
+    for registration in registry.iter() {
+        if let Some(reflect) = registration.data::<ReflectAsset>() {
+            let stored = world
+                .components()
+                .get_resource_id(reflect.assets_resource_type_id())
+                .is_some_and(|id| world.get_resource_by_id(id).is_some());
+            if stored {
+                ids.extend(reflect.ids(world));
+            }
+        }
+
+        let (Some(handle), Some(reflect)) = (
+            registration.data::<ReflectHandle>(),
+            registration.data::<ReflectComponent>(),
+        ) else {
+            continue;
+        };
+        let Some(comp) = world.components().get_id(registration.type_id()) else {
+            continue;
+        };
+        for archetype in world.archetypes().iter().filter(|a| a.contains(comp)) {
+            for entity in archetype.entities() {
+                let value = reflect.reflect(world.entity(entity.entity()));
+                if let Some(handle) = value.and_then(|v| handle.downcast_handle_untyped(v.as_any()))
+                {
+                    ids.insert(handle.id());
+                }
+            }
+        }
+    }
+    ids
+}
+
+/// An asset loaded by the [`AssetServer`], tracked by the assets tab.
+struct TrackedAsset {
+    path: AssetPath<'static>,
+    type_name: String,
+    state: LoadState,
+    /// Why the asset failed to load, found once it failed.
+    failure: Option<String>,
+    /// The image, if the asset is one.
+    image: Option<AssetId<Image>>,
+}
+
+/// A scene listed by the assets tab.
+struct SceneRow {
+    scene: SceneAsset,
+    label: String,
+    entities: usize,
+}
+
+#[derive(Default)]
+struct AssetsTab {
+    filter: String,
+    /// Where instances are spawned.
+    transform: Transform,
+    scenes: Vec<SceneRow>,
+    problems_only: bool,
+    /// The tracked assets, which are kept until the asset server drops them, so that assets
+    /// that fail are still listed once nothing references them.
+    assets: HashMap<UntypedAssetId, TrackedAsset>,
+    /// The path of the asset to load.
+    load_path: String,
+    /// The handles of the assets loaded by path, which keep them loaded.
+    loaded: Vec<Handle<LoadedUntypedAsset>>,
+}
+
+impl Tab for AssetsTab {
+    fn name(&self) -> &str {
+        "Assets"
+    }
+
+    fn collect(&mut self, world: &mut World) {
+        let mut scenes = vec![];
+        if let Some(assets) = world.get_resource::<Assets<Scene>>() {
+            scenes.extend(assets.ids().map(SceneAsset::Scene));
+        }
+        if let Some(assets) = world.get_resource::<Assets<DynamicScene>>() {
+            scenes.extend(assets.ids().map(SceneAsset::Dynamic));
+        }
+        self.scenes = scenes
+            .into_iter()
+            .map(|scene| SceneRow {
+                scene,
+                label: scene.path(world).map_or_else(
+                    || match scene {
+                        SceneAsset::Scene(_) => "unnamed scene".to_string(),
+                        SceneAsset::Dynamic(_) => "unnamed dynamic scene".to_string(),
+                    },
+                    |path| path.to_string(),
+                ),
+                entities: scene.entities(world).unwrap_or_default(),
+            })
+            .collect();
+        self.scenes.sort_by(|a, b| a.label.cmp(&b.label));
+
+        let Some(server) = world.get_resource::<AssetServer>() else {
+            return;
+        };
+        let registry = world.resource::<AppTypeRegistry>().read();
+        for id in asset_ids(world) {
+            let Some(path) = server.get_path(id) else {
+                continue;
+            };
+            self.assets.entry(id).or_insert_with(|| TrackedAsset {
+                path: path.into_owned(),
+                type_name: registry
+                    .get_type_info(id.type_id())
+                    .map_or("asset", |info| info.type_path_table().short_path())
+                    .to_string(),
+                state: LoadState::NotLoaded,
+                failure: None,
+                image: (id.type_id() == std::any::TypeId::of::<Image>()).then(|| id.typed()),
+            });
+        }
+        self.assets.retain(|&id, asset| {
+            let Some(state) = server.get_load_state(id) else {
+                return false;
+            };
+            if state != LoadState::Failed {
+                asset.failure = None;
+            } else if asset.failure.is_none() {
+                asset.failure = Some(failure_reason(server, &asset.path));
+            }
+            asset.state = state;
+            true
+        });
+    }
+
+    fn draw(&mut self, ui: &mut Ui, world: &mut World) {
+        self.draw_scenes(ui, world);
+        ui.separator();
+        self.draw_load_states(ui, world);
+    }
+}
+
+impl AssetsTab {
+    fn draw_scenes(&mut self, ui: &mut Ui, world: &mut World) {
+        ui.strong("Scenes");
+        ui.label("Instances are spawned at:");
+        let editors = world.remove_resource::<ReprEditors>().unwrap();
+        let mut states = world.remove_resource::<EditorStates>().unwrap();
+        states.begin_frame(false);
+        ui.push_id("scene_spawn_transform", |ui| {
+            states.push_path("Transform");
+            let editor = editors.get(&self.transform);
+            editor(ui, &mut self.transform, world, &editors, &mut states);
+            states.pop_path();
+        });
+        states.end_frame();
+        world.insert_resource(states);
+        world.insert_resource(editors);
+        ui.separator();
+
+        egui::TextEdit::singleline(&mut self.filter)
+            .hint_text("Filter scenes and assets")
+            .show(ui);
+        if self.scenes.is_empty() {
+            ui.weak("no scenes are loaded");
+        }
+        let filter = self.filter.to_lowercase();
+        let mut spawn = None;
+        egui::Grid::new("assets_scenes")
+            .striped(true)
+            .show(ui, |ui| {
+                for row in self
+                    .scenes
+                    .iter()
+                    .filter(|row| row.label.to_lowercase().contains(&filter))
+                {
+                    ui.label(&row.label)
+                        .on_hover_text(format!("{:?}", row.scene));
+                    ui.label(format!("{} entities", row.entities));
+                    if ui.button("spawn instance").clicked() {
+                        spawn = Some(row.scene);
+                    }
+                    ui.end_row();
+                }
+            });
+
+        if let Some(scene) = spawn {
+            let root = spawn_scene(world, scene, self.transform);
+            world.send_event(SelectEntity(Some(root)));
+        }
+    }
+
+    fn draw_load_states(&mut self, ui: &mut Ui, world: &mut World) {
+        ui.strong("Load states");
+        let Some(server) = world.get_resource::<AssetServer>() else {
+            ui.weak("there is no asset server");
+            return;
+        };
+        ui.horizontal(|ui| {
+            egui::TextEdit::singleline(&mut self.load_path)
+                .hint_text("Path of an asset to load")
+                .show(ui);
+            if let Some(folder) = world.get_resource::<AssetFolder>() {
+                asset_path_picker(ui, &mut self.load_path, folder, &[]);
+            }
+            let path = self.load_path.trim();
+            if ui
+                .add_enabled(!path.is_empty(), egui::Button::new("load"))
+                .clicked()
+            {
+                self.loaded.push(server.load_untyped(path.to_string()));
+            }
+        });
+        ui.checkbox(
+            &mut self.problems_only,
+            "only show loading and failed assets",
+        );
+
+        let mut assets = self
+            .assets
+            .values()
+            .filter(|asset| {
+                !self.problems_only || matches!(asset.state, LoadState::Loading | LoadState::Failed)
+            })
+            .filter(|asset| {
+                asset
+                    .path
+                    .to_string()
+                    .to_lowercase()
+                    .contains(&self.filter.to_lowercase())
+            })
+            .collect::<Vec<_>>();
+        assets.sort_by_key(|asset| (asset.state != LoadState::Failed, asset.path.to_string()));
+        if assets.is_empty() {
+            ui.weak("no assets were loaded by the asset server");
+        }
+        let mut preview = None;
+        egui::Grid::new("assets_load_states")
+            .striped(true)
+            .show(ui, |ui| {
+                for asset in assets {
+                    ui.label(asset.path.to_string());
+                    ui.label(get_short_name(&asset.type_name));
+                    if asset.state == LoadState::Failed {
+                        ui.colored_label(ui.visuals().error_fg_color, "failed");
+                    } else {
+                        ui.label(format!("{:?}", asset.state).to_lowercase());
+                    }
+                    ui.horizontal(|ui| {
+                        if ui.small_button("reload").clicked() {
+                            server.reload(asset.path.clone());
+                        }
+                        if let Some(image) = asset.image {
+                            if ui.small_button("preview").clicked() {
+                                preview = Some(image);
+                            }
+                        }
+                    });
+                    ui.end_row();
+                    if let Some(failure) = &asset.failure {
+                        ui.label("");
+                        ui.colored_label(ui.visuals().error_fg_color, failure);
+                        ui.end_row();
+                    }
+                }
+            });
+        if let Some(image) = preview {
+            world.insert_resource(ImagePreview {
+                image: Some(image),
+                zoom: 1.0,
+            });
+        }
+    }
+}
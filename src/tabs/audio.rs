@@ -0,0 +1,123 @@
+//! The audio tab module. Lists the entities that are playing audio, with controls for their
+//! playback, volume and speed, and the [`GlobalVolume`]. Only available with the `audio` feature.
+
+use bevy::audio::{AudioSink, AudioSinkPlayback, GlobalVolume, SpatialAudioSink, VolumeLevel};
+use bevy::prelude::*;
+
+use crate::compat::egui::{self, Ui};
+use crate::{Spyglass, Tab};
+
+/// The plugin that adds the audio tab to the end of the [`Spyglass`] tab list.
+pub struct AudioTabPlugin;
+
+impl Plugin for AudioTabPlugin {
+    fn build(&self, app: &mut App) {
+        if cfg!(feature = "disabled") {
+            return;
+        }
+
+        let mut spyglass = app.world.resource_mut::<Spyglass>();
+        spyglass.add_tab(Box::new(AudioTab));
+    }
+}
+
+struct AudioTab;
+
+impl Tab for AudioTab {
+    fn name(&self) -> &str {
+        "Audio"
+    }
+
+    fn draw(&mut self, ui: &mut Ui, world: &mut World) {
+        if let Some(mut global) = world.get_resource_mut::<GlobalVolume>() {
+            ui.horizontal(|ui| {
+                let mut volume = global.volume.get();
+                ui.label("global volume");
+                if ui
+                    .add(egui::Slider::new(&mut volume, 0.0..=2.0))
+                    .on_hover_text("Only affects audio that starts playing afterwards")
+                    .changed()
+                {
+                    global.volume = VolumeLevel::new(volume);
+                }
+            });
+            ui.separator();
+        }
+
+        let mut sinks = world.query::<(Entity, Option<&Name>, &AudioSink)>();
+        let mut spatial = world.query::<(Entity, Option<&Name>, &SpatialAudioSink)>();
+        let mut any = false;
+        for (entity, name, sink) in sinks.iter(world) {
+            any = true;
+            draw_sink(ui, entity, name, false, sink);
+        }
+        for (entity, name, sink) in spatial.iter(world) {
+            any = true;
+            draw_sink(ui, entity, name, true, sink);
+        }
+        if !any {
+            ui.weak("nothing is playing");
+        }
+    }
+}
+
+fn draw_sink(
+    ui: &mut Ui,
+    entity: Entity,
+    name: Option<&Name>,
+    spatial: bool,
+    sink: &impl AudioSinkPlayback,
+) {
+    ui.group(|ui| {
+        ui.horizontal(|ui| {
+            match name {
+                Some(name) => ui.strong(format!("{name} ({entity:?})")),
+                None => ui.strong(format!("{entity:?}")),
+            };
+            if spatial {
+                ui.weak("spatial");
+            }
+            let state = match (sink.empty(), sink.is_paused()) {
+                (true, _) => "stopped",
+                (false, true) => "paused",
+                (false, false) => "playing",
+            };
+            ui.label(state);
+        });
+
+        ui.horizontal(|ui| {
+            let stopped = sink.empty();
+            ui.add_enabled_ui(!stopped, |ui| {
+                if sink.is_paused() {
+                    if ui.button("play").clicked() {
+                        sink.play();
+                    }
+                } else if ui.button("pause").clicked() {
+                    sink.pause();
+                }
+                if ui.button("stop").clicked() {
+                    sink.stop();
+                }
+            });
+        });
+
+        egui::Grid::new(("audio_sink", entity)).show(ui, |ui| {
+            let mut volume = sink.volume();
+            ui.label("volume");
+            if ui.add(egui::Slider::new(&mut volume, 0.0..=2.0)).changed() {
+                sink.set_volume(volume);
+            }
+            ui.end_row();
+
+            let mut speed = sink.speed();
+            ui.label("speed");
+            if ui
+                .add(egui::Slider::new(&mut speed, 0.1..=4.0).logarithmic(true))
+                .changed()
+            {
+                sink.set_speed(speed);
+            }
+            ui.end_row();
+        });
+    });
+}
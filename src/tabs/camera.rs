@@ -0,0 +1,172 @@
+//! The camera tab module. Lists every [`Camera`] with its render target, order and viewport, can
+//! toggle whether cameras are active, and can fly a [`DebugCamera`] to frame the entity selected
+//! in the entities tab.
+
+use bevy::prelude::*;
+use bevy::render::camera::RenderTarget;
+use bevy::render::primitives::Aabb;
+use bevy::window::WindowRef;
+
+use crate::compat::egui::{self, Ui};
+use crate::tabs::entities::{Popups, SelectedEntity};
+use crate::{Spyglass, Tab};
+
+/// How far away from the selected entity the [`DebugCamera`] is placed, relative to the size of
+/// its bounding box. Entities without one are framed as if they were a unit cube.
+const FRAMING_DISTANCE: f32 = 3.0;
+
+/// The plugin that adds the camera tab to the end of the [`Spyglass`] tab list.
+pub struct CameraTabPlugin;
+
+impl Plugin for CameraTabPlugin {
+    fn build(&self, app: &mut App) {
+        if cfg!(feature = "disabled") {
+            return;
+        }
+
+        let mut spyglass = app.world.resource_mut::<Spyglass>();
+        spyglass.add_tab(Box::new(CameraTab));
+
+        app.register_type::<DebugCamera>().init_resource::<Popups>();
+    }
+}
+
+/// Marks the camera that the camera tab moves to frame the selected entity. Can be inserted by
+/// the app on a dedicated debug camera, or from the camera tab on any camera.
+#[derive(Clone, Copy, Debug, Default, Component, Reflect)]
+#[reflect(Component)]
+pub struct DebugCamera;
+
+/// Move every [`DebugCamera`] so that it looks at `target` from its current direction, far enough
+/// away to frame it.
+pub fn frame_entity(world: &mut World, target: Entity) -> Result<(), String> {
+    let Some(global) = world.get::<GlobalTransform>(target) else {
+        return Err("the selected entity has no `GlobalTransform`".into());
+    };
+    let (center, radius) = match world.get::<Aabb>(target) {
+        Some(aabb) => {
+            let scale = global.compute_transform().scale;
+            let center = global.transform_point(aabb.center.into());
+            (center, (Vec3::from(aabb.half_extents) * scale).length())
+        }
+        None => (global.translation(), 0.5),
+    };
+    let distance = radius.max(0.1) * FRAMING_DISTANCE;
+
+    let mut cameras = world.query_filtered::<(Entity, &GlobalTransform), With<DebugCamera>>();
+    let cameras = cameras
+        .iter(world)
+        .map(|(entity, global)| (entity, global.forward()))
+        .collect::<Vec<_>>();
+    if cameras.is_empty() {
+        return Err("there is no `DebugCamera`".into());
+    }
+
+    for (camera, forward) in cameras {
+        let eye = center - forward * distance;
+        let mut desired = Transform::from_translation(eye).looking_at(center, Vec3::Y);
+        if let Some(parent) = world.get::<Parent>(camera).map(Parent::get) {
+            if let Some(parent) = world.get::<GlobalTransform>(parent) {
+                desired = GlobalTransform::from(desired).reparented_to(parent);
+            }
+        }
+        if let Some(mut transform) = world.get_mut::<Transform>(camera) {
+            *transform = desired;
+        }
+    }
+    Ok(())
+}
+
+struct CameraTab;
+
+impl Tab for CameraTab {
+    fn name(&self) -> &str {
+        "Cameras"
+    }
+
+    fn draw(&mut self, ui: &mut Ui, world: &mut World) {
+        let selected = world.get_resource::<SelectedEntity>().map(|s| s.id);
+        ui.horizontal(|ui| {
+            let fly = ui
+                .add_enabled(selected.is_some(), egui::Button::new("fly to selected"))
+                .on_hover_text("Move the debug cameras to frame the selected entity")
+                .on_disabled_hover_text("Select an entity in the entities tab first");
+            if let (true, Some(selected)) = (fly.clicked(), selected) {
+                if let Err(err) = frame_entity(world, selected) {
+                    world
+                        .resource_mut::<Popups>()
+                        .error(format!("can't fly to the selection: {err}"));
+                }
+            }
+        });
+        ui.separator();
+
+        let mut cameras = world.query::<(Entity, Option<&Name>, &Camera, Has<DebugCamera>)>();
+        let mut toggled = vec![];
+        let mut debug = vec![];
+        let mut any = false;
+        egui::Grid::new("cameras").striped(true).show(ui, |ui| {
+            ui.strong("camera");
+            ui.strong("active");
+            ui.strong("order");
+            ui.strong("target");
+            ui.strong("viewport");
+            ui.strong("debug");
+            ui.end_row();
+
+            for (entity, name, camera, is_debug) in cameras.iter(world) {
+                any = true;
+                match name {
+                    Some(name) => ui.label(format!("{name} ({entity:?})")),
+                    None => ui.label(format!("{entity:?}")),
+                };
+                let mut active = camera.is_active;
+                if ui.checkbox(&mut active, "").changed() {
+                    toggled.push((entity, active));
+                }
+                ui.label(camera.order.to_string());
+                ui.label(target_label(&camera.target));
+                ui.label(match &camera.viewport {
+                    Some(viewport) => format!(
+                        "{}x{} at ({}, {})",
+                        viewport.physical_size.x,
+                        viewport.physical_size.y,
+                        viewport.physical_position.x,
+                        viewport.physical_position.y
+                    ),
+                    None => "full".to_string(),
+                });
+                let mut use_debug = is_debug;
+                if ui.checkbox(&mut use_debug, "").changed() {
+                    debug.push((entity, use_debug));
+                }
+                ui.end_row();
+            }
+        });
+        if !any {
+            ui.weak("there are no cameras");
+        }
+
+        for (entity, active) in toggled {
+            if let Some(mut camera) = world.get_mut::<Camera>(entity) {
+                camera.is_active = active;
+            }
+        }
+        for (entity, use_debug) in debug {
+            let mut entity = world.entity_mut(entity);
+            match use_debug {
+                true => entity.insert(DebugCamera),
+                false => entity.remove::<DebugCamera>(),
+            };
+        }
+    }
+}
+
+fn target_label(target: &RenderTarget) -> String {
+    match target {
+        RenderTarget::Window(WindowRef::Primary) => "primary window".to_string(),
+        RenderTarget::Window(WindowRef::Entity(window)) => format!("window {window:?}"),
+        RenderTarget::Image(image) => format!("image {:?}", image.id()),
+        RenderTarget::TextureView(view) => format!("texture view {view:?}"),
+    }
+}
@@ -0,0 +1,432 @@
+//! The console tab module. A REPL for commands that operate on the world through reflection, such
+//! as `set Player Transform.translation.x 5` or `get Res<Score>`. Apps add their own commands to
+//! [`ConsoleCommands`].
+//!
+//! Entities are referred to by [`Name`], or by id, such as `12v0` or `12`. Names that contain
+//! spaces are quoted, e.g. `despawn "Big Rock"`. Values are written in RON, and deserialized as
+//! the type of the field they are set to.
+
+use bevy::prelude::*;
+use bevy::reflect::serde::TypedReflectDeserializer;
+use bevy::reflect::{GetPath, TypeRegistration, TypeRegistry};
+use serde::de::DeserializeSeed;
+
+use crate::compat::egui::{self, Key, Ui};
+use crate::tabs::entities::search::resolve_search;
+use crate::tabs::watches::{WatchExpression, WatchTarget};
+use crate::{Spyglass, Tab};
+
+/// The most lines kept in the console log.
+const MAX_LOG_LINES: usize = 1000;
+
+/// The plugin that adds the console tab to the end of the [`Spyglass`] tab list.
+pub struct ConsoleTabPlugin;
+
+impl Plugin for ConsoleTabPlugin {
+    fn build(&self, app: &mut App) {
+        if cfg!(feature = "disabled") {
+            return;
+        }
+
+        let mut spyglass = app.world.resource_mut::<Spyglass>();
+        spyglass.add_tab(Box::new(ConsoleTab::default()));
+
+        app.init_resource::<ConsoleCommands>();
+    }
+}
+
+/// A function that runs a console command, with the text after the command name. Returns the
+/// output to print, or the error.
+pub type ConsoleCommandFn = dyn Fn(&mut World, &str) -> Result<String, String> + Send + Sync;
+
+/// A command of the console.
+pub struct ConsoleCommand {
+    /// The name the command is invoked by, e.g. `spawn`.
+    pub name: String,
+    /// The usage printed by `help`, e.g. `spawn [name]: spawn an entity`.
+    pub help: String,
+    /// Runs the command.
+    pub run: Box<ConsoleCommandFn>,
+}
+
+/// The resource that contains the commands of the console. Starts with the builtin commands, and
+/// apps can add their own. Commands added later take precedence over those with the same name.
+#[derive(Resource)]
+pub struct ConsoleCommands {
+    /// The commands.
+    pub commands: Vec<ConsoleCommand>,
+}
+
+impl Default for ConsoleCommands {
+    fn default() -> Self {
+        let mut commands = Self { commands: vec![] };
+        commands.add("spawn", "spawn [name]: spawn an empty entity", spawn);
+        commands.add(
+            "despawn",
+            "despawn <entity>: despawn an entity and its descendants",
+            despawn,
+        );
+        commands.add(
+            "query",
+            "query <Component>: list the entities that have a component",
+            query,
+        );
+        commands.add(
+            "get",
+            "get [entity] <Component.path> | get Res<Resource>.path: print a value",
+            get,
+        );
+        commands.add(
+            "set",
+            "set <entity> <Component.path> <value> | set Res<Resource>.path <value>: set a value, \
+            written in RON",
+            set,
+        );
+        commands
+    }
+}
+
+impl ConsoleCommands {
+    /// Add a command.
+    pub fn add(
+        &mut self,
+        name: impl Into<String>,
+        help: impl Into<String>,
+        run: impl Fn(&mut World, &str) -> Result<String, String> + Send + Sync + 'static,
+    ) {
+        self.commands.push(ConsoleCommand {
+            name: name.into(),
+            help: help.into(),
+            run: Box::new(run),
+        });
+    }
+
+    /// Run a line of input, and return its output or error.
+    pub fn execute(world: &mut World, line: &str) -> Result<String, String> {
+        let line = line.trim();
+        let (name, args) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+        let commands = world
+            .remove_resource::<Self>()
+            .ok_or("the console commands are missing")?;
+        let result = match commands.commands.iter().rev().find(|c| c.name == name) {
+            Some(command) => (command.run)(world, args.trim()),
+            None if name == "help" => Ok(commands
+                .commands
+                .iter()
+                .map(|command| command.help.as_str())
+                .collect::<Vec<_>>()
+                .join("\n")),
+            None => Err(format!("unknown command `{name}`, try `help`")),
+        };
+        world.insert_resource(commands);
+        result
+    }
+}
+
+/// Split the first argument off, which may be quoted.
+fn next_arg(args: &str) -> Result<(&str, &str), String> {
+    let args = args.trim_start();
+    if let Some(quoted) = args.strip_prefix('"') {
+        let (arg, rest) = quoted
+            .split_once('"')
+            .ok_or_else(|| format!("`{args}` is missing a closing quote"))?;
+        return Ok((arg, rest.trim_start()));
+    }
+    let (arg, rest) = args.split_once(char::is_whitespace).unwrap_or((args, ""));
+    if arg.is_empty() {
+        return Err("missing argument".into());
+    }
+    Ok((arg, rest.trim_start()))
+}
+
+/// Find an entity by name, by a path of names such as `Level/Boss`, or by id, such as `12v0` or
+/// `12`.
+fn resolve_entity(world: &World, arg: &str) -> Result<Entity, String> {
+    if let Some(entity) = world.iter_entities().find(|entity| {
+        entity
+            .get::<Name>()
+            .is_some_and(|name| name.as_str() == arg)
+    }) {
+        return Ok(entity.id());
+    }
+    resolve_search(world, arg)
+        .and_then(|entities| entities.first().copied())
+        .ok_or_else(|| format!("there is no entity `{arg}`"))
+}
+
+/// Look up a registered type by full or short type path.
+fn registration<'r>(
+    registry: &'r TypeRegistry,
+    name: &str,
+) -> Result<&'r TypeRegistration, String> {
+    registry
+        .get_with_type_path(name)
+        .or_else(|| registry.get_with_short_type_path(name))
+        .ok_or_else(|| format!("`{name}` is not a registered type"))
+}
+
+fn spawn(world: &mut World, args: &str) -> Result<String, String> {
+    let mut entity = world.spawn_empty();
+    if !args.is_empty() {
+        let (name, _) = next_arg(args)?;
+        entity.insert(Name::new(name.to_string()));
+    }
+    Ok(format!("spawned {:?}", entity.id()))
+}
+
+fn despawn(world: &mut World, args: &str) -> Result<String, String> {
+    let (arg, _) = next_arg(args)?;
+    let entity = resolve_entity(world, arg)?;
+    despawn_with_children_recursive(world, entity);
+    Ok(format!("despawned {entity:?}"))
+}
+
+/// The entities that have the component with the given full or short type path.
+pub(crate) fn entities_with(world: &World, component: &str) -> Result<Vec<Entity>, String> {
+    let registry = world.resource::<AppTypeRegistry>().read();
+    let type_id = registration(&registry, component)?.type_id();
+    let Some(id) = world.components().get_id(type_id) else {
+        return Ok(vec![]);
+    };
+    Ok(world
+        .iter_entities()
+        .filter(|entity| entity.contains_id(id))
+        .map(|entity| entity.id())
+        .collect())
+}
+
+fn query(world: &mut World, args: &str) -> Result<String, String> {
+    let (component, _) = next_arg(args)?;
+    let entities = entities_with(world, component)?;
+    if entities.is_empty() {
+        return Err(format!("no entity has `{component}`"));
+    }
+    Ok(entities
+        .into_iter()
+        .map(|entity| match world.get::<Name>(entity) {
+            Some(name) => format!("{entity:?} {name}"),
+            None => format!("{entity:?}"),
+        })
+        .collect::<Vec<_>>()
+        .join("\n"))
+}
+
+fn get(world: &mut World, args: &str) -> Result<String, String> {
+    let (first, rest) = next_arg(args)?;
+    if rest.is_empty() {
+        // `Res<Resource>.path`, or `Component.path` of every entity that has it.
+        let expression = WatchExpression::parse(first)?;
+        let values = expression.evaluate_all(world, |value| format!("{value:?}"))?;
+        if values.is_empty() {
+            return Err(format!("nothing matches `{first}`"));
+        }
+        return Ok(values.join("\n"));
+    }
+
+    let entity = resolve_entity(world, first)?;
+    let expression = WatchExpression::parse(rest)?;
+    let WatchTarget::Component { component, .. } = &expression.target else {
+        return Err(format!("`{rest}` is not a component path"));
+    };
+    let registry = world.resource::<AppTypeRegistry>().read();
+    let reflect = registration(&registry, component)?
+        .data::<ReflectComponent>()
+        .ok_or_else(|| format!("`{component}` does not reflect `Component`"))?;
+    let value = reflect
+        .reflect(world.entity(entity))
+        .ok_or_else(|| format!("{entity:?} has no `{component}`"))?;
+    let value = match expression.path.is_empty() {
+        true => value,
+        false => value
+            .reflect_path(expression.path.as_str())
+            .map_err(|err| format!("invalid path `{}`: {err}", expression.path))?,
+    };
+    Ok(format!("{value:?}"))
+}
+
+fn set(world: &mut World, args: &str) -> Result<String, String> {
+    let (first, rest) = next_arg(args)?;
+    let (entity, path, value) = match first.starts_with("Res<") {
+        true => (None, first, rest),
+        false => {
+            let (path, value) = next_arg(rest)?;
+            (Some(resolve_entity(world, first)?), path, value)
+        }
+    };
+    if value.is_empty() {
+        return Err("missing the value to set".into());
+    }
+    let expression = WatchExpression::parse(path)?;
+
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    let registry = registry.read();
+    let field = &expression.path;
+    match (&expression.target, entity) {
+        (WatchTarget::Resource(resource), _) => {
+            let mut target = registration(&registry, resource)?
+                .data::<ReflectResource>()
+                .ok_or_else(|| format!("`{resource}` does not reflect `Resource`"))?
+                .reflect_mut(world)
+                .ok_or_else(|| format!("`{resource}` does not exist"))?;
+            set_field(target.as_reflect_mut(), field, value, &registry)
+        }
+        (WatchTarget::Component { component, .. }, Some(entity)) => {
+            let mut entity_mut = world.entity_mut(entity);
+            let mut target = registration(&registry, component)?
+                .data::<ReflectComponent>()
+                .ok_or_else(|| format!("`{component}` does not reflect `Component`"))?
+                .reflect_mut(&mut entity_mut)
+                .ok_or_else(|| format!("{entity:?} has no `{component}`"))?;
+            set_field(target.as_reflect_mut(), field, value, &registry)
+        }
+        (WatchTarget::Component { .. }, None) => {
+            Err(format!("`{path}` needs an entity to set it on"))
+        }
+    }
+}
+
+/// Deserialize `value` as the type of the field at `path` within `target`, and apply it.
+fn set_field(
+    target: &mut dyn Reflect,
+    path: &str,
+    value: &str,
+    registry: &TypeRegistry,
+) -> Result<String, String> {
+    let field = match path.is_empty() {
+        true => target,
+        false => target
+            .reflect_path_mut(path)
+            .map_err(|err| format!("invalid path `{path}`: {err}"))?,
+    };
+    let type_path = field
+        .get_represented_type_info()
+        .map(|info| info.type_path())
+        .ok_or_else(|| format!("`{path}` has no type information"))?;
+    let registration = registry
+        .get_with_type_path(type_path)
+        .ok_or_else(|| format!("`{type_path}` is not a registered type"))?;
+    let mut de = ron::Deserializer::from_str(value).map_err(|err| err.to_string())?;
+    let parsed = TypedReflectDeserializer::new(registration, registry)
+        .deserialize(&mut de)
+        .or_else(|err| match field.is::<String>() {
+            // Strings may be written without quotes.
+            true => Ok(Box::new(value.to_string())),
+            false => Err(format!("`{value}` is not a valid `{type_path}`: {err}")),
+        })?;
+    field.apply(parsed.as_ref());
+    Ok(format!("{field:?}"))
+}
+
+/// What a line of the console log is.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum LineKind {
+    Input,
+    Output,
+    Error,
+}
+
+#[derive(Default)]
+struct ConsoleTab {
+    input: String,
+    log: Vec<(LineKind, String)>,
+    /// The lines that were run, oldest first.
+    history: Vec<String>,
+    /// The position in `history` while browsing it with the arrow keys.
+    browsing: Option<usize>,
+}
+
+impl ConsoleTab {
+    fn run(&mut self, world: &mut World) {
+        let line = std::mem::take(&mut self.input);
+        if line.trim().is_empty() {
+            return;
+        }
+        self.log.push((LineKind::Input, format!("> {line}")));
+        if line.trim() == "clear" {
+            self.log.clear();
+        } else {
+            match ConsoleCommands::execute(world, &line) {
+                Ok(output) if output.is_empty() => (),
+                Ok(output) => self.log.push((LineKind::Output, output)),
+                Err(err) => self.log.push((LineKind::Error, err)),
+            }
+        }
+        if self.history.last() != Some(&line) {
+            self.history.push(line);
+        }
+        self.browsing = None;
+        let excess = self.log.len().saturating_sub(MAX_LOG_LINES);
+        self.log.drain(..excess);
+    }
+
+    /// Move through the history with the arrow keys.
+    fn browse(&mut self, ui: &Ui) {
+        let (up, down) = ui.input(|input| {
+            (
+                input.key_pressed(Key::ArrowUp),
+                input.key_pressed(Key::ArrowDown),
+            )
+        });
+        let browsing = match (up, down, self.browsing) {
+            (true, _, None) => self.history.len().checked_sub(1),
+            (true, _, Some(i)) => Some(i.saturating_sub(1)),
+            (_, true, Some(i)) if i + 1 < self.history.len() => Some(i + 1),
+            (_, true, Some(_)) => {
+                self.browsing = None;
+                self.input.clear();
+                return;
+            }
+            _ => return,
+        };
+        if let Some(i) = browsing {
+            self.browsing = Some(i);
+            self.input = self.history[i].clone();
+        }
+    }
+}
+
+impl Tab for ConsoleTab {
+    fn name(&self) -> &str {
+        "Console"
+    }
+
+    fn draw(&mut self, ui: &mut Ui, world: &mut World) {
+        egui::ScrollArea::vertical()
+            .id_source("console_log")
+            .max_height(400.0)
+            .auto_shrink([false, true])
+            .stick_to_bottom(true)
+            .show(ui, |ui| {
+                for (kind, line) in &self.log {
+                    let text = egui::RichText::new(line).monospace();
+                    match kind {
+                        LineKind::Input => ui.label(text.strong()),
+                        LineKind::Output => ui.label(text),
+                        LineKind::Error => ui.colored_label(ui.visuals().error_fg_color, text),
+                    };
+                }
+            });
+
+        let response = ui
+            .horizontal(|ui| {
+                let response = egui::TextEdit::singleline(&mut self.input)
+                    .hint_text("Type a command, or `help`")
+                    .font(egui::TextStyle::Monospace)
+                    .desired_width(ui.available_width() - 40.0)
+                    .show(ui)
+                    .response;
+                if ui.button("run").clicked() {
+                    self.run(world);
+                }
+                response
+            })
+            .inner;
+        if response.has_focus() {
+            self.browse(ui);
+        }
+        if response.lost_focus() && ui.input(|input| input.key_pressed(Key::Enter)) {
+            self.run(world);
+            response.request_focus();
+        }
+    }
+}
@@ -1,18 +1,66 @@
 //! The entities tab module. Manages the inspector that selects entities, displays information
 //! about them, and allows editing their components.
 
+pub mod bundles;
+pub mod compare;
+pub mod duplicate;
 pub mod editors;
+pub mod gizmo;
+pub mod groups;
+pub mod hierarchy;
+pub mod history;
+pub mod labels;
+pub mod reflect_commands;
+pub mod search;
+pub mod selection;
 
+use std::any::TypeId;
+use std::borrow::Cow;
+use std::ffi::OsString;
+use std::fmt::Display;
+use std::num::{
+    NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU16, NonZeroU32,
+    NonZeroU64, NonZeroU8, NonZeroUsize,
+};
+use std::panic::AssertUnwindSafe;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use bevy::asset::{ReflectAsset, ReflectHandle};
+use bevy::core::FrameCount;
+use bevy::ecs::component::Tick;
 use bevy::prelude::*;
-use bevy::utils::{HashMap, HashSet};
-use bevy_egui::egui::{self, Ui};
-use bevy_egui::EguiContexts;
+use bevy::reflect::{ReflectRef, TypeInfo, TypePath};
+use bevy::utils::{get_short_name, HashMap, HashSet};
 
-use crate::{Spyglass, SpyglassWindow, Tab};
+use crate::commands::SpyglassCommands;
+use crate::compat::egui::{self, Ui};
+use crate::compat::{Egui, EguiBackend};
+use crate::tabs::watches::Watches;
+use crate::widgets::image_preview;
+use crate::{Spyglass, SpyglassContext, SpyglassSettings, SpyglassWindow, Tab};
 
+use self::bundles::{add_component_menu, SpyglassBundles};
+use self::compare::{draw_comparison, CompareEntities};
+use self::duplicate::duplicate_entity;
 use self::editors::{
-    array_editor, bool_editor, composite_editor, enum_editor, list_editor, map_editor, num_editor,
-    string_editor, value_editor, EditorStates, VariantProxy,
+    array_editor, bool_editor, clear_editor_states_on_selection, collect_editor_states,
+    composite_editor, cow_str_editor, entity_editor, enum_editor, image_handle_editor, is_option,
+    list_editor, map_editor, newtype_editor, num_editor, option_editor, os_string_editor,
+    path_buf_editor, string_editor, type_path, value_editor, EditorStates, VariantProxy,
+};
+use self::gizmo::{draw_transform_gizmo, GizmoMode, TransformGizmo};
+use self::groups::{EntityGroupBy, EntityGroups};
+use self::hierarchy::draw_hierarchy;
+use self::history::{diff, record_history, ComponentHistory};
+use self::labels::{draw_entity_labels, EntityLabels};
+use self::reflect_commands::{apply_reflect_commands, ReflectCommand, ReflectCommands};
+use self::search::resolve_search;
+use self::selection::{
+    apply_selection_requests, record_selection_history, report_selection_changes, EntitySelected,
+    SelectEntity, SelectionHistory,
 };
 
 /// The plugin that adds the entity tab to the inspector. Adds necessary resources, and
@@ -21,27 +69,77 @@ pub struct EntitiesTabPlugin;
 
 impl Plugin for EntitiesTabPlugin {
     fn build(&self, app: &mut App) {
+        if cfg!(feature = "disabled") {
+            return;
+        }
+
         let mut spyglass = app.world.resource_mut::<Spyglass>();
-        spyglass.tabs.push(Box::new(EntitiesTab));
+        spyglass.add_tab(Box::new(EntitiesTab));
+
+        let mut commands = app
+            .world
+            .get_resource_or_insert_with(SpyglassCommands::default);
+        commands.add("go back to the previous selection", |world| {
+            SelectionHistory::go_back(world);
+        });
+        commands.add("go forward to the next selection", |world| {
+            SelectionHistory::go_forward(world);
+        });
+        commands.add("clear selection", |world| {
+            world.remove_resource::<SelectedEntity>();
+        });
+        commands.add("toggle transform gizmo", |world| {
+            let mut gizmo = world.resource_mut::<TransformGizmo>();
+            gizmo.enabled = !gizmo.enabled;
+        });
+        commands.add("toggle entity labels", |world| {
+            let mut labels = world.resource_mut::<EntityLabels>();
+            labels.enabled = !labels.enabled;
+        });
 
-        app.init_resource::<EntityTracker>()
-            .init_resource::<EntitySearch>()
+        app.init_resource::<EntitySearch>()
             .init_resource::<ReprEditors>()
+            .init_resource::<ComponentReprs>()
+            .init_resource::<ExternalTools>()
+            .init_resource::<SpyglassBundles>()
+            .init_resource::<EntityGroups>()
+            .init_resource::<ComponentHistory>()
             .init_resource::<EditorStates>()
             .init_resource::<Popups>()
+            .init_resource::<TransformGizmo>()
+            .init_resource::<EntityLabels>()
+            .init_resource::<CompareEntities>()
+            .init_resource::<SpawnOrder>()
+            .init_resource::<EntityList>()
+            .init_resource::<SelectionHistory>()
+            .init_resource::<ReflectCommands>()
+            .add_event::<SelectEntity>()
+            .add_event::<EntitySelected>()
             .add_systems(
                 Update,
                 (
                     (
                         display_popups,
+                        apply_selection_requests,
                         collect_entity_state,
-                        track_entities,
-                        untrack_entities,
+                        record_history,
+                        draw_transform_gizmo,
+                        draw_entity_labels,
                     )
                         .chain()
                         .before(SpyglassWindow),
                     apply_entity_state.after(SpyglassWindow),
                 ),
+            )
+            .add_systems(
+                Last,
+                (
+                    apply_reflect_commands,
+                    report_selection_changes,
+                    record_selection_history,
+                    clear_editor_states_on_selection.after(report_selection_changes),
+                    collect_editor_states,
+                ),
             );
     }
 }
@@ -54,133 +152,744 @@ impl Tab for EntitiesTab {
     }
 
     fn draw(&mut self, ui: &mut Ui, world: &mut World) {
-        let tracker = world.remove_resource::<EntityTracker>().unwrap();
         let mut search = world.remove_resource::<EntitySearch>().unwrap();
         let mut states = world.remove_resource::<EditorStates>().unwrap();
+        states.begin_frame(SpyglassSettings::is_read_only(world));
 
-        if world.contains_resource::<SelectedEntity>() {
+        if world.resource::<CompareEntities>().right.is_some() {
+            draw_comparison(ui, world);
+        } else if world.contains_resource::<SelectedEntity>() {
             draw_selection(ui, world, &mut states);
         } else {
-            draw_no_selection(ui, world, &tracker, &mut search);
+            draw_no_selection(ui, world, &mut search);
         }
+        states.end_frame();
 
-        world.insert_resource(tracker);
         world.insert_resource(search);
         world.insert_resource(states);
     }
 }
 
 fn draw_selection(ui: &mut Ui, world: &mut World, states: &mut EditorStates) {
-    if ui.button("back").clicked() {
+    let mut list = false;
+    let navigated = ui
+        .horizontal(|ui| {
+            let navigated = history_buttons(ui, world);
+            list = ui.button("entity list").clicked();
+            navigated
+        })
+        .inner;
+    if list {
         world.remove_resource::<SelectedEntity>();
+    }
+    if navigated || list || deselect_despawned(world) {
         return;
     }
 
     let editors = world.remove_resource::<ReprEditors>().unwrap();
     let mut selected = world.remove_resource::<SelectedEntity>().unwrap();
+    states.set_entity(Some(selected.id));
+    let tools = world.remove_resource::<ExternalTools>().unwrap_or_default();
+    let mut history = world
+        .remove_resource::<ComponentHistory>()
+        .unwrap_or_default();
 
+    let mut compare = false;
+    let mut duplicate = None;
+    let mut navigate = None;
     ui.group(|ui| {
         ui.vertical_centered(|ui| {
-            ui.heading(&selected.name);
+            ui.add_enabled_ui(!states.read_only(), |ui| {
+                name_editor(ui, world, &mut selected);
+            });
+            ui.horizontal_wrapped(|ui| {
+                compare = ui.button("compare with…").clicked();
+                ui.add_enabled_ui(!states.read_only(), |ui| {
+                    if ui.button("duplicate").clicked() {
+                        duplicate = Some(false);
+                    }
+                    if world.get::<Children>(selected.id).is_some()
+                        && ui.button("duplicate with children").clicked()
+                    {
+                        duplicate = Some(true);
+                    }
+                    add_component_menu(ui, world, selected.id);
+                });
+                for tool in tools.tools.iter() {
+                    if ui.button(&tool.label).clicked() {
+                        (tool.open)(world, selected.id);
+                    }
+                }
+            });
         });
 
+        navigate = draw_hierarchy(ui, world, selected.id);
+        if world.get::<Transform>(selected.id).is_some() {
+            gizmo_controls(ui, world);
+        }
+        let filter = component_filter(ui, &selected.state.components, states);
+        let settings = world
+            .remove_resource::<SpyglassSettings>()
+            .unwrap_or_default();
+        let mut reload = None;
+        let mut resolve = None;
+        let internal = selected
+            .state
+            .components
+            .iter()
+            .filter(|comp| settings.is_internal(comp))
+            .count();
         for comp in selected.state.components.iter() {
+            if !comp.to_lowercase().contains(&filter) || settings.is_hidden(comp) {
+                continue;
+            }
+            let label = component_label(comp, &selected.state.components);
             if let Some(repr) = selected.state.reprs.get_mut(comp) {
-                let editor = editors.get(repr.type_name());
+                let refresh = settings.component_refresh.get(comp).copied();
+                if let Some(refresh) = refresh.filter(|&r| r != ComponentRefresh::EveryFrame) {
+                    ui.horizontal(|ui| {
+                        ui.weak(format!("{}: {refresh:?}", get_short_name(comp)));
+                        if ui.small_button("refresh").clicked() {
+                            reload = Some(comp.clone());
+                        }
+                    });
+                }
+                if selected.state.conflicts.contains(comp) {
+                    ui.horizontal(|ui| {
+                        let color = ui.visuals().warn_fg_color;
+                        ui.colored_label(color, format!("{label} changed in the game"))
+                            .on_hover_text(
+                                "A game system changed this component while it was edited, so \
+                                the edits were not applied.",
+                            );
+                        if ui.small_button("keep mine").clicked() {
+                            resolve = Some((comp.clone(), true));
+                        }
+                        if ui.small_button("take theirs").clicked() {
+                            resolve = Some((comp.clone(), false));
+                        }
+                    });
+                }
+                let editor = editors.get(repr.as_ref());
+                if label != get_short_name(comp) {
+                    states.suffix_next_header(format!("({comp})"));
+                }
+                states.push_path(label.clone());
+                states.take_committed();
                 editor(ui, repr.as_mut(), world, &editors, states);
+                if states.take_committed() {
+                    selected.state.committed.insert(comp.clone());
+                }
+                states.pop_path();
+                states.take_header_suffix();
+                history.draw_diff(ui, comp, repr.as_ref());
             } else {
                 ui.label(comp).on_hover_ui(|ui| {
                     ui.label(
                         "No editable representation could be created for this component. \
                     Try implementing reflect for it, make sure to register its type with the app, \
-                    and consider a TODO: custom representation.",
+                    or consider adding a custom representation to ComponentReprs.",
                     );
                 });
             }
+            draw_handle_asset(ui, world, selected.id, comp, &label, &editors, states);
+        }
+        world.insert_resource(settings);
+        watch_requested(world, &selected, states);
+        if let Some(comp) = reload {
+            selected.state.reload(world, selected.id, &comp);
+        }
+        if let Some((comp, keep)) = resolve {
+            selected.state.resolve(world, selected.id, &comp, keep);
+        }
+
+        if internal > 0 {
+            let mut settings = world.resource_mut::<SpyglassSettings>();
+            let text = format!("hide {internal} internal components");
+            ui.checkbox(&mut settings.hide_internal_components, text);
         }
     });
 
+    world.insert_resource(history);
+    world.insert_resource(tools);
     world.insert_resource(editors);
-    world.insert_resource(selected);
+    let id = selected.id;
+    if compare {
+        world.resource_mut::<CompareEntities>().left = Some(id);
+    } else {
+        world.insert_resource(selected);
+    }
+
+    if let Some(recursive) = duplicate {
+        let duplicate = duplicate_entity(world, id, recursive);
+        if !duplicate.skipped.is_empty() {
+            world.resource_mut::<Popups>().warn(format!(
+                "these components were not duplicated, as they do not reflect `Component`: {}",
+                duplicate.skipped.join(", ")
+            ));
+        }
+        let selected = SelectedEntity::new(world, duplicate.entity);
+        world.insert_resource(selected);
+    } else if let Some(entity) = navigate {
+        let selected = SelectedEntity::new(world, entity);
+        world.insert_resource(selected);
+    }
 }
 
-fn draw_no_selection(
+/// If `comp` is a handle, such as `Handle<StandardMaterial>`, draw the editor of the asset it
+/// points to, so that assets like materials can be tuned live. Edited assets are written back
+/// through [`Assets::get_mut`], which flags them as modified so that the GPU picks them up. Only
+/// works for asset types registered with `register_asset_reflect`. Images are shown as a preview.
+fn draw_handle_asset(
     ui: &mut Ui,
     world: &mut World,
-    tracker: &EntityTracker,
-    search: &mut EntitySearch,
+    entity: Entity,
+    comp: &str,
+    label: &str,
+    editors: &ReprEditors,
+    states: &mut EditorStates,
 ) {
+    let (component, handle, asset) = {
+        let registry = world.resource::<AppTypeRegistry>().read();
+        let Some(registration) = registry.get_with_type_path(comp) else {
+            return;
+        };
+        let (Some(component), Some(handle)) = (
+            registration.data::<ReflectComponent>(),
+            registration.data::<ReflectHandle>(),
+        ) else {
+            return;
+        };
+        let Some(asset) = registry.get_type_data::<ReflectAsset>(handle.asset_type_id()) else {
+            return;
+        };
+        (component.clone(), handle.clone(), asset.clone())
+    };
+    let Some(handle) = world
+        .get_entity(entity)
+        .and_then(|entity| component.reflect(entity))
+        .and_then(|value| handle.downcast_handle_untyped(value.as_any()))
+    else {
+        return;
+    };
+    // Images are opaque to reflection, so they are previewed rather than edited.
+    if handle.type_id() == TypeId::of::<Image>() {
+        let image = handle.id().typed::<Image>();
+        ui.indent("handle_asset", |ui| image_preview(ui, world, image));
+        return;
+    }
+    let Some(original) = asset.get(world, handle.clone()).map(Reflect::clone_value) else {
+        ui.weak("the asset is not loaded");
+        return;
+    };
+
+    let mut value = original.clone_value();
+    let editor = editors.get(value.as_ref());
+    states.push_path(format!("{label}(asset)"));
+    states.take_committed();
+    ui.indent("handle_asset", |ui| {
+        editor(ui, value.as_mut(), world, editors, states);
+    });
+    states.pop_path();
+
+    if states.take_committed() {
+        if let Some(asset) = asset.get_mut(world, handle) {
+            asset.apply(value.as_ref());
+        }
+    }
+}
+
+/// Draw the [`Name`] of the selected entity as an editable heading. Typing a name inserts a
+/// [`Name`] if the entity has none, and clearing it removes the [`Name`].
+fn name_editor(ui: &mut Ui, world: &mut World, selected: &mut SelectedEntity) {
+    let mut name = world
+        .get::<Name>(selected.id)
+        .map(|name| name.to_string())
+        .unwrap_or_default();
+    let edit = egui::TextEdit::singleline(&mut name)
+        .font(egui::TextStyle::Heading)
+        .hint_text(format!("{:?}", selected.id))
+        .show(ui);
+    if !edit.response.changed() {
+        return;
+    }
+
+    let mut entity = world.entity_mut(selected.id);
+    if name.is_empty() {
+        entity.remove::<Name>();
+        selected.name = format!("{:?}", selected.id);
+    } else {
+        entity.insert(Name::new(name.clone()));
+        selected.name = name;
+    }
+}
+
+/// Draw the controls of the [`TransformGizmo`].
+fn gizmo_controls(ui: &mut Ui, world: &mut World) {
+    let mut gizmo = world.resource_mut::<TransformGizmo>();
+    ui.horizontal(|ui| {
+        ui.checkbox(&mut gizmo.enabled, "gizmo")
+            .on_hover_text("Drag handles in the viewport to edit the transform");
+        ui.add_enabled_ui(gizmo.enabled, |ui| {
+            ui.selectable_value(&mut gizmo.mode, GizmoMode::Translate, "translate");
+            ui.selectable_value(&mut gizmo.mode, GizmoMode::Rotate, "rotate");
+            ui.selectable_value(&mut gizmo.mode, GizmoMode::Scale, "scale");
+        });
+    });
+}
+
+/// Watch the fields requested by editors this frame.
+fn watch_requested(world: &mut World, selected: &SelectedEntity, states: &mut EditorStates) {
+    let requests = states.take_watch_requests();
+    let Some(mut watches) = world.get_resource_mut::<Watches>() else {
+        return;
+    };
+    for request in requests {
+        let split = request.find(['.', '[']).unwrap_or(request.len());
+        let (short, path) = request.split_at(split);
+        let component = selected
+            .state
+            .components
+            .iter()
+            .find(|comp| component_label(comp, &selected.state.components) == short);
+        if let Some(component) = component {
+            watches.watch(selected.id, component.clone(), path);
+        }
+    }
+}
+
+/// Draw a text box that filters the components of the selected entity by type name, and buttons
+/// that expand or collapse every component. Returns the lowercase filter.
+fn component_filter(ui: &mut Ui, components: &[String], states: &mut EditorStates) -> String {
+    let filter_id = ui.id().with("component_filter");
+    let mut filter = ui.data_mut(|data| data.get_temp::<String>(filter_id).unwrap_or_default());
+    ui.horizontal(|ui| {
+        egui::TextEdit::singleline(&mut filter)
+            .hint_text("Filter components")
+            .show(ui);
+        if ui.button("expand all").clicked() {
+            for comp in components {
+                states.request_expand(component_label(comp, components));
+            }
+        }
+        if ui.button("collapse all").clicked() {
+            for comp in components {
+                states.request_collapse(component_label(comp, components));
+            }
+        }
+    });
+    let lowercase = filter.to_lowercase();
+    ui.data_mut(|data| data.insert_temp(filter_id, filter));
+    lowercase
+}
+
+/// Browser-style back and forward buttons, which walk the [`SelectionHistory`]. Returns whether
+/// the selection changed.
+fn history_buttons(ui: &mut Ui, world: &mut World) -> bool {
+    let history = world.resource::<SelectionHistory>();
+    let (back, forward) = (
+        !history.back().is_empty(),
+        history.forward().next().is_some(),
+    );
+    let back = ui.add_enabled(back, egui::Button::new("⏴ back")).clicked()
+        && SelectionHistory::go_back(world);
+    let forward = ui
+        .add_enabled(forward, egui::Button::new("forward ⏵"))
+        .clicked()
+        && SelectionHistory::go_forward(world);
+    back || forward
+}
+
+fn draw_no_selection(ui: &mut Ui, world: &mut World, search: &mut EntitySearch) {
+    let history = world.resource::<SelectionHistory>();
+    if !history.back().is_empty() || history.forward().next().is_some() {
+        let navigated = ui.horizontal(|ui| history_buttons(ui, world)).inner;
+        if navigated {
+            return;
+        }
+    }
     ui.vertical_centered(|ui| {
         egui::TextEdit::singleline(&mut search.0)
             .clip_text(false)
             .min_size(egui::vec2(ui.available_width() * 0.9, 0.0))
-            .hint_text("Search for an entity")
+            .hint_text("Search for an entity by name, id like 42v3, or path like Level/Boss")
             .show(ui);
     });
+    let mut labels = world.resource::<EntityLabels>().enabled;
+    if let Some(mut settings) = world.get_resource_mut::<SpyglassSettings>() {
+        ui.horizontal(|ui| {
+            let sort = &mut settings.entity_sort;
+            egui::ComboBox::from_label("sort by")
+                .selected_text(sort.label())
+                .show_ui(ui, |ui| {
+                    for (value, label) in EntitySort::ALL {
+                        ui.selectable_value(sort, value, label);
+                    }
+                });
+            group_by_selector(ui, &mut settings.entity_group_by);
+            ui.checkbox(&mut labels, "scene labels")
+                .on_hover_text("Label named entities in the viewport, click a label to select it");
+        });
+    }
+    world.resource_mut::<EntityLabels>().enabled = labels;
 
-    for entity in tracker.tracked.iter().copied() {
-        let name = world
-            .get::<Name>(entity)
-            .map(|name| name.to_string())
-            .unwrap_or_else(|| format!("{entity:?}"));
+    let (sort, group_by) = world
+        .get_resource::<SpyglassSettings>()
+        .map(|settings| (settings.entity_sort, settings.entity_group_by.clone()))
+        .unwrap_or_default();
+    let mut list = world.remove_resource::<EntityList>().unwrap_or_default();
+    list.update(world, sort);
 
-        if !name.starts_with(&search.0) {
-            continue;
-        }
+    let compare = world.resource::<CompareEntities>();
+    if let Some(left) = compare.left.filter(|_| compare.picking()) {
+        ui.horizontal(|ui| {
+            let name = entity_name(world, left);
+            ui.strong(format!("pick an entity to compare with {name}"));
+            if ui.small_button("cancel").clicked() {
+                world.resource_mut::<CompareEntities>().left = None;
+            }
+        });
+    }
 
-        if ui.button(&name).clicked() {
-            let state = EntityComponents::from_entity(world, entity);
-            world.insert_resource(SelectedEntity {
-                id: entity,
-                name,
-                state,
+    let mut groups = world.remove_resource::<EntityGroups>().unwrap_or_default();
+    draw_groups(ui, world, &list.entities, &mut groups);
+    world.insert_resource(groups);
+
+    // Names are only needed to match a search, otherwise only the visible rows are named.
+    let exact = resolve_search(world, &search.0);
+    let matches = match (exact, search.0.is_empty()) {
+        (Some(exact), _) => Cow::Owned(
+            list.sorted
+                .iter()
+                .copied()
+                .filter(|entity| exact.contains(entity))
+                .collect::<Vec<_>>(),
+        ),
+        (None, true) => Cow::Borrowed(list.sorted.as_slice()),
+        (None, false) => Cow::Owned(
+            list.sorted
+                .iter()
+                .copied()
+                .filter(|&entity| entity_name(world, entity).starts_with(&search.0))
+                .collect(),
+        ),
+    };
+    let total = matches.len();
+    let matches = &matches[..total.min(MAX_LISTED_ENTITIES)];
+    if matches.len() < total {
+        ui.weak(format!(
+            "showing {} of {total} entities, refine the search to find the others",
+            matches.len()
+        ));
+    }
+
+    let mut despawned = false;
+    if group_by == EntityGroupBy::None {
+        despawned = draw_entity_rows(ui, world, "entity_list", matches);
+    } else {
+        egui::ScrollArea::vertical()
+            .id_source("entity_buckets")
+            .max_height(ENTITY_LIST_HEIGHT)
+            .auto_shrink([false, true])
+            .show(ui, |ui| {
+                for (label, rows) in group_by.bucket(world, matches) {
+                    egui::CollapsingHeader::new(format!("{label} ({})", rows.len()))
+                        .id_source(("entity_bucket", &label))
+                        .show(ui, |ui| {
+                            let id = ("entity_bucket_rows", &label);
+                            despawned |= draw_entity_rows(ui, world, id, &rows);
+                        });
+                }
             });
-        }
     }
+    list.stale |= despawned;
+    world.insert_resource(list);
+}
+
+/// List entities in a scroll area. Only the visible rows are laid out and named, so huge worlds do
+/// not tank the frame rate. Returns whether a visible entity was despawned, and skipped.
+fn draw_entity_rows(
+    ui: &mut Ui,
+    world: &mut World,
+    id: impl std::hash::Hash,
+    entities: &[Entity],
+) -> bool {
+    let row_height = ui.spacing().interact_size.y;
+    let mut despawned = false;
+    egui::ScrollArea::vertical()
+        .id_source(id)
+        .max_height(ENTITY_LIST_HEIGHT)
+        .auto_shrink([false, true])
+        .show_rows(ui, row_height, entities.len(), |ui, rows| {
+            for &entity in &entities[rows] {
+                if world.get_entity(entity).is_none() {
+                    despawned = true;
+                    continue;
+                }
+                let name = entity_name(world, entity);
+                select_button(ui, world, entity, &name);
+            }
+        });
+    despawned
 }
 
+/// A selector for how the entity list is grouped, with a field for the marker component.
+fn group_by_selector(ui: &mut Ui, group_by: &mut EntityGroupBy) {
+    let selected = match group_by {
+        EntityGroupBy::None => "nothing",
+        EntityGroupBy::Archetype => "archetype",
+        EntityGroupBy::Marker(_) => "marker component",
+    };
+    egui::ComboBox::from_label("group by")
+        .selected_text(selected)
+        .show_ui(ui, |ui| {
+            ui.selectable_value(group_by, EntityGroupBy::None, "nothing");
+            ui.selectable_value(group_by, EntityGroupBy::Archetype, "archetype");
+            let marker = matches!(group_by, EntityGroupBy::Marker(_));
+            if ui.selectable_label(marker, "marker component").clicked() && !marker {
+                *group_by = EntityGroupBy::Marker(String::new());
+            }
+        });
+    if let EntityGroupBy::Marker(marker) = group_by {
+        egui::TextEdit::singleline(marker)
+            .hint_text("Component")
+            .desired_width(120.0)
+            .show(ui);
+    }
+}
+
+/// How often, in frames, the [`EntityList`] is rebuilt even though the number of entities did not
+/// change, to pick up entities spawned as others were despawned, and changes to the names,
+/// components and archetypes the list is sorted by.
+const ENTITY_LIST_RESCAN_FRAMES: u32 = 60;
+
+/// The entities listed by the entities tab, cached across frames so that huge worlds are not
+/// walked every frame. Listing the entities on demand, rather than tracking them with a marker
+/// component, keeps the inspector from moving every entity to a new archetype.
 #[derive(Default, Resource)]
-struct EntityTracker {
-    tracked: HashSet<Entity>,
+struct EntityList {
+    /// Every entity, sorted by id.
+    entities: Vec<Entity>,
+    /// Every entity, sorted by [`sort`](Self::sort).
+    sorted: Vec<Entity>,
+    sort: EntitySort,
+    /// The number of entities in the world when the list was built.
+    len: u32,
+    /// The number of updates since the list was built.
+    age: u32,
+    /// Whether a listed entity was found despawned, so the list must be rebuilt.
+    stale: bool,
 }
 
-#[derive(Component)]
-struct TrackedInSpyglass;
+impl EntityList {
+    /// Rebuild the list if entities were spawned or despawned, the sort changed, or it is due for
+    /// a rescan.
+    fn update(&mut self, world: &mut World, sort: EntitySort) {
+        self.age += 1;
+        let len = world.entities().len();
+        let fresh = !self.stale
+            && self.len == len
+            && self.sort == sort
+            && self.age < ENTITY_LIST_RESCAN_FRAMES
+            && !self.entities.is_empty();
+        if fresh {
+            return;
+        }
 
-fn track_entities(
-    mut c: Commands,
-    q: Query<Entity, Without<TrackedInSpyglass>>,
-    mut state: ResMut<EntityTracker>,
-) {
-    for entity in &q {
-        c.entity(entity).insert(TrackedInSpyglass);
-        state.tracked.insert(entity);
+        self.entities = world.iter_entities().map(|entity| entity.id()).collect();
+        self.entities.sort_unstable();
+        let mut order = world.remove_resource::<SpawnOrder>().unwrap_or_default();
+        order.update(world, &self.entities);
+        world.insert_resource(order);
+        self.sorted.clone_from(&self.entities);
+        sort_entities(world, &mut self.sorted, sort);
+        self.sort = sort;
+        self.len = len;
+        self.age = 0;
+        self.stale = false;
+    }
+}
+
+/// The most entities listed at once in the entities tab.
+const MAX_LISTED_ENTITIES: usize = 10_000;
+
+/// The height of the scroll area that lists entities.
+const ENTITY_LIST_HEIGHT: f32 = 400.0;
+
+fn draw_groups(ui: &mut Ui, world: &mut World, entities: &[Entity], groups: &mut EntityGroups) {
+    let mut removed = None;
+    for (i, group) in groups.groups.iter().enumerate() {
+        let entities = entities
+            .iter()
+            .copied()
+            .filter(|&entity| group.filter.matches(world, entity))
+            .collect::<Vec<_>>();
+
+        egui::CollapsingHeader::new(format!("{} ({})", group.name, entities.len()))
+            .id_source(("entity_group", i))
+            .show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    ui.weak(&group.expr);
+                    if ui.small_button("remove group").clicked() {
+                        removed = Some(i);
+                    }
+                });
+                for entity in entities {
+                    entity_button(ui, world, entity, "");
+                }
+            });
+    }
+    if let Some(i) = removed {
+        groups.groups.remove(i);
     }
+
+    let definition_id = ui.id().with("entity_group_definition");
+    let mut definition =
+        ui.data_mut(|data| data.get_temp::<String>(definition_id).unwrap_or_default());
+    ui.horizontal(|ui| {
+        egui::TextEdit::singleline(&mut definition)
+            .hint_text("New group, e.g. `Pickups: has:Pickup`")
+            .show(ui);
+        if ui.button("add group").clicked() {
+            match groups.add_definition(&definition) {
+                Ok(()) => definition.clear(),
+                Err(err) => world
+                    .resource_mut::<Popups>()
+                    .error(format!("failed to add group: {err}")),
+            }
+        }
+    });
+    ui.data_mut(|data| data.insert_temp(definition_id, definition));
+
+    ui.separator();
 }
 
-fn untrack_entities(mut q: RemovedComponents<TrackedInSpyglass>, mut state: ResMut<EntityTracker>) {
-    for entity in &mut q.read() {
-        state.tracked.remove(&entity);
+/// A button that selects the entity when clicked, if its name starts with `search`.
+fn entity_button(ui: &mut Ui, world: &mut World, entity: Entity, search: &str) {
+    let name = entity_name(world, entity);
+    if name.starts_with(search) {
+        select_button(ui, world, entity, &name);
     }
 }
 
+pub(crate) fn entity_name(world: &World, entity: Entity) -> String {
+    world
+        .get::<Name>(entity)
+        .map(|name| name.to_string())
+        .unwrap_or_else(|| format!("{entity:?}"))
+}
+
+/// A button labelled `name` that selects the entity when clicked, or picks it as the entity to
+/// compare with while one is being picked.
+fn select_button(ui: &mut Ui, world: &mut World, entity: Entity, name: &str) {
+    if !ui.button(name).clicked() {
+        return;
+    }
+    let mut compare = world.resource_mut::<CompareEntities>();
+    if compare.picking() {
+        compare.right = Some(entity);
+        return;
+    }
+    let selected = SelectedEntity::new(world, entity);
+    world.insert_resource(selected);
+}
+
 struct EntityComponents {
     components: Vec<String>,
     reprs: HashMap<String, Box<dyn Reflect>>,
+    /// The values the reprs refreshed this frame had in the world. Only these reprs are applied
+    /// back, and only if an editor changed them.
+    pristine: HashMap<String, Box<dyn Reflect>>,
+    /// The change ticks of the world when the pristine values were taken or last applied. If a
+    /// component changed after its tick, a game system changed it while it was being edited.
+    since: HashMap<String, Tick>,
+    /// The type names of components whose edits were not applied because a game system changed
+    /// them too. Their reprs are kept like throttled reprs until the conflict is resolved.
+    conflicts: HashSet<String>,
+    /// The type names of conflicting components whose edits the user chose to keep, which are
+    /// applied without checking for conflicts again.
+    keep: HashSet<String>,
+    /// The type names of components an editor committed an edit to, see
+    /// [`EditorStates::commit`]. Only these are applied back, so unchanged components are not
+    /// compared with their pristine values every frame.
+    committed: HashSet<String>,
 }
 
 impl EntityComponents {
     fn from_entity(world: &World, entity: Entity) -> Self {
-        let loc = world.entities().get(entity).unwrap();
-        let archetype = world.archetypes().get(loc.archetype_id).unwrap();
+        Self::refresh(
+            world,
+            entity,
+            None,
+            &HashSet::default(),
+            &HashSet::default(),
+        )
+    }
+
+    /// Collect the components of an entity. The reprs of components whose type names are in
+    /// `collapsed` are not cloned again, but kept from `previous`, and are not applied back. The
+    /// reprs of components whose type names are in `throttled` are kept from `previous` too, but
+    /// are still applied back if they are edited.
+    fn refresh(
+        world: &World,
+        entity: Entity,
+        mut previous: Option<Self>,
+        collapsed: &HashSet<String>,
+        throttled: &HashSet<String>,
+    ) -> Self {
+        // A despawned entity has no components, rather than panicking.
+        let component_ids = world
+            .entities()
+            .get(entity)
+            .and_then(|loc| world.archetypes().get(loc.archetype_id))
+            .into_iter()
+            .flat_map(|archetype| archetype.components());
+        let tick = world.read_change_tick();
+        let (previous_conflicts, keep, committed) = previous
+            .as_mut()
+            .map(|previous| {
+                let conflicts = std::mem::take(&mut previous.conflicts);
+                let committed = std::mem::take(&mut previous.committed);
+                (conflicts, std::mem::take(&mut previous.keep), committed)
+            })
+            .unwrap_or_default();
         let mut components = vec![];
         let mut reprs = HashMap::default();
-        for comp in archetype.components() {
+        let mut pristine = HashMap::default();
+        let mut since = HashMap::default();
+        let mut conflicts = HashSet::default();
+        for comp in component_ids {
             let name = if let Some(name) = world.components().get_name(comp) {
-                if let Some(refl) = get_reflect_impl(world, name) {
-                    if let Some(repr) = refl.reflect(world.entity(entity)) {
-                        reprs.insert(name.to_string(), repr.clone_value());
+                let conflicted = previous_conflicts.contains(name);
+                let throttled = previous
+                    .as_mut()
+                    .filter(|_| throttled.contains(name) || conflicted)
+                    .and_then(|previous| {
+                        let pristine = previous.pristine.remove(name)?;
+                        let since = previous.since.remove(name)?;
+                        Some((previous.reprs.remove(name)?, pristine, since))
+                    });
+                if let Some((repr, kept, kept_since)) = throttled {
+                    reprs.insert(name.to_string(), repr);
+                    pristine.insert(name.to_string(), kept);
+                    since.insert(name.to_string(), kept_since);
+                    if conflicted {
+                        conflicts.insert(name.to_string());
                     }
+                    components.push(name.to_string());
+                    continue;
+                }
+
+                let kept = previous
+                    .as_mut()
+                    .filter(|_| collapsed.contains(name))
+                    .and_then(|previous| previous.reprs.remove(name));
+                if let Some(repr) = kept {
+                    reprs.insert(name.to_string(), repr);
+                } else if let Some(repr) = reflect_component(world, entity, name) {
+                    pristine.insert(name.to_string(), repr.clone_value());
+                    since.insert(name.to_string(), tick);
+                    reprs.insert(name.to_string(), repr);
                 }
                 name.to_string()
             } else if let Some(id) = world.components().get_info(comp).map(|info| info.type_id()) {
@@ -192,25 +901,306 @@ impl EntityComponents {
             components.push(name);
         }
         components.sort_unstable();
-        Self { components, reprs }
+        let keep = keep.intersection(&conflicts).cloned().collect();
+        // Committed edits that were held back by a conflict are still applied once it is resolved.
+        let committed = committed.intersection(&conflicts).cloned().collect();
+        Self {
+            components,
+            reprs,
+            pristine,
+            since,
+            conflicts,
+            keep,
+            committed,
+        }
+    }
+}
+
+impl EntityComponents {
+    /// Refresh the repr of a component from the world, discarding unapplied edits.
+    fn reload(&mut self, world: &World, entity: Entity, name: &str) {
+        self.conflicts.remove(name);
+        self.keep.remove(name);
+        self.committed.remove(name);
+        if let Some(repr) = reflect_component(world, entity, name) {
+            self.pristine.insert(name.to_string(), repr.clone_value());
+            self.since
+                .insert(name.to_string(), world.read_change_tick());
+            self.reprs.insert(name.to_string(), repr);
+        }
+    }
+
+    /// Resolve the conflict of a component, keeping its edits or taking the value in the world.
+    fn resolve(&mut self, world: &World, entity: Entity, name: &str, keep: bool) {
+        match keep {
+            true => {
+                self.keep.insert(name.to_string());
+            }
+            false => self.reload(world, entity, name),
+        }
+    }
+}
+
+/// Whether any of the fields at `paths` of a component differs from its `pristine` value. An empty
+/// path is the whole component.
+fn fields_changed<'a>(
+    world: &World,
+    entity: Entity,
+    name: &str,
+    pristine: &dyn Reflect,
+    mut paths: impl Iterator<Item = &'a str>,
+) -> bool {
+    let Some(current) = reflect_component(world, entity, name) else {
+        return true;
+    };
+    fn field<'r>(value: &'r dyn Reflect, path: &str) -> Option<&'r dyn Reflect> {
+        match path.is_empty() {
+            true => Some(value),
+            false => value.reflect_path(path).ok(),
+        }
+    }
+    paths.any(
+        |path| match (field(current.as_ref(), path), field(pristine, path)) {
+            (Some(now), Some(then)) => now.reflect_partial_eq(then) != Some(true),
+            _ => true,
+        },
+    )
+}
+
+/// Mark a component of the selected entity as applied by the inspector, so that its own write is
+/// not taken for a conflicting change of a game system.
+fn mark_applied(world: &mut World, entity: Entity, name: &str) {
+    let tick = world.read_change_tick();
+    let Some(mut selected) = world.get_resource_mut::<SelectedEntity>() else {
+        return;
+    };
+    if selected.id == entity && selected.state.since.contains_key(name) {
+        selected.state.since.insert(name.to_string(), tick);
+    }
+}
+
+/// Whether the component with the type name `name` was changed after the change tick `since`.
+fn changed_since(world: &World, entity: Entity, name: &str, since: Tick) -> bool {
+    let Some(info) = world.components().iter().find(|info| info.name() == name) else {
+        return false;
+    };
+    world
+        .get_entity(entity)
+        .and_then(|entity| entity.get_change_ticks_by_id(info.id()))
+        .is_some_and(|ticks| ticks.is_changed(since, world.read_change_tick()))
+}
+
+/// How edits of components are written back to the world, set in
+/// [`SpyglassSettings::apply_mode`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Reflect)]
+pub enum ApplyMode {
+    /// Edited components are written back whole right after the inspector is drawn, overwriting
+    /// changes game systems made to their other fields since they were collected.
+    #[default]
+    Components,
+    /// Only the edited fields are queued as [`ReflectCommand`]s, which are applied at the end of
+    /// the frame, so changes game systems make to the other fields are kept.
+    Fields,
+}
+
+/// The order the entities tab lists entities in, set in [`SpyglassSettings::entity_sort`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Reflect)]
+pub enum EntitySort {
+    /// By index, then generation.
+    #[default]
+    Id,
+    /// By name, with unnamed entities last.
+    Name,
+    /// By the number of components, most first.
+    ComponentCount,
+    /// By archetype, so entities with the same components are listed together.
+    Archetype,
+    /// By the order the inspector first saw the entities in, newest last. Entities that existed
+    /// before the entity list was first shown are ordered by id.
+    SpawnOrder,
+}
+
+impl EntitySort {
+    const ALL: [(Self, &'static str); 5] = [
+        (Self::Id, "id"),
+        (Self::Name, "name"),
+        (Self::ComponentCount, "component count"),
+        (Self::Archetype, "archetype"),
+        (Self::SpawnOrder, "spawn order"),
+    ];
+
+    fn label(self) -> &'static str {
+        Self::ALL
+            .iter()
+            .find(|(sort, _)| *sort == self)
+            .map_or("", |(_, label)| label)
     }
 }
 
+/// The order the entity list first saw entities in, for [`EntitySort::SpawnOrder`].
+#[derive(Default, Resource)]
+struct SpawnOrder {
+    seen: HashMap<Entity, u64>,
+    next: u64,
+}
+
+impl SpawnOrder {
+    /// Record the entities that were not seen before, in id order, and forget despawned ones.
+    /// Only called when the [`EntityList`] is rebuilt, and despawned entities are only looked for
+    /// when more entities were seen than are listed.
+    fn update(&mut self, world: &World, entities: &[Entity]) {
+        for &entity in entities {
+            self.seen.entry(entity).or_insert_with(|| {
+                self.next += 1;
+                self.next
+            });
+        }
+        if self.seen.len() > entities.len() {
+            self.seen
+                .retain(|&entity, _| world.get_entity(entity).is_some());
+        }
+    }
+}
+
+/// Sort the listed entities, which are already sorted by id.
+fn sort_entities(world: &World, entities: &mut [Entity], sort: EntitySort) {
+    match sort {
+        EntitySort::Id => (),
+        EntitySort::Name => entities.sort_by_cached_key(|entity| {
+            world
+                .get::<Name>(*entity)
+                .map(|name| name.as_str().to_lowercase())
+                .map_or((true, String::new()), |name| (false, name))
+        }),
+        EntitySort::ComponentCount => entities.sort_by_cached_key(|entity| {
+            std::cmp::Reverse(world.entity(*entity).archetype().components().count())
+        }),
+        EntitySort::Archetype => {
+            entities.sort_by_key(|entity| world.entity(*entity).archetype().id())
+        }
+        EntitySort::SpawnOrder => {
+            let order = world.resource::<SpawnOrder>();
+            entities.sort_by_key(|entity| order.seen.get(entity).copied());
+        }
+    }
+}
+
+/// How often the editable representation of a component is refreshed from the world while it is
+/// shown. Set per component type in [`SpyglassSettings::component_refresh`], for heavy components
+/// whose cloning would otherwise slow down every frame. Also sets how often a whole tab collects
+/// its data, in [`Spyglass::refresh`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Reflect)]
+pub enum ComponentRefresh {
+    /// Refresh every frame.
+    #[default]
+    EveryFrame,
+    /// Refresh once every given number of frames.
+    EveryNFrames(u32),
+    /// Only refresh when the component's refresh button is clicked.
+    Manual,
+}
+
+/// Create the editable representation of a component, through its custom [`ComponentRepr`] or
+/// its [`ReflectComponent`] impl.
+fn reflect_component(world: &World, entity: Entity, name: &str) -> Option<Box<dyn Reflect>> {
+    let custom = world.get_resource::<ComponentReprs>();
+    if let Some(custom) = custom.and_then(|custom| custom.reprs.get(name)) {
+        return (custom.reflect)(world, entity);
+    }
+    let refl = get_reflect_impl(world, name)?;
+    Some(refl.reflect(world.get_entity(entity)?)?.clone_value())
+}
+
+/// The label of a component in the UI and in editor paths: its short type name, or, if another of
+/// `components` has the same short name, its full type name, so that they don't collide.
+pub(crate) fn component_label(name: &str, components: &[String]) -> String {
+    let short = get_short_name(name);
+    let collides = components
+        .iter()
+        .any(|other| other != name && get_short_name(other) == short);
+    match collides {
+        true => format!("{short} ({name})"),
+        false => short,
+    }
+}
+
+/// Look a component up by its full type name, as reported by the world's components. Falls back to
+/// its [`TypeId`] for types whose type name differs from their type path, and to its short type
+/// path, which is only found if unambiguous.
 fn get_reflect_impl(world: &World, name: &str) -> Option<ReflectComponent> {
     let registry = world.get_resource::<AppTypeRegistry>()?.read();
-    let registration = registry.get_with_short_type_path(name)?;
+    let registration = registry
+        .get_with_type_path(name)
+        .or_else(|| {
+            let info = world.components().iter().find(|info| info.name() == name)?;
+            registry.get(info.type_id()?)
+        })
+        .or_else(|| registry.get_with_short_type_path(name))?;
     registration.data::<ReflectComponent>().cloned()
 }
 
 #[derive(Resource)]
-struct SelectedEntity {
-    id: Entity,
-    name: String,
+pub(crate) struct SelectedEntity {
+    pub(crate) id: Entity,
+    pub(crate) name: String,
     state: EntityComponents,
 }
 
+impl SelectedEntity {
+    pub(crate) fn new(world: &World, entity: Entity) -> Self {
+        Self {
+            id: entity,
+            name: world
+                .get::<Name>(entity)
+                .map(|name| name.to_string())
+                .unwrap_or_else(|| format!("{entity:?}")),
+            state: EntityComponents::from_entity(world, entity),
+        }
+    }
+
+    /// Iterate over the edited representations of the components, by type name.
+    pub(crate) fn reprs(&self) -> impl Iterator<Item = (&str, &dyn Reflect)> {
+        self.state
+            .reprs
+            .iter()
+            .map(|(name, repr)| (name.as_str(), repr.as_ref()))
+    }
+
+    /// The type names of the components whose edits conflict with changes of game systems.
+    pub(crate) fn conflicts(&self) -> impl Iterator<Item = &str> {
+        self.state.conflicts.iter().map(String::as_str)
+    }
+
+    /// Get the type name of the component with an editable representation by its short type
+    /// name.
+    pub(crate) fn repr_name(&self, short_name: &str) -> Option<String> {
+        let components = &self.state.components;
+        self.state
+            .reprs
+            .keys()
+            .find(|name| component_label(name, components) == short_name)
+            .cloned()
+    }
+
+    /// Get the edited representation of a component by its short type name, to edit it. If it
+    /// was not refreshed this frame, it is refreshed from the world first. It is applied back at
+    /// the end of the frame, as if an editor committed an edit to it.
+    pub(crate) fn repr_mut(
+        &mut self,
+        world: &World,
+        short_name: &str,
+    ) -> Option<&mut Box<dyn Reflect>> {
+        let name = self.repr_name(short_name)?;
+        if !self.state.pristine.contains_key(&name) {
+            self.state.reload(world, self.id, &name);
+        }
+        self.state.committed.insert(name.clone());
+        self.state.reprs.get_mut(&name)
+    }
+}
+
 #[derive(Default, Resource)]
-struct EntitySearch(String);
+pub(crate) struct EntitySearch(pub(crate) String);
 
 /// An editor of a given type. Arguments:
 /// - `ui: &mut Ui`
@@ -230,6 +1220,27 @@ pub type ReprEditor =
 pub struct ReprEditors {
     /// A map from [`type_name`](std::any::type_name)s to [`ReprEditor`].
     pub editors: HashMap<String, Box<ReprEditor>>,
+    /// A map from [`TypeId`]s to [`ReprEditor`], filled by [`insert`](Self::insert). These take
+    /// precedence over the editors registered by name.
+    pub typed_editors: HashMap<TypeId, Box<ReprEditor>>,
+    /// A map from the base type paths of generic types, without their generic arguments (e.g.
+    /// `bevy_asset::handle::Handle`), to a [`ReprEditor`] used for every instantiation of them.
+    /// Exact matches in [`editors`](Self::editors) take precedence.
+    pub generic_editors: HashMap<String, Box<ReprEditor>>,
+    /// The maximum number of fields a composite type may have to be drawn inline on one row,
+    /// provided all of its fields are values. Set to `0` to always use collapsing headers.
+    pub inline_threshold: usize,
+    /// The length, in characters, above which strings are edited with a multiline editor.
+    /// Strings containing newlines always are.
+    pub multiline_threshold: usize,
+    /// The number of rows multiline string editors grow to before they scroll instead.
+    pub multiline_max_rows: usize,
+    /// The type paths of single-field tuple structs which should be edited as regular tuple
+    /// structs, rather than transparently editing their inner value.
+    pub opaque_newtypes: HashSet<String>,
+    /// The type paths of custom editors that panicked, which are disabled for the rest of the
+    /// session in favor of the default reflect-powered editor.
+    disabled: Mutex<HashSet<String>>,
 }
 
 impl Default for ReprEditors {
@@ -249,22 +1260,70 @@ impl Default for ReprEditors {
                 ("usize".to_string(), Box::new(num_editor::<usize>)),
                 ("f32".to_string(), Box::new(num_editor::<f32>)),
                 ("f64".to_string(), Box::new(num_editor::<f64>)),
+                nonzero_editor::<NonZeroI8>(),
+                nonzero_editor::<NonZeroI16>(),
+                nonzero_editor::<NonZeroI32>(),
+                nonzero_editor::<NonZeroI64>(),
+                nonzero_editor::<NonZeroIsize>(),
+                nonzero_editor::<NonZeroU8>(),
+                nonzero_editor::<NonZeroU16>(),
+                nonzero_editor::<NonZeroU32>(),
+                nonzero_editor::<NonZeroU64>(),
+                nonzero_editor::<NonZeroUsize>(),
                 ("alloc::string::String".to_string(), Box::new(string_editor)),
+                (
+                    Cow::<'static, str>::type_path().to_string(),
+                    Box::new(cow_str_editor),
+                ),
+                (PathBuf::type_path().to_string(), Box::new(path_buf_editor)),
+                (Entity::type_path().to_string(), Box::new(entity_editor)),
+                (
+                    Handle::<Image>::type_path().to_string(),
+                    Box::new(image_handle_editor),
+                ),
+                (
+                    OsString::type_path().to_string(),
+                    Box::new(os_string_editor),
+                ),
                 (
                     std::any::type_name::<VariantProxy>().to_string(),
                     Box::new(VariantProxy::editor),
                 ),
             ]),
+            typed_editors: HashMap::default(),
+            generic_editors: HashMap::default(),
+            inline_threshold: 3,
+            multiline_threshold: 80,
+            multiline_max_rows: 12,
+            opaque_newtypes: HashSet::default(),
+            disabled: Mutex::default(),
         }
     }
 }
 
+/// `NonZero*` integers parse and display like their primitives, rejecting zero when parsing, so
+/// they can share [`num_editor`].
+fn nonzero_editor<T: Copy + Reflect + TypePath + FromStr + Display>() -> (String, Box<ReprEditor>)
+where
+    T::Err: Display,
+{
+    (T::type_path().to_string(), Box::new(num_editor::<T>))
+}
+
 impl ReprEditors {
     const REFLECT_EDITOR: &ReprEditor = &|ui, repr, world, editors, states| match repr.reflect_mut()
     {
         bevy::reflect::ReflectMut::Struct(repr) => {
             composite_editor(ui, repr, world, editors, states, false)
         }
+        bevy::reflect::ReflectMut::TupleStruct(repr)
+            if repr.field_len() == 1
+                && !editors
+                    .opaque_newtypes
+                    .contains(type_path(repr.as_reflect())) =>
+        {
+            newtype_editor(ui, repr, world, editors, states)
+        }
         bevy::reflect::ReflectMut::TupleStruct(repr) => {
             composite_editor(ui, repr, world, editors, states, false)
         }
@@ -274,104 +1333,677 @@ impl ReprEditors {
         bevy::reflect::ReflectMut::List(repr) => list_editor(ui, repr, world, editors, states),
         bevy::reflect::ReflectMut::Array(repr) => array_editor(ui, repr, world, editors, states),
         bevy::reflect::ReflectMut::Map(repr) => map_editor(ui, repr, world, editors, states),
+        bevy::reflect::ReflectMut::Enum(repr) if is_option(repr.as_reflect()) => {
+            option_editor(ui, repr, world, editors, states)
+        }
         bevy::reflect::ReflectMut::Enum(repr) => enum_editor(ui, repr, world, editors, states),
         bevy::reflect::ReflectMut::Value(repr) => value_editor(ui, repr),
     };
 
-    /// Get an editor for a type based on its name. Returns either a custom [`ReprEditor`] or a
-    /// default reflect-powered one if none exists.
-    pub fn get(&self, name: &str) -> &ReprEditor {
-        self.editors
-            .get(name)
-            .map(Box::as_ref)
-            .unwrap_or(Self::REFLECT_EDITOR)
+    /// Register an editor for every instantiation of the generic type that `T` instantiates,
+    /// e.g. `insert_generic::<Handle<Image>>(editor)` registers `editor` for all handles.
+    pub fn insert_generic<T: TypePath>(
+        &mut self,
+        editor: impl Fn(&mut Ui, &mut dyn Reflect, &mut World, &ReprEditors, &mut EditorStates)
+            + Send
+            + Sync
+            + 'static,
+    ) {
+        self.generic_editors
+            .insert(base_type_path(T::type_path()).to_string(), Box::new(editor));
+    }
+
+    /// Register an editor for the type `T`. Editors registered this way are found by
+    /// [`TypeId`], so unlike [`editors`](Self::editors) they don't depend on type paths staying
+    /// the same across versions.
+    pub fn insert<T: Reflect>(
+        &mut self,
+        editor: impl Fn(&mut Ui, &mut dyn Reflect, &mut World, &ReprEditors, &mut EditorStates)
+            + Send
+            + Sync
+            + 'static,
+    ) {
+        self.typed_editors
+            .insert(TypeId::of::<T>(), Box::new(editor));
+    }
+
+    /// Register an editor for the type `T` which edits a `T` directly, rather than a
+    /// `dyn Reflect`. Values that only represent a `T`, like the dynamic clones components are
+    /// edited through, are converted with [`FromReflect`] and the edits applied back to them.
+    pub fn insert_typed<T: FromReflect>(
+        &mut self,
+        editor: impl Fn(&mut Ui, &mut T, &mut World, &ReprEditors, &mut EditorStates)
+            + Send
+            + Sync
+            + 'static,
+    ) {
+        self.insert::<T>(move |ui, repr, world, editors, states| {
+            if let Some(value) = repr.downcast_mut::<T>() {
+                return editor(ui, value, world, editors, states);
+            }
+
+            let Some(mut value) = T::from_reflect(repr) else {
+                return Self::REFLECT_EDITOR(ui, repr, world, editors, states);
+            };
+            editor(ui, &mut value, world, editors, states);
+            repr.apply(&value);
+        });
+    }
+
+    /// Whether a custom [`ReprEditor`] is registered for a type, through any of
+    /// [`insert`](Self::insert), [`editors`](Self::editors) or
+    /// [`insert_generic`](Self::insert_generic).
+    pub fn has_custom(&self, info: &TypeInfo) -> bool {
+        self.typed_editors.contains_key(&info.type_id())
+            || self.editors.contains_key(info.type_path())
+            || self
+                .generic_editors
+                .contains_key(base_type_path(info.type_path()))
+    }
+
+    /// Get an editor for a value. Returns either a custom [`ReprEditor`], looked up by the
+    /// [`TypeId`] of the type the value represents first, then by its exact type path and then
+    /// by its [`base_type_path`], or a default reflect-powered one if none exists.
+    ///
+    /// If a custom editor panics, the panic is caught, the editor is disabled for the rest of the
+    /// session, and a [`Popup`] is shown naming the type. The default editor is used instead.
+    ///
+    /// Editors registered in [`editors`](Self::editors) report their edits with
+    /// [`EditorStates::commit`]. The values of other custom editors are compared before and after
+    /// they are drawn, and committed if they changed.
+    pub fn get(
+        &self,
+        repr: &dyn Reflect,
+    ) -> impl Fn(&mut Ui, &mut dyn Reflect, &mut World, &ReprEditors, &mut EditorStates) + '_ {
+        let typed = repr.get_represented_type_info().and_then(|info| {
+            let editor = self.typed_editors.get(&info.type_id())?;
+            Some((info.type_path(), editor, false))
+        });
+        let name = type_path(repr);
+        let custom = typed
+            .or_else(|| {
+                if let Some((name, editor)) = self.editors.get_key_value(name) {
+                    return Some((name.as_str(), editor, true));
+                }
+                let (name, editor) = self.generic_editors.get_key_value(base_type_path(name))?;
+                Some((name.as_str(), editor, false))
+            })
+            .filter(|(name, ..)| !self.disabled.lock().unwrap().contains(*name));
+
+        move |ui, repr, world, editors, states| {
+            // Read-only values without children are disabled as a whole, while composite values
+            // stay expandable.
+            let leaf = custom.is_some() || matches!(repr.reflect_ref(), ReflectRef::Value(_));
+            ui.add_enabled_ui(!(leaf && states.read_only()), |ui| {
+                let Some((name, custom, reports)) = custom else {
+                    return Self::REFLECT_EDITOR(ui, repr, world, editors, states);
+                };
+                let before = (!reports).then(|| repr.clone_value());
+
+                let result = std::panic::catch_unwind(AssertUnwindSafe(|| {
+                    custom(ui, &mut *repr, &mut *world, editors, &mut *states)
+                }));
+                if result.is_err() {
+                    self.disabled.lock().unwrap().insert(name.to_string());
+                    if let Some(mut popups) = world.get_resource_mut::<Popups>() {
+                        popups.recoverable_error(format!(
+                            "The custom editor for `{name}` panicked, and has been disabled \
+                            for this session."
+                        ));
+                    }
+                    Self::REFLECT_EDITOR(ui, repr, world, editors, states);
+                }
+                if before.is_some_and(|before| before.reflect_partial_eq(repr) != Some(true)) {
+                    states.commit();
+                }
+            });
+        }
     }
 }
 
-fn collect_entity_state(world: &mut World) {
-    let Some(SelectedEntity { id, name, state: _ }) = world.remove_resource::<SelectedEntity>() else { return };
+/// Strip the generic arguments from a type path, e.g. `core::option::Option<i32>` becomes
+/// `core::option::Option`. Paths of tuples, arrays, slices and references are returned as-is.
+pub fn base_type_path(path: &str) -> &str {
+    if path.starts_with(['(', '[', '&']) {
+        return path;
+    }
+    path.split_once('<').map_or(path, |(base, _)| base)
+}
 
-    world.insert_resource(SelectedEntity {
-        id,
-        name,
-        state: EntityComponents::from_entity(world, id),
+pub(crate) fn collect_entity_state(world: &mut World) {
+    deselect_despawned(world);
+    let Some(SelectedEntity { id, name, state }) = world.remove_resource::<SelectedEntity>() else {
+        return;
+    };
+
+    // Nothing is drawn or edited while the entities tab is hidden, so nothing is cloned either.
+    let state = match entities_tab_shown(world) {
+        true => {
+            let collapsed = world
+                .get_resource::<EditorStates>()
+                .map(|states| collapsed_components(states, &state.components))
+                .unwrap_or_default();
+            // While the tab is throttled, every component is kept like a throttled component.
+            let throttled = match Spyglass::tab_refreshes(world, "Entities") {
+                true => throttled_components(world),
+                false => state.components.iter().cloned().collect(),
+            };
+            EntityComponents::refresh(world, id, Some(state), &collapsed, &throttled)
+        }
+        false => EntityComponents {
+            pristine: HashMap::default(),
+            since: HashMap::default(),
+            ..state
+        },
+    };
+
+    world.insert_resource(SelectedEntity { id, name, state });
+}
+
+/// Deselect the selected entity if it was despawned, e.g. by the game, with a popup telling why.
+/// Returns whether it was.
+fn deselect_despawned(world: &mut World) -> bool {
+    let Some(selected) = world.get_resource::<SelectedEntity>() else {
+        return false;
+    };
+    if world.get_entity(selected.id).is_some() {
+        return false;
+    }
+    let selected = world.remove_resource::<SelectedEntity>().unwrap();
+    if let Some(mut popups) = world.get_resource_mut::<Popups>() {
+        popups.warn(format!("entity despawned: {}", selected.name));
+    }
+    true
+}
+
+fn entities_tab_shown(world: &mut World) -> bool {
+    let selected_tab = world.get_resource::<Spyglass>().is_some_and(|spyglass| {
+        spyglass
+            .selected
+            .and_then(|selected| spyglass.tabs.get(selected))
+            .is_some_and(|tab| tab.name() == "Entities")
     });
+    selected_tab && SpyglassContext::window(world).is_some()
+}
+
+/// The type names of components whose [`ComponentRefresh`] skips this frame.
+fn throttled_components(world: &World) -> HashSet<String> {
+    let Some(settings) = world.get_resource::<SpyglassSettings>() else {
+        return HashSet::default();
+    };
+    let frame = world
+        .get_resource::<FrameCount>()
+        .map_or(0, |frame| frame.0);
+    settings
+        .component_refresh
+        .iter()
+        .filter(|(_, refresh)| match refresh {
+            ComponentRefresh::EveryFrame => false,
+            ComponentRefresh::EveryNFrames(n) => !frame.is_multiple_of((*n).max(1)),
+            ComponentRefresh::Manual => true,
+        })
+        .map(|(name, _)| name.clone())
+        .collect()
+}
+
+/// The type names of the `components` whose collapsing headers were drawn closed in the last
+/// frame.
+fn collapsed_components(states: &EditorStates, components: &[String]) -> HashSet<String> {
+    let expanded = states.expanded().iter().collect::<HashSet<_>>();
+    let collapsed = states
+        .headers()
+        .iter()
+        .filter(|path| !path.contains(['.', '[']) && !expanded.contains(path))
+        .collect::<HashSet<_>>();
+    components
+        .iter()
+        .filter(|comp| collapsed.contains(&component_label(comp, components)))
+        .cloned()
+        .collect()
 }
 
 fn apply_entity_state(world: &mut World) {
-    let Some(SelectedEntity { id, name, state }) = world.remove_resource::<SelectedEntity>() else { return };
+    deselect_despawned(world);
+    let Some(SelectedEntity {
+        id,
+        name,
+        mut state,
+    }) = world.remove_resource::<SelectedEntity>()
+    else {
+        return;
+    };
+
+    let custom = world
+        .remove_resource::<ComponentReprs>()
+        .unwrap_or_default();
+    let mode = world
+        .get_resource::<SpyglassSettings>()
+        .map(|settings| settings.apply_mode)
+        .unwrap_or_default();
 
     for (name, repr) in state.reprs.iter() {
-        let refl = get_reflect_impl(world, name).unwrap();
+        // Applying unchanged values would trigger change detection of every component each frame,
+        // so only values an editor committed an edit to are applied.
+        if !state.committed.contains(name) {
+            continue;
+        }
+        let Some(pristine) = state.pristine.get_mut(name) else {
+            continue;
+        };
+        let edits = (mode == ApplyMode::Fields && !custom.reprs.contains_key(name)).then(|| {
+            let mut edits = vec![];
+            diff(String::new(), pristine.as_ref(), repr.as_ref(), &mut edits);
+            edits
+                .into_iter()
+                .map(|(path, _, value)| (path, value.clone_value()))
+                .collect::<Vec<_>>()
+        });
+        // A game system changed the component while it was edited, so applying the edits would
+        // silently overwrite its changes. They are held back until the user picks a side. If only
+        // the edited fields are applied, only changes of those fields conflict.
+        let conflicted = state.conflicts.contains(name);
+        if conflicted && !state.keep.contains(name) {
+            continue;
+        }
+        let changed = state
+            .since
+            .get(name)
+            .is_some_and(|&since| changed_since(world, id, name, since))
+            && edits.as_ref().is_none_or(|edits| {
+                let paths = edits.iter().map(|(path, _)| path.as_str());
+                fields_changed(world, id, name, pristine.as_ref(), paths)
+            });
+        if changed && !conflicted {
+            state.conflicts.insert(name.clone());
+            continue;
+        }
+        state.conflicts.remove(name);
+        state.keep.remove(name);
+        state.committed.remove(name);
+        // Throttled reprs are kept across frames, so they must not be applied again.
+        *pristine = repr.clone_value();
+        state.since.insert(name.clone(), world.read_change_tick());
+
+        if let Some(custom) = custom.reprs.get(name) {
+            (custom.apply)(world, id, &**repr);
+            continue;
+        }
+        if let Some(edits) = edits {
+            let mut commands = world.resource_mut::<ReflectCommands>();
+            for (path, value) in edits {
+                commands.push(ReflectCommand {
+                    entity: id,
+                    component: name.clone(),
+                    path,
+                    value,
+                });
+            }
+            continue;
+        }
 
-        refl.apply(&mut world.entity_mut(id), &**repr);
+        let Some(refl) = get_reflect_impl(world, name) else {
+            report_apply_failure(world, name, "its type is not registered");
+            continue;
+        };
+        let Some(mut entity) = world.get_entity_mut(id) else {
+            break;
+        };
+        let Some(mut component) = refl.reflect_mut(&mut entity) else {
+            report_apply_failure(world, name, "the entity no longer has it");
+            continue;
+        };
+        let applied = std::panic::catch_unwind(AssertUnwindSafe(|| component.apply(&**repr)));
+        if applied.is_err() {
+            report_apply_failure(world, name, "the edited value does not match its type");
+        }
     }
 
+    world.insert_resource(custom);
     world.insert_resource(SelectedEntity { id, name, state });
 }
 
-/// The resource that stores a list of current [`Popup`]s.
+/// Report an edit of a component of the selected entity that could not be applied, and was
+/// skipped.
+fn report_apply_failure(world: &mut World, name: &str, reason: &str) {
+    if let Some(mut popups) = world.get_resource_mut::<Popups>() {
+        popups.recoverable_error(format!(
+            "failed to apply `{}`: {reason}",
+            get_short_name(name)
+        ));
+    }
+}
+
+/// A function that creates an editable representation of a component of an entity, if it has one.
+pub type ComponentReflector = dyn Fn(&World, Entity) -> Option<Box<dyn Reflect>> + Send + Sync;
+
+/// A function that applies an edited representation back to the component of an entity.
+pub type ComponentApplier = dyn Fn(&mut World, Entity, &dyn Reflect) + Send + Sync;
+
+/// A custom representation of a component, used instead of its [`ReflectComponent`] impl. This
+/// allows components that do not implement [`Reflect`], such as FFI wrappers, to be displayed and
+/// edited through a stand-in type that does.
+pub struct ComponentRepr {
+    /// Creates the representation that is displayed and edited.
+    pub reflect: Box<ComponentReflector>,
+    /// Applies the edited representation back to the component.
+    pub apply: Box<ComponentApplier>,
+}
+
+/// The resource that contains [`ComponentRepr`]s, mapping from component
+/// [`type_name`](std::any::type_name)s to their custom representation.
+#[derive(Default, Resource)]
+pub struct ComponentReprs {
+    /// A map from component [`type_name`](std::any::type_name)s to [`ComponentRepr`].
+    pub reprs: HashMap<String, ComponentRepr>,
+}
+
+impl ComponentReprs {
+    /// Add a custom representation for the component `C`.
+    pub fn insert<C: Component>(
+        &mut self,
+        reflect: impl Fn(&World, Entity) -> Option<Box<dyn Reflect>> + Send + Sync + 'static,
+        apply: impl Fn(&mut World, Entity, &dyn Reflect) + Send + Sync + 'static,
+    ) {
+        self.reprs.insert(
+            std::any::type_name::<C>().to_string(),
+            ComponentRepr {
+                reflect: Box::new(reflect),
+                apply: Box::new(apply),
+            },
+        );
+    }
+}
+
+/// A function that opens an entity in external tooling.
+pub type ExternalToolFn = dyn Fn(&mut World, Entity) + Send + Sync;
+
+/// A link from the inspector into a team's external tooling, such as "open source asset" or
+/// "open in level editor". Displayed as a button in the header of the selected entity.
+pub struct ExternalTool {
+    /// The label of the button.
+    pub label: String,
+    /// Invoked with the selected entity when the button is clicked. It has access to the world,
+    /// so it can look up whatever it needs to open the entity, like the path of the asset it was
+    /// spawned from.
+    pub open: Box<ExternalToolFn>,
+}
+
+/// The resource that contains the [`ExternalTool`]s shown for the selected entity.
+#[derive(Default, Resource)]
+pub struct ExternalTools {
+    /// The tools, in the order their buttons are displayed.
+    pub tools: Vec<ExternalTool>,
+}
+
+impl ExternalTools {
+    /// Add a tool with the given button label.
+    pub fn add(
+        &mut self,
+        label: impl Into<String>,
+        open: impl Fn(&mut World, Entity) + Send + Sync + 'static,
+    ) {
+        self.tools.push(ExternalTool {
+            label: label.into(),
+            open: Box::new(open),
+        });
+    }
+}
+
+/// The most popups displayed at once. The rest wait in the queue, behind an indicator of how
+/// many there are.
+const MAX_SHOWN_POPUPS: usize = 5;
+
+/// How long toasts without a timeout of their own are displayed for.
+const TOAST_TIMEOUT: Duration = Duration::from_secs(8);
+
+/// The widest a toast gets, in points.
+const TOAST_WIDTH: f32 = 320.0;
+
+/// How long [`Popups::info`] popups are displayed for.
+const INFO_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The resource that stores a queue of current [`Popup`]s, oldest first.
 #[derive(Default, Resource)]
 pub struct Popups {
     popups: Vec<Popup>,
 }
 
 impl Popups {
-    /// Display the contained popups to the given [`egui::Context`].
-    pub fn display_popups(&mut self, ui: &mut egui::Context) {
-        let mut i = 0;
-        loop {
-            if i >= self.popups.len() {
-                break;
-            }
+    /// Display the oldest popups to the given [`egui::Context`], along with the number of queued
+    /// ones, and remove the dismissed and timed out ones. Recoverable errors are displayed
+    /// according to `recoverable`.
+    pub fn display_popups(&mut self, ctx: &mut egui::Context, recoverable: NotificationStyle) {
+        let now = ctx.input(|input| input.time);
+        let is_toast = |popup: &Popup| popup.recoverable && recoverable == NotificationStyle::Toast;
+        let (toasts, windows): (Vec<_>, Vec<_>) =
+            (0..self.popups.len()).partition(|&i| is_toast(&self.popups[i]));
 
-            let popup = &self.popups[i];
-            if popup.display(i, ui) {
-                self.popups.swap_remove(i);
-            } else {
-                i += 1;
+        let mut dismissed = vec![];
+        for &i in windows.iter().take(MAX_SHOWN_POPUPS) {
+            let popup = &mut self.popups[i];
+            if popup.expired(now, None) || popup.display(i, ctx) {
+                dismissed.push(i);
             }
         }
+        if !toasts.is_empty() {
+            egui::Area::new("spyglass_toasts")
+                .anchor(egui::Align2::RIGHT_BOTTOM, egui::vec2(-8.0, -8.0))
+                .order(egui::Order::Foreground)
+                .show(ctx, |ui| {
+                    ui.set_max_width(TOAST_WIDTH);
+                    for &i in toasts.iter().take(MAX_SHOWN_POPUPS) {
+                        let popup = &mut self.popups[i];
+                        if popup.expired(now, Some(TOAST_TIMEOUT)) || popup.display_toast(ui) {
+                            dismissed.push(i);
+                        }
+                    }
+                });
+        }
+        dismissed.sort_unstable();
+        for i in dismissed.into_iter().rev() {
+            self.popups.remove(i);
+        }
+
+        let queued = windows.len().saturating_sub(MAX_SHOWN_POPUPS);
+        if queued > 0 {
+            egui::Window::new("")
+                .id(egui::Id::new("popup_queue"))
+                .title_bar(false)
+                .collapsible(false)
+                .resizable(false)
+                .show(ctx, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label(format!("{queued} more…"));
+                        if ui.small_button("dismiss all").clicked() {
+                            self.popups.retain(is_toast);
+                        }
+                    });
+                });
+        }
     }
 
-    /// Push a new popup onto the list.
+    /// Push a new popup onto the queue.
     pub fn add(&mut self, popup: Popup) {
         self.popups.push(popup);
     }
+
+    /// Push an [`Severity::Info`] popup, which is dismissed after a few seconds.
+    pub fn info(&mut self, msg: impl Into<String>) {
+        self.add(Popup::new(msg).with_timeout(INFO_TIMEOUT));
+    }
+
+    /// Push a [`Severity::Warning`] popup.
+    pub fn warn(&mut self, msg: impl Into<String>) {
+        self.add(Popup::new(msg).with_severity(Severity::Warning));
+    }
+
+    /// Push a [`Severity::Error`] popup.
+    pub fn error(&mut self, msg: impl Into<String>) {
+        self.add(Popup::new(msg).with_severity(Severity::Error));
+    }
+
+    /// Push a [`Severity::Error`] popup for a recoverable error, such as an edit that failed to
+    /// parse. These are displayed as set in [`SpyglassSettings::recoverable_errors`].
+    pub fn recoverable_error(&mut self, msg: impl Into<String>) {
+        self.add(Popup::new(msg).with_severity(Severity::Error).recoverable());
+    }
+
+    /// The queued popups, oldest first.
+    pub fn iter(&self) -> impl Iterator<Item = &Popup> {
+        self.popups.iter()
+    }
+}
+
+/// How recoverable errors are displayed, set in [`SpyglassSettings::recoverable_errors`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Reflect)]
+pub enum NotificationStyle {
+    /// As popup windows, which stay until they are dismissed.
+    Popup,
+    /// As toasts stacked in the bottom right corner, which do not block anything and are dismissed
+    /// after a few seconds.
+    #[default]
+    Toast,
+}
+
+/// How important a [`Popup`] is, which decides its color.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Severity {
+    /// Something the user may want to know, such as a finished export.
+    #[default]
+    Info,
+    /// Something that did not go as expected, but did not fail.
+    Warning,
+    /// Something that failed.
+    Error,
+}
+
+impl Severity {
+    fn color(self, visuals: &egui::Visuals) -> egui::Color32 {
+        match self {
+            Self::Info => visuals.widgets.noninteractive.fg_stroke.color,
+            Self::Warning => visuals.warn_fg_color,
+            Self::Error => visuals.error_fg_color,
+        }
+    }
 }
 
-/// A message popup, to be used with [`Popups`]. Commonly used for error messages.
+/// A message popup, to be used with [`Popups`]. Commonly used for error messages. Popups stay
+/// until they are dismissed, or until their timeout runs out.
 pub struct Popup {
     message: String,
+    severity: Severity,
+    timeout: Option<Duration>,
+    recoverable: bool,
+    /// The egui time the popup was first displayed at.
+    shown_at: Option<f64>,
 }
 
 impl Popup {
-    /// Create a new message popup.
+    /// Create a new [`Severity::Info`] message popup, without a timeout.
     pub fn new(msg: impl Into<String>) -> Self {
         Popup {
             message: msg.into(),
+            severity: Severity::default(),
+            timeout: None,
+            recoverable: false,
+            shown_at: None,
         }
     }
 
-    /// Display a popup to the given [`egui::Context`] with a given [`egui::Id`] source.
+    /// Set the severity of the popup.
+    pub fn with_severity(mut self, severity: Severity) -> Self {
+        self.severity = severity;
+        self
+    }
+
+    /// Dismiss the popup automatically once it has been displayed for `timeout`.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Mark the popup as reporting a recoverable error, which may be displayed as a toast.
+    pub fn recoverable(mut self) -> Self {
+        self.recoverable = true;
+        self
+    }
+
+    /// Whether the popup reports a recoverable error.
+    pub fn is_recoverable(&self) -> bool {
+        self.recoverable
+    }
+
+    /// The message of the popup.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// The severity of the popup.
+    pub fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    /// Whether the popup has been displayed for longer than its timeout, or `default` if it has
+    /// none. Starts the timeout the first time it is called.
+    fn expired(&mut self, now: f64, default: Option<Duration>) -> bool {
+        let shown_at = *self.shown_at.get_or_insert(now);
+        self.timeout
+            .or(default)
+            .is_some_and(|timeout| now - shown_at >= timeout.as_secs_f64())
+    }
+
+    /// Display the popup as a toast. Returns whether it was dismissed.
+    fn display_toast(&self, ui: &mut Ui) -> bool {
+        let color = self.severity.color(ui.visuals());
+        egui::Frame::popup(ui.style())
+            .stroke(egui::Stroke::new(1.5, color))
+            .show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    let dismissed = ui.small_button("×").clicked();
+                    ui.colored_label(color, &self.message);
+                    dismissed
+                })
+                .inner
+            })
+            .inner
+    }
+
+    /// Display a popup to the given [`egui::Context`] with a given [`egui::Id`] source. Returns
+    /// whether it was dismissed.
     pub fn display(&self, id: usize, ctx: &mut egui::Context) -> bool {
+        let color = self.severity.color(&ctx.style().visuals);
+        let frame = egui::Frame::window(&ctx.style()).stroke(egui::Stroke::new(1.5, color));
         let win = egui::Window::new("")
             .id(egui::Id::new("popup_window").with(id))
             .title_bar(false)
             .collapsible(false)
+            .frame(frame)
             .show(ctx, |ui| {
                 ui.vertical(|ui| {
+                    if self.severity != Severity::Info {
+                        ui.colored_label(color, format!("{:?}", self.severity).to_lowercase());
+                    }
                     ui.label(&self.message);
                     ui.vertical_centered(|ui| ui.button("ok").clicked())
                 })
             })
             .unwrap();
-        win.response.clicked_elsewhere()
-            || ctx.input(|inp| !inp.keys_down.is_empty())
-            || win.inner.unwrap().inner.inner
+        win.inner.unwrap().inner.inner
     }
 }
 
-fn display_popups(mut egui: EguiContexts, mut popups: ResMut<Popups>) {
-    popups.display_popups(egui.ctx_mut())
+fn display_popups(world: &mut World) {
+    let Some(window) = SpyglassContext::window(world) else {
+        return;
+    };
+    let Some(mut ctx) = Egui::context(world, window) else {
+        return;
+    };
+    let recoverable = world
+        .get_resource::<SpyglassSettings>()
+        .map(|settings| settings.recoverable_errors)
+        .unwrap_or_default();
+    world
+        .resource_mut::<Popups>()
+        .display_popups(&mut ctx, recoverable);
 }
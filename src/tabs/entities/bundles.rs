@@ -0,0 +1,154 @@
+//! The "add component" menu of the selected entity. It lists the presets registered in
+//! [`SpyglassBundles`] as one-click groups, then every registered component that reflects
+//! `Default` and the entity does not have yet, which speeds up composing test entities.
+
+use bevy::ecs::world::EntityWorldMut;
+use bevy::prelude::*;
+
+use crate::compat::egui::{self, ScrollArea, Ui};
+
+/// A function that inserts a bundle into an entity.
+pub type BundleInsertFn = dyn Fn(&mut EntityWorldMut) + Send + Sync;
+
+/// A named bundle, listed in the "add component" menu.
+pub struct SpyglassBundle {
+    /// The name the bundle is listed by, e.g. "SpriteBundle defaults" or "Enemy base".
+    pub name: String,
+    /// Invoked with the selected entity when the bundle is picked.
+    pub insert: Box<BundleInsertFn>,
+}
+
+/// The resource that contains the bundles listed in the "add component" menu of the entities tab.
+#[derive(Default, Resource)]
+pub struct SpyglassBundles {
+    /// The bundles, in the order they are listed.
+    pub bundles: Vec<SpyglassBundle>,
+}
+
+impl SpyglassBundles {
+    /// Add a bundle with the given name, which is created by `bundle` every time it is inserted.
+    pub fn add<B: Bundle>(
+        &mut self,
+        name: impl Into<String>,
+        bundle: impl Fn() -> B + Send + Sync + 'static,
+    ) {
+        self.bundles.push(SpyglassBundle {
+            name: name.into(),
+            insert: Box::new(move |entity| {
+                entity.insert(bundle());
+            }),
+        });
+    }
+
+    /// Insert the first bundle named `name` into `entity`. Returns whether there was one, and the
+    /// entity exists. The resource is removed while the bundle is inserted.
+    pub fn insert(world: &mut World, entity: Entity, name: &str) -> bool {
+        let Some(bundles) = world.remove_resource::<Self>() else {
+            return false;
+        };
+        let bundle = bundles.bundles.iter().find(|bundle| bundle.name == name);
+        let inserted = match (bundle, world.get_entity_mut(entity)) {
+            (Some(bundle), Some(mut entity)) => {
+                (bundle.insert)(&mut entity);
+                true
+            }
+            _ => false,
+        };
+        world.insert_resource(bundles);
+        inserted
+    }
+}
+
+/// What was picked in the "add component" menu.
+enum Pick {
+    Bundle(String),
+    /// A component, by type path.
+    Component(String),
+}
+
+/// The registered components that reflect `Default` and `entity` does not have, as their short
+/// and full type paths, sorted by short path.
+fn addable_components(world: &World, entity: Entity) -> Vec<(String, String)> {
+    let Some(entity) = world.get_entity(entity) else {
+        return vec![];
+    };
+    let registry = world.resource::<AppTypeRegistry>().read();
+    let mut components = registry
+        .iter()
+        .filter(|registration| {
+            registration.data::<ReflectComponent>().is_some()
+                && registration.data::<ReflectDefault>().is_some()
+                && !entity.contains_type_id(registration.type_id())
+        })
+        .map(|registration| {
+            let table = registration.type_info().type_path_table();
+            (table.short_path().to_string(), table.path().to_string())
+        })
+        .collect::<Vec<_>>();
+    components.sort();
+    components
+}
+
+/// Insert the default value of the component with the type path `path` into `entity`.
+fn insert_default_component(world: &mut World, entity: Entity, path: &str) {
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    let registry = registry.read();
+    let Some(registration) = registry.get_with_type_path(path) else {
+        return;
+    };
+    let (Some(component), Some(default)) = (
+        registration.data::<ReflectComponent>(),
+        registration.data::<ReflectDefault>(),
+    ) else {
+        return;
+    };
+    if let Some(mut entity) = world.get_entity_mut(entity) {
+        component.insert(&mut entity, default.default().as_ref());
+    }
+}
+
+/// A button that opens the "add component" menu of `entity`, filterable by name.
+pub(crate) fn add_component_menu(ui: &mut Ui, world: &mut World, entity: Entity) {
+    let filter_id = ui.id().with("add_component_filter");
+    let mut pick = None;
+
+    ui.menu_button("add component", |ui| {
+        let mut filter = ui.data_mut(|data| data.get_temp::<String>(filter_id).unwrap_or_default());
+        egui::TextEdit::singleline(&mut filter)
+            .hint_text("Filter")
+            .show(ui);
+        let matches = |name: &str| name.to_lowercase().contains(&filter.to_lowercase());
+
+        ScrollArea::new([false, true])
+            .max_height(300.0)
+            .show(ui, |ui| {
+                let bundles = world.get_resource::<SpyglassBundles>();
+                for bundle in bundles.iter().flat_map(|bundles| bundles.bundles.iter()) {
+                    if matches(&bundle.name) && ui.button(&bundle.name).clicked() {
+                        pick = Some(Pick::Bundle(bundle.name.clone()));
+                        ui.close_menu();
+                    }
+                }
+                ui.separator();
+                for (short, path) in addable_components(world, entity) {
+                    if !matches(&short) {
+                        continue;
+                    }
+                    if ui.button(&short).on_hover_text(&path).clicked() {
+                        pick = Some(Pick::Component(path));
+                        ui.close_menu();
+                    }
+                }
+            });
+
+        ui.data_mut(|data| data.insert_temp(filter_id, filter));
+    });
+
+    match pick {
+        Some(Pick::Bundle(name)) => {
+            SpyglassBundles::insert(world, entity, &name);
+        }
+        Some(Pick::Component(path)) => insert_default_component(world, entity, &path),
+        None => (),
+    }
+}
@@ -0,0 +1,276 @@
+//! Compares the reflected components of two entities field by field, to answer questions like
+//! "why does this enemy behave differently from that one". Started with the "compare with" button
+//! of the selected entity.
+
+use bevy::prelude::*;
+use bevy::reflect::ReflectRef;
+use bevy::utils::get_short_name;
+
+use super::entity_name;
+use crate::compat::egui::{self, Ui};
+
+/// How deep [`diff_reflect`] descends into nested values before comparing them as a whole.
+const MAX_DEPTH: usize = 8;
+
+/// The resource that holds the entities being compared. While only `left` is set, the entity
+/// list picks the entity to compare it with.
+#[derive(Default, Resource)]
+pub struct CompareEntities {
+    /// The entity the comparison was started from.
+    pub left: Option<Entity>,
+    /// The entity it is compared with.
+    pub right: Option<Entity>,
+    /// Whether only the fields that differ are listed.
+    pub only_differences: bool,
+}
+
+impl CompareEntities {
+    /// Whether an entity to compare with is being picked.
+    pub fn picking(&self) -> bool {
+        self.left.is_some() && self.right.is_none()
+    }
+}
+
+/// A leaf field of a component, as formatted on both entities.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FieldDiff {
+    /// The path of the field within the component, e.g. `.translation.x`.
+    pub path: String,
+    /// The value on the left entity.
+    pub left: String,
+    /// The value on the right entity.
+    pub right: String,
+}
+
+impl FieldDiff {
+    /// Whether the values differ.
+    pub fn differs(&self) -> bool {
+        self.left != self.right
+    }
+}
+
+/// The comparison of the components of two entities.
+#[derive(Clone, Debug, Default)]
+pub struct EntityComparison {
+    /// The fields of each reflected component both entities have, by component type name.
+    pub shared: Vec<(String, Vec<FieldDiff>)>,
+    /// The components only the left entity has.
+    pub only_left: Vec<String>,
+    /// The components only the right entity has.
+    pub only_right: Vec<String>,
+}
+
+impl EntityComparison {
+    /// Compare the components of two entities.
+    pub fn new(world: &World, left: Entity, right: Entity) -> Self {
+        let names = |entity| {
+            world
+                .inspect_entity(entity)
+                .into_iter()
+                .map(|info| (info.name().to_string(), info.type_id()))
+                .collect::<Vec<_>>()
+        };
+        let (left_components, right_components) = (names(left), names(right));
+
+        let registry = world.resource::<AppTypeRegistry>().read();
+        let mut comparison = Self::default();
+        for (name, type_id) in &left_components {
+            if !right_components.iter().any(|(other, _)| other == name) {
+                comparison.only_left.push(name.clone());
+                continue;
+            }
+            let reflect = type_id
+                .and_then(|type_id| registry.get(type_id))
+                .and_then(|registration| registration.data::<ReflectComponent>());
+            let values = reflect.and_then(|reflect| {
+                Some((
+                    reflect.reflect(world.entity(left))?,
+                    reflect.reflect(world.entity(right))?,
+                ))
+            });
+            if let Some((left, right)) = values {
+                comparison
+                    .shared
+                    .push((name.clone(), diff_reflect(left, right)));
+            }
+        }
+        comparison.only_right = right_components
+            .into_iter()
+            .filter(|(name, _)| !left_components.iter().any(|(other, _)| other == name))
+            .map(|(name, _)| name)
+            .collect();
+        comparison.shared.sort_by(|a, b| a.0.cmp(&b.0));
+        comparison
+    }
+}
+
+/// Compare two reflected values field by field. Every leaf field is listed, whether it differs or
+/// not. Values whose shapes differ, such as enums in different variants or lists of different
+/// lengths, are listed as a whole.
+pub fn diff_reflect(left: &dyn Reflect, right: &dyn Reflect) -> Vec<FieldDiff> {
+    let mut diffs = vec![];
+    diff_at(left, right, &mut String::new(), 0, &mut diffs);
+    diffs
+}
+
+fn diff_at(
+    left: &dyn Reflect,
+    right: &dyn Reflect,
+    path: &mut String,
+    depth: usize,
+    diffs: &mut Vec<FieldDiff>,
+) {
+    let mut child = |segment: String, left: &dyn Reflect, right: &dyn Reflect| {
+        let len = path.len();
+        path.push_str(&segment);
+        diff_at(left, right, path, depth + 1, diffs);
+        path.truncate(len);
+    };
+
+    if depth < MAX_DEPTH {
+        match (left.reflect_ref(), right.reflect_ref()) {
+            (ReflectRef::Struct(l), ReflectRef::Struct(r)) if l.field_len() == r.field_len() => {
+                for i in 0..l.field_len() {
+                    let name = l.name_at(i).unwrap_or_default();
+                    child(
+                        format!(".{name}"),
+                        l.field_at(i).unwrap(),
+                        r.field_at(i).unwrap(),
+                    );
+                }
+                return;
+            }
+            (ReflectRef::TupleStruct(l), ReflectRef::TupleStruct(r))
+                if l.field_len() == r.field_len() =>
+            {
+                for i in 0..l.field_len() {
+                    child(format!(".{i}"), l.field(i).unwrap(), r.field(i).unwrap());
+                }
+                return;
+            }
+            (ReflectRef::Tuple(l), ReflectRef::Tuple(r)) if l.field_len() == r.field_len() => {
+                for i in 0..l.field_len() {
+                    child(format!(".{i}"), l.field(i).unwrap(), r.field(i).unwrap());
+                }
+                return;
+            }
+            (ReflectRef::List(l), ReflectRef::List(r)) if l.len() == r.len() => {
+                for i in 0..l.len() {
+                    child(format!("[{i}]"), l.get(i).unwrap(), r.get(i).unwrap());
+                }
+                return;
+            }
+            (ReflectRef::Array(l), ReflectRef::Array(r)) if l.len() == r.len() => {
+                for i in 0..l.len() {
+                    child(format!("[{i}]"), l.get(i).unwrap(), r.get(i).unwrap());
+                }
+                return;
+            }
+            (ReflectRef::Enum(l), ReflectRef::Enum(r))
+                if l.variant_name() == r.variant_name() && l.field_len() > 0 =>
+            {
+                for i in 0..l.field_len() {
+                    let segment = match l.name_at(i) {
+                        Some(name) => format!(".{name}"),
+                        None => format!(".{i}"),
+                    };
+                    child(segment, l.field_at(i).unwrap(), r.field_at(i).unwrap());
+                }
+                return;
+            }
+            _ => (),
+        }
+    }
+
+    diffs.push(FieldDiff {
+        path: path.clone(),
+        left: format!("{left:?}"),
+        right: format!("{right:?}"),
+    });
+}
+
+/// Draw the comparison of the entities in [`CompareEntities`].
+pub(crate) fn draw_comparison(ui: &mut Ui, world: &mut World) {
+    let compare = world.resource::<CompareEntities>();
+    let (Some(left), Some(right)) = (compare.left, compare.right) else {
+        return;
+    };
+    let mut only_differences = compare.only_differences;
+    if ui.button("back").clicked()
+        || world.get_entity(left).is_none()
+        || world.get_entity(right).is_none()
+    {
+        *world.resource_mut::<CompareEntities>() = CompareEntities {
+            only_differences,
+            ..default()
+        };
+        return;
+    }
+
+    let (left_name, right_name) = (entity_name(world, left), entity_name(world, right));
+    ui.vertical_centered(|ui| ui.heading(format!("{left_name}  ↔  {right_name}")));
+    ui.checkbox(&mut only_differences, "only show differences");
+    world.resource_mut::<CompareEntities>().only_differences = only_differences;
+
+    let comparison = EntityComparison::new(world, left, right);
+    egui::ScrollArea::vertical()
+        .id_source("entity_comparison")
+        .show(ui, |ui| {
+            for (component, fields) in &comparison.shared {
+                let differing = fields.iter().filter(|field| field.differs()).count();
+                if only_differences && differing == 0 {
+                    continue;
+                }
+                let header = match differing {
+                    0 => get_short_name(component),
+                    n => format!("{} ({n} differ)", get_short_name(component)),
+                };
+                egui::CollapsingHeader::new(header)
+                    .id_source(("compare", component))
+                    .default_open(differing > 0)
+                    .show(ui, |ui| {
+                        egui::Grid::new(("compare_fields", component))
+                            .striped(true)
+                            .show(ui, |ui| {
+                                for field in fields {
+                                    if only_differences && !field.differs() {
+                                        continue;
+                                    }
+                                    let path = match field.path.is_empty() {
+                                        true => "value",
+                                        false => field.path.as_str(),
+                                    };
+                                    match field.differs() {
+                                        true => {
+                                            let color = ui.visuals().warn_fg_color;
+                                            ui.colored_label(color, path);
+                                            ui.colored_label(color, &field.left);
+                                            ui.colored_label(color, &field.right);
+                                        }
+                                        false => {
+                                            ui.label(path);
+                                            ui.label(&field.left);
+                                            ui.label(&field.right);
+                                        }
+                                    }
+                                    ui.end_row();
+                                }
+                            });
+                    });
+            }
+
+            for (components, name) in [
+                (&comparison.only_left, &left_name),
+                (&comparison.only_right, &right_name),
+            ] {
+                if components.is_empty() {
+                    continue;
+                }
+                ui.separator();
+                ui.label(format!("only on {name}:"));
+                for component in components {
+                    ui.weak(get_short_name(component));
+                }
+            }
+        });
+}
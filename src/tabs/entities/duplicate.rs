@@ -0,0 +1,86 @@
+//! Duplicates entities through their reflected components, so that a configured entity can be
+//! used as a blueprint for more of them.
+
+use bevy::prelude::*;
+use bevy::utils::get_short_name;
+
+/// The result of [`duplicate_entity`].
+#[derive(Clone, Debug)]
+pub struct Duplicate {
+    /// The copy of the entity.
+    pub entity: Entity,
+    /// The type names of the components that could not be cloned, as they do not reflect
+    /// [`Component`], across the entity and its copied descendants.
+    pub skipped: Vec<String>,
+}
+
+/// Clone every reflected component of `entity` into a new entity, which gets the same parent. If
+/// `recursive` is set, the children are duplicated as well, and added to the copy. The hierarchy
+/// components, [`Parent`] and [`Children`], are never cloned, as they are rebuilt instead.
+pub fn duplicate_entity(world: &mut World, entity: Entity, recursive: bool) -> Duplicate {
+    let mut skipped = vec![];
+    let copy = duplicate_recursive(world, entity, recursive, &mut skipped);
+    if let Some(parent) = world.get::<Parent>(entity).map(Parent::get) {
+        world.entity_mut(parent).add_child(copy);
+    }
+    skipped.sort();
+    skipped.dedup();
+    Duplicate {
+        entity: copy,
+        skipped,
+    }
+}
+
+fn duplicate_recursive(
+    world: &mut World,
+    entity: Entity,
+    recursive: bool,
+    skipped: &mut Vec<String>,
+) -> Entity {
+    let hierarchy = [
+        std::any::TypeId::of::<Parent>(),
+        std::any::TypeId::of::<Children>(),
+    ];
+    let components = world
+        .inspect_entity(entity)
+        .into_iter()
+        .filter(|info| info.type_id().is_none_or(|id| !hierarchy.contains(&id)))
+        .map(|info| (info.type_id(), info.name().to_string()))
+        .collect::<Vec<_>>();
+
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    let registry = registry.read();
+    let mut values = vec![];
+    for (type_id, name) in components {
+        let reflect = type_id
+            .and_then(|id| registry.get(id))
+            .and_then(|registration| registration.data::<ReflectComponent>());
+        let value = reflect.and_then(|reflect| {
+            Some((
+                reflect,
+                reflect.reflect(world.entity(entity))?.clone_value(),
+            ))
+        });
+        match value {
+            Some(value) => values.push(value),
+            None => skipped.push(get_short_name(&name)),
+        }
+    }
+
+    let copy = world.spawn_empty().id();
+    for (reflect, value) in values {
+        reflect.insert(&mut world.entity_mut(copy), value.as_ref());
+    }
+
+    if recursive {
+        let children = world
+            .get::<Children>(entity)
+            .map(|children| children.to_vec())
+            .unwrap_or_default();
+        for child in children {
+            let child_copy = duplicate_recursive(world, child, true, skipped);
+            world.entity_mut(copy).add_child(child_copy);
+        }
+    }
+    copy
+}
@@ -1,24 +1,31 @@
 //! A module that defines the editors used in the entity inspector.
 
+use std::borrow::Cow;
+use std::ffi::OsString;
 use std::fmt::Display;
+use std::path::PathBuf;
 use std::str::FromStr;
 
+use bevy::core::FrameCount;
 use bevy::prelude::*;
 use bevy::reflect::{
     Array, DynamicArray, DynamicEnum, DynamicList, DynamicMap, DynamicStruct, DynamicTuple,
-    DynamicTupleStruct, DynamicVariant, Enum, EnumInfo, List, Map, Tuple, TypeInfo, VariantInfo,
-    VariantType,
+    DynamicTupleStruct, DynamicVariant, Enum, EnumInfo, List, Map, ReflectRef, Tuple, TypeInfo,
+    VariantInfo, VariantType,
 };
-use bevy::utils::HashMap;
-use bevy_egui::egui::{self, InnerResponse, ScrollArea, Ui};
+use bevy::utils::{get_short_name, HashMap, HashSet};
 
-use super::ReprEditors;
+use super::selection::{EntitySelected, SelectEntity};
+use super::{entity_name, Popups, ReprEditors};
+use crate::compat::egui::{self, InnerResponse, ScrollArea, Ui};
+use crate::tabs::watches::Watches;
+use crate::widgets::{asset_path_picker, image_preview, AssetFolder};
 
 /// The state of an editor. These are assembled into a tree of states in [`EditorStates`]. This
 /// allows having persistent state for each editor. This state is stored based on [`egui::Id`],
 /// so make sure to keep those consistent. They are also generally cleared all at once sometimes,
-/// so make sure to check for freshness and clear any child editor states when necessary.
-/// The builtin editors are a good example of how to do this.
+/// so editors with child editors should draw them in [`EditorStates::scope`], which clears the
+/// states of the children when the state of the parent is fresh.
 pub enum EditorState {
     /// Persistent state for a text editor. This prevents attempting to apply incomplete text
     /// immediately.
@@ -32,7 +39,8 @@ pub enum EditorState {
 }
 
 impl EditorState {
-    /// Unwrap [`EditorState::TextEdit`] from an [`EditorState`].
+    /// Unwrap [`EditorState::TextEdit`] from an [`EditorState`]. Panics on other variants, see
+    /// [`try_text_edit`](Self::try_text_edit).
     pub fn text_edit(&mut self) -> &mut String {
         match self {
             Self::TextEdit { temp_value } => temp_value,
@@ -40,13 +48,30 @@ impl EditorState {
         }
     }
 
-    /// Unwrap [`EditorState::Composite`] from an [`EditorState`].
+    /// Unwrap [`EditorState::Composite`] from an [`EditorState`]. Panics on other variants, see
+    /// [`try_composite`](Self::try_composite).
     pub fn composite(&mut self) {
         match self {
             Self::Composite => (),
             _ => panic!(),
         }
     }
+
+    /// Get the text of an [`EditorState::TextEdit`], or `None` for other variants.
+    pub fn try_text_edit(&mut self) -> Option<&mut String> {
+        match self {
+            Self::TextEdit { temp_value } => Some(temp_value),
+            _ => None,
+        }
+    }
+
+    /// Check for an [`EditorState::Composite`], returning `None` for other variants.
+    pub fn try_composite(&mut self) -> Option<()> {
+        match self {
+            Self::Composite => Some(()),
+            _ => None,
+        }
+    }
 }
 
 /// A constructor. These represent windows that are used to construct a value of a given type,
@@ -82,13 +107,16 @@ impl Ctor {
 
                     ui.vertical_centered(|ui| ui.heading("Constructor"));
 
-                    let editor = editors.get(value.type_name());
+                    let editor = editors.get(&**value);
+                    // Edits of the value being constructed are not committed to the edited value.
+                    let committed = states.take_committed();
                     ui.push_id(0, |ui| {
                         if self.fresh {
                             states.remove(ui.id());
                         }
                         editor(ui, &mut **value, world, editors, states)
                     });
+                    states.committed = committed;
                     ui.vertical_centered(|ui| {
                         if ui.button("apply").clicked() {
                             self.value.take()
@@ -138,15 +166,50 @@ impl Ctors {
 }
 
 /// Stores the state of editors. This comes in the form of [`EditorState`] and [`Ctors`].
+///
+/// Every access stamps the id with the current frame, and states that were not accessed for
+/// [`STATE_IDLE_FRAMES`] frames are dropped, so the states of editors that are no longer drawn,
+/// such as those of despawned entities, don't accumulate. All states are also cleared when the
+/// selected entity changes.
 #[derive(Default, Resource)]
 pub struct EditorStates {
     state: HashMap<egui::Id, EditorState>,
     ctors: HashMap<egui::Id, Ctors>,
+    /// The frame each id was last accessed in.
+    touched: HashMap<egui::Id, u32>,
+    frame: u32,
+    /// The ids of the [`scope`](Self::scope)s being drawn, innermost last.
+    scopes: Vec<egui::Id>,
+    /// The ids accessed in each scope.
+    children: HashMap<egui::Id, HashSet<egui::Id>>,
+    path: Vec<String>,
+    expanded: Vec<String>,
+    pending: Vec<(String, String)>,
+    expand_requests: HashSet<String>,
+    collapse_requests: HashSet<String>,
+    headers: Vec<String>,
+    header_suffix: Option<String>,
+    /// The entity whose components are drawn, which recent values are recorded for.
+    entity: Option<Entity>,
+    recent_values: HashMap<(Option<Entity>, String), Vec<String>>,
+    watch_requests: Vec<String>,
+    read_only: bool,
+    committed: bool,
 }
 
 impl EditorStates {
+    fn touch(&mut self, id: egui::Id) {
+        self.touched.insert(id, self.frame);
+        if let Some(&scope) = self.scopes.last().filter(|&&scope| scope != id) {
+            self.children.entry(scope).or_default().insert(id);
+        }
+    }
+
     /// Get the [`EditorState`] for a given id.
     pub fn get(&mut self, id: egui::Id) -> Option<&mut EditorState> {
+        if self.state.contains_key(&id) {
+            self.touch(id);
+        }
         self.state.get_mut(&id)
     }
 
@@ -156,16 +219,18 @@ impl EditorStates {
         id: egui::Id,
         default: impl FnOnce() -> EditorState,
     ) -> &mut EditorState {
+        self.touch(id);
         self.state.entry(id).or_insert_with(default)
     }
 
     /// Get the [`EditorState`] for a given id or use the default function given to create it.
-    /// Unlike [`get_or`], returns a bool representing if the state had to be init (freshness).
+    /// Unlike [`get_or`](Self::get_or), returns a bool representing if the state had to be init (freshness).
     pub fn init(
         &mut self,
         id: egui::Id,
         default: impl FnOnce() -> EditorState,
     ) -> (bool, &mut EditorState) {
+        self.touch(id);
         match self.state.contains_key(&id) {
             true => (false, self.state.get_mut(&id).unwrap()),
             false => {
@@ -175,8 +240,26 @@ impl EditorStates {
         }
     }
 
+    /// Get the text of the [`EditorState::TextEdit`] for a given id, or use the default function
+    /// given to create it. If the id has a state of another kind, e.g. because another editor
+    /// used the same id, it is replaced instead of panicking.
+    pub fn text_edit(&mut self, id: egui::Id, default: impl FnOnce() -> String) -> &mut String {
+        self.touch(id);
+        let state = self.state.entry(id).or_insert(EditorState::Composite);
+        if state.try_text_edit().is_none() {
+            *state = EditorState::TextEdit {
+                temp_value: default(),
+            };
+        }
+        match state {
+            EditorState::TextEdit { temp_value } => temp_value,
+            EditorState::Composite => unreachable!(),
+        }
+    }
+
     /// Insert a new state for a given id.
     pub fn insert(&mut self, id: egui::Id, state: EditorState) {
+        self.touch(id);
         self.state.insert(id, state);
     }
 
@@ -185,6 +268,89 @@ impl EditorStates {
         self.state.remove(&id)
     }
 
+    /// Run `f` in the scope of the editor drawn in `ui`, whose state is initialized to
+    /// [`EditorState::Composite`]. The states accessed in `f`, i.e. those of child editors, are
+    /// recorded as children of the scope, and are cleared before `f` runs if the state of the
+    /// scope is fresh, so that child editors don't keep state from a previous value.
+    pub fn scope<R>(&mut self, ui: &mut Ui, f: impl FnOnce(&mut Ui, &mut Self) -> R) -> R {
+        let id = ui.id();
+        let (mut fresh, state) = self.init(id, || EditorState::Composite);
+        // A state of another kind is reset, as if it was fresh.
+        if state.try_composite().is_none() {
+            *state = EditorState::Composite;
+            fresh = true;
+        }
+        if fresh {
+            self.clear_children(id);
+        }
+        self.scopes.push(id);
+        let res = f(ui, self);
+        self.scopes.pop();
+        res
+    }
+
+    /// Remove the states and constructors of every id accessed in the [`scope`](Self::scope) of
+    /// `id`, recursively. Use this when the value of an editor is replaced, e.g. when another
+    /// enum variant is chosen, so its child editors start fresh.
+    pub fn clear_children(&mut self, id: egui::Id) {
+        let mut ids = self
+            .children
+            .remove(&id)
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>();
+        while let Some(id) = ids.pop() {
+            self.state.remove(&id);
+            self.ctors.remove(&id);
+            self.touched.remove(&id);
+            ids.extend(self.children.remove(&id).into_iter().flatten());
+        }
+    }
+
+    /// Remove the states and constructors of every id, so every editor starts fresh.
+    pub fn clear(&mut self) {
+        self.state.clear();
+        self.ctors.clear();
+        self.touched.clear();
+        self.children.clear();
+        self.recent_values.clear();
+    }
+
+    /// The number of ids that have a state or constructors.
+    pub fn len(&self) -> usize {
+        self.touched.len()
+    }
+
+    /// Whether no id has a state or constructors.
+    pub fn is_empty(&self) -> bool {
+        self.touched.is_empty()
+    }
+
+    /// Drop the states and constructors of ids that were not accessed for more than `max_idle`
+    /// frames.
+    pub fn collect_garbage(&mut self, max_idle: u32) {
+        let frame = self.frame;
+        let Self {
+            state,
+            ctors,
+            touched,
+            children,
+            ..
+        } = self;
+        touched.retain(|id, touched| {
+            let keep = frame.wrapping_sub(*touched) <= max_idle;
+            if !keep {
+                state.remove(id);
+                ctors.remove(id);
+            }
+            keep
+        });
+        children.retain(|id, children| {
+            children.retain(|child| touched.contains_key(child));
+            touched.contains_key(id)
+        });
+    }
+
     /// Get access to the ctors of an id in a closure. Do not nest calls to this for the same id.
     /// Necessary to be able to access constructors and state at the same time.
     pub fn ctors<R>(
@@ -192,11 +358,208 @@ impl EditorStates {
         id: egui::Id,
         f: impl FnOnce(&mut EditorStates, &mut Ctors) -> R,
     ) -> R {
+        self.touch(id);
         let mut ctors = self.ctors.remove(&id).unwrap_or_default();
         let res = f(self, &mut ctors);
         self.ctors.insert(id, ctors);
         res
     }
+
+    /// Push a segment (such as `.field` or `[0]`) onto the path of the value being edited. Editors
+    /// should push a segment before drawing the editor of a child value, and pop it afterwards.
+    pub fn push_path(&mut self, segment: impl Into<String>) {
+        self.path.push(segment.into());
+    }
+
+    /// Pop the last segment off the path of the value being edited.
+    pub fn pop_path(&mut self) {
+        self.path.pop();
+    }
+
+    /// Get the path of the value currently being edited, starting with the component name,
+    /// e.g. `Transform.translation.y`.
+    pub fn path(&self) -> String {
+        self.path.concat()
+    }
+
+    /// Record that the value at the current path is expanded (its collapsing header is open).
+    pub fn mark_expanded(&mut self) {
+        self.expanded.push(self.path());
+    }
+
+    /// Request that the collapsing header of the value at `path` is opened the next time it is
+    /// drawn, e.g. `Transform.translation`.
+    pub fn request_expand(&mut self, path: impl Into<String>) {
+        self.expand_requests.insert(path.into());
+    }
+
+    /// Request that the collapsing header of the value at `path` is closed the next time it is
+    /// drawn.
+    pub fn request_collapse(&mut self, path: impl Into<String>) {
+        self.collapse_requests.insert(path.into());
+    }
+
+    /// Create a collapsing header for the value at the current path, which opens or closes itself
+    /// if that was requested with [`request_expand`](Self::request_expand) or
+    /// [`request_collapse`](Self::request_collapse).
+    pub fn collapsing_header(
+        &mut self,
+        heading: impl Into<egui::WidgetText>,
+    ) -> egui::CollapsingHeader {
+        let path = self.path();
+        let open = match self.expand_requests.remove(&path) {
+            true => Some(true),
+            false => self.collapse_requests.remove(&path).then_some(false),
+        };
+        self.headers.push(path);
+        let heading = heading.into();
+        let heading = match self.header_suffix.take() {
+            Some(suffix) => format!("{} {suffix}", heading.text()).into(),
+            None => heading,
+        };
+        egui::CollapsingHeader::new(heading).open(open)
+    }
+
+    /// Append `suffix` to the heading of the next [`collapsing_header`](Self::collapsing_header),
+    /// e.g. to tell apart components with the same short name.
+    pub(crate) fn suffix_next_header(&mut self, suffix: impl Into<String>) {
+        self.header_suffix = Some(suffix.into());
+    }
+
+    /// Take the suffix set by [`suffix_next_header`](Self::suffix_next_header) if no collapsing
+    /// header used it, e.g. because the value was drawn inline.
+    pub(crate) fn take_header_suffix(&mut self) -> Option<String> {
+        self.header_suffix.take()
+    }
+
+    /// Record that the value at the current path has an uncommitted text edit.
+    pub fn mark_pending(&mut self, text: &str) {
+        self.pending.push((self.path(), text.to_string()));
+    }
+
+    /// Record a value committed to the value at the current path, as text. The last
+    /// [`RECENT_VALUES`] distinct values are kept per entity and path, so that they can be picked
+    /// again.
+    pub fn record_value(&mut self, value: impl Into<String>) {
+        let value = value.into();
+        let key = (self.entity, self.path());
+        let recent = self.recent_values.entry(key).or_default();
+        recent.retain(|recent| *recent != value);
+        recent.push(value);
+        if recent.len() > RECENT_VALUES {
+            recent.remove(0);
+        }
+    }
+
+    /// The values recorded with [`record_value`](Self::record_value) for the value at the current
+    /// path of the current entity, oldest first.
+    pub fn recent_values(&self) -> &[String] {
+        self.recent_values
+            .get(&(self.entity, self.path()))
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Request that the value at the current path is watched, if the
+    /// [`WatchesTabPlugin`](crate::tabs::watches::WatchesTabPlugin) is added.
+    pub fn request_watch(&mut self) {
+        self.watch_requests.push(self.path());
+    }
+
+    /// Take the paths requested with [`request_watch`](Self::request_watch).
+    pub(crate) fn take_watch_requests(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.watch_requests)
+    }
+
+    /// The paths of values that were expanded in the last drawn frame.
+    pub fn expanded(&self) -> &[String] {
+        &self.expanded
+    }
+
+    /// The paths and text of uncommitted text edits in the last drawn frame.
+    pub fn pending(&self) -> &[(String, String)] {
+        &self.pending
+    }
+
+    /// The paths of values whose collapsing headers were drawn in the last drawn frame, whether
+    /// or not they were expanded.
+    pub fn headers(&self) -> &[String] {
+        &self.headers
+    }
+
+    /// Record that an editor committed an edit to the value being drawn, so that the value is
+    /// written back to the world. The editors registered in [`ReprEditors::editors`] must call
+    /// this whenever they change their value. The edits of editors registered with
+    /// [`ReprEditors::insert`] or [`ReprEditors::insert_generic`] are found by comparing their
+    /// value before and after they are drawn instead.
+    pub fn commit(&mut self) {
+        self.committed = true;
+    }
+
+    /// Whether an editor committed an edit since the last call, see [`commit`](Self::commit).
+    pub(crate) fn take_committed(&mut self) -> bool {
+        std::mem::take(&mut self.committed)
+    }
+
+    /// Whether the values being drawn must not be edited, see
+    /// [`SpyglassSettings::read_only`](crate::SpyglassSettings::read_only). Editors of values
+    /// without children are disabled by [`ReprEditors::get`], and editors of composite values
+    /// disable the controls that add, remove or switch their children.
+    pub fn read_only(&self) -> bool {
+        self.read_only
+    }
+
+    /// Clear the per-frame paths, and set whether the values drawn this frame are read-only.
+    /// Called before editors are drawn.
+    pub(crate) fn begin_frame(&mut self, read_only: bool) {
+        self.read_only = read_only;
+        self.entity = None;
+        self.path.clear();
+        self.expanded.clear();
+        self.pending.clear();
+        self.headers.clear();
+    }
+
+    /// Set the entity whose components are drawn this frame, which recent values are recorded
+    /// for. Values drawn without an entity, such as resources, share their recent values.
+    pub(crate) fn set_entity(&mut self, entity: Option<Entity>) {
+        self.entity = entity;
+    }
+
+    /// Drop expansion requests for values that were not drawn this frame.
+    pub(crate) fn end_frame(&mut self) {
+        self.expand_requests.clear();
+        self.collapse_requests.clear();
+    }
+}
+
+/// The number of committed values [`EditorStates::record_value`] keeps per path.
+pub const RECENT_VALUES: usize = 20;
+
+/// The number of frames after which the states of editors that were not drawn are dropped.
+pub const STATE_IDLE_FRAMES: u32 = 600;
+
+/// How often, in frames, idle editor states are looked for.
+const STATE_GC_INTERVAL: u32 = 60;
+
+/// Stamp new accesses of [`EditorStates`] with the current frame, and periodically drop the
+/// states that were idle for [`STATE_IDLE_FRAMES`] frames.
+pub(crate) fn collect_editor_states(frame: Res<FrameCount>, mut states: ResMut<EditorStates>) {
+    states.frame = frame.0;
+    if frame.0.is_multiple_of(STATE_GC_INTERVAL) {
+        states.collect_garbage(STATE_IDLE_FRAMES);
+    }
+}
+
+/// Clear [`EditorStates`] when the selected entity changes, as the states of the previous
+/// entity's editors are not drawn anymore.
+pub(crate) fn clear_editor_states_on_selection(
+    mut events: EventReader<EntitySelected>,
+    mut states: ResMut<EditorStates>,
+) {
+    if events.read().count() > 0 {
+        states.clear();
+    }
 }
 
 /// A generic trait that represents the field access ability of several traits from `bevy_reflect`.
@@ -213,6 +576,16 @@ pub trait FieldAccess {
 
     /// Get the type name of the implementor.
     fn type_name(&self) -> &str;
+
+    /// Get the documentation of the nth field, if captured. See [`field_docs`].
+    fn docs(&self, _index: usize) -> Option<&'static str> {
+        None
+    }
+
+    /// Get the documentation of the implementor's type, if captured. See [`type_docs`].
+    fn type_docs(&self) -> Option<&'static str> {
+        None
+    }
 }
 
 impl FieldAccess for &mut dyn Struct {
@@ -229,7 +602,15 @@ impl FieldAccess for &mut dyn Struct {
     }
 
     fn type_name(&self) -> &str {
-        <dyn Struct>::type_name(*self)
+        type_path(self.as_reflect())
+    }
+
+    fn docs(&self, index: usize) -> Option<&'static str> {
+        field_docs(self.as_reflect(), index)
+    }
+
+    fn type_docs(&self) -> Option<&'static str> {
+        type_docs(self.as_reflect())
     }
 }
 
@@ -247,7 +628,15 @@ impl FieldAccess for &mut dyn TupleStruct {
     }
 
     fn type_name(&self) -> &str {
-        <dyn TupleStruct>::type_name(*self)
+        type_path(self.as_reflect())
+    }
+
+    fn docs(&self, index: usize) -> Option<&'static str> {
+        field_docs(self.as_reflect(), index)
+    }
+
+    fn type_docs(&self) -> Option<&'static str> {
+        type_docs(self.as_reflect())
     }
 }
 
@@ -265,7 +654,15 @@ impl FieldAccess for &mut dyn Tuple {
     }
 
     fn type_name(&self) -> &str {
-        <dyn Tuple>::type_name(*self)
+        type_path(self.as_reflect())
+    }
+
+    fn docs(&self, index: usize) -> Option<&'static str> {
+        field_docs(self.as_reflect(), index)
+    }
+
+    fn type_docs(&self) -> Option<&'static str> {
+        type_docs(self.as_reflect())
     }
 }
 
@@ -283,54 +680,131 @@ impl FieldAccess for &mut dyn Enum {
     }
 
     fn type_name(&self) -> &str {
-        <dyn Enum>::type_name(*self)
+        type_path(self.as_reflect())
+    }
+
+    fn docs(&self, index: usize) -> Option<&'static str> {
+        field_docs(self.as_reflect(), index)
+    }
+
+    fn type_docs(&self) -> Option<&'static str> {
+        type_docs(self.as_reflect())
     }
 }
 
 /// An editor for composite types. Includes structs, tuples, tuple structs, and enums.
+/// Types with no more than [`ReprEditors::inline_threshold`] fields, all of which are values
+/// (such as `Vec3`), are drawn inline on a single row instead of behind a collapsing header.
 pub fn composite_editor(
     ui: &mut Ui,
-    mut repr: impl FieldAccess,
+    repr: impl FieldAccess,
     world: &mut World,
     editors: &ReprEditors,
     states: &mut EditorStates,
     headless: bool,
 ) {
-    let (fresh, state) = states.init(ui.id(), || EditorState::Composite);
-    state.composite();
+    states.scope(ui, |ui, states| {
+        composite_fields(ui, repr, world, editors, states, headless)
+    });
+}
 
+fn composite_fields(
+    ui: &mut Ui,
+    mut repr: impl FieldAccess,
+    world: &mut World,
+    editors: &ReprEditors,
+    states: &mut EditorStates,
+    headless: bool,
+) {
+    // Types are labelled by their short name, with their full path and docs on hover.
     let type_name = repr.type_name().to_string();
+    let hover = match repr.type_docs() {
+        Some(docs) => format!("{type_name}\n\n{docs}"),
+        None => type_name.clone(),
+    };
+    let field_len = repr.field_len();
+    let inline = !headless
+        && field_len <= editors.inline_threshold
+        && (0..field_len).all(|i| matches!(repr.field(i).reflect_ref(), ReflectRef::Value(_)));
+    let header =
+        (!headless && !inline).then(|| states.collapsing_header(get_short_name(&type_name)));
+
+    let mut field = |ui: &mut Ui, i: usize| {
+        let name = repr
+            .name(i)
+            .map(str::to_string)
+            .unwrap_or_else(|| format!(".{i}"));
+        let label = ui.label(&name);
+        if let Some(docs) = repr.docs(i) {
+            label.on_hover_text(docs);
+        }
+        let field = repr.field(i);
+        let editor = editors.get(field);
+        ui.push_id(i, |ui| {
+            states.push_path(format!(".{}", name.trim_start_matches('.')));
+            editor(ui, field, world, editors, states);
+            states.pop_path();
+        });
+    };
+
+    if inline {
+        ui.horizontal(|ui| {
+            ui.label(get_short_name(&type_name)).on_hover_text(hover);
+            for i in 0..field_len {
+                field(ui, i);
+            }
+        });
+        return;
+    }
 
     let mut inner = |ui: &mut Ui| {
         ui.vertical(|ui| {
-            for i in 0..repr.field_len() {
-                ui.horizontal(|ui| {
-                    ui.label(
-                        repr.name(i)
-                            .map(str::to_string)
-                            .unwrap_or_else(|| format!(".{i}")),
-                    );
-                    let field = repr.field(i);
-                    let editor = editors.get(field.type_name());
-                    ui.push_id(i, |ui| {
-                        if fresh {
-                            states.remove(ui.id());
-                        }
-                        editor(ui, field, world, editors, states)
-                    });
-                });
+            for i in 0..field_len {
+                ui.horizontal(|ui| field(ui, i));
             }
         })
     };
 
-    if !headless {
-        ui.collapsing(type_name, |ui| inner(ui));
+    if let Some(header) = header {
+        let response = header.show(ui, |ui| inner(ui));
+        response.header_response.on_hover_text(hover);
+        if response.body_returned.is_some() {
+            states.mark_expanded();
+        }
     } else {
         inner(ui);
     }
 }
 
-/// An editor for lists.
+/// An editor for newtypes, i.e. single-field tuple structs. Skips the wrapper and edits the inner
+/// value directly, labelled with the type of the newtype. Types can opt out of this through
+/// [`ReprEditors::opaque_newtypes`].
+pub fn newtype_editor(
+    ui: &mut Ui,
+    repr: &mut dyn TupleStruct,
+    world: &mut World,
+    editors: &ReprEditors,
+    states: &mut EditorStates,
+) {
+    states.scope(ui, |ui, states| {
+        ui.horizontal(|ui| {
+            let path = type_path(repr.as_reflect());
+            ui.label(get_short_name(path)).on_hover_text(path);
+            let Some(field) = repr.field_mut(0) else {
+                return;
+            };
+            let editor = editors.get(field);
+            ui.push_id(0, |ui| {
+                states.push_path(".0");
+                editor(ui, field, world, editors, states);
+                states.pop_path();
+            });
+        });
+    });
+}
+
+/// An editor for lists. Works for every type that reflects as a [`List`], such as `Vec`,
+/// `VecDeque` and `SmallVec`. Items can be added, but not removed.
 pub fn list_editor(
     ui: &mut Ui,
     repr: &mut dyn List,
@@ -338,61 +812,52 @@ pub fn list_editor(
     editors: &ReprEditors,
     states: &mut EditorStates,
 ) {
-    let id = ui.id();
-    let (fresh, _) = states.init(id, || EditorState::Composite);
-
-    ui.collapsing(repr.type_name().to_string(), |ui| {
-        ui.vertical(|ui| {
-            let mut i = 0;
-            loop {
-                if i == repr.len() {
-                    break;
-                }
-
-                ui.horizontal(|ui| {
-                    let item = repr.get_mut(i).unwrap();
-                    let editor = editors.get(item.type_name());
-                    ui.label(format!("[{i}]"));
-                    ui.push_id(i, |ui| {
-                        if fresh {
-                            states.remove(ui.id());
+    states.scope(ui, |ui, states| {
+        let response = states
+            .collapsing_header(get_short_name(type_path(repr.as_reflect())))
+            .show(ui, |ui| {
+                ui.vertical(|ui| {
+                    let mut i = 0;
+                    loop {
+                        if i == repr.len() {
+                            break;
                         }
-                        editor(ui, item, world, editors, states);
-                    });
-                    // TODO: Currently bevy's reflection capabilites are limiting when it comes to
-                    // adding/removing from lists, so this is omitted for now.
-                    // if ui.button("-").clicked() {
-                    //     repr.remove(i);
-                    //     i = i.wrapping_sub(1);
-                    // }
-                });
 
-                i = i.wrapping_add(1);
-            }
+                        ui.horizontal(|ui| {
+                            let item = repr.get_mut(i).unwrap();
+                            let editor = editors.get(item);
+                            ui.label(format!("[{i}]"));
+                            ui.push_id(i, |ui| {
+                                states.push_path(format!("[{i}]"));
+                                editor(ui, item, world, editors, states);
+                                states.pop_path();
+                            });
+                            // TODO: Currently bevy's reflection capabilites are limiting when it
+                            // comes to adding/removing from lists, so this is omitted for now.
+                            // if ui.button("-").clicked() {
+                            //     repr.remove(i);
+                            //     i = i.wrapping_sub(1);
+                            // }
+                        });
+
+                        i = i.wrapping_add(1);
+                    }
 
-            // states.ctors(id, |states, ctors| {
-            // let ctor = ctors.first();
-
-            // TODO: Currently bevy's reflection capabilites are limiting when it comes to
-            // adding/removing from lists, so this is omitted for now.
-            // if ui.button("+").clicked() {
-            //     match (|| {
-            //         let item_name = match get_type_info(world, repr.type_name())? {
-            //             TypeInfo::List(info) => info.item_type_name(),
-            //             _ => todo!(),
-            //             // TypeInfo::Dynamic(_) => ,
-            //         };
-            //         let item_info = get_type_info(world, item_name)?;
-            //         default_value(item_info, world)
-            //     })() {
-            //         Some(item) => ctor.start(item),
-            //         None => world
-            //             .resource_mut::<Popups>()
-            //             .add(Popup::new("failed to find reflection info")),
-            //     }
-            // }
-            // });
-        })
+                    let add = egui::Button::new("+");
+                    if ui.add_enabled(!states.read_only(), add).clicked() {
+                        match list_item_default(repr, world) {
+                            Some(item) => {
+                                repr.push(item);
+                                states.commit();
+                            }
+                            None => report_missing_default(world, repr.as_reflect()),
+                        }
+                    }
+                })
+            });
+        if response.body_returned.is_some() {
+            states.mark_expanded();
+        }
     });
 }
 
@@ -404,29 +869,33 @@ pub fn array_editor(
     editors: &ReprEditors,
     states: &mut EditorStates,
 ) {
-    let (fresh, state) = states.init(ui.id(), || EditorState::Composite);
-    state.composite();
-
-    ui.collapsing(repr.type_name().to_string(), |ui| {
-        ui.vertical(|ui| {
-            for i in 0..repr.len() {
-                let item = repr.get_mut(i).unwrap();
-                let editor = editors.get(item.type_name());
-                ui.horizontal(|ui| {
-                    ui.label(format!("[{i}]"));
-                    ui.push_id(i, |ui| {
-                        if fresh {
-                            states.remove(ui.id());
-                        }
-                        editor(ui, item, world, editors, states);
-                    });
-                });
-            }
-        })
+    states.scope(ui, |ui, states| {
+        let response = states
+            .collapsing_header(get_short_name(type_path(repr.as_reflect())))
+            .show(ui, |ui| {
+                ui.vertical(|ui| {
+                    for i in 0..repr.len() {
+                        let item = repr.get_mut(i).unwrap();
+                        let editor = editors.get(item);
+                        ui.horizontal(|ui| {
+                            ui.label(format!("[{i}]"));
+                            ui.push_id(i, |ui| {
+                                states.push_path(format!("[{i}]"));
+                                editor(ui, item, world, editors, states);
+                                states.pop_path();
+                            });
+                        });
+                    }
+                })
+            });
+        if response.body_returned.is_some() {
+            states.mark_expanded();
+        }
     });
 }
 
-/// An editor for maps.
+/// An editor for maps. Entries can be removed, and added by constructing their key; the value of
+/// a new entry is the default of its type.
 pub fn map_editor(
     ui: &mut Ui,
     repr: &mut dyn Map,
@@ -435,63 +904,68 @@ pub fn map_editor(
     states: &mut EditorStates,
 ) {
     let id = ui.id();
-    let (fresh, _) = states.init(id, || EditorState::Composite);
 
-    ui.collapsing(repr.type_name().to_string(), |ui| {
-        ui.vertical(|ui| {
-            let repr_len = repr.len();
-            let mut i = 0;
-            loop {
-                if i == repr_len {
-                    break;
-                }
+    states.scope(ui, |ui, states| {
+        let response = states
+            .collapsing_header(get_short_name(type_path(repr.as_reflect())))
+            .show(ui, |ui| {
+                ui.vertical(|ui| {
+                    let repr_len = repr.len();
+                    let mut removed = None;
+                    for i in 0..repr_len {
+                        ui.horizontal(|ui| {
+                            let (key, _) = repr.get_at(i).unwrap();
+                            let key = key.clone_value();
+                            ui.label(format!("[{i}] {key:?}: "));
+                            let value = repr.get_mut(&*key).unwrap();
+                            let value_editor = editors.get(value);
+                            ui.push_id(repr_len + i, |ui| {
+                                states.push_path(format!("[{key:?}]"));
+                                value_editor(ui, &mut *value, world, editors, states);
+                                states.pop_path();
+                            });
+                            let remove = egui::Button::new("-");
+                            if ui.add_enabled(!states.read_only(), remove).clicked() {
+                                removed = Some(i);
+                            }
+                        });
+                    }
+                    if let Some(index) = removed {
+                        remove_map_entry(repr, index);
+                        states.commit();
+                    }
 
-                ui.horizontal(|ui| {
-                    let (key, _) = repr.get_at(i).unwrap();
-                    let key = key.clone_value();
-                    ui.label(format!("[{i}] {key:?}: "));
-                    let value = repr.get_mut(&*key).unwrap();
-                    let value_editor = editors.get(value.type_name());
-                    ui.push_id(repr_len + i, |ui| {
-                        if fresh {
-                            states.remove(ui.id());
+                    states.ctors(id, |states, ctors| {
+                        let ctor = ctors.first();
+                        let add = egui::Button::new("+");
+                        if ui.add_enabled(!states.read_only(), add).clicked() {
+                            match map_entry_default(repr, world) {
+                                Some((key, _)) => ctor.start(key),
+                                None => report_missing_default(world, repr.as_reflect()),
+                            }
+                        }
+                        let Some(key) = ctor.poll(ui, world, editors, states) else {
+                            return;
+                        };
+                        if repr.get(&*key).is_some() {
+                            if let Some(mut popups) = world.get_resource_mut::<Popups>() {
+                                popups.recoverable_error(format!("the map already has {key:?}"));
+                            }
+                            return;
+                        }
+                        match map_entry_default(repr, world) {
+                            Some((_, value)) => {
+                                repr.insert_boxed(key, value);
+                                states.commit();
+                            }
+                            None => report_missing_default(world, repr.as_reflect()),
                         }
-                        value_editor(ui, &mut *value, world, editors, states);
                     });
-                    // TODO: Currently bevy's reflection capabilites are limiting when it comes to
-                    // adding/removing from lists, so this is omitted for now.
-                    // if ui.button("-").clicked() {
-                    //     repr.remove(i);
-                    //     i = i.wrapping_sub(1);
-                    // }
-                });
-
-                i = i.wrapping_add(1);
-            }
-
-            // states.ctors(id, |states, ctors| {
-            // let ctor = ctors.first();
-
-            // TODO: Currently bevy's reflection capabilites are limiting when it comes to
-            // adding/removing from lists, so this is omitted for now.
-            // if ui.button("+").clicked() {
-            //     match (|| {
-            //         let item_name = match get_type_info(world, repr.type_name())? {
-            //             TypeInfo::List(info) => info.item_type_name(),
-            //             _ => todo!(),
-            //             // TypeInfo::Dynamic(_) => ,
-            //         };
-            //         let item_info = get_type_info(world, item_name)?;
-            //         default_value(item_info, world)
-            //     })() {
-            //         Some(item) => ctor.start(item),
-            //         None => world
-            //             .resource_mut::<Popups>()
-            //             .add(Popup::new("failed to find reflection info")),
-            //     }
-            // }
-            // });
-        })
+                })
+            });
+        if response.body_returned.is_some() {
+            states.mark_expanded();
+        }
     });
 }
 
@@ -505,40 +979,44 @@ pub fn enum_editor(
 ) {
     let id = ui.id();
 
-    let Some(TypeInfo::Enum(info)) = get_type_info(world, repr.type_name()).cloned() else {
+    let Some(TypeInfo::Enum(info)) = get_type_info(world, type_path(repr.as_reflect())).cloned()
+    else {
         ui.label("unable to reflect enum type");
         return;
     };
 
-    ui.collapsing(repr.type_name().to_string(), |ui| {
-        ui.vertical(|ui| {
-            let button = variant_menu_button(ui, repr, &info, world, states, id);
-
-            if button.response.lost_focus() {}
-
-            let (fresh, state) = states.init(id, || EditorState::Composite);
-            state.composite();
-
-            states.ctors(id, |states, ctors| {
-                if let Some(value) = ctors.first().poll(ui, world, editors, states) {
-                    let variant = value.take::<VariantProxy>().unwrap();
-                    let value = variant.into_enum(repr.type_name());
-                    repr.apply(&value);
-                }
-            });
+    states.scope(ui, |ui, states| {
+        let response = states
+            .collapsing_header(get_short_name(type_path(repr.as_reflect())))
+            .show(ui, |ui| {
+                ui.vertical(|ui| {
+                    ui.add_enabled_ui(!states.read_only(), |ui| {
+                        variant_menu_button(ui, repr, &info, world, states, id)
+                    });
 
-            match repr.variant_type() {
-                VariantType::Unit => (),
-                _ => {
-                    ui.push_id(0, |ui| {
-                        if fresh {
-                            states.remove(ui.id());
+                    states.ctors(id, |states, ctors| {
+                        if let Some(value) = ctors.first().poll(ui, world, editors, states) {
+                            let variant = value.take::<VariantProxy>().unwrap();
+                            let value = variant.into_enum();
+                            repr.apply(&value);
+                            states.commit();
+                            states.clear_children(id);
                         }
-                        composite_editor(ui, repr, world, editors, states, true)
                     });
-                }
-            }
-        });
+
+                    match repr.variant_type() {
+                        VariantType::Unit => (),
+                        _ => {
+                            ui.push_id(0, |ui| {
+                                composite_editor(ui, repr, world, editors, states, true)
+                            });
+                        }
+                    }
+                });
+            });
+        if response.body_returned.is_some() {
+            states.mark_expanded();
+        }
     });
 }
 
@@ -563,7 +1041,9 @@ fn variant_menu_button(
                         match variant {
                             VariantInfo::Unit(_) => {
                                 let value = value.take::<VariantProxy>().unwrap();
-                                repr.apply(&value.into_enum(repr.type_name()));
+                                repr.apply(&value.into_enum());
+                                states.commit();
+                                states.clear_children(enum_id);
                             }
                             _ => states.ctors(enum_id, |_, ctors| {
                                 ctors.first().start(value);
@@ -578,6 +1058,140 @@ fn variant_menu_button(
     })
 }
 
+/// An editor for [`Option`]s. Displays a checkbox for whether the value is `Some`, constructing
+/// a default inner value when checked, and an editor for the inner value if there is one.
+pub fn option_editor(
+    ui: &mut Ui,
+    repr: &mut dyn Enum,
+    world: &mut World,
+    editors: &ReprEditors,
+    states: &mut EditorStates,
+) {
+    states.scope(ui, |ui, states| {
+        let id = ui.id();
+        ui.horizontal(|ui| option_fields(ui, id, repr, world, editors, states));
+    });
+}
+
+fn option_fields(
+    ui: &mut Ui,
+    id: egui::Id,
+    repr: &mut dyn Enum,
+    world: &mut World,
+    editors: &ReprEditors,
+    states: &mut EditorStates,
+) {
+    let mut is_some = repr.variant_name() == "Some";
+    let checkbox = egui::Checkbox::new(&mut is_some, "");
+    if ui.add_enabled(!states.read_only(), checkbox).changed() {
+        states.clear_children(id);
+        states.commit();
+        if !is_some {
+            repr.apply(&DynamicEnum::new("None", ()));
+        } else if let Some(value) = default_some(repr, world) {
+            repr.apply(&value);
+        } else if let Some(mut popups) = world.get_resource_mut::<Popups>() {
+            popups.recoverable_error(format!(
+                "failed to construct a default value for `{}`",
+                type_path(repr.as_reflect())
+            ));
+        }
+    }
+
+    let Some(field) = repr.field_at_mut(0) else {
+        ui.weak("None");
+        return;
+    };
+    let editor = editors.get(field);
+    ui.push_id(0, |ui| {
+        states.push_path(".0");
+        editor(ui, field, world, editors, states);
+        states.pop_path();
+    });
+}
+
+fn default_some(repr: &dyn Enum, world: &World) -> Option<DynamicEnum> {
+    let Some(TypeInfo::Enum(info)) = repr.get_represented_type_info() else {
+        return None;
+    };
+    let Some(VariantInfo::Tuple(some)) = info.variant("Some") else {
+        return None;
+    };
+    let value = default_for_type_path(some.field_at(0)?.type_path(), world)?;
+
+    let mut tuple = DynamicTuple::default();
+    tuple.insert_boxed(value);
+    Some(DynamicEnum::new("Some", tuple))
+}
+
+/// Create a default item for a list, e.g. a `Vec`, `VecDeque` or `SmallVec`.
+fn list_item_default(repr: &dyn List, world: &World) -> Option<Box<dyn Reflect>> {
+    let Some(TypeInfo::List(info)) = repr.get_represented_type_info() else {
+        return None;
+    };
+    default_for_type_path(info.item_type_path_table().path(), world)
+}
+
+/// Remove the entry at `index` of a map. `DynamicMap::remove` does not update the indices of the
+/// entries after the removed one, so those are removed from the last, which leaves the others in
+/// place, and inserted back in order.
+fn remove_map_entry(repr: &mut dyn Map, index: usize) {
+    let mut after = (index + 1..repr.len())
+        .filter_map(|i| repr.get_at(i))
+        .map(|(key, value)| (key.clone_value(), value.clone_value()))
+        .collect::<Vec<_>>();
+    for (key, _) in after.iter().rev() {
+        repr.remove(&**key);
+    }
+    if let Some((key, _)) = repr.get_at(index) {
+        let key = key.clone_value();
+        repr.remove(&*key);
+    }
+    for (key, value) in after.drain(..) {
+        repr.insert_boxed(key, value);
+    }
+}
+
+/// Create a default key and value for a map, e.g. a `HashMap`.
+fn map_entry_default(
+    repr: &dyn Map,
+    world: &World,
+) -> Option<(Box<dyn Reflect>, Box<dyn Reflect>)> {
+    let Some(TypeInfo::Map(info)) = repr.get_represented_type_info() else {
+        return None;
+    };
+    Some((
+        default_for_type_path(info.key_type_path_table().path(), world)?,
+        default_for_type_path(info.value_type_path_table().path(), world)?,
+    ))
+}
+
+/// Report that a default item could not be created for a container.
+fn report_missing_default(world: &mut World, container: &dyn Reflect) {
+    if let Some(mut popups) = world.get_resource_mut::<Popups>() {
+        popups.recoverable_error(format!(
+            "failed to create a default item for `{}`",
+            type_path(container)
+        ));
+    }
+}
+
+/// Create a default value of a type, through its [`ReflectDefault`] if registered, or else by
+/// recursively defaulting its fields.
+fn default_for_type_path(path: &str, world: &World) -> Option<Box<dyn Reflect>> {
+    let value = {
+        let registry = world.get_resource::<AppTypeRegistry>()?.read();
+        registry
+            .get_with_type_path(path)
+            .and_then(|registration| registration.data::<ReflectDefault>())
+            .map(ReflectDefault::default)
+    };
+    match value {
+        Some(value) => Some(value),
+        None => default_value(get_type_info(world, path)?, world),
+    }
+}
+
 #[derive(Reflect)]
 enum VariantKind {
     Struct(#[reflect(ignore)] DynamicStruct),
@@ -613,9 +1227,9 @@ impl VariantProxy {
         }
     }
 
-    fn into_enum(self, name: &str) -> DynamicEnum {
+    fn into_enum(self) -> DynamicEnum {
         DynamicEnum::new(
-            name,
+            self.variant,
             match self.value {
                 VariantKind::Struct(value) => DynamicVariant::from(value),
                 VariantKind::Tuple(value) => DynamicVariant::from(value),
@@ -741,7 +1355,71 @@ fn default_value(info: &TypeInfo, world: &World) -> Option<Box<dyn Reflect>> {
 
 fn get_type_info<'w>(world: &'w World, name: &str) -> Option<&'w TypeInfo> {
     let registry = world.get_resource::<AppTypeRegistry>()?.read();
-    Some(registry.get_with_short_type_path(name)?.type_info())
+    let registration = registry
+        .get_with_type_path(name)
+        .or_else(|| registry.get_with_short_type_path(name))?;
+    Some(registration.type_info())
+}
+
+/// Returns whether a value represents an [`Option`].
+pub(crate) fn is_option(repr: &dyn Reflect) -> bool {
+    let Some(TypeInfo::Enum(info)) = repr.get_represented_type_info() else {
+        return false;
+    };
+    let table = info.type_path_table();
+    table.module_path() == Some("core::option") && table.ident() == Some("Option")
+}
+
+/// Get the documentation of the nth field of a value's type, or of its active variant for enums.
+/// Doc comments are only captured when bevy's `documentation` feature is enabled, which is
+/// enabled by this crate's `documentation` feature.
+pub fn field_docs(repr: &dyn Reflect, index: usize) -> Option<&'static str> {
+    #[cfg(feature = "documentation")]
+    {
+        match repr.get_represented_type_info()? {
+            TypeInfo::Struct(info) => info.field_at(index)?.docs(),
+            TypeInfo::TupleStruct(info) => info.field_at(index)?.docs(),
+            TypeInfo::Tuple(info) => info.field_at(index)?.docs(),
+            TypeInfo::Enum(info) => {
+                let ReflectRef::Enum(value) = repr.reflect_ref() else {
+                    return None;
+                };
+                match info.variant(value.variant_name())? {
+                    VariantInfo::Struct(variant) => variant.field_at(index)?.docs(),
+                    VariantInfo::Tuple(variant) => variant.field_at(index)?.docs(),
+                    VariantInfo::Unit(_) => None,
+                }
+            }
+            _ => None,
+        }
+    }
+    #[cfg(not(feature = "documentation"))]
+    {
+        let _ = (repr, index);
+        None
+    }
+}
+
+/// Get the documentation of a value's type. Like [`field_docs`], this needs the `documentation`
+/// feature.
+pub fn type_docs(repr: &dyn Reflect) -> Option<&'static str> {
+    #[cfg(feature = "documentation")]
+    {
+        repr.get_represented_type_info()?.docs()
+    }
+    #[cfg(not(feature = "documentation"))]
+    {
+        let _ = repr;
+        None
+    }
+}
+
+/// Get the type path of the type a value represents. For dynamic types, this is the path of the
+/// type they represent if known, rather than the path of the dynamic type itself.
+pub(crate) fn type_path(repr: &dyn Reflect) -> &str {
+    repr.get_represented_type_info()
+        .map(TypeInfo::type_path)
+        .unwrap_or_else(|| repr.reflect_type_path())
 }
 
 /// A default fallback editor for value types. Prints the debug representation of the value.
@@ -758,35 +1436,126 @@ pub fn bool_editor(
     repr: &mut dyn Reflect,
     _: &mut World,
     _: &ReprEditors,
-    _: &mut EditorStates,
+    states: &mut EditorStates,
 ) {
     let value = repr.downcast_mut::<bool>().unwrap();
-    ui.checkbox(value, "");
+    if ui.checkbox(value, "").changed() {
+        states.commit();
+    }
 }
 
 /// A generic number editor that works for all integer + floating point types.
+///
+/// While the text does not parse, the editor is outlined in red. If it still does not parse when
+/// the edit is committed, the old value is kept and a [`Popup`](super::Popup) describes the parse
+/// error.
 pub fn num_editor<T: Copy + Reflect + FromStr + Display>(
     ui: &mut Ui,
     repr: &mut dyn Reflect,
-    _: &mut World,
+    world: &mut World,
     _: &ReprEditors,
     states: &mut EditorStates,
-) {
+) where
+    T::Err: Display,
+{
+    edit_num::<T>(ui, repr, world, states, |value| value);
+}
+
+/// Create a number editor like [`num_editor`] which clamps committed values to `min` and/or
+/// `max`. Register it in [`ReprEditors`] for types whose values must stay within a range.
+pub fn clamped_num_editor<T: Copy + PartialOrd + Reflect + FromStr + Display>(
+    min: Option<T>,
+    max: Option<T>,
+) -> impl Fn(&mut Ui, &mut dyn Reflect, &mut World, &ReprEditors, &mut EditorStates) + Send + Sync
+where
+    T::Err: Display,
+{
+    move |ui, repr, world, _, states| {
+        edit_num::<T>(ui, repr, world, states, |value| match (min, max) {
+            (Some(min), _) if value < min => min,
+            (_, Some(max)) if value > max => max,
+            _ => value,
+        });
+    }
+}
+
+fn edit_num<T: Copy + Reflect + FromStr + Display>(
+    ui: &mut Ui,
+    repr: &mut dyn Reflect,
+    world: &mut World,
+    states: &mut EditorStates,
+    clamp: impl Fn(T) -> T,
+) where
+    T::Err: Display,
+{
     let &value = repr.downcast_ref::<T>().unwrap();
-    let text = states
-        .get_or(ui.id(), || EditorState::TextEdit {
-            temp_value: value.to_string(),
-        })
-        .text_edit();
+    let text = states.text_edit(ui.id(), || value.to_string());
 
     let edit = ui.text_edit_singleline(text);
+    let parsed = text.parse::<T>();
+    if parsed.is_err() {
+        ui.painter().rect_stroke(
+            edit.rect,
+            ui.visuals().widgets.inactive.rounding,
+            egui::Stroke::new(1.0, ui.visuals().error_fg_color),
+        );
+    }
+
+    let pending = edit.has_focus().then(|| text.clone());
     if edit.lost_focus() {
-        let value = text.parse::<T>().unwrap_or(value);
+        match parsed {
+            Ok(parsed) => {
+                let parsed = clamp(parsed);
+                // The value before the first edit is kept too, so that it can be reverted to.
+                if states.recent_values().is_empty() {
+                    states.record_value(value.to_string());
+                }
+                states.record_value(parsed.to_string());
+                if repr.reflect_partial_eq(&parsed) != Some(true) {
+                    repr.apply(&parsed);
+                    states.commit();
+                }
+            }
+            Err(err) => {
+                if let Some(mut popups) = world.get_resource_mut::<Popups>() {
+                    popups.recoverable_error(format!(
+                        "`{text}` is not a valid `{}`, so the edit was discarded: {err}",
+                        type_path(repr)
+                    ));
+                }
+            }
+        }
         states.remove(ui.id());
-        repr.apply(&value);
     }
-    if !edit.has_focus() {
-        states.remove(ui.id());
+    match pending {
+        Some(text) => states.mark_pending(&text),
+        None => {
+            states.remove(ui.id());
+        }
+    }
+
+    let recent = states.recent_values().to_vec();
+    let watchable = world.contains_resource::<Watches>();
+    if !recent.is_empty() || watchable {
+        edit.context_menu(|ui| {
+            if watchable && ui.button("watch").clicked() {
+                states.request_watch();
+                ui.close_menu();
+            }
+            if recent.is_empty() {
+                return;
+            }
+            ui.label("recent values");
+            for text in recent.into_iter().rev() {
+                if ui.button(&text).clicked() {
+                    if let Ok(recent) = text.parse::<T>() {
+                        repr.apply(&clamp(recent));
+                        states.commit();
+                    }
+                    ui.close_menu();
+                }
+            }
+        });
     }
 }
 
@@ -795,21 +1564,157 @@ pub fn string_editor(
     ui: &mut Ui,
     repr: &mut dyn Reflect,
     _: &mut World,
+    editors: &ReprEditors,
+    states: &mut EditorStates,
+) {
+    text_editor::<String>(ui, repr, editors, states, String::clone);
+}
+
+/// The editor for `Cow<'static, str>`, edited as a string.
+pub fn cow_str_editor(
+    ui: &mut Ui,
+    repr: &mut dyn Reflect,
+    _: &mut World,
+    editors: &ReprEditors,
+    states: &mut EditorStates,
+) {
+    text_editor::<Cow<'static, str>>(ui, repr, editors, states, |value| value.to_string());
+}
+
+/// The editor for [`PathBuf`], edited as a string.
+pub fn path_buf_editor(
+    ui: &mut Ui,
+    repr: &mut dyn Reflect,
+    _: &mut World,
+    editors: &ReprEditors,
+    states: &mut EditorStates,
+) {
+    text_editor::<PathBuf>(ui, repr, editors, states, |value| {
+        value.to_string_lossy().into_owned()
+    });
+}
+
+/// The editor for [`OsString`], edited as a string. Non-unicode contents are displayed lossily.
+pub fn os_string_editor(
+    ui: &mut Ui,
+    repr: &mut dyn Reflect,
+    _: &mut World,
+    editors: &ReprEditors,
+    states: &mut EditorStates,
+) {
+    text_editor::<OsString>(ui, repr, editors, states, |value| {
+        value.to_string_lossy().into_owned()
+    });
+}
+
+/// The editor for [`Entity`] references, such as the one in [`Parent`]. Displays the name of the
+/// entity as a link, which selects it.
+pub fn entity_editor(
+    ui: &mut Ui,
+    repr: &mut dyn Reflect,
+    world: &mut World,
+    _: &ReprEditors,
+    _: &mut EditorStates,
+) {
+    let entity = *repr.downcast_ref::<Entity>().unwrap();
+    if world.get_entity(entity).is_none() {
+        ui.weak(format!("{entity:?} (despawned)"));
+        return;
+    }
+    let link = ui
+        .link(entity_name(world, entity))
+        .on_hover_text(format!("{entity:?}"));
+    if link.clicked() {
+        if let Some(mut events) = world.get_resource_mut::<Events<SelectEntity>>() {
+            events.send(SelectEntity(Some(entity)));
+        }
+    }
+}
+
+/// The extensions of the image files an [`image_handle_editor`] lists. Which of them can be
+/// loaded depends on the image features Bevy was built with.
+const IMAGE_EXTENSIONS: &[&str] = &[
+    "png", "jpg", "jpeg", "bmp", "tga", "dds", "ktx2", "basis", "webp", "hdr", "exr", "pam", "pbm",
+    "pgm", "ppm",
+];
+
+/// An editor for [`Handle<Image>`] that shows a thumbnail of the image. The handle can be
+/// replaced by picking another image from the [`AssetFolder`], which loads it.
+pub fn image_handle_editor(
+    ui: &mut Ui,
+    repr: &mut dyn Reflect,
+    world: &mut World,
     _: &ReprEditors,
     states: &mut EditorStates,
 ) {
-    let value = repr.downcast_ref::<String>().unwrap();
-    let text = states
-        .get_or(ui.id(), || EditorState::TextEdit {
-            temp_value: value.into(),
-        })
-        .text_edit();
-    let edit = ui.text_edit_singleline(text);
+    // Cloned handles are dynamic enums, so they are converted rather than downcast.
+    let Some(id) = Handle::<Image>::from_reflect(repr).map(|handle| handle.id()) else {
+        return;
+    };
+    let path = world
+        .get_resource::<AssetServer>()
+        .and_then(|server| server.get_path(id))
+        .map(|path| path.to_string());
+    ui.vertical(|ui| {
+        ui.horizontal(|ui| {
+            ui.label(path.as_deref().unwrap_or("unnamed image"))
+                .on_hover_text(format!("{id:?}"));
+            let (Some(server), Some(folder)) = (
+                world.get_resource::<AssetServer>(),
+                world.get_resource::<AssetFolder>(),
+            ) else {
+                return;
+            };
+            let mut picked = path.clone().unwrap_or_default();
+            if asset_path_picker(ui, &mut picked, folder, IMAGE_EXTENSIONS) {
+                repr.apply(&server.load::<Image>(picked));
+                states.commit();
+            }
+        });
+        image_preview(ui, world, id);
+    });
+}
+
+/// A text editor for string-like types, which are converted to text to be edited and converted
+/// back from a [`String`] when the edit is committed. Switches to a multiline editor when the
+/// text contains newlines or is longer than [`ReprEditors::multiline_threshold`].
+fn text_editor<T: Reflect + From<String>>(
+    ui: &mut Ui,
+    repr: &mut dyn Reflect,
+    editors: &ReprEditors,
+    states: &mut EditorStates,
+    to_text: impl FnOnce(&T) -> String,
+) {
+    let value = repr.downcast_ref::<T>().unwrap();
+    let text = states.text_edit(ui.id(), || to_text(value));
+    let edit = if text.contains('\n') || text.chars().count() > editors.multiline_threshold {
+        let row_height = ui.text_style_height(&egui::TextStyle::Body);
+        ScrollArea::vertical()
+            .max_height(row_height * editors.multiline_max_rows as f32)
+            .show(ui, |ui| {
+                egui::TextEdit::multiline(text)
+                    .desired_rows(1)
+                    .desired_width(f32::INFINITY)
+                    .show(ui)
+                    .response
+            })
+            .inner
+    } else {
+        ui.text_edit_singleline(text)
+    };
+    let pending = edit.has_focus().then(|| text.clone());
     if edit.lost_focus() {
-        repr.apply(text);
+        let edited = T::from(text.clone());
+        if repr.reflect_partial_eq(&edited) != Some(true) {
+            repr.apply(&edited);
+            states.commit();
+        }
         states.remove(ui.id());
     }
-    if !edit.has_focus() {
-        states.remove(ui.id());
+    match pending {
+        Some(text) => states.mark_pending(&text),
+        None => {
+            states.remove(ui.id());
+        }
     }
 }
@@ -0,0 +1,261 @@
+//! A transform gizmo drawn over the viewport for the selected entity, while the entities tab is
+//! shown. Dragging its handles translates, rotates or scales the entity along its local axes.
+//! Edits are written to the `Transform` representation the editors edit, so they are applied the
+//! same way.
+
+use bevy::prelude::*;
+use bevy::render::camera::RenderTarget;
+use bevy::window::{PrimaryWindow, WindowRef};
+
+use super::{entities_tab_shown, SelectedEntity};
+use crate::compat::egui::{self, Color32, Pos2, Stroke};
+use crate::compat::{Egui, EguiBackend};
+
+/// How close the pointer has to be to a handle to grab it, in points.
+const GRAB_DISTANCE: f32 = 8.0;
+
+/// What dragging the handles of the [`TransformGizmo`] does.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum GizmoMode {
+    /// Move the entity along an axis.
+    #[default]
+    Translate,
+    /// Rotate the entity around an axis.
+    Rotate,
+    /// Scale the entity along an axis.
+    Scale,
+}
+
+/// The resource that configures the transform gizmo of the selected entity.
+#[derive(Resource)]
+pub struct TransformGizmo {
+    /// Whether the gizmo is drawn.
+    pub enabled: bool,
+    /// What dragging the handles does.
+    pub mode: GizmoMode,
+    /// The length of the handles, in points.
+    pub size: f32,
+    drag: Option<Drag>,
+}
+
+impl Default for TransformGizmo {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            mode: GizmoMode::default(),
+            size: 80.0,
+            drag: None,
+        }
+    }
+}
+
+/// A handle being dragged.
+struct Drag {
+    axis: usize,
+    start: Pos2,
+    transform: Transform,
+    /// The direction of the axis on screen, normalized.
+    screen_axis: egui::Vec2,
+    /// How many points one world unit along the axis spans on screen.
+    points_per_unit: f32,
+}
+
+/// A projected handle of the gizmo.
+struct Handle {
+    axis: usize,
+    world_axis: Vec3,
+    tip: Pos2,
+    screen_axis: egui::Vec2,
+    points_per_unit: f32,
+}
+
+const COLORS: [Color32; 3] = [
+    Color32::from_rgb(230, 70, 70),
+    Color32::from_rgb(90, 200, 90),
+    Color32::from_rgb(70, 120, 240),
+];
+
+/// The camera that renders to the primary window with the highest order.
+pub(super) fn viewport_camera(
+    world: &mut World,
+    window: Entity,
+) -> Option<(Camera, GlobalTransform)> {
+    let mut cameras = world.query::<(&Camera, &GlobalTransform)>();
+    cameras
+        .iter(world)
+        .filter(|(camera, _)| camera.is_active)
+        .filter(|(camera, _)| match camera.target {
+            RenderTarget::Window(WindowRef::Primary) => true,
+            RenderTarget::Window(WindowRef::Entity(target)) => target == window,
+            _ => false,
+        })
+        .max_by_key(|(camera, _)| camera.order)
+        .map(|(camera, global)| (camera.clone(), *global))
+}
+
+pub(crate) fn draw_transform_gizmo(world: &mut World) {
+    let Some(mut gizmo) = world.remove_resource::<TransformGizmo>() else {
+        return;
+    };
+    draw_gizmo(world, &mut gizmo);
+    world.insert_resource(gizmo);
+}
+
+fn draw_gizmo(world: &mut World, gizmo: &mut TransformGizmo) {
+    let Some(entity) = world.get_resource::<SelectedEntity>().map(|s| s.id) else {
+        gizmo.drag = None;
+        return;
+    };
+    let Some(global) = world.get::<GlobalTransform>(entity).copied() else {
+        return;
+    };
+    if !gizmo.enabled || !entities_tab_shown(world) || !world.entity(entity).contains::<Transform>()
+    {
+        gizmo.drag = None;
+        return;
+    }
+    let mut windows = world.query_filtered::<Entity, With<PrimaryWindow>>();
+    let Ok(window) = windows.get_single(world) else {
+        return;
+    };
+    let (Some((camera, camera_global)), Some(ctx)) =
+        (viewport_camera(world, window), Egui::context(world, window))
+    else {
+        return;
+    };
+
+    let project = |point: Vec3| {
+        let point = camera.world_to_viewport(&camera_global, point)?;
+        Some(Pos2::new(point.x, point.y))
+    };
+    let Some(origin) = project(global.translation()) else {
+        return;
+    };
+
+    let handles = (0..3)
+        .filter_map(|axis| {
+            let world_axis = global
+                .affine()
+                .transform_vector3(Vec3::AXES[axis])
+                .normalize_or_zero();
+            let unit = project(global.translation() + world_axis)? - origin;
+            let points_per_unit = unit.length();
+            (points_per_unit > 1e-3).then(|| Handle {
+                axis,
+                world_axis,
+                tip: origin + unit / points_per_unit * gizmo.size,
+                screen_axis: unit / points_per_unit,
+                points_per_unit,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let painter = ctx.layer_painter(egui::LayerId::new(
+        egui::Order::Background,
+        egui::Id::new("spyglass_gizmo"),
+    ));
+    for handle in &handles {
+        let color = COLORS[handle.axis];
+        let width = match &gizmo.drag {
+            Some(drag) if drag.axis == handle.axis => 4.0,
+            _ => 2.0,
+        };
+        painter.line_segment([origin, handle.tip], Stroke::new(width, color));
+        match gizmo.mode {
+            GizmoMode::Translate => {
+                painter.circle_filled(handle.tip, 5.0, color);
+            }
+            GizmoMode::Rotate => {
+                painter.circle_stroke(handle.tip, 7.0, Stroke::new(width, color));
+            }
+            GizmoMode::Scale => {
+                let rect = egui::Rect::from_center_size(handle.tip, egui::vec2(9.0, 9.0));
+                painter.rect_filled(rect, 0.0, color);
+            }
+        }
+    }
+
+    let (pointer, pressed, down) = ctx.input(|input| {
+        (
+            input.pointer.interact_pos(),
+            input.pointer.primary_pressed(),
+            input.pointer.primary_down(),
+        )
+    });
+    let Some(pointer) = pointer else {
+        return;
+    };
+
+    if pressed && !ctx.wants_pointer_input() {
+        let grabbed = handles
+            .iter()
+            .map(|handle| (handle, distance_to_segment(pointer, origin, handle.tip)))
+            .filter(|&(_, distance)| distance < GRAB_DISTANCE)
+            .min_by(|(_, a), (_, b)| a.total_cmp(b));
+        if let Some((handle, _)) = grabbed {
+            gizmo.drag = Some(Drag {
+                axis: handle.axis,
+                start: pointer,
+                transform: *world.get::<Transform>(entity).unwrap(),
+                screen_axis: handle.screen_axis,
+                points_per_unit: handle.points_per_unit,
+            });
+        }
+    }
+    if !down {
+        gizmo.drag = None;
+    }
+    let Some(drag) = &gizmo.drag else {
+        return;
+    };
+
+    let delta = pointer - drag.start;
+    let along = delta.x * drag.screen_axis.x + delta.y * drag.screen_axis.y;
+    let mut transform = drag.transform;
+    match gizmo.mode {
+        GizmoMode::Translate => {
+            let world_axis = handles
+                .iter()
+                .find(|handle| handle.axis == drag.axis)
+                .map_or(Vec3::ZERO, |handle| handle.world_axis);
+            let mut offset = world_axis * along / drag.points_per_unit;
+            let parent = world.get::<Parent>(entity).map(Parent::get);
+            if let Some(parent) = parent.and_then(|parent| world.get::<GlobalTransform>(parent)) {
+                offset = parent.affine().inverse().transform_vector3(offset);
+            }
+            transform.translation += offset;
+        }
+        GizmoMode::Rotate => {
+            // Dragging across the axis rotates around it, like turning a wheel.
+            let across = delta.x * -drag.screen_axis.y + delta.y * drag.screen_axis.x;
+            let angle = across / gizmo.size;
+            transform.rotation *= Quat::from_axis_angle(Vec3::AXES[drag.axis], angle);
+        }
+        GizmoMode::Scale => {
+            let factor = (1.0 + along / gizmo.size).max(0.01);
+            transform.scale[drag.axis] *= factor;
+        }
+    }
+    set_transform(world, entity, transform);
+}
+
+/// Write the transform to the selected entity's representation of its `Transform`, falling back
+/// to the component itself if it has none.
+fn set_transform(world: &mut World, entity: Entity, transform: Transform) {
+    let mut selected = world.remove_resource::<SelectedEntity>().unwrap();
+    match selected.repr_mut(world, "Transform") {
+        Some(repr) => repr.apply(&transform),
+        None => {
+            if let Some(mut component) = world.get_mut::<Transform>(entity) {
+                *component = transform;
+            }
+        }
+    }
+    world.insert_resource(selected);
+}
+
+fn distance_to_segment(point: Pos2, a: Pos2, b: Pos2) -> f32 {
+    let ab = b - a;
+    let t = ((point - a).dot(ab) / ab.length_sq().max(1e-6)).clamp(0.0, 1.0);
+    point.distance(a + ab * t)
+}
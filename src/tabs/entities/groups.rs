@@ -0,0 +1,187 @@
+//! Named groups of entities, defined by filter expressions. Groups are displayed as virtual
+//! folders in the entity list, and their contents are refreshed every frame.
+
+use bevy::ecs::archetype::ArchetypeId;
+use bevy::prelude::*;
+use bevy::utils::{get_short_name, HashMap};
+
+/// A single term of an [`EntityFilter`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum FilterTerm {
+    /// `has:Component` matches entities with a component, by short or full type name.
+    Has(String),
+    /// `name:text` matches entities whose [`Name`] contains the text.
+    Name(String),
+    /// `!term` matches entities that don't match the term.
+    Not(Box<FilterTerm>),
+}
+
+impl FilterTerm {
+    fn parse(term: &str) -> Result<Self, String> {
+        if let Some(term) = term.strip_prefix('!') {
+            return Ok(Self::Not(Box::new(Self::parse(term)?)));
+        }
+
+        match term.split_once(':') {
+            Some(("has", component)) if !component.is_empty() => Ok(Self::Has(component.into())),
+            Some(("name", name)) => Ok(Self::Name(name.into())),
+            _ => Err(format!(
+                "unknown filter term `{term}`, expected `has:Component` or `name:text`"
+            )),
+        }
+    }
+
+    fn matches(&self, world: &World, entity: Entity) -> bool {
+        match self {
+            Self::Has(component) => {
+                let Some(archetype) = world
+                    .entities()
+                    .get(entity)
+                    .and_then(|loc| world.archetypes().get(loc.archetype_id))
+                else {
+                    return false;
+                };
+                archetype.components().any(|comp| {
+                    world
+                        .components()
+                        .get_name(comp)
+                        .is_some_and(|name| name == component || get_short_name(name) == *component)
+                })
+            }
+            Self::Name(text) => world
+                .get::<Name>(entity)
+                .is_some_and(|name| name.as_str().contains(text.as_str())),
+            Self::Not(term) => !term.matches(world, entity),
+        }
+    }
+}
+
+/// A filter expression over entities, made of whitespace-separated [`FilterTerm`]s which must all
+/// match, e.g. `has:Pickup !has:Collected`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct EntityFilter {
+    /// The terms of the filter. An empty filter matches every entity.
+    pub terms: Vec<FilterTerm>,
+}
+
+impl EntityFilter {
+    /// Parse a filter expression.
+    pub fn parse(expr: &str) -> Result<Self, String> {
+        let terms = expr
+            .split_whitespace()
+            .map(FilterTerm::parse)
+            .collect::<Result<_, _>>()?;
+        Ok(Self { terms })
+    }
+
+    /// Returns whether the entity matches every term of the filter.
+    pub fn matches(&self, world: &World, entity: Entity) -> bool {
+        self.terms.iter().all(|term| term.matches(world, entity))
+    }
+}
+
+/// A named group of entities, displayed as a folder in the entity list.
+pub struct EntityGroup {
+    /// The name of the group.
+    pub name: String,
+    /// The expression the filter was parsed from, as displayed in the inspector.
+    pub expr: String,
+    /// The filter that entities must match to be in the group.
+    pub filter: EntityFilter,
+}
+
+/// The resource that contains the [`EntityGroup`]s displayed in the entity list.
+#[derive(Default, Resource)]
+pub struct EntityGroups {
+    /// The groups, in display order.
+    pub groups: Vec<EntityGroup>,
+}
+
+impl EntityGroups {
+    /// Add a group named `name` of the entities matching the filter expression `expr`.
+    pub fn add(&mut self, name: impl Into<String>, expr: impl Into<String>) -> Result<(), String> {
+        let expr = expr.into();
+        self.groups.push(EntityGroup {
+            name: name.into(),
+            filter: EntityFilter::parse(&expr)?,
+            expr,
+        });
+        Ok(())
+    }
+
+    /// Add a group from a definition of the form `name: expr`, e.g. `All pickups: has:Pickup`.
+    pub fn add_definition(&mut self, definition: &str) -> Result<(), String> {
+        let (name, expr) = definition
+            .split_once(": ")
+            .ok_or_else(|| format!("expected `name: filter`, found `{definition}`"))?;
+        self.add(name.trim(), expr.trim())
+    }
+}
+
+/// How the entity list buckets entities, set in
+/// [`SpyglassSettings::entity_group_by`](crate::SpyglassSettings::entity_group_by).
+#[derive(Clone, Debug, Default, PartialEq, Eq, Reflect)]
+pub enum EntityGroupBy {
+    /// The entities are listed without grouping.
+    #[default]
+    None,
+    /// By archetype, so that entities with the same components are listed together.
+    Archetype,
+    /// By whether the entities have a marker component, by short or full type name.
+    Marker(String),
+}
+
+impl EntityGroupBy {
+    /// Bucket the entities, keeping their order within each bucket. Buckets are ordered by their
+    /// first entity, and labelled by the components they share.
+    pub fn bucket(&self, world: &World, entities: &[Entity]) -> Vec<(String, Vec<Entity>)> {
+        let mut buckets: Vec<(String, Vec<Entity>)> = vec![];
+        let mut push = |label: String, entity| match buckets.iter_mut().find(|b| b.0 == label) {
+            Some((_, entities)) => entities.push(entity),
+            None => buckets.push((label, vec![entity])),
+        };
+        match self {
+            Self::None => return vec![(String::new(), entities.to_vec())],
+            Self::Archetype => {
+                let mut labels = HashMap::<ArchetypeId, String>::default();
+                for &entity in entities {
+                    let Some(location) = world.entities().get(entity) else {
+                        continue;
+                    };
+                    let label = labels
+                        .entry(location.archetype_id)
+                        .or_insert_with(|| archetype_label(world, location.archetype_id));
+                    push(label.clone(), entity);
+                }
+            }
+            Self::Marker(marker) => {
+                let has = FilterTerm::Has(marker.clone());
+                for &entity in entities {
+                    let label = match has.matches(world, entity) {
+                        true => format!("with {marker}"),
+                        false => format!("without {marker}"),
+                    };
+                    push(label, entity);
+                }
+            }
+        }
+        buckets
+    }
+}
+
+/// The short names of the components of an archetype, sorted.
+fn archetype_label(world: &World, archetype: ArchetypeId) -> String {
+    let Some(archetype) = world.archetypes().get(archetype) else {
+        return String::new();
+    };
+    let mut names = archetype
+        .components()
+        .filter_map(|comp| world.components().get_name(comp))
+        .map(get_short_name)
+        .collect::<Vec<_>>();
+    if names.is_empty() {
+        return "no components".into();
+    }
+    names.sort();
+    names.join(", ")
+}
@@ -0,0 +1,83 @@
+//! Shows and edits the place of the selected entity in the hierarchy. Changes are made with the
+//! hierarchy commands, which keep [`Parent`] and [`Children`] consistent, rather than by editing
+//! those components directly.
+
+use bevy::ecs::system::CommandQueue;
+use bevy::prelude::*;
+
+use super::entity_name;
+use crate::compat::egui::Ui;
+use crate::widgets::entity_picker;
+
+/// Whether `ancestor` is `entity` or one of its ancestors.
+fn is_ancestor(world: &World, ancestor: Entity, mut entity: Entity) -> bool {
+    loop {
+        if entity == ancestor {
+            return true;
+        }
+        match world.get::<Parent>(entity) {
+            Some(parent) => entity = parent.get(),
+            None => return false,
+        }
+    }
+}
+
+/// Draw the parent and children of `entity`, with controls to reparent it, detach it from its
+/// parent, or adopt another entity as a child. Returns the entity to select, if a parent or child
+/// was clicked.
+pub(crate) fn draw_hierarchy(ui: &mut Ui, world: &mut World, entity: Entity) -> Option<Entity> {
+    let mut select = None;
+    let parent = world.get::<Parent>(entity).map(Parent::get);
+    let children = world
+        .get::<Children>(entity)
+        .map(|children| children.to_vec())
+        .unwrap_or_default();
+
+    let mut queue = CommandQueue::default();
+    let mut commands = Commands::new(&mut queue, world);
+    ui.collapsing(format!("hierarchy ({} children)", children.len()), |ui| {
+        ui.horizontal_wrapped(|ui| {
+            ui.label("parent:");
+            match parent {
+                Some(parent) => {
+                    if ui.link(entity_name(world, parent)).clicked() {
+                        select = Some(parent);
+                    }
+                    if ui.small_button("detach").clicked() {
+                        commands.entity(entity).remove_parent();
+                    }
+                }
+                None => {
+                    ui.weak("none");
+                }
+            }
+            // Parenting an entity to one of its descendants would create a cycle.
+            let new_parent = entity_picker(ui, "reparent", world, |other| {
+                Some(other) == parent || is_ancestor(world, entity, other)
+            });
+            if let Some(new_parent) = new_parent {
+                commands.entity(entity).set_parent(new_parent);
+            }
+        });
+
+        ui.horizontal_wrapped(|ui| {
+            ui.label("children:");
+            if children.is_empty() {
+                ui.weak("none");
+            }
+            for &child in &children {
+                if ui.link(entity_name(world, child)).clicked() {
+                    select = Some(child);
+                }
+            }
+            let child = entity_picker(ui, "add child", world, |other| {
+                children.contains(&other) || is_ancestor(world, other, entity)
+            });
+            if let Some(child) = child {
+                commands.entity(entity).add_child(child);
+            }
+        });
+    });
+    queue.apply(world);
+    select
+}
@@ -0,0 +1,204 @@
+//! Records the reflected components of the selected entity over the last frames, so they can be
+//! compared against their current values to pinpoint when a value went wrong.
+
+use std::collections::VecDeque;
+
+use bevy::prelude::*;
+use bevy::reflect::ReflectRef;
+use bevy::utils::HashMap;
+
+use super::SelectedEntity;
+use crate::compat::egui::{self, Ui};
+
+/// The offsets, in frames, that can be picked to compare against.
+const OFFSETS: [usize; 6] = [1, 5, 10, 30, 60, 120];
+
+/// The resource that records the history of the selected entity's components.
+#[derive(Resource)]
+pub struct ComponentHistory {
+    /// The number of frames of history kept. Cloning every component of the selected entity
+    /// each frame has a cost, so set this to `0` to disable recording.
+    pub capacity: usize,
+    entity: Option<Entity>,
+    frames: VecDeque<HashMap<String, Box<dyn Reflect>>>,
+    compare: HashMap<String, usize>,
+}
+
+impl Default for ComponentHistory {
+    fn default() -> Self {
+        Self {
+            capacity: 120,
+            entity: None,
+            frames: VecDeque::new(),
+            compare: HashMap::default(),
+        }
+    }
+}
+
+impl ComponentHistory {
+    /// Get the recorded value of a component `frames` frames ago, if it was recorded.
+    pub fn get(&self, component: &str, frames: usize) -> Option<&dyn Reflect> {
+        let index = self.frames.len().checked_sub(frames + 1)?;
+        self.frames[index].get(component).map(Box::as_ref)
+    }
+
+    /// Draw the "compare with" selector of a component, and the diff against the chosen frame.
+    pub(crate) fn draw_diff(&mut self, ui: &mut Ui, component: &str, current: &dyn Reflect) {
+        let available = OFFSETS
+            .into_iter()
+            .filter(|&offset| self.get(component, offset).is_some())
+            .collect::<Vec<_>>();
+        if available.is_empty() {
+            return;
+        }
+
+        let mut offset = self.compare.get(component).copied();
+        ui.horizontal(|ui| {
+            ui.weak("compare with");
+            egui::ComboBox::from_id_source(("compare_with", component))
+                .selected_text(offset.map_or("-".to_string(), |offset| format!("T−{offset}")))
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut offset, None, "-");
+                    for available in available {
+                        ui.selectable_value(
+                            &mut offset,
+                            Some(available),
+                            format!("T−{available} frames"),
+                        );
+                    }
+                });
+        });
+
+        let Some(offset) = offset else {
+            self.compare.remove(component);
+            return;
+        };
+        self.compare.insert(component.to_string(), offset);
+
+        let Some(past) = self.get(component, offset) else {
+            return;
+        };
+        let mut diffs = vec![];
+        diff(String::new(), past, current, &mut diffs);
+
+        if diffs.is_empty() {
+            ui.weak("no changes");
+            return;
+        }
+        egui::Grid::new(("component_diff", component))
+            .striped(true)
+            .show(ui, |ui| {
+                ui.strong("field");
+                ui.strong(format!("T−{offset}"));
+                ui.strong("now");
+                ui.end_row();
+                for (path, past, now) in diffs {
+                    ui.label(path);
+                    ui.label(format!("{past:?}"));
+                    ui.label(format!("{now:?}"));
+                    ui.end_row();
+                }
+            });
+    }
+}
+
+/// Collect the paths of the fields that differ between `past` and `now`, with both values.
+pub(crate) fn diff<'a>(
+    path: String,
+    past: &'a dyn Reflect,
+    now: &'a dyn Reflect,
+    out: &mut Vec<(String, &'a dyn Reflect, &'a dyn Reflect)>,
+) {
+    let differs = |out: &mut Vec<_>| out.push((path.clone(), past, now));
+
+    match (past.reflect_ref(), now.reflect_ref()) {
+        (ReflectRef::Struct(past), ReflectRef::Struct(now)) => {
+            for (i, field) in now.iter_fields().enumerate() {
+                let name = now.name_at(i).unwrap_or_default();
+                match past.field(name) {
+                    Some(past) => diff(format!("{path}.{name}"), past, field, out),
+                    None => differs(out),
+                }
+            }
+        }
+        (ReflectRef::TupleStruct(past), ReflectRef::TupleStruct(now)) => {
+            for (i, field) in now.iter_fields().enumerate() {
+                match past.field(i) {
+                    Some(past) => diff(format!("{path}.{i}"), past, field, out),
+                    None => differs(out),
+                }
+            }
+        }
+        (ReflectRef::Tuple(past), ReflectRef::Tuple(now)) => {
+            for (i, field) in now.iter_fields().enumerate() {
+                match past.field(i) {
+                    Some(past) => diff(format!("{path}.{i}"), past, field, out),
+                    None => differs(out),
+                }
+            }
+        }
+        (ReflectRef::List(past), ReflectRef::List(now)) if past.len() == now.len() => {
+            for (i, (past, now)) in past.iter().zip(now.iter()).enumerate() {
+                diff(format!("{path}[{i}]"), past, now, out);
+            }
+        }
+        (ReflectRef::Array(past), ReflectRef::Array(now)) if past.len() == now.len() => {
+            for (i, (past, now)) in past.iter().zip(now.iter()).enumerate() {
+                diff(format!("{path}[{i}]"), past, now, out);
+            }
+        }
+        (ReflectRef::Enum(past_enum), ReflectRef::Enum(now_enum))
+            if past_enum.variant_name() == now_enum.variant_name() =>
+        {
+            for (i, field) in now_enum.iter_fields().enumerate() {
+                let segment = field.name().map_or_else(|| i.to_string(), str::to_string);
+                match past_enum.field_at(i) {
+                    Some(past) => diff(format!("{path}.{segment}"), past, field.value(), out),
+                    None => differs(out),
+                }
+            }
+        }
+        _ => {
+            let equal = past
+                .reflect_partial_eq(now)
+                .unwrap_or_else(|| format!("{past:?}") == format!("{now:?}"));
+            if !equal {
+                differs(out);
+            }
+        }
+    }
+}
+
+pub(super) fn record_history(
+    mut history: ResMut<ComponentHistory>,
+    selected: Option<Res<SelectedEntity>>,
+) {
+    let Some(selected) = selected else {
+        history.entity = None;
+        history.frames.clear();
+        return;
+    };
+
+    if history.entity != Some(selected.id) {
+        history.entity = Some(selected.id);
+        history.frames.clear();
+        history.compare.clear();
+    }
+    if history.capacity == 0 {
+        history.frames.clear();
+        return;
+    }
+
+    // Collapsed components are not refreshed, so their stale values are not recorded.
+    let frame = selected
+        .state
+        .reprs
+        .iter()
+        .filter(|(name, _)| selected.state.pristine.contains_key(*name))
+        .map(|(name, repr)| (name.clone(), repr.clone_value()))
+        .collect();
+    history.frames.push_back(frame);
+    while history.frames.len() > history.capacity {
+        history.frames.pop_front();
+    }
+}
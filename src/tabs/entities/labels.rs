@@ -0,0 +1,95 @@
+//! An overlay that labels entities with their names at their positions in the viewport. Clicking
+//! a label selects its entity in the inspector, which bridges the entity list and the scene.
+
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+
+use super::gizmo::viewport_camera;
+use super::groups::EntityFilter;
+use super::selection::SelectEntity;
+use crate::compat::egui;
+use crate::compat::{Egui, EguiBackend};
+use crate::Spyglass;
+
+/// The resource that configures the entity labels overlay.
+#[derive(Resource)]
+pub struct EntityLabels {
+    /// Whether the labels are drawn.
+    pub enabled: bool,
+    /// The filter named entities must match to be labelled. The empty default labels every
+    /// entity with a [`Name`] and a [`GlobalTransform`].
+    pub filter: EntityFilter,
+    /// The most labels drawn, so crowded scenes stay readable. The entities closest to the camera
+    /// are labelled first.
+    pub max_labels: usize,
+}
+
+impl Default for EntityLabels {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            filter: EntityFilter::default(),
+            max_labels: 100,
+        }
+    }
+}
+
+pub(crate) fn draw_entity_labels(world: &mut World) {
+    let Some(labels) = world.remove_resource::<EntityLabels>() else {
+        return;
+    };
+    if labels.enabled {
+        draw_labels(world, &labels);
+    }
+    world.insert_resource(labels);
+}
+
+fn draw_labels(world: &mut World, labels: &EntityLabels) {
+    let mut windows = world.query_filtered::<Entity, With<PrimaryWindow>>();
+    let Ok(window) = windows.get_single(world) else {
+        return;
+    };
+    let (Some((camera, camera_global)), Some(ctx)) =
+        (viewport_camera(world, window), Egui::context(world, window))
+    else {
+        return;
+    };
+
+    let mut named = world.query::<(Entity, &Name, &GlobalTransform)>();
+    let mut visible = named
+        .iter(world)
+        .filter(|(entity, _, _)| labels.filter.matches(world, *entity))
+        .filter_map(|(entity, name, global)| {
+            let point = camera.world_to_viewport(&camera_global, global.translation())?;
+            let distance = camera_global.translation().distance(global.translation());
+            Some((
+                entity,
+                name.to_string(),
+                egui::pos2(point.x, point.y),
+                distance,
+            ))
+        })
+        .collect::<Vec<_>>();
+    visible.sort_by(|a, b| a.3.total_cmp(&b.3));
+    visible.truncate(labels.max_labels);
+
+    let mut clicked = None;
+    for (entity, name, pos, _) in visible {
+        egui::Area::new(egui::Id::new(("spyglass_entity_label", entity)))
+            .fixed_pos(pos)
+            .pivot(egui::Align2::CENTER_BOTTOM)
+            .order(egui::Order::Background)
+            .show(&ctx, |ui| {
+                if ui.small_button(name).clicked() {
+                    clicked = Some(entity);
+                }
+            });
+    }
+
+    if let Some(entity) = clicked {
+        world.send_event(SelectEntity(Some(entity)));
+        if let Some(mut spyglass) = world.get_resource_mut::<Spyglass>() {
+            spyglass.select_tab_by_name("Entities");
+        }
+    }
+}
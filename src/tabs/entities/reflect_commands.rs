@@ -0,0 +1,80 @@
+//! Targeted edits of component fields. In [`ApplyMode::Fields`](super::ApplyMode::Fields), the
+//! entities tab queues the fields editors changed as [`ReflectCommand`]s instead of writing whole
+//! components back right after the inspector is drawn. The commands are applied at the end of the
+//! frame, so changes game systems make to the other fields of a component in the meantime are
+//! kept.
+
+use std::panic::AssertUnwindSafe;
+
+use bevy::prelude::*;
+use bevy::reflect::GetPath;
+
+use super::{get_reflect_impl, mark_applied, report_apply_failure};
+
+/// An edit of a single field of a component.
+pub struct ReflectCommand {
+    /// The entity the component belongs to.
+    pub entity: Entity,
+    /// The type name of the component.
+    pub component: String,
+    /// The path of the field in the component, e.g. `.translation.x`. Empty for the whole
+    /// component.
+    pub path: String,
+    /// The new value of the field.
+    pub value: Box<dyn Reflect>,
+}
+
+impl ReflectCommand {
+    /// Apply the edit to the world. Fails if the entity no longer has the component or the field,
+    /// or if the value does not match the type of the field.
+    pub fn apply(&self, world: &mut World) -> Result<(), String> {
+        let refl = get_reflect_impl(world, &self.component).ok_or("its type is not registered")?;
+        let mut entity = world
+            .get_entity_mut(self.entity)
+            .ok_or("the entity was despawned")?;
+        let component = refl
+            .reflect_mut(&mut entity)
+            .ok_or("the entity no longer has it")?
+            .into_inner();
+        let field = match self.path.is_empty() {
+            true => component,
+            false => component
+                .reflect_path_mut(self.path.as_str())
+                .map_err(|err| format!("`{}` is not a field: {err}", self.path))?,
+        };
+        std::panic::catch_unwind(AssertUnwindSafe(|| field.apply(&*self.value))).map_err(|_| {
+            format!(
+                "the edited value of `{}` does not match its type",
+                self.path
+            )
+        })
+    }
+}
+
+/// The resource that queues [`ReflectCommand`]s until they are applied at the end of the frame.
+#[derive(Default, Resource)]
+pub struct ReflectCommands {
+    commands: Vec<ReflectCommand>,
+}
+
+impl ReflectCommands {
+    /// Queue an edit.
+    pub fn push(&mut self, command: ReflectCommand) {
+        self.commands.push(command);
+    }
+
+    /// The queued edits, oldest first.
+    pub fn iter(&self) -> impl Iterator<Item = &ReflectCommand> {
+        self.commands.iter()
+    }
+}
+
+pub(super) fn apply_reflect_commands(world: &mut World) {
+    let commands = std::mem::take(&mut world.resource_mut::<ReflectCommands>().commands);
+    for command in commands {
+        match command.apply(world) {
+            Ok(()) => mark_applied(world, command.entity, &command.component),
+            Err(err) => report_apply_failure(world, &command.component, &err),
+        }
+    }
+}
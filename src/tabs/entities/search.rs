@@ -0,0 +1,60 @@
+//! Resolves entity searches that identify entities exactly: entity ids as printed by `Debug`,
+//! such as `42v3`, which is how logs print entities, and paths of names through the hierarchy,
+//! such as `Level1/Enemies/Boss`. Other searches match entity names by prefix.
+
+use bevy::prelude::*;
+
+/// Find the entity with the id `text`, either `<index>v<generation>` or just `<index>` for
+/// whichever generation is alive. Returns `None` if the text is not an id, or the entity does not
+/// exist.
+pub fn entity_by_id(world: &World, text: &str) -> Option<Entity> {
+    let (index, generation) = text.split_once('v').unwrap_or((text, ""));
+    let index = index.parse::<u32>().ok()?;
+    let entity = if generation.is_empty() {
+        world.entities().resolve_from_id(index)?
+    } else {
+        let generation = generation.parse::<u32>().ok()?;
+        Entity::from_bits((u64::from(generation) << 32) | u64::from(index))
+    };
+    world.get_entity(entity).map(|entity| entity.id())
+}
+
+/// Find the entities at the path of names `path`, separated by `/`. The first name may be the
+/// name of any entity, and each following name that of a child of the previous one.
+pub fn entities_by_path(world: &World, path: &str) -> Vec<Entity> {
+    let mut segments = path.split('/').map(str::trim);
+    let Some(first) = segments.next() else {
+        return vec![];
+    };
+    let has_name = |entity: Entity, name: &str| {
+        world
+            .get::<Name>(entity)
+            .is_some_and(|n| n.as_str() == name)
+    };
+    let mut entities = world
+        .iter_entities()
+        .map(|entity| entity.id())
+        .filter(|&entity| has_name(entity, first))
+        .collect::<Vec<_>>();
+    for segment in segments {
+        entities = entities
+            .into_iter()
+            .filter_map(|entity| world.get::<Children>(entity))
+            .flat_map(|children| children.iter().copied())
+            .filter(|&child| has_name(child, segment))
+            .collect();
+    }
+    entities
+}
+
+/// Resolve a search that identifies entities exactly: an entity id, or a path of names if the
+/// search contains a `/`. Returns `None` for other searches, which match by name prefix.
+pub fn resolve_search(world: &World, search: &str) -> Option<Vec<Entity>> {
+    let search = search.trim();
+    if let Some(entity) = entity_by_id(world, search) {
+        return Some(vec![entity]);
+    }
+    search
+        .contains('/')
+        .then(|| entities_by_path(world, search))
+}
@@ -0,0 +1,124 @@
+//! Events that synchronize the selection of the entities tab with other tools, such as an external
+//! level editor. Send [`SelectEntity`] to change the selection, and read [`EntitySelected`] to
+//! follow it.
+
+use bevy::prelude::*;
+
+use super::SelectedEntity;
+
+/// Send this event to select an entity in the entities tab, or to clear the selection with
+/// `None`. Entities that do not exist are ignored.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Event)]
+pub struct SelectEntity(pub Option<Entity>);
+
+/// Sent whenever the selection of the entities tab changes, whether by the user, a script, or a
+/// [`SelectEntity`] event. `None` means the selection was cleared.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Event)]
+pub struct EntitySelected(pub Option<Entity>);
+
+pub(crate) fn apply_selection_requests(world: &mut World) {
+    let requests = world
+        .resource_mut::<Events<SelectEntity>>()
+        .drain()
+        .collect::<Vec<_>>();
+    for SelectEntity(entity) in requests {
+        match entity {
+            Some(entity) if world.get_entity(entity).is_some() => {
+                let selected = SelectedEntity::new(world, entity);
+                world.insert_resource(selected);
+            }
+            Some(_) => (),
+            None => {
+                world.remove_resource::<SelectedEntity>();
+            }
+        }
+    }
+}
+
+pub(crate) fn report_selection_changes(
+    selected: Option<Res<SelectedEntity>>,
+    mut previous: Local<Option<Entity>>,
+    mut events: EventWriter<EntitySelected>,
+) {
+    let current = selected.map(|selected| selected.id);
+    if *previous != current {
+        *previous = current;
+        events.send(EntitySelected(current));
+    }
+}
+
+/// The resource that records the selections of the entities tab, for browser-style back and
+/// forward navigation. `None` entries stand for the entity list. Every way of changing the
+/// selection is recorded, including following entity references in components.
+#[derive(Default, Resource)]
+pub struct SelectionHistory {
+    back: Vec<Option<Entity>>,
+    forward: Vec<Option<Entity>>,
+    current: Option<Entity>,
+}
+
+impl SelectionHistory {
+    /// The selections that going back returns to, oldest first.
+    pub fn back(&self) -> &[Option<Entity>] {
+        &self.back
+    }
+
+    /// The selections that going forward returns to, in the order they are returned to.
+    pub fn forward(&self) -> impl Iterator<Item = Option<Entity>> + '_ {
+        self.forward.iter().rev().copied()
+    }
+
+    /// Go back to the previous selection that still exists. Returns whether there was one.
+    pub fn go_back(world: &mut World) -> bool {
+        Self::navigate(world, false)
+    }
+
+    /// Go forward to the next selection that still exists. Returns whether there was one.
+    pub fn go_forward(world: &mut World) -> bool {
+        Self::navigate(world, true)
+    }
+
+    fn navigate(world: &mut World, forward: bool) -> bool {
+        let mut history = world.remove_resource::<Self>().unwrap_or_default();
+        let Self {
+            back,
+            forward: ahead,
+            current,
+        } = &mut history;
+        let (from, to) = match forward {
+            true => (ahead, back),
+            false => (back, ahead),
+        };
+        // Selections of entities that were despawned since are skipped.
+        let target = std::iter::from_fn(|| from.pop())
+            .find(|target| target.is_none_or(|entity| world.get_entity(entity).is_some()));
+        if let Some(target) = target {
+            to.push(*current);
+            *current = target;
+            match target {
+                Some(entity) => {
+                    let selected = SelectedEntity::new(world, entity);
+                    world.insert_resource(selected);
+                }
+                None => {
+                    world.remove_resource::<SelectedEntity>();
+                }
+            }
+        }
+        world.insert_resource(history);
+        target.is_some()
+    }
+}
+
+pub(crate) fn record_selection_history(
+    selected: Option<Res<SelectedEntity>>,
+    mut history: ResMut<SelectionHistory>,
+) {
+    let current = selected.map(|selected| selected.id);
+    if history.current != current {
+        let previous = history.current;
+        history.back.push(previous);
+        history.forward.clear();
+        history.current = current;
+    }
+}
@@ -0,0 +1,180 @@
+//! The input tab module. Shows the live state of the keyboard, mouse, gamepads and touches, and
+//! briefly flashes buttons when they are just pressed or released, which makes input mapping bugs
+//! much easier to spot.
+
+use bevy::input::gamepad::{GamepadAxis, GamepadButton, Gamepads};
+use bevy::input::touch::Touches;
+use bevy::prelude::*;
+use bevy::time::Real;
+use bevy::window::PrimaryWindow;
+
+use crate::compat::egui::{self, Color32, Ui};
+use crate::{Spyglass, Tab};
+
+/// How long a just pressed or released button stays highlighted, in seconds.
+const FLASH_SECONDS: f64 = 0.75;
+
+/// The plugin that adds the input tab to the end of the [`Spyglass`] tab list.
+pub struct InputTabPlugin;
+
+impl Plugin for InputTabPlugin {
+    fn build(&self, app: &mut App) {
+        if cfg!(feature = "disabled") {
+            return;
+        }
+
+        let mut spyglass = app.world.resource_mut::<Spyglass>();
+        spyglass.add_tab(Box::new(InputTab::default()));
+    }
+}
+
+struct Flash {
+    label: String,
+    pressed: bool,
+    at: f64,
+}
+
+#[derive(Default)]
+struct InputTab {
+    flashes: Vec<Flash>,
+}
+
+impl InputTab {
+    /// Record the buttons of `input` that were just pressed or released.
+    fn record<T: Copy + Eq + std::hash::Hash + Send + Sync + std::fmt::Debug>(
+        &mut self,
+        input: &Input<T>,
+        now: f64,
+    ) {
+        let pressed = input.get_just_pressed().map(|button| (button, true));
+        let released = input.get_just_released().map(|button| (button, false));
+        for (button, pressed) in pressed.chain(released) {
+            self.flashes.push(Flash {
+                label: format!("{button:?}"),
+                pressed,
+                at: now,
+            });
+        }
+    }
+}
+
+impl Tab for InputTab {
+    fn name(&self) -> &str {
+        "Input"
+    }
+
+    fn draw(&mut self, ui: &mut Ui, world: &mut World) {
+        let now = world.resource::<Time<Real>>().elapsed_seconds_f64();
+        self.flashes.retain(|flash| now - flash.at < FLASH_SECONDS);
+
+        if let Some(keys) = world.get_resource::<Input<KeyCode>>() {
+            self.record(keys, now);
+            pressed_row(ui, "keys", keys.get_pressed());
+        }
+        if let Some(buttons) = world.get_resource::<Input<MouseButton>>() {
+            self.record(buttons, now);
+            pressed_row(ui, "mouse", buttons.get_pressed());
+        }
+        if let Some(buttons) = world.get_resource::<Input<GamepadButton>>() {
+            self.record(buttons, now);
+        }
+
+        ui.horizontal_wrapped(|ui| {
+            ui.label("recent");
+            for flash in self.flashes.iter().rev() {
+                let fade = 1.0 - ((now - flash.at) / FLASH_SECONDS) as f32;
+                let color = match flash.pressed {
+                    true => Color32::from_rgb(80, 200, 80),
+                    false => Color32::from_rgb(220, 90, 60),
+                };
+                let arrow = if flash.pressed { '↓' } else { '↑' };
+                ui.colored_label(
+                    color.gamma_multiply(fade.max(0.2)),
+                    format!("{arrow} {}", flash.label),
+                );
+            }
+        });
+        ui.separator();
+
+        let cursor = world
+            .query_filtered::<&Window, With<PrimaryWindow>>()
+            .get_single(world)
+            .ok()
+            .and_then(Window::cursor_position);
+        ui.label(match cursor {
+            Some(cursor) => format!("cursor: ({:.1}, {:.1})", cursor.x, cursor.y),
+            None => "cursor: outside of the primary window".to_string(),
+        });
+
+        if let Some(touches) = world.get_resource::<Touches>() {
+            for touch in touches.iter() {
+                let position = touch.position();
+                ui.label(format!(
+                    "touch {}: ({:.1}, {:.1})",
+                    touch.id(),
+                    position.x,
+                    position.y
+                ));
+            }
+        }
+        ui.separator();
+
+        draw_gamepads(ui, world);
+    }
+}
+
+fn pressed_row<T: std::fmt::Debug>(ui: &mut Ui, label: &str, pressed: impl Iterator<Item = T>) {
+    ui.horizontal_wrapped(|ui| {
+        ui.label(label);
+        let mut any = false;
+        for button in pressed {
+            any = true;
+            ui.strong(format!("{button:?}"));
+        }
+        if !any {
+            ui.weak("none");
+        }
+    });
+}
+
+fn draw_gamepads(ui: &mut Ui, world: &World) {
+    let Some(gamepads) = world.get_resource::<Gamepads>() else {
+        return;
+    };
+    let buttons = world.get_resource::<Input<GamepadButton>>();
+    let axes = world.get_resource::<Axis<GamepadAxis>>();
+
+    let mut any = false;
+    for gamepad in gamepads.iter() {
+        any = true;
+        let name = gamepads.name(gamepad).unwrap_or("unnamed gamepad");
+        ui.collapsing(format!("{name} ({})", gamepad.id), |ui| {
+            if let Some(buttons) = buttons {
+                let pressed = buttons
+                    .get_pressed()
+                    .filter(|button| button.gamepad == gamepad)
+                    .map(|button| button.button_type);
+                pressed_row(ui, "buttons", pressed);
+            }
+
+            let Some(axes) = axes else {
+                return;
+            };
+            egui::Grid::new(("gamepad_axes", gamepad.id)).show(ui, |ui| {
+                for axis in axes.devices().filter(|axis| axis.gamepad == gamepad) {
+                    let value = axes.get(*axis).unwrap_or_default();
+                    ui.label(format!("{:?}", axis.axis_type));
+                    ui.add(
+                        egui::ProgressBar::new((value + 1.0) / 2.0)
+                            .text(format!("{value:.2}"))
+                            .desired_width(150.0),
+                    );
+                    ui.end_row();
+                }
+            });
+        });
+    }
+    if !any {
+        ui.weak("no gamepads connected");
+    }
+}
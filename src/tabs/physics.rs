@@ -0,0 +1,317 @@
+//! The physics tab module. Shows what a physics engine knows about the world: its rigid bodies
+//! with their velocities and whether they sleep, its colliders with their shapes, the pairs of
+//! colliders in contact and the latest collision events, with buttons to wake bodies, put them to
+//! sleep and push them with an impulse. Each engine is behind its own feature: `rapier` for
+//! bevy_rapier3d, and `xpbd` for bevy_xpbd_3d.
+
+#[cfg(feature = "rapier")]
+pub mod rapier;
+#[cfg(feature = "xpbd")]
+pub mod xpbd;
+
+use std::collections::VecDeque;
+use std::marker::PhantomData;
+
+use bevy::prelude::*;
+
+use crate::compat::egui::{self, Ui};
+use crate::tabs::entities::entity_name;
+use crate::tabs::entities::selection::SelectEntity;
+use crate::{FocusTab, SpyglassSettings, Tab};
+
+/// How many collision events a [`CollisionLog`] keeps.
+pub const COLLISION_LOG_LEN: usize = 100;
+
+/// A collision event, as recorded in a [`CollisionLog`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CollisionRecord {
+    /// The frame the event was read in.
+    pub frame: u32,
+    /// Whether the colliders started touching, rather than stopped.
+    pub started: bool,
+    /// The colliders.
+    pub entities: (Entity, Entity),
+}
+
+/// The latest collision events of an engine, oldest first.
+#[derive(Debug, Default)]
+pub struct CollisionLog {
+    /// The events, at most [`COLLISION_LOG_LEN`] of them.
+    pub events: VecDeque<CollisionRecord>,
+}
+
+impl CollisionLog {
+    /// Record an event, forgetting the oldest one past [`COLLISION_LOG_LEN`].
+    pub fn push(&mut self, record: CollisionRecord) {
+        if self.events.len() == COLLISION_LOG_LEN {
+            self.events.pop_front();
+        }
+        self.events.push_back(record);
+    }
+}
+
+/// A rigid body, as shown in the bodies table.
+struct Body {
+    entity: Entity,
+    kind: &'static str,
+    linear: Vec3,
+    angular: Vec3,
+    sleeping: bool,
+}
+
+/// A collider, as shown in the colliders table.
+struct ColliderRow {
+    entity: Entity,
+    shape: String,
+    /// The size of the shape's bounding box, before scaling.
+    size: Vec3,
+    sensor: bool,
+}
+
+/// What the tab needs from a physics engine.
+trait PhysicsEngine: 'static {
+    /// The name of the tab.
+    const NAME: &'static str;
+    /// Shown next to the events, when the engine only reports the events of some colliders.
+    const EVENTS_HINT: Option<&'static str> = None;
+
+    fn bodies(world: &mut World) -> Vec<Body>;
+    fn colliders(world: &mut World) -> Vec<ColliderRow>;
+    /// The pairs of colliders in contact.
+    fn contacts(world: &World) -> Vec<(Entity, Entity)>;
+    fn log(world: &World) -> Option<&CollisionLog>;
+    fn set_sleeping(world: &mut World, entity: Entity, sleeping: bool);
+    fn apply_impulse(world: &mut World, entity: Entity, impulse: Vec3);
+}
+
+/// A change requested by a button, applied once the tables are drawn.
+enum Action {
+    Sleep(Entity, bool),
+    Impulse(Entity),
+    Open(Entity),
+}
+
+struct PhysicsTab<E> {
+    bodies: Vec<Body>,
+    colliders: Vec<ColliderRow>,
+    contacts: Vec<(Entity, Entity)>,
+    /// The impulse the "push" buttons apply.
+    impulse: Vec3,
+    engine: PhantomData<fn() -> E>,
+}
+
+impl<E> Default for PhysicsTab<E> {
+    fn default() -> Self {
+        Self {
+            bodies: vec![],
+            colliders: vec![],
+            contacts: vec![],
+            impulse: Vec3::Y,
+            engine: PhantomData,
+        }
+    }
+}
+
+impl<E: PhysicsEngine> Tab for PhysicsTab<E> {
+    fn name(&self) -> &str {
+        E::NAME
+    }
+
+    fn collect(&mut self, world: &mut World) {
+        self.bodies = E::bodies(world);
+        self.colliders = E::colliders(world);
+        self.contacts = E::contacts(world);
+    }
+
+    fn draw(&mut self, ui: &mut Ui, world: &mut World) {
+        let read_only = SpyglassSettings::is_read_only(world);
+        let mut action = None;
+
+        egui::ScrollArea::vertical()
+            .id_source("physics")
+            .auto_shrink([false, true])
+            .show(ui, |ui| {
+                egui::CollapsingHeader::new(format!("bodies ({})", self.bodies.len()))
+                    .default_open(true)
+                    .show(ui, |ui| {
+                        ui.add_enabled_ui(!read_only, |ui| {
+                            ui.horizontal(|ui| {
+                                ui.label("impulse");
+                                vec3_editor(ui, &mut self.impulse);
+                            });
+                        });
+                        self.draw_bodies(ui, world, read_only, &mut action);
+                    });
+                egui::CollapsingHeader::new(format!("colliders ({})", self.colliders.len()))
+                    .show(ui, |ui| self.draw_colliders(ui, world, &mut action));
+                egui::CollapsingHeader::new(format!("contacts ({})", self.contacts.len()))
+                    .show(ui, |ui| self.draw_contacts(ui, world, &mut action));
+                let header = egui::CollapsingHeader::new("collision events").show(ui, |ui| {
+                    draw_events(ui, world, E::log(world), &mut action);
+                });
+                if let Some(hint) = E::EVENTS_HINT {
+                    header.header_response.on_hover_text(hint);
+                }
+            });
+
+        match action {
+            Some(Action::Sleep(entity, sleeping)) => E::set_sleeping(world, entity, sleeping),
+            Some(Action::Impulse(entity)) => E::apply_impulse(world, entity, self.impulse),
+            Some(Action::Open(entity)) => {
+                world.send_event(SelectEntity(Some(entity)));
+                world.send_event(FocusTab("Entities".to_string()));
+            }
+            None => {}
+        }
+    }
+}
+
+impl<E> PhysicsTab<E> {
+    fn draw_bodies(
+        &self,
+        ui: &mut Ui,
+        world: &World,
+        read_only: bool,
+        action: &mut Option<Action>,
+    ) {
+        if self.bodies.is_empty() {
+            ui.weak("no rigid bodies");
+            return;
+        }
+        egui::Grid::new("physics_bodies")
+            .num_columns(6)
+            .striped(true)
+            .show(ui, |ui| {
+                for header in [
+                    "body",
+                    "kind",
+                    "linear velocity",
+                    "angular velocity",
+                    "state",
+                ] {
+                    ui.strong(header);
+                }
+                ui.end_row();
+
+                for body in &self.bodies {
+                    entity_link(ui, world, body.entity, action);
+                    ui.label(body.kind);
+                    ui.label(format_vec3(body.linear));
+                    ui.label(format_vec3(body.angular));
+                    ui.label(if body.sleeping { "sleeping" } else { "awake" });
+                    ui.add_enabled_ui(!read_only, |ui| {
+                        ui.horizontal(|ui| {
+                            let (label, sleep) = match body.sleeping {
+                                true => ("wake", false),
+                                false => ("sleep", true),
+                            };
+                            if ui.small_button(label).clicked() {
+                                *action = Some(Action::Sleep(body.entity, sleep));
+                            }
+                            if ui
+                                .small_button("push")
+                                .on_hover_text("Apply the impulse to the body")
+                                .clicked()
+                            {
+                                *action = Some(Action::Impulse(body.entity));
+                            }
+                        });
+                    });
+                    ui.end_row();
+                }
+            });
+    }
+
+    fn draw_colliders(&self, ui: &mut Ui, world: &World, action: &mut Option<Action>) {
+        if self.colliders.is_empty() {
+            ui.weak("no colliders");
+            return;
+        }
+        egui::Grid::new("physics_colliders")
+            .num_columns(3)
+            .striped(true)
+            .show(ui, |ui| {
+                for header in ["collider", "shape", "size"] {
+                    ui.strong(header);
+                }
+                ui.end_row();
+
+                for collider in &self.colliders {
+                    entity_link(ui, world, collider.entity, action);
+                    match collider.sensor {
+                        true => ui.label(format!("{} (sensor)", collider.shape)),
+                        false => ui.label(&collider.shape),
+                    };
+                    ui.label(format_vec3(collider.size));
+                    ui.end_row();
+                }
+            });
+    }
+
+    fn draw_contacts(&self, ui: &mut Ui, world: &World, action: &mut Option<Action>) {
+        if self.contacts.is_empty() {
+            ui.weak("no colliders are touching");
+        }
+        for &(a, b) in &self.contacts {
+            pair_label(ui, world, a, b, action);
+        }
+    }
+}
+
+fn draw_events(
+    ui: &mut Ui,
+    world: &World,
+    log: Option<&CollisionLog>,
+    action: &mut Option<Action>,
+) {
+    let Some(log) = log.filter(|log| !log.events.is_empty()) else {
+        ui.weak("no collision events");
+        return;
+    };
+    for record in log.events.iter().rev() {
+        ui.horizontal(|ui| {
+            ui.weak(format!("#{}", record.frame));
+            ui.label(if record.started { "started" } else { "stopped" });
+            let (a, b) = record.entities;
+            pair_label(ui, world, a, b, action);
+        });
+    }
+}
+
+/// A link to each of two colliders.
+fn pair_label(ui: &mut Ui, world: &World, a: Entity, b: Entity, action: &mut Option<Action>) {
+    ui.horizontal(|ui| {
+        entity_link(ui, world, a, action);
+        ui.label("and");
+        entity_link(ui, world, b, action);
+    });
+}
+
+/// The name of an entity, which opens it in the entities tab when clicked.
+fn entity_link(ui: &mut Ui, world: &World, entity: Entity, action: &mut Option<Action>) {
+    if ui
+        .link(entity_name(world, entity))
+        .on_hover_text("Open in the entities tab")
+        .clicked()
+    {
+        *action = Some(Action::Open(entity));
+    }
+}
+
+fn vec3_editor(ui: &mut Ui, value: &mut Vec3) {
+    for (axis, label) in [
+        (&mut value.x, "x"),
+        (&mut value.y, "y"),
+        (&mut value.z, "z"),
+    ] {
+        ui.add(
+            egui::DragValue::new(axis)
+                .speed(0.1)
+                .prefix(format!("{label} ")),
+        );
+    }
+}
+
+fn format_vec3(value: Vec3) -> String {
+    format!("{:.2}, {:.2}, {:.2}", value.x, value.y, value.z)
+}
@@ -0,0 +1,147 @@
+//! The physics tab for bevy_rapier3d. Velocities and sleep states are read from the
+//! [`RapierContext`], so they show even for bodies without a [`Velocity`] or [`Sleeping`]
+//! component, and the buttons act on the context directly.
+
+use bevy::core::FrameCount;
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+
+use super::{Body, ColliderRow, CollisionLog, CollisionRecord, PhysicsEngine, PhysicsTab};
+use crate::Spyglass;
+
+/// The plugin that adds the rapier tab to the end of the [`Spyglass`] tab list, and the system
+/// that records collision events in [`RapierCollisions`].
+pub struct RapierTabPlugin;
+
+impl Plugin for RapierTabPlugin {
+    fn build(&self, app: &mut App) {
+        if cfg!(feature = "disabled") {
+            return;
+        }
+
+        let mut spyglass = app.world.resource_mut::<Spyglass>();
+        spyglass.add_tab(Box::<PhysicsTab<Rapier>>::default());
+
+        app.init_resource::<RapierCollisions>()
+            .add_systems(Update, record_collisions);
+    }
+}
+
+/// The latest rapier collision events, as shown in the rapier tab.
+#[derive(Debug, Default, Deref, DerefMut, Resource)]
+pub struct RapierCollisions(pub CollisionLog);
+
+fn record_collisions(
+    mut events: EventReader<CollisionEvent>,
+    mut log: ResMut<RapierCollisions>,
+    frame: Res<FrameCount>,
+) {
+    for event in events.read() {
+        let (started, a, b) = match *event {
+            CollisionEvent::Started(a, b, _) => (true, a, b),
+            CollisionEvent::Stopped(a, b, _) => (false, a, b),
+        };
+        log.push(CollisionRecord {
+            frame: frame.0,
+            started,
+            entities: (a, b),
+        });
+    }
+}
+
+struct Rapier;
+
+impl PhysicsEngine for Rapier {
+    const NAME: &'static str = "Rapier";
+    const EVENTS_HINT: Option<&'static str> =
+        Some("Only colliders with ActiveEvents::COLLISION_EVENTS report events");
+
+    fn bodies(world: &mut World) -> Vec<Body> {
+        let mut bodies = world.query::<(Entity, &RigidBody, &RapierRigidBodyHandle)>();
+        let Some(context) = world.get_resource::<RapierContext>() else {
+            return vec![];
+        };
+        bodies
+            .iter(world)
+            .filter_map(|(entity, kind, handle)| {
+                let body = context.bodies.get(handle.0)?;
+                Some(Body {
+                    entity,
+                    kind: match kind {
+                        RigidBody::Dynamic => "dynamic",
+                        RigidBody::Fixed => "fixed",
+                        RigidBody::KinematicPositionBased => "kinematic (position)",
+                        RigidBody::KinematicVelocityBased => "kinematic (velocity)",
+                    },
+                    linear: (*body.linvel()).into(),
+                    angular: (*body.angvel()).into(),
+                    sleeping: body.is_sleeping(),
+                })
+            })
+            .collect()
+    }
+
+    fn colliders(world: &mut World) -> Vec<ColliderRow> {
+        let mut colliders = world.query::<(Entity, &Collider, Has<Sensor>)>();
+        colliders
+            .iter(world)
+            .map(|(entity, collider, sensor)| {
+                let extents = collider.raw.compute_local_aabb().extents();
+                ColliderRow {
+                    entity,
+                    shape: format!("{:?}", collider.raw.shape_type()),
+                    size: Vec3::new(extents.x, extents.y, extents.z),
+                    sensor,
+                }
+            })
+            .collect()
+    }
+
+    fn contacts(world: &World) -> Vec<(Entity, Entity)> {
+        let Some(context) = world.get_resource::<RapierContext>() else {
+            return vec![];
+        };
+        context
+            .contact_pairs()
+            .filter(|pair| pair.has_any_active_contacts())
+            .map(|pair| (pair.collider1(), pair.collider2()))
+            .collect()
+    }
+
+    fn log(world: &World) -> Option<&CollisionLog> {
+        world.get_resource::<RapierCollisions>().map(|log| &log.0)
+    }
+
+    fn set_sleeping(world: &mut World, entity: Entity, sleeping: bool) {
+        let Some(handle) = world
+            .get::<RapierRigidBodyHandle>(entity)
+            .map(|handle| handle.0)
+        else {
+            return;
+        };
+        let Some(mut context) = world.get_resource_mut::<RapierContext>() else {
+            return;
+        };
+        if let Some(body) = context.bodies.get_mut(handle) {
+            match sleeping {
+                true => body.sleep(),
+                false => body.wake_up(true),
+            }
+        }
+    }
+
+    fn apply_impulse(world: &mut World, entity: Entity, impulse: Vec3) {
+        let Some(handle) = world
+            .get::<RapierRigidBodyHandle>(entity)
+            .map(|handle| handle.0)
+        else {
+            return;
+        };
+        let Some(mut context) = world.get_resource_mut::<RapierContext>() else {
+            return;
+        };
+        if let Some(body) = context.bodies.get_mut(handle) {
+            body.apply_impulse(impulse.into(), true);
+        }
+    }
+}
@@ -0,0 +1,139 @@
+//! The physics tab for bevy_xpbd_3d. Sleeping bodies are marked with a [`Sleeping`] component,
+//! so the buttons insert or remove it, and impulses go through [`ExternalImpulse`].
+
+use bevy::core::FrameCount;
+use bevy::prelude::*;
+use bevy_xpbd_3d::prelude::*;
+
+use super::{Body, ColliderRow, CollisionLog, CollisionRecord, PhysicsEngine, PhysicsTab};
+use crate::Spyglass;
+
+/// The plugin that adds the xpbd tab to the end of the [`Spyglass`] tab list, and the system that
+/// records collision events in [`XpbdCollisions`].
+pub struct XpbdTabPlugin;
+
+impl Plugin for XpbdTabPlugin {
+    fn build(&self, app: &mut App) {
+        if cfg!(feature = "disabled") {
+            return;
+        }
+
+        let mut spyglass = app.world.resource_mut::<Spyglass>();
+        spyglass.add_tab(Box::<PhysicsTab<Xpbd>>::default());
+
+        app.init_resource::<XpbdCollisions>()
+            .add_systems(Update, record_collisions);
+    }
+}
+
+/// The latest xpbd collision events, as shown in the xpbd tab.
+#[derive(Debug, Default, Deref, DerefMut, Resource)]
+pub struct XpbdCollisions(pub CollisionLog);
+
+fn record_collisions(
+    mut started: EventReader<CollisionStarted>,
+    mut ended: EventReader<CollisionEnded>,
+    mut log: ResMut<XpbdCollisions>,
+    frame: Res<FrameCount>,
+) {
+    let started = started.read().map(|event| (true, event.0, event.1));
+    let ended = ended.read().map(|event| (false, event.0, event.1));
+    for (started, a, b) in started.chain(ended) {
+        log.push(CollisionRecord {
+            frame: frame.0,
+            started,
+            entities: (a, b),
+        });
+    }
+}
+
+struct Xpbd;
+
+impl PhysicsEngine for Xpbd {
+    const NAME: &'static str = "Xpbd";
+
+    fn bodies(world: &mut World) -> Vec<Body> {
+        let mut bodies = world.query::<(
+            Entity,
+            &RigidBody,
+            Option<&LinearVelocity>,
+            Option<&AngularVelocity>,
+            Has<Sleeping>,
+        )>();
+        bodies
+            .iter(world)
+            .map(|(entity, kind, linear, angular, sleeping)| Body {
+                entity,
+                kind: match kind {
+                    RigidBody::Dynamic => "dynamic",
+                    RigidBody::Static => "static",
+                    RigidBody::Kinematic => "kinematic",
+                },
+                linear: linear.map_or(Vec3::ZERO, |linear| linear.0),
+                angular: angular.map_or(Vec3::ZERO, |angular| angular.0),
+                sleeping,
+            })
+            .collect()
+    }
+
+    fn colliders(world: &mut World) -> Vec<ColliderRow> {
+        let mut colliders = world.query::<(Entity, &Collider, Has<Sensor>)>();
+        colliders
+            .iter(world)
+            .map(|(entity, collider, sensor)| {
+                let extents = collider.shape().compute_local_aabb().extents();
+                ColliderRow {
+                    entity,
+                    shape: format!("{:?}", collider.shape().shape_type()),
+                    size: Vec3::new(extents.x, extents.y, extents.z),
+                    sensor,
+                }
+            })
+            .collect()
+    }
+
+    fn contacts(world: &World) -> Vec<(Entity, Entity)> {
+        let Some(collisions) = world.get_resource::<Collisions>() else {
+            return vec![];
+        };
+        collisions
+            .get_internal()
+            .values()
+            .filter(|contacts| contacts.during_current_frame)
+            .map(|contacts| (contacts.entity1, contacts.entity2))
+            .collect()
+    }
+
+    fn log(world: &World) -> Option<&CollisionLog> {
+        world.get_resource::<XpbdCollisions>().map(|log| &log.0)
+    }
+
+    fn set_sleeping(world: &mut World, entity: Entity, sleeping: bool) {
+        let Some(mut entity) = world.get_entity_mut(entity) else {
+            return;
+        };
+        if sleeping {
+            entity.insert(Sleeping);
+            return;
+        }
+        entity.remove::<Sleeping>();
+        if let Some(mut time) = entity.get_mut::<TimeSleeping>() {
+            time.0 = 0.0;
+        }
+    }
+
+    fn apply_impulse(world: &mut World, entity: Entity, impulse: Vec3) {
+        let Some(mut entity) = world.get_entity_mut(entity) else {
+            return;
+        };
+        // Changing the impulse wakes the body up.
+        match entity.get_mut::<ExternalImpulse>() {
+            Some(mut external) => {
+                external.apply_impulse(impulse);
+            }
+            None => {
+                entity.insert(ExternalImpulse::new(impulse));
+            }
+        }
+    }
+}
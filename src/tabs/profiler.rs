@@ -0,0 +1,185 @@
+//! The profiler tab module. Shows the time each schedule took in the last frame as a bar, and
+//! every system in a sortable table, to answer which system is eating the frame. Only available
+//! with the `profiler` feature.
+
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy::utils::get_short_name;
+
+use crate::compat::egui::{self, Color32, Ui};
+use crate::profiler::{Profiler, ProfilerPlugin};
+use crate::{Spyglass, Tab};
+
+/// The colors the schedules of the bar cycle through.
+const SCHEDULE_COLORS: [Color32; 6] = [
+    Color32::from_rgb(86, 156, 214),
+    Color32::from_rgb(220, 160, 80),
+    Color32::from_rgb(120, 190, 120),
+    Color32::from_rgb(200, 100, 120),
+    Color32::from_rgb(160, 130, 210),
+    Color32::from_rgb(100, 190, 190),
+];
+
+/// The plugin that adds the profiler tab to the end of the [`Spyglass`] tab list. Adds the
+/// [`ProfilerPlugin`] if it was not added yet.
+pub struct ProfilerTabPlugin;
+
+impl Plugin for ProfilerTabPlugin {
+    fn build(&self, app: &mut App) {
+        if cfg!(feature = "disabled") {
+            return;
+        }
+
+        if !app.world.contains_resource::<Profiler>() {
+            app.add_plugins(ProfilerPlugin);
+        }
+        let mut spyglass = app.world.resource_mut::<Spyglass>();
+        spyglass.add_tab(Box::new(ProfilerTab::default()));
+    }
+}
+
+/// The column the system table is sorted by.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum SortBy {
+    Name,
+    #[default]
+    LastFrame,
+    Average,
+}
+
+/// A row of the system table.
+struct SystemRow {
+    name: String,
+    last_frame: Duration,
+    average: Duration,
+}
+
+#[derive(Default)]
+struct ProfilerTab {
+    sort: SortBy,
+    filter: String,
+    installed: bool,
+    schedules: Vec<(String, Duration)>,
+    systems: Vec<SystemRow>,
+}
+
+impl Tab for ProfilerTab {
+    fn name(&self) -> &str {
+        "Profiler"
+    }
+
+    fn collect(&mut self, world: &mut World) {
+        let Some(profiler) = world.get_resource::<Profiler>() else {
+            return;
+        };
+        self.installed = profiler.installed();
+        self.schedules = profiler.last_frame.schedules.clone();
+        self.systems = profiler
+            .averages
+            .iter()
+            .map(|(name, &average)| SystemRow {
+                name: name.clone(),
+                last_frame: profiler
+                    .last_frame
+                    .systems
+                    .get(name)
+                    .copied()
+                    .unwrap_or_default(),
+                average,
+            })
+            .collect();
+    }
+
+    fn draw(&mut self, ui: &mut Ui, _: &mut World) {
+        if !self.installed {
+            ui.weak(
+                "Another tracing subscriber was installed, so the profiler only receives spans \
+                 if SpyglassProfilerLayer was added to it.",
+            );
+        }
+        draw_schedule_bar(ui, &self.schedules);
+        ui.separator();
+
+        egui::TextEdit::singleline(&mut self.filter)
+            .hint_text("Filter systems")
+            .show(ui);
+        match self.sort {
+            SortBy::Name => self.systems.sort_by(|a, b| a.name.cmp(&b.name)),
+            SortBy::LastFrame => self
+                .systems
+                .sort_by_key(|row| std::cmp::Reverse(row.last_frame)),
+            SortBy::Average => self
+                .systems
+                .sort_by_key(|row| std::cmp::Reverse(row.average)),
+        }
+        egui::Grid::new("profiler_systems")
+            .striped(true)
+            .show(ui, |ui| {
+                for (sort, label) in [
+                    (SortBy::Name, "system"),
+                    (SortBy::LastFrame, "last frame"),
+                    (SortBy::Average, "average"),
+                ] {
+                    ui.selectable_value(&mut self.sort, sort, label);
+                }
+                ui.end_row();
+                let filter = self.filter.to_lowercase();
+                for row in self
+                    .systems
+                    .iter()
+                    .filter(|row| row.name.to_lowercase().contains(&filter))
+                {
+                    ui.label(get_short_name(&row.name)).on_hover_text(&row.name);
+                    ui.monospace(format_ms(row.last_frame));
+                    ui.monospace(format_ms(row.average));
+                    ui.end_row();
+                }
+            });
+    }
+}
+
+fn format_ms(duration: Duration) -> String {
+    format!("{:.3} ms", duration.as_secs_f64() * 1000.0)
+}
+
+/// Draw the schedules of the last frame as a bar, each as wide as its share of the frame.
+fn draw_schedule_bar(ui: &mut Ui, schedules: &[(String, Duration)]) {
+    // `Main` runs the other schedules, so it spans the whole bar instead of being a segment.
+    let (main, segments): (Vec<_>, Vec<_>) = schedules.iter().partition(|(name, _)| name == "Main");
+    let total = main
+        .first()
+        .map(|(_, time)| *time)
+        .unwrap_or_else(|| segments.iter().map(|(_, time)| *time).sum());
+    ui.label(format!("schedules, {} in total", format_ms(total)));
+    if total.is_zero() {
+        ui.weak("no schedules were timed in the last frame");
+        return;
+    }
+
+    let width = ui.available_width();
+    let (rect, _) = ui.allocate_exact_size(egui::vec2(width, 24.0), egui::Sense::hover());
+    let painter = ui.painter_at(rect);
+    painter.rect_filled(rect, 2.0, ui.visuals().extreme_bg_color);
+    let mut x = rect.left();
+    for (i, (name, time)) in segments.iter().enumerate() {
+        let segment_width = (time.as_secs_f64() / total.as_secs_f64()) as f32 * width;
+        let segment = egui::Rect::from_min_size(
+            egui::pos2(x, rect.top()),
+            egui::vec2(segment_width, rect.height()),
+        );
+        painter.rect_filled(segment, 0.0, SCHEDULE_COLORS[i % SCHEDULE_COLORS.len()]);
+        if segment_width > 40.0 {
+            painter.text(
+                segment.center(),
+                egui::Align2::CENTER_CENTER,
+                name,
+                egui::FontId::proportional(11.0),
+                Color32::BLACK,
+            );
+        }
+        ui.interact(segment, ui.id().with(("schedule", i)), egui::Sense::hover())
+            .on_hover_text(format!("{name}: {}", format_ms(*time)));
+        x += segment_width;
+    }
+}
@@ -0,0 +1,287 @@
+//! The query tab module. Builds a query over component types interactively, like the filters of
+//! a [`Query`], and lists the matching entities live, each of which can be opened in the entities
+//! tab. Chosen fields of the matching entities can be exported to CSV, for analysis in a
+//! spreadsheet.
+
+use std::any::TypeId;
+use std::path::PathBuf;
+
+use bevy::ecs::archetype::Archetype;
+use bevy::ecs::component::{ComponentId, Tick};
+use bevy::prelude::*;
+use bevy::utils::get_short_name;
+
+use crate::compat::egui::{self, Ui};
+use crate::export::{write_export, Exports};
+use crate::formats::{entities_to_csv, CsvColumn};
+use crate::tabs::entities::selection::SelectEntity;
+use crate::widgets::component_picker;
+use crate::{FocusTab, Spyglass, Tab};
+
+/// The plugin that adds the query tab to the end of the [`Spyglass`] tab list.
+pub struct QueryTabPlugin;
+
+impl Plugin for QueryTabPlugin {
+    fn build(&self, app: &mut App) {
+        if cfg!(feature = "disabled") {
+            return;
+        }
+
+        let mut spyglass = app.world.resource_mut::<Spyglass>();
+        spyglass.add_tab(Box::new(QueryTab::default()));
+    }
+}
+
+/// A query over the components of entities, like the [`With`], [`Without`] and [`Changed`]
+/// filters of a [`Query`]. Components are referred to by type, so components no entity had yet
+/// can be filtered on.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ComponentQuery {
+    /// The components matching entities have.
+    pub with: Vec<TypeId>,
+    /// The components matching entities don't have.
+    pub without: Vec<TypeId>,
+    /// The components matching entities have, and which changed since the query last ran.
+    pub changed: Vec<TypeId>,
+}
+
+/// The ids of the components of the given types in the world. Components no entity had yet have
+/// none, and are left out.
+fn component_ids(world: &World, types: &[TypeId]) -> Vec<ComponentId> {
+    types
+        .iter()
+        .filter_map(|&type_id| world.components().get_id(type_id))
+        .collect()
+}
+
+/// The full name of a component type, from the registry or the world.
+fn type_name(world: &World, type_id: TypeId) -> String {
+    let registry = world.resource::<AppTypeRegistry>().read();
+    registry
+        .get_type_info(type_id)
+        .map(|info| info.type_path().to_string())
+        .or_else(|| {
+            let id = world.components().get_id(type_id)?;
+            Some(world.components().get_name(id)?.to_string())
+        })
+        .unwrap_or_else(|| format!("{type_id:?}"))
+}
+
+impl ComponentQuery {
+    /// The entities matching the query, sorted. `last_run` is the change tick the query last ran
+    /// at, which the [`changed`](Self::changed) components must have changed after.
+    pub fn run(&self, world: &World, last_run: Tick) -> Vec<Entity> {
+        let with = component_ids(world, &self.with);
+        let changed = component_ids(world, &self.changed);
+        let without = component_ids(world, &self.without);
+        // No entity has a component that is not in the world yet.
+        if with.len() < self.with.len() || changed.len() < self.changed.len() {
+            return vec![];
+        }
+        let matches_archetype = |archetype: &Archetype| {
+            with.iter()
+                .chain(&changed)
+                .all(|&id| archetype.contains(id))
+                && !without.iter().any(|&id| archetype.contains(id))
+        };
+
+        let this_run = world.read_change_tick();
+        let mut entities = world
+            .archetypes()
+            .iter()
+            .filter(|archetype| matches_archetype(archetype))
+            .flat_map(|archetype| archetype.entities().iter().map(|entity| entity.entity()))
+            .filter(|&entity| {
+                let entity = world.entity(entity);
+                changed.iter().all(|&id| {
+                    entity
+                        .get_change_ticks_by_id(id)
+                        .is_some_and(|ticks| ticks.is_changed(last_run, this_run))
+                })
+            })
+            .collect::<Vec<_>>();
+        entities.sort_unstable();
+        entities
+    }
+
+    /// The query as the type of a system parameter, e.g. `Query<Entity, (With<A>, Without<B>)>`.
+    pub fn to_rust(&self, world: &World) -> String {
+        let name = |id: TypeId| get_short_name(&type_name(world, id));
+        let filters = [
+            ("With", &self.with),
+            ("Without", &self.without),
+            ("Changed", &self.changed),
+        ]
+        .into_iter()
+        .flat_map(|(filter, ids)| ids.iter().map(move |&id| format!("{filter}<{}>", name(id))))
+        .collect::<Vec<_>>();
+        match filters.len() {
+            0 => "Query<Entity>".to_string(),
+            1 => format!("Query<Entity, {}>", filters[0]),
+            _ => format!("Query<Entity, ({})>", filters.join(", ")),
+        }
+    }
+}
+
+/// Export the given columns of the given entities to a CSV file at `path`, see
+/// [`entities_to_csv`]. The values are read right away, and written on a worker thread through
+/// [`Exports`].
+pub fn export_csv(world: &mut World, entities: &[Entity], columns: &[CsvColumn], path: PathBuf) {
+    let csv = entities_to_csv(world, entities, columns);
+    world.resource_mut::<Exports>().spawn(
+        format!("CSV of {} entities", entities.len()),
+        move || {
+            write_export(&path, csv.as_bytes())?;
+            Ok(path)
+        },
+    );
+}
+
+struct QueryTab {
+    query: ComponentQuery,
+    /// The change tick the query last ran at.
+    last_run: Tick,
+    /// The matching entities with their names.
+    matches: Vec<(Entity, String)>,
+    /// The fields exported to CSV.
+    columns: Vec<CsvColumn>,
+    /// The file the CSV is exported to.
+    export_path: String,
+}
+
+impl Default for QueryTab {
+    fn default() -> Self {
+        Self {
+            query: ComponentQuery::default(),
+            last_run: Tick::new(0),
+            matches: vec![],
+            columns: vec![],
+            export_path: "query.csv".to_string(),
+        }
+    }
+}
+
+impl Tab for QueryTab {
+    fn name(&self) -> &str {
+        "Query"
+    }
+
+    fn collect(&mut self, world: &mut World) {
+        self.matches = self
+            .query
+            .run(world, self.last_run)
+            .into_iter()
+            .map(|entity| match world.get::<Name>(entity) {
+                Some(name) => (entity, format!("{name} ({entity:?})")),
+                None => (entity, format!("{entity:?}")),
+            })
+            .collect();
+        self.last_run = world.read_change_tick();
+    }
+
+    fn draw(&mut self, ui: &mut Ui, world: &mut World) {
+        let query = &mut self.query;
+        let used = [&query.with, &query.without, &query.changed]
+            .into_iter()
+            .flatten()
+            .copied()
+            .collect::<Vec<_>>();
+        for (label, ids) in [
+            ("with", &mut query.with),
+            ("without", &mut query.without),
+            ("changed", &mut query.changed),
+        ] {
+            ui.horizontal_wrapped(|ui| {
+                ui.label(format!("{label}:"));
+                let mut removed = None;
+                for (i, &id) in ids.iter().enumerate() {
+                    let name = type_name(world, id);
+                    if ui
+                        .small_button(format!("{} ✖", get_short_name(&name)))
+                        .on_hover_text(name)
+                        .clicked()
+                    {
+                        removed = Some(i);
+                    }
+                }
+                if let Some(i) = removed {
+                    ids.remove(i);
+                }
+                if let Some(id) = component_picker(ui, "+", world, |id| used.contains(&id)) {
+                    ids.push(id);
+                }
+            });
+        }
+        ui.horizontal(|ui| {
+            ui.monospace(query.to_rust(world));
+            if ui.small_button("clear").clicked() {
+                *query = ComponentQuery::default();
+            }
+        });
+        ui.separator();
+        self.draw_export(ui, world);
+        ui.separator();
+
+        ui.label(format!("{} matching entities", self.matches.len()));
+        let mut open = None;
+        let row_height = ui.spacing().interact_size.y;
+        egui::ScrollArea::vertical()
+            .id_source("query_matches")
+            .auto_shrink([false, true])
+            .show_rows(ui, row_height, self.matches.len(), |ui, rows| {
+                for (entity, name) in &self.matches[rows] {
+                    if ui
+                        .button(name)
+                        .on_hover_text("Open in the entities tab")
+                        .clicked()
+                    {
+                        open = Some(*entity);
+                    }
+                }
+            });
+        if let Some(entity) = open {
+            world.send_event(SelectEntity(Some(entity)));
+            world.send_event(FocusTab("Entities".to_string()));
+        }
+    }
+}
+
+impl QueryTab {
+    /// Draw the columns of the CSV export, and export the matching entities when asked to.
+    fn draw_export(&mut self, ui: &mut Ui, world: &mut World) {
+        ui.horizontal_wrapped(|ui| {
+            ui.label("CSV columns:");
+            let mut removed = None;
+            for (i, column) in self.columns.iter_mut().enumerate() {
+                ui.label(get_short_name(&column.component))
+                    .on_hover_text(&column.component);
+                egui::TextEdit::singleline(&mut column.path)
+                    .hint_text("field, e.g. translation.y")
+                    .desired_width(120.0)
+                    .show(ui);
+                if ui.small_button("✖").clicked() {
+                    removed = Some(i);
+                }
+            }
+            if let Some(i) = removed {
+                self.columns.remove(i);
+            }
+            if let Some(id) = component_picker(ui, "+", world, |_| false) {
+                self.columns.push(CsvColumn {
+                    component: type_name(world, id),
+                    path: String::new(),
+                });
+            }
+        });
+        ui.horizontal(|ui| {
+            ui.label("file");
+            ui.text_edit_singleline(&mut self.export_path);
+            if ui.button("export CSV").clicked() {
+                let entities = self.matches.iter().map(|(entity, _)| *entity);
+                let entities = entities.collect::<Vec<_>>();
+                let path = PathBuf::from(&self.export_path);
+                export_csv(world, &entities, &self.columns, path);
+            }
+        });
+    }
+}
@@ -0,0 +1,254 @@
+//! The reflection tab module. Reports how well the component types present in the world are
+//! covered by reflection, grouped by crate, so that gaps in registration can be closed
+//! systematically.
+
+use std::any::TypeId;
+use std::collections::BTreeMap;
+
+use bevy::prelude::*;
+use bevy::reflect::{TypeInfo, TypeRegistry, VariantInfo};
+use bevy::utils::{get_short_name, HashMap, HashSet};
+
+use crate::compat::egui::{self, Ui};
+use crate::tabs::entities::{ComponentReprs, ReprEditors};
+use crate::{Spyglass, Tab};
+
+/// The plugin that adds the reflection coverage tab to the inspector. Must be added after the
+/// [`SpyglassPlugin`](crate::SpyglassPlugin), as it adds its tab to the end of the [`Spyglass`]
+/// tab list.
+pub struct ReflectionTabPlugin;
+
+impl Plugin for ReflectionTabPlugin {
+    fn build(&self, app: &mut App) {
+        if cfg!(feature = "disabled") {
+            return;
+        }
+
+        let mut spyglass = app.world.resource_mut::<Spyglass>();
+        spyglass.add_tab(Box::new(ReflectionTab::default()));
+    }
+}
+
+/// How well the inspector can handle a component type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Coverage {
+    /// Every value in the component can be edited.
+    Editable,
+    /// The component is displayed, but contains values without an editor, which are only shown
+    /// by their debug representation.
+    ReadOnly,
+    /// The component is not displayed at all, as it is not reflected, not registered, or does not
+    /// reflect [`ReflectComponent`].
+    Invisible,
+}
+
+/// The coverage of a single component type.
+#[derive(Clone, Debug)]
+pub struct ComponentCoverage {
+    /// The type name of the component.
+    pub name: String,
+    /// How well the component is covered.
+    pub coverage: Coverage,
+    /// Why the component is not editable, if it is not.
+    pub reason: Option<String>,
+    /// The number of entities with the component.
+    pub entities: usize,
+}
+
+/// A report of the [`Coverage`] of every component type present in the world.
+#[derive(Clone, Debug, Default)]
+pub struct CoverageReport {
+    /// The covered components, grouped by the crate they are defined in.
+    pub crates: BTreeMap<String, Vec<ComponentCoverage>>,
+}
+
+impl CoverageReport {
+    /// Scan the component types of every entity in the world.
+    pub fn scan(world: &World, editors: &ReprEditors, reprs: &ComponentReprs) -> Self {
+        let mut entities = HashMap::<_, usize>::default();
+        for archetype in world.archetypes().iter().filter(|a| !a.is_empty()) {
+            for comp in archetype.components() {
+                *entities.entry(comp).or_default() += archetype.len();
+            }
+        }
+
+        let registry = world.resource::<AppTypeRegistry>().read();
+        let mut report = Self::default();
+        for (comp, entities) in entities {
+            let Some(info) = world.components().get_info(comp) else {
+                continue;
+            };
+            let name = info.name().to_string();
+            let (coverage, reason) = if reprs.reprs.contains_key(&name) {
+                (Coverage::Editable, None)
+            } else {
+                component_coverage(info.type_id(), &registry, editors)
+            };
+
+            let krate = name.split("::").next().unwrap_or_default().to_string();
+            report
+                .crates
+                .entry(krate)
+                .or_default()
+                .push(ComponentCoverage {
+                    name,
+                    coverage,
+                    reason,
+                    entities,
+                });
+        }
+
+        for components in report.crates.values_mut() {
+            components.sort_by(|a, b| (a.coverage, &a.name).cmp(&(b.coverage, &b.name)));
+        }
+        report
+    }
+
+    /// Count the components of each [`Coverage`] in a crate, or in every crate if `None`.
+    pub fn count(&self, krate: Option<&str>, coverage: Coverage) -> usize {
+        self.crates
+            .iter()
+            .filter(|(name, _)| krate.is_none_or(|krate| krate == name.as_str()))
+            .flat_map(|(_, components)| components)
+            .filter(|comp| comp.coverage == coverage)
+            .count()
+    }
+}
+
+fn component_coverage(
+    type_id: Option<TypeId>,
+    registry: &TypeRegistry,
+    editors: &ReprEditors,
+) -> (Coverage, Option<String>) {
+    let Some(type_id) = type_id else {
+        return (Coverage::Invisible, Some("the component is dynamic".into()));
+    };
+    let Some(registration) = registry.get(type_id) else {
+        return (
+            Coverage::Invisible,
+            Some("the type is not registered".into()),
+        );
+    };
+    if registration.data::<ReflectComponent>().is_none() {
+        return (
+            Coverage::Invisible,
+            Some("the type does not reflect `Component`".into()),
+        );
+    }
+
+    match uneditable_value(registration.type_info(), registry, editors, &mut default()) {
+        Some(reason) => (Coverage::ReadOnly, Some(reason)),
+        None => (Coverage::Editable, None),
+    }
+}
+
+/// Find a value within a type that no editor can edit, and describe it.
+fn uneditable_value(
+    info: &TypeInfo,
+    registry: &TypeRegistry,
+    editors: &ReprEditors,
+    visited: &mut HashSet<TypeId>,
+) -> Option<String> {
+    if editors.has_custom(info) || !visited.insert(info.type_id()) {
+        return None;
+    }
+
+    let mut check = |type_id: TypeId, type_path: &str| match registry.get_type_info(type_id) {
+        Some(info) => uneditable_value(info, registry, editors, visited),
+        None => Some(format!("`{}` is not registered", get_short_name(type_path))),
+    };
+
+    match info {
+        TypeInfo::Struct(info) => info
+            .iter()
+            .find_map(|field| check(field.type_id(), field.type_path())),
+        TypeInfo::TupleStruct(info) => info
+            .iter()
+            .find_map(|field| check(field.type_id(), field.type_path())),
+        TypeInfo::Tuple(info) => info
+            .iter()
+            .find_map(|field| check(field.type_id(), field.type_path())),
+        TypeInfo::List(info) => check(info.item_type_id(), info.item_type_path_table().path()),
+        TypeInfo::Array(info) => check(info.item_type_id(), info.item_type_path_table().path()),
+        TypeInfo::Map(info) => check(info.key_type_id(), info.key_type_path_table().path())
+            .or_else(|| check(info.value_type_id(), info.value_type_path_table().path())),
+        TypeInfo::Enum(info) => info.iter().find_map(|variant| match variant {
+            VariantInfo::Struct(variant) => variant
+                .iter()
+                .find_map(|field| check(field.type_id(), field.type_path())),
+            VariantInfo::Tuple(variant) => variant
+                .iter()
+                .find_map(|field| check(field.type_id(), field.type_path())),
+            VariantInfo::Unit(_) => None,
+        }),
+        TypeInfo::Value(info) => Some(format!(
+            "`{}` has no editor",
+            get_short_name(info.type_path())
+        )),
+    }
+}
+
+#[derive(Default)]
+struct ReflectionTab {
+    report: Option<CoverageReport>,
+    hide_editable: bool,
+}
+
+impl Tab for ReflectionTab {
+    fn name(&self) -> &str {
+        "Reflection"
+    }
+
+    fn draw(&mut self, ui: &mut Ui, world: &mut World) {
+        ui.horizontal(|ui| {
+            if ui.button("scan").clicked() {
+                let editors = world.get_resource::<ReprEditors>();
+                let reprs = world.get_resource::<ComponentReprs>();
+                if let (Some(editors), Some(reprs)) = (editors, reprs) {
+                    self.report = Some(CoverageReport::scan(world, editors, reprs));
+                }
+            }
+            ui.checkbox(&mut self.hide_editable, "hide editable");
+        });
+
+        let Some(report) = &self.report else {
+            ui.weak("Scan the world to report which component types can be inspected.");
+            return;
+        };
+
+        ui.label(format!(
+            "{} editable, {} read-only, {} invisible",
+            report.count(None, Coverage::Editable),
+            report.count(None, Coverage::ReadOnly),
+            report.count(None, Coverage::Invisible),
+        ));
+        ui.separator();
+
+        for (krate, components) in report.crates.iter() {
+            egui::CollapsingHeader::new(format!(
+                "{krate} - {} editable, {} read-only, {} invisible",
+                report.count(Some(krate), Coverage::Editable),
+                report.count(Some(krate), Coverage::ReadOnly),
+                report.count(Some(krate), Coverage::Invisible),
+            ))
+            .id_source(("reflection_crate", krate))
+            .show(ui, |ui| {
+                egui::Grid::new(("reflection_components", krate))
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for comp in components.iter() {
+                            if self.hide_editable && comp.coverage == Coverage::Editable {
+                                continue;
+                            }
+                            ui.label(get_short_name(&comp.name))
+                                .on_hover_text(&comp.name);
+                            ui.label(format!("{:?}", comp.coverage));
+                            ui.weak(format!("{} entities", comp.entities));
+                            ui.weak(comp.reason.as_deref().unwrap_or_default());
+                            ui.end_row();
+                        }
+                    });
+            });
+        }
+    }
+}
@@ -0,0 +1,395 @@
+//! The remote tab module. Connects to a [`SpyglassServer`](crate::remote::SpyglassServer) in
+//! another app, or to a Bevy Remote Protocol endpoint through the [`brp`](crate::remote::brp)
+//! adapter, and inspects its entities with the same editors as the entities tab.
+
+use std::net::ToSocketAddrs;
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy::time::Real;
+use bevy::utils::get_short_name;
+
+use crate::compat::egui::{self, Ui};
+use crate::formats::{
+    deserialize_reflect, serialize_reflect, JsonFormat, SpyglassFormat, SpyglassFormats,
+};
+use crate::remote::brp::BrpConnection;
+use crate::remote::{
+    deserialize_message, serialize_message, RemoteConnection, RemoteEntity, RemoteRequest,
+    RemoteResponse,
+};
+use crate::tabs::entities::editors::EditorStates;
+use crate::tabs::entities::{Popups, ReprEditors};
+use crate::{Spyglass, SpyglassSettings, SpyglassWindow, Tab};
+
+/// The plugin that adds the remote tab to the inspector. Must be added after the
+/// [`SpyglassPlugin`](crate::SpyglassPlugin), as it adds its tab to the end of the [`Spyglass`]
+/// tab list.
+pub struct RemoteTabPlugin;
+
+impl Plugin for RemoteTabPlugin {
+    fn build(&self, app: &mut App) {
+        if cfg!(feature = "disabled") {
+            return;
+        }
+
+        let mut spyglass = app.world.resource_mut::<Spyglass>();
+        spyglass.add_tab(Box::new(RemoteTab {
+            address: "127.0.0.1:7878".to_string(),
+            brp: false,
+            search: String::new(),
+        }));
+
+        app.init_resource::<RemoteClient>()
+            .add_systems(Update, poll_remote_client.before(SpyglassWindow));
+    }
+}
+
+/// A component of the entity selected in a [`RemoteClient`].
+pub struct RemoteValue {
+    /// The type path of the component.
+    pub type_path: String,
+    /// The value of the component, if it is reflected and registered in this app.
+    pub value: Option<Box<dyn Reflect>>,
+    serialized: Option<String>,
+}
+
+/// The resource that holds the connection to a remote world and what was received from it.
+#[derive(Resource)]
+pub struct RemoteClient {
+    /// How often the selected entity is requested again.
+    pub refresh_interval: Duration,
+    /// The entities of the remote world, as of the last [`RemoteRequest::ListEntities`].
+    pub entities: Vec<RemoteEntity>,
+    /// The selected remote entity, if any.
+    pub selected: Option<Entity>,
+    /// The components of the selected entity, as of the last response.
+    pub components: Vec<RemoteValue>,
+    connection: Option<ClientConnection>,
+    last_refresh: Duration,
+}
+
+enum ClientConnection {
+    Spyglass(RemoteConnection),
+    Brp(BrpConnection),
+}
+
+impl Default for RemoteClient {
+    fn default() -> Self {
+        Self {
+            refresh_interval: Duration::from_secs(1),
+            entities: vec![],
+            selected: None,
+            components: vec![],
+            connection: None,
+            last_refresh: Duration::ZERO,
+        }
+    }
+}
+
+impl RemoteClient {
+    /// Connect to a server, replacing any existing connection.
+    pub fn connect(&mut self, address: impl ToSocketAddrs) -> std::io::Result<()> {
+        self.disconnect();
+        self.connection = Some(ClientConnection::Spyglass(RemoteConnection::connect(
+            address,
+        )?));
+        Ok(())
+    }
+
+    /// Connect to a Bevy Remote Protocol endpoint, replacing any existing connection.
+    pub fn connect_brp(&mut self, address: impl Into<String>) -> std::io::Result<()> {
+        self.disconnect();
+        self.connection = Some(ClientConnection::Brp(BrpConnection::connect(address)?));
+        Ok(())
+    }
+
+    /// The format component values are exchanged in. BRP always uses JSON.
+    pub fn value_format<'a>(&self, formats: &'a SpyglassFormats) -> &'a dyn SpyglassFormat {
+        match self.connection {
+            Some(ClientConnection::Brp(_)) => &JsonFormat,
+            _ => formats.current(),
+        }
+    }
+
+    /// Close the connection and forget everything received.
+    pub fn disconnect(&mut self) {
+        self.connection = None;
+        self.entities.clear();
+        self.select(None);
+    }
+
+    /// Whether the client is connected.
+    pub fn is_connected(&self) -> bool {
+        self.connection.is_some()
+    }
+
+    /// Select a remote entity. Its components arrive with the next response.
+    pub fn select(&mut self, entity: Option<Entity>) {
+        self.selected = entity;
+        self.components.clear();
+    }
+
+    /// Send a request to the server. The connection is closed if this fails.
+    pub fn send(
+        &mut self,
+        formats: &SpyglassFormats,
+        request: &RemoteRequest,
+    ) -> Result<(), String> {
+        match self.connection.as_mut() {
+            Some(ClientConnection::Spyglass(connection)) => {
+                let bytes =
+                    serialize_message(formats.current(), request).map_err(|e| e.to_string())?;
+                connection.send(&bytes).map_err(|err| {
+                    self.disconnect();
+                    err.to_string()
+                })
+            }
+            Some(ClientConnection::Brp(connection)) => {
+                connection.send(request.clone());
+                Ok(())
+            }
+            None => Err("not connected".to_string()),
+        }
+    }
+
+    fn receive(&mut self, formats: &SpyglassFormats) -> std::io::Result<Vec<RemoteResponse>> {
+        match self.connection.as_mut() {
+            Some(ClientConnection::Spyglass(connection)) => Ok(connection
+                .receive()?
+                .into_iter()
+                .map(|message| {
+                    deserialize_message(formats.current(), &message).unwrap_or_else(|err| {
+                        RemoteResponse::Error(format!("invalid remote response: {err}"))
+                    })
+                })
+                .collect()),
+            Some(ClientConnection::Brp(connection)) => Ok(connection.receive()),
+            None => Ok(vec![]),
+        }
+    }
+}
+
+fn poll_remote_client(
+    mut client: ResMut<RemoteClient>,
+    formats: Res<SpyglassFormats>,
+    registry: Res<AppTypeRegistry>,
+    time: Res<Time<Real>>,
+    mut popups: ResMut<Popups>,
+) {
+    let responses = match client.receive(&formats) {
+        Ok(responses) => responses,
+        Err(err) => {
+            client.disconnect();
+            popups.error(format!("disconnected from the remote world: {err}"));
+            return;
+        }
+    };
+
+    let format = client.value_format(&formats);
+    let registry = registry.read();
+    for response in responses {
+        match response {
+            RemoteResponse::Entities(entities) => client.entities = entities,
+            RemoteResponse::Entity { entity, components } => {
+                if client.selected != Some(entity) {
+                    continue;
+                }
+                client.components = components
+                    .into_iter()
+                    .map(|comp| {
+                        let value = comp.value.and_then(|value| {
+                            deserialize_reflect(format, value.as_bytes(), &registry).ok()
+                        });
+                        let serialized = value.as_ref().and_then(|value| {
+                            let bytes = serialize_reflect(format, value.as_ref(), &registry);
+                            String::from_utf8(bytes.ok()?).ok()
+                        });
+                        RemoteValue {
+                            type_path: comp.type_path,
+                            value,
+                            serialized,
+                        }
+                    })
+                    .collect();
+            }
+            RemoteResponse::Error(err) => popups.error(err),
+        }
+    }
+
+    let now = time.elapsed();
+    if let Some(entity) = client.selected {
+        if now - client.last_refresh >= client.refresh_interval {
+            client.last_refresh = now;
+            if let Err(err) = client.send(&formats, &RemoteRequest::GetEntity(entity)) {
+                popups.error(format!("failed to refresh the remote entity: {err}"));
+            }
+        }
+    }
+}
+
+struct RemoteTab {
+    address: String,
+    brp: bool,
+    search: String,
+}
+
+impl Tab for RemoteTab {
+    fn name(&self) -> &str {
+        "Remote"
+    }
+
+    fn draw(&mut self, ui: &mut Ui, world: &mut World) {
+        let mut client = world.remove_resource::<RemoteClient>().unwrap();
+        let mut requests = vec![];
+
+        if !client.is_connected() {
+            ui.horizontal(|ui| {
+                ui.selectable_value(&mut self.brp, false, "spyglass");
+                ui.selectable_value(&mut self.brp, true, "BRP");
+                egui::TextEdit::singleline(&mut self.address)
+                    .hint_text("Server address")
+                    .show(ui);
+                if ui.button("connect").clicked() {
+                    let connected = match self.brp {
+                        true => client.connect_brp(self.address.as_str()),
+                        false => client.connect(self.address.as_str()),
+                    };
+                    match connected {
+                        Ok(()) => requests.push(RemoteRequest::ListEntities),
+                        Err(err) => world
+                            .resource_mut::<Popups>()
+                            .error(format!("failed to connect to `{}`: {err}", self.address)),
+                    }
+                }
+            });
+        } else {
+            ui.horizontal(|ui| {
+                ui.label(format!("connected to {}", self.address));
+                if ui.button("disconnect").clicked() {
+                    client.disconnect();
+                }
+            });
+            ui.separator();
+
+            match client.selected {
+                Some(entity) => draw_remote_entity(ui, world, &mut client, entity, &mut requests),
+                None => self.draw_entity_list(ui, &mut client, &mut requests),
+            }
+        }
+
+        let formats = world.resource::<SpyglassFormats>();
+        let errors = requests
+            .iter()
+            .filter_map(|request| client.send(formats, request).err())
+            .collect::<Vec<_>>();
+        for err in errors {
+            world
+                .resource_mut::<Popups>()
+                .error(format!("failed to send a remote request: {err}"));
+        }
+
+        world.insert_resource(client);
+    }
+}
+
+impl RemoteTab {
+    fn draw_entity_list(
+        &mut self,
+        ui: &mut Ui,
+        client: &mut RemoteClient,
+        requests: &mut Vec<RemoteRequest>,
+    ) {
+        ui.horizontal(|ui| {
+            egui::TextEdit::singleline(&mut self.search)
+                .hint_text("Search for an entity")
+                .show(ui);
+            if ui.button("refresh").clicked() {
+                requests.push(RemoteRequest::ListEntities);
+            }
+        });
+
+        let mut selected = None;
+        for remote in client.entities.iter() {
+            let name = remote
+                .name
+                .clone()
+                .unwrap_or_else(|| format!("{:?}", remote.entity));
+            if name.starts_with(&self.search) && ui.button(&name).clicked() {
+                selected = Some(remote.entity);
+            }
+        }
+        if let Some(entity) = selected {
+            client.select(Some(entity));
+            requests.push(RemoteRequest::GetEntity(entity));
+        }
+    }
+}
+
+fn draw_remote_entity(
+    ui: &mut Ui,
+    world: &mut World,
+    client: &mut RemoteClient,
+    entity: Entity,
+    requests: &mut Vec<RemoteRequest>,
+) {
+    if ui.button("back").clicked() {
+        client.select(None);
+        return;
+    }
+
+    let name = client
+        .entities
+        .iter()
+        .find(|remote| remote.entity == entity)
+        .and_then(|remote| remote.name.clone())
+        .unwrap_or_else(|| format!("{entity:?}"));
+    let editors = world.remove_resource::<ReprEditors>().unwrap();
+    let mut states = world.remove_resource::<EditorStates>().unwrap();
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    states.begin_frame(SpyglassSettings::is_read_only(world));
+
+    ui.push_id("remote_entity", |ui| {
+        ui.group(|ui| {
+            ui.vertical_centered(|ui| ui.heading(&name));
+
+            let brp = matches!(client.connection, Some(ClientConnection::Brp(_)));
+            for comp in client.components.iter_mut() {
+                let Some(value) = comp.value.as_mut() else {
+                    ui.label(&comp.type_path).on_hover_text(
+                        "This component is not reflected in the remote world, or its type is not \
+                        registered in this app.",
+                    );
+                    continue;
+                };
+
+                let editor = editors.get(value.as_ref());
+                states.push_path(get_short_name(&comp.type_path));
+                editor(ui, value.as_mut(), world, &editors, &mut states);
+                states.pop_path();
+
+                let format = match brp {
+                    true => &JsonFormat,
+                    false => world.resource::<SpyglassFormats>().current(),
+                };
+                let serialized = serialize_reflect(format, value.as_ref(), &registry.read())
+                    .ok()
+                    .and_then(|bytes| String::from_utf8(bytes).ok());
+                if let Some(serialized) = serialized {
+                    if comp.serialized.as_ref() != Some(&serialized) {
+                        comp.serialized = Some(serialized.clone());
+                        requests.push(RemoteRequest::SetComponent {
+                            entity,
+                            component: comp.type_path.clone(),
+                            value: serialized,
+                        });
+                    }
+                }
+            }
+        });
+    });
+
+    states.end_frame();
+    world.insert_resource(states);
+    world.insert_resource(editors);
+}
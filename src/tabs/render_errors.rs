@@ -0,0 +1,162 @@
+//! The render errors tab module. Lists the render and compute pipelines that failed to compile,
+//! with the error and the paths of their shaders, so WGSL errors don't have to be found in the
+//! console. Errors are read from the pipeline cache of the render app, and disappear once the
+//! pipeline compiles, e.g. after the shader was fixed and hot reloaded.
+
+use std::sync::{Arc, Mutex};
+
+use bevy::prelude::*;
+use bevy::render::render_resource::{
+    CachedPipelineState, PipelineCache, PipelineCacheError, PipelineDescriptor,
+};
+use bevy::render::{Render, RenderApp, RenderSet};
+
+use crate::compat::egui::{self, Ui};
+use crate::{Spyglass, Tab};
+
+/// The plugin that adds the render errors tab to the end of the [`Spyglass`] tab list. Must be
+/// added after the `RenderPlugin`, as it adds a system to the render app.
+pub struct RenderErrorsTabPlugin;
+
+impl Plugin for RenderErrorsTabPlugin {
+    fn build(&self, app: &mut App) {
+        if cfg!(feature = "disabled") {
+            return;
+        }
+
+        let mut spyglass = app.world.resource_mut::<Spyglass>();
+        spyglass.add_tab(Box::new(RenderErrorsTab));
+
+        let errors = RenderErrors::default();
+        app.insert_resource(errors.clone());
+        if let Ok(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app
+                .insert_resource(errors)
+                .add_systems(Render, collect_render_errors.in_set(RenderSet::Cleanup));
+        }
+    }
+}
+
+/// A pipeline that failed to compile.
+#[derive(Clone, Debug)]
+pub struct RenderError {
+    /// The label of the pipeline, if it has one.
+    pub pipeline: Option<String>,
+    /// The shaders of the pipeline.
+    pub shaders: Vec<AssetId<Shader>>,
+    /// The error, including the location in the shader source for shader errors.
+    pub message: String,
+}
+
+impl RenderError {
+    /// The paths of the shaders of the pipeline, or their ids if they were not loaded from files.
+    pub fn shader_paths(&self, world: &World) -> Vec<String> {
+        let shaders = world.get_resource::<Assets<Shader>>();
+        self.shaders
+            .iter()
+            .map(|&id| {
+                shaders
+                    .and_then(|shaders| shaders.get(id))
+                    .map(|shader| shader.path.clone())
+                    .filter(|path| !path.is_empty())
+                    .unwrap_or_else(|| format!("{id:?}"))
+            })
+            .collect()
+    }
+}
+
+/// The resource that contains the pipelines that currently fail to compile. It is shared with
+/// the render app, which updates it every frame.
+#[derive(Clone, Default, Resource)]
+pub struct RenderErrors(Arc<Mutex<Vec<RenderError>>>);
+
+impl RenderErrors {
+    /// The pipelines that failed to compile, as of the last rendered frame.
+    pub fn errors(&self) -> Vec<RenderError> {
+        self.0.lock().unwrap().clone()
+    }
+}
+
+fn collect_render_errors(cache: Res<PipelineCache>, errors: Res<RenderErrors>) {
+    let collected = cache
+        .pipelines()
+        .filter_map(|pipeline| {
+            let CachedPipelineState::Err(err) = &pipeline.state else {
+                return None;
+            };
+            // These errors only mean the pipeline is still waiting for its shaders.
+            if matches!(
+                err,
+                PipelineCacheError::ShaderNotLoaded(_)
+                    | PipelineCacheError::ShaderImportNotYetAvailable
+            ) {
+                return None;
+            }
+            let (label, shaders) = match &pipeline.descriptor {
+                PipelineDescriptor::RenderPipelineDescriptor(descriptor) => (
+                    &descriptor.label,
+                    std::iter::once(descriptor.vertex.shader.id())
+                        .chain(descriptor.fragment.as_ref().map(|f| f.shader.id()))
+                        .collect(),
+                ),
+                PipelineDescriptor::ComputePipelineDescriptor(descriptor) => {
+                    (&descriptor.label, vec![descriptor.shader.id()])
+                }
+            };
+            Some(RenderError {
+                pipeline: label.as_ref().map(ToString::to_string),
+                shaders,
+                message: err.to_string(),
+            })
+        })
+        .collect();
+    *errors.0.lock().unwrap() = collected;
+}
+
+struct RenderErrorsTab;
+
+impl Tab for RenderErrorsTab {
+    fn name(&self) -> &str {
+        "Render Errors"
+    }
+
+    fn badge(&self, world: &World) -> Option<String> {
+        let count = world
+            .get_resource::<RenderErrors>()?
+            .0
+            .lock()
+            .unwrap()
+            .len();
+        (count > 0).then(|| count.to_string())
+    }
+
+    fn draw(&mut self, ui: &mut Ui, world: &mut World) {
+        let Some(errors) = world
+            .get_resource::<RenderErrors>()
+            .map(RenderErrors::errors)
+        else {
+            return;
+        };
+        if errors.is_empty() {
+            ui.weak("all pipelines compiled");
+        }
+        for (i, error) in errors.iter().enumerate() {
+            let pipeline = error.pipeline.as_deref().unwrap_or("unlabelled pipeline");
+            egui::CollapsingHeader::new(pipeline)
+                .id_source(("render_error", i))
+                .default_open(true)
+                .show(ui, |ui| {
+                    for path in error.shader_paths(world) {
+                        ui.horizontal(|ui| {
+                            ui.label("shader");
+                            ui.monospace(path);
+                        });
+                    }
+                    ui.colored_label(
+                        ui.visuals().error_fg_color,
+                        egui::RichText::new(&error.message).monospace(),
+                    );
+                });
+        }
+    }
+}
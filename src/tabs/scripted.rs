@@ -0,0 +1,310 @@
+//! The scripted tabs module, enabled by the `rhai` feature. Tabs can be written in
+//! [rhai](https://rhai.rs) and put in the `spyglass` folder of the [`AssetFolder`]. They are loaded
+//! at startup and can be reloaded at runtime, so designers can extend the inspector without
+//! recompiling.
+//!
+//! A script runs every frame the tab is drawn, and draws it in immediate mode. It has the same
+//! constrained access to the world as the [console](crate::tabs::console), whose commands it runs,
+//! including those the app registers:
+//!
+//! ```rhai
+//! const NAME = "Player tuning";
+//!
+//! label("Movement");
+//! for player in query("Player") {
+//!     label(`x: ${get(player, "Transform.translation.x")}`);
+//!     if button("Reset position") {
+//!         set(player, "Transform.translation", "(x: 0, y: 0, z: 0)");
+//!     }
+//! }
+//! separator();
+//! label(`score: ${get("Res<Score>.points")}`);
+//! ```
+//!
+//! - `const NAME` sets the name of the tab, which defaults to the file name.
+//! - `label(text)` displays text, and `separator()` a separator.
+//! - `button(text)` displays a button, and returns whether it was clicked.
+//! - `query(component)` returns the ids of the entities that have a component, by full or short
+//!   type path.
+//! - `get(entity, "Component.path")` and `get("Res<Resource>.path")` return a value. Numbers,
+//!   booleans and strings are returned as such, and other values as their debug text.
+//! - `set(entity, "Component.path", value)` and `set("Res<Resource>.path", value)` set a value.
+//!   Strings are parsed as RON.
+//! - `command(line)` runs any console command, and returns its output.
+//!
+//! Entities are referred to by name or id, as in the console. Errors stop the script, and are
+//! displayed in the tab.
+
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use bevy::prelude::*;
+use rhai::{Array, Dynamic, Engine, EvalAltResult, AST};
+
+use crate::compat::egui::Ui;
+use crate::tabs::console::{entities_with, ConsoleCommands, ConsoleTabPlugin};
+use crate::widgets::AssetFolder;
+use crate::{Spyglass, Tab};
+
+/// The extension of scripted tab files.
+pub const SCRIPT_EXTENSION: &str = "rhai";
+
+/// The most operations a script may run per frame, so a script stuck in a loop does not freeze
+/// the app.
+const MAX_OPERATIONS: u64 = 1_000_000;
+
+/// The plugin that loads the scripted tabs in the `spyglass` folder of the [`AssetFolder`], and
+/// adds them to the end of the [`Spyglass`] tab list. Adds the [`ConsoleTabPlugin`] if it was not
+/// added yet, as scripts run console commands.
+pub struct ScriptedTabsPlugin;
+
+impl Plugin for ScriptedTabsPlugin {
+    fn build(&self, app: &mut App) {
+        if cfg!(feature = "disabled") {
+            return;
+        }
+
+        if !app.world.contains_resource::<ConsoleCommands>() {
+            app.add_plugins(ConsoleTabPlugin);
+        }
+        let folder = app.world.resource::<AssetFolder>().0.join("spyglass");
+        let mut paths = std::fs::read_dir(folder)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == SCRIPT_EXTENSION))
+            .collect::<Vec<_>>();
+        paths.sort();
+
+        let mut spyglass = app.world.resource_mut::<Spyglass>();
+        for path in paths {
+            spyglass.add_tab(Box::new(ScriptedTab::load(path)));
+        }
+    }
+}
+
+/// A widget a script drew.
+enum Widget {
+    Label(String),
+    Separator,
+    Button(String),
+}
+
+/// What the functions of a script share while it runs.
+#[derive(Default)]
+struct ScriptState {
+    /// The world, swapped in while the script runs, and empty otherwise.
+    world: World,
+    widgets: Vec<Widget>,
+    /// The index of the button that was clicked in the last frame, among the widgets.
+    clicked: Option<usize>,
+}
+
+type Shared = Arc<Mutex<ScriptState>>;
+
+/// A tab defined by a rhai script. Usually loaded by the [`ScriptedTabsPlugin`], but can also be
+/// added to the [`Spyglass`] tab list directly.
+pub struct ScriptedTab {
+    name: String,
+    path: Option<PathBuf>,
+    engine: Engine,
+    ast: Result<AST, String>,
+    state: Shared,
+    /// The error of the last run, if it failed.
+    error: Option<String>,
+}
+
+impl ScriptedTab {
+    /// Load a tab from a script file. If it can't be read or compiled, the tab shows why.
+    pub fn load(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        let name = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let mut tab = match read(&path) {
+            Ok(source) => Self::parse(&name, &source),
+            Err(err) => {
+                let mut tab = Self::parse(&name, "");
+                tab.ast = Err(err);
+                tab
+            }
+        };
+        tab.path = Some(path);
+        tab
+    }
+
+    /// Compile a tab from the source of a script. `name` is used unless the script names the tab.
+    pub fn parse(name: &str, source: &str) -> Self {
+        let state = Shared::default();
+        let engine = engine(&state);
+        let ast = engine.compile(source).map_err(|err| err.to_string());
+        let name = ast
+            .as_ref()
+            .ok()
+            .and_then(|ast| {
+                ast.iter_literal_variables(true, false)
+                    .find(|(variable, ..)| *variable == "NAME")
+                    .map(|(_, _, value)| value.to_string())
+            })
+            .unwrap_or_else(|| name.to_string());
+        Self {
+            name,
+            path: None,
+            engine,
+            ast,
+            state,
+            error: None,
+        }
+    }
+
+    /// Whether the script compiled.
+    pub fn is_valid(&self) -> bool {
+        self.ast.is_ok()
+    }
+
+    /// Run the script against the world, and return the widgets it drew.
+    fn run(&mut self, world: &mut World) -> Vec<Widget> {
+        let Ok(ast) = &self.ast else {
+            return vec![];
+        };
+        std::mem::swap(world, &mut self.state.lock().unwrap().world);
+        let result = self.engine.run_ast(ast);
+        let mut state = self.state.lock().unwrap();
+        std::mem::swap(world, &mut state.world);
+        state.clicked = None;
+        self.error = result.err().map(|err| err.to_string());
+        std::mem::take(&mut state.widgets)
+    }
+}
+
+fn read(path: &Path) -> Result<String, String> {
+    std::fs::read_to_string(path)
+        .map_err(|err| format!("failed to read `{}`: {err}", path.display()))
+}
+
+/// Create the engine of a script, with the functions it can call.
+fn engine(state: &Shared) -> Engine {
+    let mut engine = Engine::new();
+    engine.set_max_operations(MAX_OPERATIONS);
+
+    let shared = state.clone();
+    engine.register_fn("label", move |text: &str| {
+        let widget = Widget::Label(text.to_string());
+        shared.lock().unwrap().widgets.push(widget);
+    });
+    let shared = state.clone();
+    engine.register_fn("separator", move || {
+        shared.lock().unwrap().widgets.push(Widget::Separator);
+    });
+    let shared = state.clone();
+    engine.register_fn("button", move |text: &str| {
+        let mut state = shared.lock().unwrap();
+        state.widgets.push(Widget::Button(text.to_string()));
+        state.clicked == Some(state.widgets.len() - 1)
+    });
+
+    let shared = state.clone();
+    engine.register_fn("query", move |component: &str| {
+        let state = shared.lock().unwrap();
+        let entities = entities_with(&state.world, component).map_err(runtime_error)?;
+        Ok::<_, Box<EvalAltResult>>(
+            entities
+                .into_iter()
+                .map(|entity| Dynamic::from(format!("{entity:?}")))
+                .collect::<Array>(),
+        )
+    });
+    let shared = state.clone();
+    engine.register_fn("get", move |entity: &str, path: &str| {
+        execute(&shared, &format!("get {} {path}", quote(entity))).map(value)
+    });
+    let shared = state.clone();
+    engine.register_fn("get", move |path: &str| {
+        execute(&shared, &format!("get {path}")).map(value)
+    });
+    let shared = state.clone();
+    engine.register_fn("set", move |entity: &str, path: &str, value: Dynamic| {
+        execute(&shared, &format!("set {} {path} {value}", quote(entity))).map(|_| ())
+    });
+    let shared = state.clone();
+    engine.register_fn("set", move |path: &str, value: Dynamic| {
+        execute(&shared, &format!("set {path} {value}")).map(|_| ())
+    });
+    let shared = state.clone();
+    engine.register_fn("command", move |line: &str| execute(&shared, line));
+    engine
+}
+
+fn runtime_error(err: String) -> Box<EvalAltResult> {
+    err.into()
+}
+
+/// Run a console command against the world of a running script.
+fn execute(state: &Shared, line: &str) -> Result<String, Box<EvalAltResult>> {
+    let mut state = state.lock().unwrap();
+    ConsoleCommands::execute(&mut state.world, line).map_err(runtime_error)
+}
+
+/// Quote an entity name, so names with spaces stay one argument of a console command.
+fn quote(entity: &str) -> String {
+    format!("\"{entity}\"")
+}
+
+/// Convert the output of `get` to a rhai value: numbers, booleans and strings as such, and other
+/// values as their debug text.
+fn value(output: String) -> Dynamic {
+    if let Ok(int) = output.parse::<rhai::INT>() {
+        return int.into();
+    }
+    if let Ok(float) = output.parse::<rhai::FLOAT>() {
+        return float.into();
+    }
+    if let Ok(bool) = output.parse::<bool>() {
+        return bool.into();
+    }
+    match output.strip_prefix('"').and_then(|s| s.strip_suffix('"')) {
+        Some(string) => string.to_string().into(),
+        None => output.into(),
+    }
+}
+
+impl Tab for ScriptedTab {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn draw(&mut self, ui: &mut Ui, world: &mut World) {
+        if let Some(path) = &self.path {
+            if ui.small_button("reload").clicked() {
+                *self = Self::load(path.clone());
+            }
+        }
+        if let Err(err) = &self.ast {
+            ui.colored_label(ui.visuals().error_fg_color, err);
+            return;
+        }
+
+        let mut clicked = None;
+        for (i, widget) in self.run(world).into_iter().enumerate() {
+            match widget {
+                Widget::Label(text) => {
+                    ui.label(text);
+                }
+                Widget::Separator => {
+                    ui.separator();
+                }
+                Widget::Button(text) => {
+                    if ui.button(text).clicked() {
+                        clicked = Some(i);
+                    }
+                }
+            }
+        }
+        if let Some(err) = &self.error {
+            ui.colored_label(ui.visuals().error_fg_color, err);
+        }
+        self.state.lock().unwrap().clicked = clicked;
+    }
+}
@@ -0,0 +1,76 @@
+//! The settings tab module. Edits the [`SpyglassSettings`] with the reflection editors the
+//! inspector uses for components, so every setting is configured in one place, and settings
+//! added later show up without changes to the tab.
+
+use bevy::prelude::*;
+
+use crate::compat::egui::Ui;
+use crate::tabs::entities::editors::EditorStates;
+use crate::tabs::entities::groups::EntityGroupBy;
+use crate::tabs::entities::{
+    ApplyMode, ComponentRefresh, EntitySort, NotificationStyle, Popups, ReprEditors,
+};
+use crate::{InspectorLayout, Spyglass, SpyglassSettings, SpyglassTheme, Tab};
+
+/// The plugin that adds the settings tab to the end of the [`Spyglass`] tab list, and registers
+/// the settings types, which the editors need to create new values. Added by the
+/// [`SpyglassPlugin`](crate::SpyglassPlugin).
+pub struct SettingsTabPlugin;
+
+impl Plugin for SettingsTabPlugin {
+    fn build(&self, app: &mut App) {
+        if cfg!(feature = "disabled") {
+            return;
+        }
+
+        let mut spyglass = app.world.resource_mut::<Spyglass>();
+        spyglass.add_tab(Box::new(SettingsTab));
+
+        app.init_resource::<Popups>()
+            .register_type::<SpyglassSettings>()
+            .register_type::<ComponentRefresh>()
+            .register_type::<EntitySort>()
+            .register_type::<EntityGroupBy>()
+            .register_type::<NotificationStyle>()
+            .register_type::<ApplyMode>()
+            .register_type::<InspectorLayout>()
+            .register_type::<SpyglassTheme>()
+            .register_type::<Vec<String>>()
+            .register_type::<(KeyCode, String)>()
+            .register_type::<Vec<(KeyCode, String)>>()
+            .register_type::<bevy::utils::HashMap<String, ComponentRefresh>>();
+    }
+}
+
+struct SettingsTab;
+
+impl Tab for SettingsTab {
+    fn name(&self) -> &str {
+        "Settings"
+    }
+
+    fn draw(&mut self, ui: &mut Ui, world: &mut World) {
+        let Some(mut settings) = world.remove_resource::<SpyglassSettings>() else {
+            ui.weak("the settings resource is missing");
+            return;
+        };
+        if ui.button("reset to defaults").clicked() {
+            settings = SpyglassSettings::default();
+        }
+        ui.separator();
+
+        let editors = world.remove_resource::<ReprEditors>().unwrap();
+        let mut states = world.remove_resource::<EditorStates>().unwrap();
+        states.begin_frame(false);
+        ui.push_id("spyglass_settings", |ui| {
+            states.push_path("SpyglassSettings");
+            let editor = editors.get(&settings);
+            editor(ui, &mut settings, world, &editors, &mut states);
+            states.pop_path();
+        });
+        states.end_frame();
+        world.insert_resource(states);
+        world.insert_resource(editors);
+        world.insert_resource(settings);
+    }
+}
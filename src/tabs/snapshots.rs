@@ -0,0 +1,334 @@
+//! The snapshots tab module. Captures the reflected state of the whole world into named
+//! snapshots which can be restored later, effectively acting as save-states for debugging.
+//! Snapshots can be exported to and imported from files in any of the [`SpyglassFormats`].
+
+use std::path::Path;
+
+use bevy::prelude::*;
+use bevy::scene::{DynamicEntity, DynamicScene, DynamicSceneBuilder, SceneSpawnError};
+use bevy::time::{Fixed, Real, Virtual};
+use bevy::utils::{HashMap, HashSet};
+
+use crate::compat::egui::{self, Ui};
+use crate::export::{write_export, Exports};
+use crate::formats::{deserialize_scene, serialize_scene, SpyglassFormats};
+use crate::tabs::entities::Popups;
+use crate::widgets::{asset_path_picker, AssetFolder};
+use crate::{Spyglass, Tab};
+
+/// The plugin that adds the snapshots tab to the inspector. Must be added after the
+/// [`SpyglassPlugin`](crate::SpyglassPlugin), as it adds its tab to the end of the [`Spyglass`]
+/// tab list.
+pub struct SnapshotPlugin;
+
+impl Plugin for SnapshotPlugin {
+    fn build(&self, app: &mut App) {
+        if cfg!(feature = "disabled") {
+            return;
+        }
+
+        let mut spyglass = app.world.resource_mut::<Spyglass>();
+        spyglass.add_tab(Box::new(SnapshotsTab {
+            name: String::new(),
+            directory: "snapshots".to_string(),
+            import_path: String::new(),
+        }));
+
+        app.init_resource::<Snapshots>()
+            .init_resource::<SpyglassFormats>()
+            .init_resource::<AssetFolder>()
+            .init_resource::<Popups>()
+            .add_systems(PreUpdate, restore_pending_snapshot);
+    }
+}
+
+/// A named capture of every entity's reflected components and every reflected resource.
+pub struct Snapshot {
+    /// The name of the snapshot, as displayed in the inspector.
+    pub name: String,
+    /// The captured state of the world.
+    pub scene: DynamicScene,
+}
+
+impl Snapshot {
+    /// Capture the state of the world. Components and resources that do not reflect
+    /// [`ReflectComponent`] or [`ReflectResource`] are not captured. Time resources are skipped,
+    /// so that restoring a snapshot does not cause a jump in time.
+    pub fn capture(world: &World, name: impl Into<String>) -> Self {
+        let scene = DynamicSceneBuilder::from_world(world)
+            .deny_resource::<Time>()
+            .deny_resource::<Time<Real>>()
+            .deny_resource::<Time<Virtual>>()
+            .deny_resource::<Time<Fixed>>()
+            .extract_entities(world.iter_entities().map(|entity| entity.id()))
+            .extract_resources()
+            .build();
+
+        Self {
+            name: name.into(),
+            scene,
+        }
+    }
+
+    /// Restore the world to the captured state. Entities spawned since the capture are despawned,
+    /// despawned entities are respawned, and reflected components added since the capture are
+    /// removed. Respawned entities receive new ids, which are remapped in components that reflect
+    /// `MapEntities`.
+    pub fn restore(&self, world: &mut World) -> Result<(), SceneSpawnError> {
+        let registry = world.resource::<AppTypeRegistry>().clone();
+
+        let captured = self
+            .scene
+            .entities
+            .iter()
+            .map(|entity| entity.entity)
+            .collect::<HashSet<_>>();
+        let spawned = world
+            .iter_entities()
+            .map(|entity| entity.id())
+            .filter(|entity| !captured.contains(entity))
+            .collect::<Vec<_>>();
+        for entity in spawned {
+            world.despawn(entity);
+        }
+
+        let mut entity_map = HashMap::default();
+        for entity in self.scene.entities.iter() {
+            if world.get_entity(entity.entity).is_none() {
+                continue;
+            }
+            entity_map.insert(entity.entity, entity.entity);
+
+            let kept = entity
+                .components
+                .iter()
+                .filter_map(|comp| comp.get_represented_type_info())
+                .map(|info| info.type_id())
+                .collect::<HashSet<_>>();
+            let added = {
+                let registry = registry.read();
+                let entity = world.entity(entity.entity);
+                entity
+                    .archetype()
+                    .components()
+                    .filter_map(|comp| world.components().get_info(comp)?.type_id())
+                    .filter(|id| !kept.contains(id))
+                    .filter_map(|id| registry.get(id)?.data::<ReflectComponent>().cloned())
+                    .collect::<Vec<_>>()
+            };
+            for refl in added {
+                refl.remove(&mut world.entity_mut(entity.entity));
+            }
+        }
+
+        self.scene
+            .write_to_world_with(world, &mut entity_map, &registry)
+    }
+}
+
+/// The resource that stores captured [`Snapshot`]s.
+#[derive(Default, Resource)]
+pub struct Snapshots {
+    /// The list of captured snapshots, in order of capture.
+    pub snapshots: Vec<Snapshot>,
+    pending_restore: Option<usize>,
+}
+
+impl Snapshots {
+    /// Request that the snapshot at `index` is restored at the start of the next frame. This is
+    /// deferred so that the restored state is not overwritten by editors applying their values.
+    pub fn restore(&mut self, index: usize) {
+        self.pending_restore = Some(index);
+    }
+}
+
+struct SnapshotsTab {
+    name: String,
+    directory: String,
+    import_path: String,
+}
+
+impl Tab for SnapshotsTab {
+    fn name(&self) -> &str {
+        "Snapshots"
+    }
+
+    fn draw(&mut self, ui: &mut Ui, world: &mut World) {
+        let mut snapshots = world.remove_resource::<Snapshots>().unwrap();
+
+        ui.horizontal(|ui| {
+            egui::TextEdit::singleline(&mut self.name)
+                .hint_text("Snapshot name")
+                .show(ui);
+            if ui.button("capture").clicked() {
+                let name = match self.name.is_empty() {
+                    true => format!("snapshot {}", snapshots.snapshots.len()),
+                    false => std::mem::take(&mut self.name),
+                };
+                snapshots.snapshots.push(Snapshot::capture(world, name));
+            }
+        });
+
+        ui.horizontal(|ui| {
+            ui.label("export to");
+            ui.text_edit_singleline(&mut self.directory);
+            world.resource_mut::<SpyglassFormats>().selector(ui);
+        });
+
+        ui.horizontal(|ui| {
+            egui::TextEdit::singleline(&mut self.import_path)
+                .hint_text("Path of a snapshot to import")
+                .show(ui);
+
+            let formats = world.resource::<SpyglassFormats>();
+            let extensions = formats
+                .formats
+                .iter()
+                .map(|format| format.extension())
+                .collect::<Vec<_>>();
+            let folder = world.resource::<AssetFolder>();
+            let mut picked = String::new();
+            if asset_path_picker(ui, &mut picked, folder, &extensions) {
+                self.import_path = folder.0.join(picked).display().to_string();
+            }
+
+            if ui.button("import").clicked() {
+                match import_snapshot(world, &self.import_path) {
+                    Ok(snapshot) => snapshots.snapshots.push(snapshot),
+                    Err(err) => world.resource_mut::<Popups>().error(format!(
+                        "failed to import snapshot `{}`: {err}",
+                        self.import_path
+                    )),
+                }
+            }
+        });
+
+        ui.separator();
+
+        let mut removed = None;
+        for (i, snapshot) in snapshots.snapshots.iter().enumerate() {
+            ui.horizontal(|ui| {
+                ui.label(&snapshot.name);
+                ui.weak(format!(
+                    "{} entities, {} resources",
+                    snapshot.scene.entities.len(),
+                    snapshot.scene.resources.len()
+                ));
+                if ui.button("restore").clicked() {
+                    snapshots.pending_restore = Some(i);
+                }
+                if ui.button("export").clicked() {
+                    export_snapshot(world, snapshot, &self.directory);
+                }
+                if ui.button("delete").clicked() {
+                    removed = Some(i);
+                }
+            });
+        }
+
+        if let Some(i) = removed {
+            snapshots.snapshots.remove(i);
+            snapshots.pending_restore = None;
+        }
+
+        world.insert_resource(snapshots);
+    }
+}
+
+fn restore_pending_snapshot(world: &mut World) {
+    let Some(mut snapshots) = world.remove_resource::<Snapshots>() else {
+        return;
+    };
+
+    if let Some(snapshot) = snapshots
+        .pending_restore
+        .take()
+        .and_then(|i| snapshots.snapshots.get(i))
+    {
+        if let Err(err) = snapshot.restore(world) {
+            world.resource_mut::<Popups>().error(format!(
+                "failed to restore snapshot `{}`: {err}",
+                snapshot.name
+            ));
+        }
+    }
+
+    world.insert_resource(snapshots);
+}
+
+/// Export a snapshot on a worker thread. The scene is cloned, as the snapshot may be deleted or
+/// restored while it is being serialized.
+fn export_snapshot(world: &mut World, snapshot: &Snapshot, directory: &str) {
+    let format = world.resource::<SpyglassFormats>().current_shared();
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    let scene = clone_scene(&snapshot.scene);
+    let file_name = format!("{}.{}", file_stem(&snapshot.name), format.extension());
+    let path = Path::new(directory).join(file_name);
+
+    world
+        .resource_mut::<Exports>()
+        .spawn(format!("snapshot `{}`", snapshot.name), move || {
+            let bytes = serialize_scene(&*format, &scene, &registry).map_err(|e| e.to_string())?;
+            write_export(&path, &bytes)?;
+            Ok(path)
+        });
+}
+
+/// The file name a snapshot is exported to, without extension. Path separators and other
+/// characters that are not allowed in file names are replaced, so that the export stays in its
+/// directory whatever the snapshot is named.
+fn file_stem(name: &str) -> String {
+    let stem = name.replace("..", "_").replace(
+        |c: char| {
+            c.is_control() || matches!(c, '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|')
+        },
+        "_",
+    );
+    match stem.trim() {
+        "" => "snapshot".to_string(),
+        stem => stem.to_string(),
+    }
+}
+
+fn clone_scene(scene: &DynamicScene) -> DynamicScene {
+    DynamicScene {
+        resources: scene
+            .resources
+            .iter()
+            .map(|res| res.clone_value())
+            .collect(),
+        entities: scene
+            .entities
+            .iter()
+            .map(|entity| DynamicEntity {
+                entity: entity.entity,
+                components: entity
+                    .components
+                    .iter()
+                    .map(|comp| comp.clone_value())
+                    .collect(),
+            })
+            .collect(),
+    }
+}
+
+fn import_snapshot(world: &World, path: &str) -> Result<Snapshot, String> {
+    let path = Path::new(path);
+    let extension = path.extension().and_then(|ext| ext.to_str()).unwrap_or("");
+    let formats = world.resource::<SpyglassFormats>();
+    let format = formats
+        .for_extension(extension)
+        .ok_or_else(|| format!("no format is known for the extension `{extension}`"))?;
+
+    let bytes = std::fs::read(path).map_err(|e| e.to_string())?;
+    let registry = world.resource::<AppTypeRegistry>().read();
+    let scene = deserialize_scene(format, &bytes, &registry).map_err(|e| e.to_string())?;
+
+    Ok(Snapshot {
+        name: path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default(),
+        scene,
+    })
+}
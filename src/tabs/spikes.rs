@@ -0,0 +1,191 @@
+//! The spikes tab module. Captures a record of every frame that took longer than a threshold,
+//! with the entity count and, with the `profiler` feature, the slowest systems of that frame, so
+//! intermittent hitches can be inspected after the fact rather than caught by eye.
+
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use bevy::core::FrameCount;
+use bevy::prelude::*;
+use bevy::time::Real;
+use bevy::utils::get_short_name;
+
+use crate::compat::egui::{self, Ui};
+use crate::{Spyglass, Tab};
+
+/// The plugin that adds the spikes tab to the end of the [`Spyglass`] tab list, and detects
+/// spikes while a threshold is set in [`FrameSpikes`].
+pub struct SpikesTabPlugin;
+
+impl Plugin for SpikesTabPlugin {
+    fn build(&self, app: &mut App) {
+        if cfg!(feature = "disabled") {
+            return;
+        }
+
+        let mut spyglass = app.world.resource_mut::<Spyglass>();
+        spyglass.add_tab(Box::new(SpikesTab));
+
+        app.init_resource::<FrameSpikes>()
+            .add_systems(Last, detect_spikes);
+    }
+}
+
+/// A frame that took longer than the [`FrameSpikes::threshold`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct FrameSpike {
+    /// The number of the slow frame.
+    pub frame: u32,
+    /// The real time since startup at the end of the slow frame, in seconds.
+    pub time: f64,
+    /// How long the frame took.
+    pub frame_time: Duration,
+    /// The number of entities after the frame.
+    pub entities: u32,
+    /// The slowest systems of the frame, slowest first. Only recorded with the `profiler`
+    /// feature, and empty otherwise.
+    pub systems: Vec<(String, Duration)>,
+}
+
+/// The resource that configures spike detection and contains the captured spikes.
+#[derive(Resource)]
+pub struct FrameSpikes {
+    /// Frames that take longer than this are captured. Detection is disabled while it is `None`.
+    pub threshold: Option<Duration>,
+    /// The captured spikes, oldest first.
+    pub spikes: VecDeque<FrameSpike>,
+    /// The number of spikes kept; older spikes are dropped.
+    pub capacity: usize,
+}
+
+impl Default for FrameSpikes {
+    fn default() -> Self {
+        Self {
+            threshold: None,
+            spikes: VecDeque::new(),
+            capacity: 100,
+        }
+    }
+}
+
+/// The ten slowest systems of the previous frame, as measured by the profiler.
+#[cfg(feature = "profiler")]
+fn slowest_systems(world: &World) -> Vec<(String, Duration)> {
+    let Some(profiler) = world.get_resource::<crate::profiler::Profiler>() else {
+        return vec![];
+    };
+    let mut systems = profiler
+        .last_frame
+        .systems
+        .iter()
+        .map(|(name, &time)| (name.clone(), time))
+        .collect::<Vec<_>>();
+    systems.sort_by_key(|(_, time)| std::cmp::Reverse(*time));
+    systems.truncate(10);
+    systems
+}
+
+#[cfg(not(feature = "profiler"))]
+fn slowest_systems(_: &World) -> Vec<(String, Duration)> {
+    vec![]
+}
+
+/// Capture the previous frame if it took longer than the threshold. The real delta of a frame is
+/// the time the previous frame took, which is also the frame the profiler timings describe.
+fn detect_spikes(world: &mut World) {
+    let Some(threshold) = world.resource::<FrameSpikes>().threshold else {
+        return;
+    };
+    let Some(time) = world.get_resource::<Time<Real>>() else {
+        return;
+    };
+    let frame_time = time.delta();
+    if frame_time <= threshold {
+        return;
+    }
+    let spike = FrameSpike {
+        frame: world
+            .get_resource::<FrameCount>()
+            .map_or(0, |frame| frame.0.saturating_sub(1)),
+        time: time.elapsed_seconds_f64(),
+        frame_time,
+        entities: world.entities().len(),
+        systems: slowest_systems(world),
+    };
+
+    let mut spikes = world.resource_mut::<FrameSpikes>();
+    spikes.spikes.push_back(spike);
+    while spikes.spikes.len() > spikes.capacity {
+        spikes.spikes.pop_front();
+    }
+}
+
+fn format_ms(duration: Duration) -> String {
+    format!("{:.2} ms", duration.as_secs_f64() * 1000.0)
+}
+
+struct SpikesTab;
+
+impl Tab for SpikesTab {
+    fn name(&self) -> &str {
+        "Spikes"
+    }
+
+    fn badge(&self, world: &World) -> Option<String> {
+        let spikes = world.get_resource::<FrameSpikes>()?;
+        (!spikes.spikes.is_empty()).then(|| spikes.spikes.len().to_string())
+    }
+
+    fn draw(&mut self, ui: &mut Ui, world: &mut World) {
+        let mut spikes = world.resource_mut::<FrameSpikes>();
+        ui.horizontal(|ui| {
+            let mut enabled = spikes.threshold.is_some();
+            ui.checkbox(&mut enabled, "capture frames slower than");
+            let mut millis = spikes
+                .threshold
+                .map_or(50.0, |threshold| threshold.as_secs_f64() * 1000.0);
+            ui.add_enabled(
+                enabled,
+                egui::DragValue::new(&mut millis)
+                    .clamp_range(0.0..=10_000.0)
+                    .suffix(" ms"),
+            );
+            spikes.threshold = enabled.then(|| Duration::from_secs_f64(millis / 1000.0));
+            if ui.button("clear").clicked() {
+                spikes.spikes.clear();
+            }
+        });
+        if !cfg!(feature = "profiler") {
+            ui.weak("Enable the `profiler` feature to record the slowest systems of each spike.");
+        }
+        ui.separator();
+
+        if spikes.spikes.is_empty() {
+            ui.weak("no spikes were captured");
+        }
+        for spike in spikes.spikes.iter().rev() {
+            egui::CollapsingHeader::new(format!(
+                "frame {} at {:.1}s: {}, {} entities",
+                spike.frame,
+                spike.time,
+                format_ms(spike.frame_time),
+                spike.entities
+            ))
+            .id_source(("spike", spike.frame))
+            .show(ui, |ui| {
+                if spike.systems.is_empty() {
+                    ui.weak("no system timings");
+                }
+                egui::Grid::new(("spike_systems", spike.frame))
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for (name, time) in &spike.systems {
+                            ui.label(get_short_name(name)).on_hover_text(name);
+                            ui.monospace(format_ms(*time));
+                            ui.end_row();
+                        }
+                    });
+            });
+        }
+    }
+}
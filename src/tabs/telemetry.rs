@@ -0,0 +1,107 @@
+//! The telemetry tab module. Loads telemetry files written by the
+//! [`TelemetryPlugin`](crate::telemetry::TelemetryPlugin), for example during a playtest, and
+//! plots their series.
+
+use std::path::Path;
+
+use bevy::prelude::*;
+
+use crate::compat::egui::{self, Ui};
+use crate::formats::SpyglassFormats;
+use crate::tabs::entities::Popups;
+use crate::telemetry::TelemetryLog;
+use crate::widgets::line_plot;
+use crate::{Spyglass, Tab};
+
+/// The plugin that adds the telemetry tab to the inspector. Must be added after the
+/// [`SpyglassPlugin`](crate::SpyglassPlugin), as it adds its tab to the end of the [`Spyglass`]
+/// tab list.
+pub struct TelemetryTabPlugin;
+
+impl Plugin for TelemetryTabPlugin {
+    fn build(&self, app: &mut App) {
+        if cfg!(feature = "disabled") {
+            return;
+        }
+
+        let mut spyglass = app.world.resource_mut::<Spyglass>();
+        spyglass.add_tab(Box::new(TelemetryTab::default()));
+
+        app.init_resource::<SpyglassFormats>()
+            .init_resource::<Popups>();
+    }
+}
+
+#[derive(Default)]
+struct TelemetryTab {
+    path: String,
+    log: Option<TelemetryLog>,
+    series: String,
+}
+
+impl Tab for TelemetryTab {
+    fn name(&self) -> &str {
+        "Telemetry"
+    }
+
+    fn draw(&mut self, ui: &mut Ui, world: &mut World) {
+        ui.horizontal(|ui| {
+            egui::TextEdit::singleline(&mut self.path)
+                .hint_text("Path of a telemetry file")
+                .show(ui);
+            if ui.button("load").clicked() {
+                match load_log(world, &self.path) {
+                    Ok(log) => {
+                        self.series = "entities".to_string();
+                        self.log = Some(log);
+                    }
+                    Err(err) => world
+                        .resource_mut::<Popups>()
+                        .error(format!("failed to load telemetry `{}`: {err}", self.path)),
+                }
+            }
+        });
+
+        let Some(log) = &self.log else {
+            ui.weak("No telemetry loaded.");
+            return;
+        };
+        let (Some(first), Some(last)) = (log.samples.first(), log.samples.last()) else {
+            ui.weak("The telemetry file has no samples.");
+            return;
+        };
+        ui.label(format!(
+            "{} samples over {:.1}s, frames {}..={}",
+            log.samples.len(),
+            last.time - first.time,
+            first.frame,
+            last.frame
+        ));
+
+        egui::ComboBox::from_id_source("telemetry_series")
+            .selected_text(&self.series)
+            .show_ui(ui, |ui| {
+                for series in log.series() {
+                    ui.selectable_value(&mut self.series, series.clone(), series);
+                }
+            });
+
+        let points = log
+            .samples
+            .iter()
+            .filter_map(|sample| Some((sample.time, sample.get(&self.series)?)))
+            .collect::<Vec<_>>();
+        line_plot(ui, &points);
+    }
+}
+
+fn load_log(world: &World, path: &str) -> Result<TelemetryLog, String> {
+    let path = Path::new(path);
+    let extension = path.extension().and_then(|ext| ext.to_str()).unwrap_or("");
+    let formats = world.resource::<SpyglassFormats>();
+    let format = formats
+        .for_extension(extension)
+        .ok_or_else(|| format!("no format is known for the extension `{extension}`"))?;
+    let bytes = std::fs::read(path).map_err(|e| e.to_string())?;
+    TelemetryLog::deserialize(format, &bytes).map_err(|e| e.to_string())
+}
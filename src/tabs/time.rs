@@ -0,0 +1,159 @@
+//! The time tab module. Shows the state of the generic, [`Virtual`] and [`Fixed`] clocks in
+//! detail, and edits the fixed timestep, the relative speed and the maximum delta of virtual time
+//! with the reflection editors. Complements the pause and step controls at the top of the
+//! inspector.
+
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy::time::{Fixed, Real, Virtual};
+
+use crate::compat::egui::{self, Ui};
+use crate::tabs::entities::editors::EditorStates;
+use crate::tabs::entities::{Popups, ReprEditors};
+use crate::{Spyglass, SpyglassSettings, Tab};
+
+/// The plugin that adds the time tab to the end of the [`Spyglass`] tab list.
+pub struct TimeTabPlugin;
+
+impl Plugin for TimeTabPlugin {
+    fn build(&self, app: &mut App) {
+        if cfg!(feature = "disabled") {
+            return;
+        }
+
+        let mut spyglass = app.world.resource_mut::<Spyglass>();
+        spyglass.add_tab(Box::new(TimeTab));
+
+        app.init_resource::<Popups>();
+    }
+}
+
+/// The editable settings of the clocks, in seconds, edited with the [`ReprEditors`] and applied
+/// through the setters of [`Time`], which validate them.
+#[derive(Clone, PartialEq, Reflect)]
+struct TimeSettings {
+    fixed_timestep: f64,
+    relative_speed: f64,
+    max_delta: f64,
+}
+
+impl TimeSettings {
+    fn read(world: &World) -> Option<Self> {
+        let fixed = world.get_resource::<Time<Fixed>>()?;
+        let virt = world.get_resource::<Time<Virtual>>()?;
+        Some(Self {
+            fixed_timestep: fixed.timestep().as_secs_f64(),
+            relative_speed: virt.relative_speed_f64(),
+            max_delta: virt.max_delta().as_secs_f64(),
+        })
+    }
+
+    fn apply(&self, world: &mut World) -> Result<(), String> {
+        let positive = |name: &str, seconds: f64| match seconds.is_finite() && seconds > 0.0 {
+            true => Ok(Duration::from_secs_f64(seconds)),
+            false => Err(format!("the {name} must be positive, not {seconds}")),
+        };
+        let timestep = positive("fixed timestep", self.fixed_timestep)?;
+        let max_delta = positive("maximum delta", self.max_delta)?;
+        if !self.relative_speed.is_finite() || self.relative_speed < 0.0 {
+            return Err(format!(
+                "the relative speed can't be negative, not {}",
+                self.relative_speed
+            ));
+        }
+
+        world.resource_mut::<Time<Fixed>>().set_timestep(timestep);
+        let mut virt = world.resource_mut::<Time<Virtual>>();
+        virt.set_relative_speed_f64(self.relative_speed);
+        virt.set_max_delta(max_delta);
+        Ok(())
+    }
+}
+
+struct TimeTab;
+
+impl Tab for TimeTab {
+    fn name(&self) -> &str {
+        "Time"
+    }
+
+    fn draw(&mut self, ui: &mut Ui, world: &mut World) {
+        egui::Grid::new("time_clocks").striped(true).show(ui, |ui| {
+            ui.strong("clock");
+            ui.strong("delta");
+            ui.strong("elapsed");
+            ui.strong("wrapped");
+            ui.end_row();
+
+            if let Some(time) = world.get_resource::<Time>() {
+                clock_row(ui, "Time", time);
+            }
+            if let Some(time) = world.get_resource::<Time<Real>>() {
+                clock_row(ui, "Time<Real>", time);
+            }
+            if let Some(time) = world.get_resource::<Time<Virtual>>() {
+                clock_row(ui, "Time<Virtual>", time);
+            }
+            if let Some(time) = world.get_resource::<Time<Fixed>>() {
+                clock_row(ui, "Time<Fixed>", time);
+            }
+        });
+        ui.separator();
+
+        if let Some(virt) = world.get_resource::<Time<Virtual>>() {
+            ui.label(format!(
+                "virtual time is {}, effective speed {:.3}",
+                if virt.is_paused() {
+                    "paused"
+                } else {
+                    "running"
+                },
+                virt.effective_speed_f64()
+            ));
+        }
+        if let Some(fixed) = world.get_resource::<Time<Fixed>>() {
+            ui.horizontal(|ui| {
+                ui.label("fixed overstep");
+                ui.add(
+                    egui::ProgressBar::new(fixed.overstep_percentage())
+                        .text(format!("{:.4}s", fixed.overstep().as_secs_f64()))
+                        .desired_width(200.0),
+                );
+            });
+        }
+        ui.separator();
+
+        let Some(settings) = TimeSettings::read(world) else {
+            ui.weak("virtual or fixed time is missing");
+            return;
+        };
+        let mut edited = settings.clone();
+        let editors = world.remove_resource::<ReprEditors>().unwrap();
+        let mut states = world.remove_resource::<EditorStates>().unwrap();
+        states.begin_frame(SpyglassSettings::is_read_only(world));
+        ui.push_id("time_settings", |ui| {
+            states.push_path("Time");
+            let editor = editors.get(&edited);
+            editor(ui, &mut edited, world, &editors, &mut states);
+            states.pop_path();
+        });
+        states.end_frame();
+        world.insert_resource(states);
+        world.insert_resource(editors);
+
+        if edited != settings {
+            if let Err(err) = edited.apply(world) {
+                world.resource_mut::<Popups>().recoverable_error(err);
+            }
+        }
+    }
+}
+
+fn clock_row<T: Default>(ui: &mut Ui, name: &str, time: &Time<T>) {
+    ui.label(name);
+    ui.label(format!("{:.5}s", time.delta_seconds_f64()));
+    ui.label(format!("{:.3}s", time.elapsed_seconds_f64()));
+    ui.label(format!("{:.3}s", time.elapsed_seconds_wrapped_f64()));
+    ui.end_row();
+}
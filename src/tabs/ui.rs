@@ -0,0 +1,346 @@
+//! The UI tab module. Shows the hierarchy of bevy_ui nodes with their computed layout rects, and
+//! edits the size, margin and flex properties of the [`Style`] of the selected node. The node
+//! under the pointer in the tab is highlighted in the viewport, which makes it easy to find what
+//! takes up space in a layout. Only available with the `ui` feature.
+
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+
+use crate::compat::egui::{self, Color32, Stroke, Ui};
+use crate::compat::{Egui, EguiBackend};
+use crate::tabs::entities::selection::SelectEntity;
+use crate::{FocusTab, Spyglass, SpyglassSettings, SpyglassWindow, Tab};
+
+const HIGHLIGHT: Color32 = Color32::from_rgb(255, 190, 40);
+
+/// The plugin that adds the UI tab to the end of the [`Spyglass`] tab list, and the system that
+/// highlights the hovered node.
+pub struct UiTabPlugin;
+
+impl Plugin for UiTabPlugin {
+    fn build(&self, app: &mut App) {
+        if cfg!(feature = "disabled") {
+            return;
+        }
+
+        let mut spyglass = app.world.resource_mut::<Spyglass>();
+        spyglass.add_tab(Box::new(UiTab::default()));
+
+        app.init_resource::<HoveredNode>()
+            .add_systems(Update, draw_node_highlight.before(SpyglassWindow));
+    }
+}
+
+/// The node hovered in the tab in the last frame.
+#[derive(Default, Resource)]
+struct HoveredNode(Option<Entity>);
+
+/// Outline the hovered node in the primary window, which the UI is laid out in. The hovered node
+/// is taken, so the outline disappears once the tab stops setting it.
+fn draw_node_highlight(world: &mut World) {
+    let Some(entity) = world.resource_mut::<HoveredNode>().0.take() else {
+        return;
+    };
+    let Some(rect) = node_rect(world, entity) else {
+        return;
+    };
+    let mut windows = world.query_filtered::<Entity, With<PrimaryWindow>>();
+    let Ok(window) = windows.get_single(world) else {
+        return;
+    };
+    let Some(ctx) = Egui::context(world, window) else {
+        return;
+    };
+
+    let scale = world
+        .get_resource::<UiScale>()
+        .map_or(1.0, |scale| scale.0 as f32);
+    let rect = egui::Rect::from_min_max(
+        egui::pos2(rect.min.x * scale, rect.min.y * scale),
+        egui::pos2(rect.max.x * scale, rect.max.y * scale),
+    );
+    let painter = ctx.layer_painter(egui::LayerId::new(
+        egui::Order::Background,
+        egui::Id::new("spyglass_ui_highlight"),
+    ));
+    painter.rect(
+        rect,
+        0.0,
+        HIGHLIGHT.gamma_multiply(0.2),
+        Stroke::new(2.0, HIGHLIGHT),
+    );
+}
+
+/// The computed rect of a node, in logical pixels.
+fn node_rect(world: &World, entity: Entity) -> Option<Rect> {
+    let entity = world.get_entity(entity)?;
+    Some(
+        entity
+            .get::<Node>()?
+            .logical_rect(entity.get::<GlobalTransform>()?),
+    )
+}
+
+/// The children of `entity` that are UI nodes.
+fn child_nodes(world: &World, entity: Entity) -> Vec<Entity> {
+    world
+        .get::<Children>(entity)
+        .into_iter()
+        .flatten()
+        .copied()
+        .filter(|&child| world.get::<Node>(child).is_some())
+        .collect()
+}
+
+#[derive(Default)]
+struct UiTab {
+    selected: Option<Entity>,
+}
+
+impl Tab for UiTab {
+    fn name(&self) -> &str {
+        "UI"
+    }
+
+    fn draw(&mut self, ui: &mut Ui, world: &mut World) {
+        // Nodes whose parent is not a node are laid out on their own, like roots.
+        let mut nodes = world.query_filtered::<(Entity, Option<&Parent>), With<Node>>();
+        let roots = nodes
+            .iter(world)
+            .filter(|(_, parent)| {
+                !parent.is_some_and(|parent| world.get::<Node>(parent.get()).is_some())
+            })
+            .map(|(entity, _)| entity)
+            .collect::<Vec<_>>();
+        if roots.is_empty() {
+            ui.weak("no UI nodes");
+            return;
+        }
+
+        let mut hovered = None;
+        egui::ScrollArea::vertical()
+            .id_source("ui_nodes")
+            .max_height(ui.available_height() / 2.0)
+            .auto_shrink([false, true])
+            .show(ui, |ui| {
+                for root in roots {
+                    self.draw_node(ui, world, root, &mut hovered);
+                }
+            });
+        if hovered.is_some() {
+            world.resource_mut::<HoveredNode>().0 = hovered;
+        }
+
+        let Some(entity) = self
+            .selected
+            .filter(|&entity| world.get::<Style>(entity).is_some())
+        else {
+            return;
+        };
+        ui.separator();
+        ui.horizontal(|ui| {
+            ui.strong(node_name(world, entity));
+            if ui
+                .small_button("open")
+                .on_hover_text("Open in the entities tab")
+                .clicked()
+            {
+                world.send_event(SelectEntity(Some(entity)));
+                world.send_event(FocusTab("Entities".to_string()));
+            }
+        });
+        let mut style = world.get::<Style>(entity).unwrap().clone();
+        ui.add_enabled_ui(!SpyglassSettings::is_read_only(world), |ui| {
+            egui::ScrollArea::vertical()
+                .id_source("ui_style")
+                .show(ui, |ui| style_editor(ui, entity, &mut style));
+        });
+        if *world.get::<Style>(entity).unwrap() != style {
+            *world.get_mut::<Style>(entity).unwrap() = style;
+        }
+    }
+}
+
+impl UiTab {
+    /// Draw a node and its child nodes, recording the one under the pointer in `hovered`.
+    fn draw_node(
+        &mut self,
+        ui: &mut Ui,
+        world: &World,
+        entity: Entity,
+        hovered: &mut Option<Entity>,
+    ) {
+        let rect = node_rect(world, entity).unwrap_or_default();
+        let text = format!(
+            "{}  {:.0}×{:.0} at ({:.0}, {:.0})",
+            node_name(world, entity),
+            rect.width(),
+            rect.height(),
+            rect.min.x,
+            rect.min.y,
+        );
+        let children = child_nodes(world, entity);
+
+        let header = |ui: &mut Ui| {
+            let response = ui.selectable_label(self.selected == Some(entity), text);
+            if response.clicked() {
+                self.selected = Some(entity);
+            }
+            if response.hovered() {
+                *hovered = Some(entity);
+            }
+        };
+        if children.is_empty() {
+            ui.horizontal(|ui| {
+                ui.add_space(ui.spacing().indent);
+                header(ui);
+            });
+            return;
+        }
+        let id = ui.make_persistent_id(("ui_node", entity));
+        egui::collapsing_header::CollapsingState::load_with_default_open(ui.ctx(), id, true)
+            .show_header(ui, header)
+            .body(|ui| {
+                for child in children {
+                    self.draw_node(ui, world, child, hovered);
+                }
+            });
+    }
+}
+
+fn node_name(world: &World, entity: Entity) -> String {
+    world
+        .get::<Name>(entity)
+        .map(|name| name.to_string())
+        .unwrap_or_else(|| format!("{entity:?}"))
+}
+
+/// Edit the size, margin, padding and flex properties of a style.
+fn style_editor(ui: &mut Ui, entity: Entity, style: &mut Style) {
+    egui::Grid::new(("ui_style", entity))
+        .num_columns(2)
+        .show(ui, |ui| {
+            ui.label("width");
+            val_editor(ui, "width", &mut style.width);
+            ui.end_row();
+            ui.label("height");
+            val_editor(ui, "height", &mut style.height);
+            ui.end_row();
+            ui.label("min width");
+            val_editor(ui, "min_width", &mut style.min_width);
+            ui.end_row();
+            ui.label("min height");
+            val_editor(ui, "min_height", &mut style.min_height);
+            ui.end_row();
+            ui.label("max width");
+            val_editor(ui, "max_width", &mut style.max_width);
+            ui.end_row();
+            ui.label("max height");
+            val_editor(ui, "max_height", &mut style.max_height);
+            ui.end_row();
+        });
+
+    ui.collapsing("margin", |ui| rect_editor(ui, "margin", &mut style.margin));
+    ui.collapsing("padding", |ui| {
+        rect_editor(ui, "padding", &mut style.padding)
+    });
+
+    egui::Grid::new(("ui_flex", entity))
+        .num_columns(2)
+        .show(ui, |ui| {
+            ui.label("direction");
+            ui.horizontal(|ui| {
+                for (direction, label) in [
+                    (FlexDirection::Row, "row"),
+                    (FlexDirection::Column, "column"),
+                    (FlexDirection::RowReverse, "row reverse"),
+                    (FlexDirection::ColumnReverse, "column reverse"),
+                ] {
+                    ui.selectable_value(&mut style.flex_direction, direction, label);
+                }
+            });
+            ui.end_row();
+            ui.label("wrap");
+            ui.horizontal(|ui| {
+                for (wrap, label) in [
+                    (FlexWrap::NoWrap, "no wrap"),
+                    (FlexWrap::Wrap, "wrap"),
+                    (FlexWrap::WrapReverse, "wrap reverse"),
+                ] {
+                    ui.selectable_value(&mut style.flex_wrap, wrap, label);
+                }
+            });
+            ui.end_row();
+            ui.label("grow");
+            ui.add(
+                egui::DragValue::new(&mut style.flex_grow)
+                    .speed(0.05)
+                    .clamp_range(0.0..=f32::MAX),
+            );
+            ui.end_row();
+            ui.label("shrink");
+            ui.add(
+                egui::DragValue::new(&mut style.flex_shrink)
+                    .speed(0.05)
+                    .clamp_range(0.0..=f32::MAX),
+            );
+            ui.end_row();
+            ui.label("basis");
+            val_editor(ui, "flex_basis", &mut style.flex_basis);
+            ui.end_row();
+        });
+}
+
+/// Edit the four sides of a margin or padding.
+fn rect_editor(ui: &mut Ui, id_source: &str, rect: &mut UiRect) {
+    egui::Grid::new(ui.id().with(id_source))
+        .num_columns(2)
+        .show(ui, |ui| {
+            for (side, label) in [
+                (&mut rect.left, "left"),
+                (&mut rect.right, "right"),
+                (&mut rect.top, "top"),
+                (&mut rect.bottom, "bottom"),
+            ] {
+                ui.label(label);
+                val_editor(ui, label, side);
+                ui.end_row();
+            }
+        });
+}
+
+/// Edit a [`Val`] as a unit and an amount. Switching units keeps the amount.
+fn val_editor(ui: &mut Ui, id_source: &str, val: &mut Val) {
+    let (unit, mut amount) = match *val {
+        Val::Auto => ("auto", 0.0),
+        Val::Px(amount) => ("px", amount),
+        Val::Percent(amount) => ("%", amount),
+        Val::Vw(amount) => ("vw", amount),
+        Val::Vh(amount) => ("vh", amount),
+        Val::VMin(amount) => ("vmin", amount),
+        Val::VMax(amount) => ("vmax", amount),
+    };
+    ui.horizontal(|ui| {
+        let mut new_unit = unit;
+        egui::ComboBox::from_id_source(ui.id().with((id_source, "unit")))
+            .selected_text(unit)
+            .width(60.0)
+            .show_ui(ui, |ui| {
+                for unit in ["auto", "px", "%", "vw", "vh", "vmin", "vmax"] {
+                    ui.selectable_value(&mut new_unit, unit, unit);
+                }
+            });
+        if new_unit != "auto" {
+            ui.add(egui::DragValue::new(&mut amount));
+        }
+        *val = match new_unit {
+            "px" => Val::Px(amount),
+            "%" => Val::Percent(amount),
+            "vw" => Val::Vw(amount),
+            "vh" => Val::Vh(amount),
+            "vmin" => Val::VMin(amount),
+            "vmax" => Val::VMax(amount),
+            _ => Val::Auto,
+        };
+    });
+}
@@ -0,0 +1,183 @@
+//! The visibility tab module. Explains the visibility of the entity selected in the entities tab,
+//! from its [`Visibility`] and the visibility it inherits, through its [`RenderLayers`], to the
+//! views it was visible from last frame, and lists the entities visible from every camera. Answers
+//! "why isn't my mesh drawing" without println debugging.
+//!
+//! The render world is not accessible from the inspector, so visibility is reported as the main
+//! world computed it, from the [`VisibleEntities`] of each view, which is what gets extracted.
+
+use bevy::prelude::*;
+use bevy::render::primitives::Aabb;
+use bevy::render::view::{NoFrustumCulling, RenderLayers, VisibleEntities};
+
+use crate::compat::egui::{self, Ui};
+use crate::tabs::entities::SelectedEntity;
+use crate::{Spyglass, Tab};
+
+/// The number of visible entities listed per camera.
+const MAX_LISTED_ENTITIES: usize = 200;
+
+/// The plugin that adds the visibility tab to the end of the [`Spyglass`] tab list.
+pub struct VisibilityTabPlugin;
+
+impl Plugin for VisibilityTabPlugin {
+    fn build(&self, app: &mut App) {
+        if cfg!(feature = "disabled") {
+            return;
+        }
+
+        let mut spyglass = app.world.resource_mut::<Spyglass>();
+        spyglass.add_tab(Box::new(VisibilityTab));
+    }
+}
+
+/// A view, and the entities that were visible from it last frame.
+struct View {
+    label: String,
+    layers: RenderLayers,
+    active: bool,
+    visible: Vec<Entity>,
+}
+
+fn views(world: &mut World) -> Vec<View> {
+    let mut q = world.query::<(
+        Entity,
+        Option<&Name>,
+        &VisibleEntities,
+        Option<&RenderLayers>,
+        Option<&Camera>,
+    )>();
+    q.iter(world)
+        .map(|(entity, name, visible, layers, camera)| View {
+            label: label(entity, name),
+            layers: layers.copied().unwrap_or_default(),
+            active: camera.is_none_or(|camera| camera.is_active),
+            visible: visible.entities.clone(),
+        })
+        .collect()
+}
+
+fn label(entity: Entity, name: Option<&Name>) -> String {
+    match name {
+        Some(name) => format!("{name} ({entity:?})"),
+        None => format!("{entity:?}"),
+    }
+}
+
+struct VisibilityTab;
+
+impl Tab for VisibilityTab {
+    fn name(&self) -> &str {
+        "Visibility"
+    }
+
+    fn draw(&mut self, ui: &mut Ui, world: &mut World) {
+        let views = views(world);
+        match world.get_resource::<SelectedEntity>().map(|s| s.id) {
+            Some(entity) if world.get_entity(entity).is_some() => {
+                draw_selected(ui, world, entity, &views);
+            }
+            _ => {
+                ui.weak("Select an entity in the entities tab to explain its visibility");
+            }
+        }
+        ui.separator();
+
+        ui.strong("views");
+        if views.is_empty() {
+            ui.weak("there are no views");
+        }
+        for view in &views {
+            let header = format!("{}: {} visible", view.label, view.visible.len());
+            ui.collapsing(header, |ui| {
+                if !view.active {
+                    ui.weak("inactive camera");
+                }
+                ui.label(format!(
+                    "layers: {:?}",
+                    view.layers.iter().collect::<Vec<_>>()
+                ));
+                for &entity in view.visible.iter().take(MAX_LISTED_ENTITIES) {
+                    ui.label(label(entity, world.get::<Name>(entity)));
+                }
+                if view.visible.len() > MAX_LISTED_ENTITIES {
+                    ui.weak(format!(
+                        "and {} more",
+                        view.visible.len() - MAX_LISTED_ENTITIES
+                    ));
+                }
+            });
+        }
+    }
+}
+
+fn draw_selected(ui: &mut Ui, world: &World, entity: Entity, views: &[View]) {
+    let entity_ref = world.entity(entity);
+    ui.heading(label(entity, entity_ref.get::<Name>()));
+
+    let visibility = entity_ref.get::<Visibility>();
+    let inherited = entity_ref.get::<InheritedVisibility>().map(|v| v.get());
+    let in_view = entity_ref.get::<ViewVisibility>().map(|v| v.get());
+    let layers = entity_ref
+        .get::<RenderLayers>()
+        .copied()
+        .unwrap_or_default();
+
+    egui::Grid::new("selected_visibility").show(ui, |ui| {
+        ui.label("visibility");
+        ui.label(visibility.map_or("missing".into(), |v| format!("{v:?}")));
+        ui.end_row();
+        ui.label("inherited visibility");
+        ui.label(inherited.map_or("missing".into(), |v| v.to_string()));
+        ui.end_row();
+        ui.label("view visibility");
+        ui.label(in_view.map_or("missing".into(), |v| v.to_string()));
+        ui.end_row();
+        ui.label("render layers");
+        ui.label(format!("{:?}", layers.iter().collect::<Vec<_>>()));
+        ui.end_row();
+        ui.label("bounding box");
+        ui.label(
+            match (
+                entity_ref.get::<Aabb>(),
+                entity_ref.contains::<NoFrustumCulling>(),
+            ) {
+                (_, true) => "not frustum culled".to_string(),
+                (Some(aabb), false) => format!("half extents {}", Vec3::from(aabb.half_extents)),
+                (None, false) => "none".to_string(),
+            },
+        );
+        ui.end_row();
+    });
+
+    ui.label("visible from:");
+    let mut any = false;
+    for view in views.iter().filter(|view| view.visible.contains(&entity)) {
+        any = true;
+        ui.label(format!("• {}", view.label));
+    }
+    if any {
+        return;
+    }
+
+    // Explain the first reason the entity was not visible from any view.
+    let reason = if visibility.is_none() || in_view.is_none() {
+        "it does not have the visibility components, such as those of `SpatialBundle`"
+    } else if visibility == Some(&Visibility::Hidden) {
+        "it is `Visibility::Hidden`"
+    } else if inherited == Some(false) {
+        "one of its ancestors is hidden"
+    } else if views.is_empty() {
+        "there are no views"
+    } else if !views.iter().any(|view| view.layers.intersects(&layers)) {
+        "its render layers do not intersect the layers of any view"
+    } else if views.iter().all(|view| !view.active) {
+        "every camera is inactive"
+    } else {
+        "it is outside of the frustum of every view, or is not drawn by any render phase"
+    };
+    ui.colored_label(
+        ui.visuals().warn_fg_color,
+        format!("not visible, as {reason}"),
+    );
+}
@@ -0,0 +1,631 @@
+//! The watches tab module. Numeric fields can be watched from the context menu of their editor in
+//! the entities tab; the watches tab then plots their values over time, which helps with tuning
+//! and spotting oscillations. Values that are not selected can be watched by typing a
+//! [`WatchExpression`] into the tab, and [`Breakpoint`]s pause the app when a comparison on an
+//! expression becomes true.
+
+use std::collections::VecDeque;
+
+use bevy::prelude::*;
+use bevy::reflect::GetPath;
+use bevy::time::{Real, Virtual};
+use bevy::utils::get_short_name;
+
+use crate::capture::Capture;
+use crate::compat::egui::{self, Ui};
+use crate::tabs::entities::Popups;
+use crate::widgets::line_plot;
+use crate::{Spyglass, SpyglassWindow, Tab};
+
+/// The plugin that adds the watches tab to the inspector. Must be added after the
+/// [`SpyglassPlugin`](crate::SpyglassPlugin), as it adds its tab to the end of the [`Spyglass`]
+/// tab list. Numeric editors only offer to watch their field when this plugin is added.
+pub struct WatchesTabPlugin;
+
+impl Plugin for WatchesTabPlugin {
+    fn build(&self, app: &mut App) {
+        if cfg!(feature = "disabled") {
+            return;
+        }
+
+        let mut spyglass = app.world.resource_mut::<Spyglass>();
+        spyglass.add_tab(Box::new(WatchesTab::default()));
+        if let Some(mut capture) = app.world.get_resource_mut::<Capture>() {
+            capture.annotators.push(Box::new(annotate_watches));
+        }
+
+        app.init_resource::<Watches>()
+            .init_resource::<Popups>()
+            .add_systems(Update, check_breakpoints.before(SpyglassWindow))
+            .add_systems(
+                Last,
+                (sample_watches, evaluate_expressions)
+                    .run_if(|spyglass: Res<Spyglass>| spyglass.refreshes("Watches")),
+            );
+    }
+}
+
+/// Annotate screenshots with the latest value of every watch and watch expression, one per line.
+fn annotate_watches(world: &World) -> Option<String> {
+    let watches = world.get_resource::<Watches>()?;
+    let lines = watches
+        .watches
+        .iter()
+        .filter_map(|watch| Some(format!("{}: {}", watch.label(), watch.samples.back()?.1)))
+        .chain(watches.expressions.iter().filter_map(|expression| {
+            let value = expression.value.as_ref().ok()?;
+            Some(format!("{}: {value}", expression.expression))
+        }))
+        .collect::<Vec<_>>();
+    (!lines.is_empty()).then(|| lines.join("\n"))
+}
+
+/// A numeric field of a component of an entity, whose values are sampled every frame.
+pub struct Watch {
+    /// The watched entity.
+    pub entity: Entity,
+    /// The type name of the watched component.
+    pub component: String,
+    /// The reflect path of the field within the component, e.g. `.translation.y`.
+    pub path: String,
+    /// The sampled `(time, value)` pairs, with the real time in seconds. The oldest samples are
+    /// dropped past [`Watches::capacity`].
+    pub samples: VecDeque<(f64, f64)>,
+}
+
+impl Watch {
+    /// A label for the watch, e.g. `3v0 Transform.translation.y`.
+    pub fn label(&self) -> String {
+        format!(
+            "{:?} {}{}",
+            self.entity,
+            get_short_name(&self.component),
+            self.path
+        )
+    }
+
+    /// Read the current value of the watched field, if the entity still has it and it is numeric.
+    pub fn read(&self, world: &World) -> Option<f64> {
+        let registry = world.resource::<AppTypeRegistry>().read();
+        let reflect = registry
+            .get_with_type_path(&self.component)?
+            .data::<ReflectComponent>()?;
+        let component = reflect.reflect(world.get_entity(self.entity)?)?;
+        let value = match self.path.is_empty() {
+            true => component,
+            false => component.reflect_path(self.path.as_str()).ok()?,
+        };
+        reflect_to_f64(value)
+    }
+}
+
+/// What a [`WatchExpression`] reads from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum WatchTarget {
+    /// A component of the first entity with the given [`Name`], or of every entity that has the
+    /// component if no name is given.
+    Component {
+        /// The name of the entity.
+        entity: Option<String>,
+        /// The short or full type path of the component.
+        component: String,
+    },
+    /// A resource, by short or full type path.
+    Resource(String),
+}
+
+/// A reflect path expression that is evaluated every frame, such as
+/// `Player/Transform.translation.y` for a component of a named entity, `Health.current` for a
+/// component of any entity, or `Res<Score>.points` for a resource.
+pub struct WatchExpression {
+    /// The expression, as typed.
+    pub expression: String,
+    /// What the expression reads from.
+    pub target: WatchTarget,
+    /// The reflect path within the target, e.g. `.translation.y`.
+    pub path: String,
+    /// The debug representation of the value in the last frame, or why it could not be read.
+    pub value: Result<String, String>,
+    /// The sampled `(time, value)` pairs, if the value is numeric.
+    pub samples: VecDeque<(f64, f64)>,
+}
+
+impl WatchExpression {
+    /// Parse an expression.
+    pub fn parse(expression: &str) -> Result<Self, String> {
+        let expression = expression.trim();
+        let (target, path) = if let Some(rest) = expression.strip_prefix("Res<") {
+            let (resource, path) = rest
+                .split_once('>')
+                .ok_or_else(|| format!("`{expression}` is missing the closing `>`"))?;
+            (WatchTarget::Resource(resource.trim().to_string()), path)
+        } else {
+            let (entity, rest) = match expression.split_once('/') {
+                Some((entity, rest)) => (Some(entity.trim().to_string()), rest),
+                None => (None, expression),
+            };
+            let split = rest.find(['.', '[']).unwrap_or(rest.len());
+            let (component, path) = rest.split_at(split);
+            if component.trim().is_empty() {
+                return Err(format!("`{expression}` does not name a component"));
+            }
+            let target = WatchTarget::Component {
+                entity,
+                component: component.trim().to_string(),
+            };
+            (target, path)
+        };
+
+        Ok(Self {
+            expression: expression.to_string(),
+            target,
+            path: path.trim().to_string(),
+            value: Err("not evaluated yet".to_string()),
+            samples: VecDeque::new(),
+        })
+    }
+
+    /// Evaluate the expression, passing the value to `f`. Expressions that match several
+    /// entities are evaluated for the first one.
+    pub fn evaluate<R>(
+        &self,
+        world: &World,
+        f: impl FnOnce(&dyn Reflect) -> R,
+    ) -> Result<R, String> {
+        let target = self
+            .targets(world)?
+            .into_iter()
+            .next()
+            .ok_or_else(|| match &self.target {
+                WatchTarget::Component { component, .. } => {
+                    format!("no entity has `{component}`")
+                }
+                WatchTarget::Resource(resource) => format!("`{resource}` does not exist"),
+            })?;
+        Ok(f(self.field(target)?))
+    }
+
+    /// Evaluate the expression for every matching entity, passing each value to `f`.
+    pub fn evaluate_all<R>(
+        &self,
+        world: &World,
+        mut f: impl FnMut(&dyn Reflect) -> R,
+    ) -> Result<Vec<R>, String> {
+        self.targets(world)?
+            .into_iter()
+            .map(|target| Ok(f(self.field(target)?)))
+            .collect()
+    }
+
+    fn field<'a>(&self, target: &'a dyn Reflect) -> Result<&'a dyn Reflect, String> {
+        match self.path.is_empty() {
+            true => Ok(target),
+            false => target
+                .reflect_path(self.path.as_str())
+                .map_err(|err| format!("invalid path `{}`: {err}", self.path)),
+        }
+    }
+
+    /// The components or resource the expression reads from.
+    fn targets<'w>(&self, world: &'w World) -> Result<Vec<&'w dyn Reflect>, String> {
+        let registry = world.resource::<AppTypeRegistry>().read();
+        let registration = |name: &str| {
+            registry
+                .get_with_type_path(name)
+                .or_else(|| registry.get_with_short_type_path(name))
+                .ok_or_else(|| format!("`{name}` is not a registered type"))
+        };
+
+        match &self.target {
+            WatchTarget::Component { entity, component } => {
+                let reflect = registration(component)?
+                    .data::<ReflectComponent>()
+                    .ok_or_else(|| format!("`{component}` does not reflect `Component`"))?
+                    .clone();
+                let Some(entity) = entity else {
+                    return Ok(world
+                        .iter_entities()
+                        .filter_map(|entity| reflect.reflect(entity))
+                        .collect());
+                };
+                let entity_ref = world
+                    .iter_entities()
+                    .find(|e| e.get::<Name>().is_some_and(|name| name.as_str() == entity))
+                    .ok_or_else(|| format!("there is no entity named `{entity}`"))?;
+                let target = reflect
+                    .reflect(entity_ref)
+                    .ok_or_else(|| format!("`{entity}` has no `{component}`"))?;
+                Ok(vec![target])
+            }
+            WatchTarget::Resource(resource) => {
+                let reflect = registration(resource)?
+                    .data::<ReflectResource>()
+                    .ok_or_else(|| format!("`{resource}` does not reflect `Resource`"))?;
+                Ok(reflect.reflect(world).into_iter().collect())
+            }
+        }
+    }
+}
+
+/// A comparison of a [`Breakpoint`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Comparison {
+    /// `<`
+    Less,
+    /// `<=`
+    LessEqual,
+    /// `>`
+    Greater,
+    /// `>=`
+    GreaterEqual,
+    /// `==`
+    Equal,
+    /// `!=`
+    NotEqual,
+}
+
+impl Comparison {
+    const ALL: [(&'static str, Self); 6] = [
+        ("<=", Self::LessEqual),
+        (">=", Self::GreaterEqual),
+        ("==", Self::Equal),
+        ("!=", Self::NotEqual),
+        ("<", Self::Less),
+        (">", Self::Greater),
+    ];
+
+    /// Compare two values.
+    pub fn matches(self, lhs: f64, rhs: f64) -> bool {
+        match self {
+            Self::Less => lhs < rhs,
+            Self::LessEqual => lhs <= rhs,
+            Self::Greater => lhs > rhs,
+            Self::GreaterEqual => lhs >= rhs,
+            Self::Equal => lhs == rhs,
+            Self::NotEqual => lhs != rhs,
+        }
+    }
+}
+
+/// A condition on a [`WatchExpression`], such as `Health.current < 10`, which pauses
+/// [`Time<Virtual>`] when it becomes true. For expressions that match several entities, the
+/// condition is true if it is true for any of them.
+pub struct Breakpoint {
+    /// The condition, as typed.
+    pub condition: String,
+    /// The left-hand side of the comparison.
+    pub expression: WatchExpression,
+    /// The comparison.
+    pub comparison: Comparison,
+    /// The right-hand side of the comparison.
+    pub value: f64,
+    /// Whether the breakpoint is checked.
+    pub enabled: bool,
+    /// The number of times the breakpoint paused the app.
+    pub hits: usize,
+    satisfied: bool,
+    error: Option<String>,
+}
+
+impl Breakpoint {
+    /// Parse a condition, such as `Player/Health.current < 10`.
+    pub fn parse(condition: &str) -> Result<Self, String> {
+        // The value is a number, so the comparison is the last operator. Searching from the left
+        // would find the brackets of `Res<…>` and generic components such as `Handle<Image>`.
+        let missing = || format!("`{condition}` has no comparison, such as `<` or `==`");
+        let end = condition.rfind(['<', '>', '=', '!']).ok_or_else(missing)? + 1;
+        let (index, symbol, comparison) = Comparison::ALL
+            .iter()
+            .find(|(symbol, _)| condition[..end].ends_with(symbol))
+            .map(|&(symbol, comparison)| (end - symbol.len(), symbol, comparison))
+            .ok_or_else(missing)?;
+        let (lhs, rhs) = (&condition[..index], &condition[index + symbol.len()..]);
+        let value = rhs
+            .trim()
+            .parse()
+            .map_err(|_| format!("`{}` is not a number", rhs.trim()))?;
+
+        Ok(Self {
+            condition: condition.trim().to_string(),
+            expression: WatchExpression::parse(lhs)?,
+            comparison,
+            value,
+            enabled: true,
+            hits: 0,
+            satisfied: false,
+            error: None,
+        })
+    }
+
+    /// Check whether the condition is currently true.
+    pub fn check(&self, world: &World) -> Result<bool, String> {
+        let values = self.expression.evaluate_all(world, reflect_to_f64)?;
+        let mut satisfied = false;
+        for value in values {
+            let value =
+                value.ok_or_else(|| format!("`{}` is not a number", self.expression.expression))?;
+            satisfied |= self.comparison.matches(value, self.value);
+        }
+        Ok(satisfied)
+    }
+}
+
+/// The resource that contains the watched fields.
+#[derive(Resource)]
+pub struct Watches {
+    /// The watched fields.
+    pub watches: Vec<Watch>,
+    /// The watched expressions.
+    pub expressions: Vec<WatchExpression>,
+    /// The breakpoints.
+    pub breakpoints: Vec<Breakpoint>,
+    /// The number of samples kept per watch.
+    pub capacity: usize,
+}
+
+impl Default for Watches {
+    fn default() -> Self {
+        Self {
+            watches: vec![],
+            expressions: vec![],
+            breakpoints: vec![],
+            capacity: 600,
+        }
+    }
+}
+
+impl Watches {
+    /// Start watching a field of a component of an entity, unless it is already watched.
+    pub fn watch(&mut self, entity: Entity, component: impl Into<String>, path: impl Into<String>) {
+        let (component, path) = (component.into(), path.into());
+        let watched = self.watches.iter().any(|watch| {
+            watch.entity == entity && watch.component == component && watch.path == path
+        });
+        if !watched {
+            self.watches.push(Watch {
+                entity,
+                component,
+                path,
+                samples: VecDeque::new(),
+            });
+        }
+    }
+}
+
+/// Convert a reflected number to an `f64`, if it is one.
+pub fn reflect_to_f64(value: &dyn Reflect) -> Option<f64> {
+    macro_rules! convert {
+        ($($ty:ty),*) => {
+            $(if let Some(&value) = value.downcast_ref::<$ty>() {
+                return Some(value as f64);
+            })*
+        };
+    }
+    convert!(f32, f64, i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+    None
+}
+
+fn sample_watches(world: &mut World) {
+    let time = world.resource::<Time<Real>>().elapsed_seconds_f64();
+    world.resource_scope(|world, mut watches: Mut<Watches>| {
+        let capacity = watches.capacity;
+        for watch in watches.watches.iter_mut() {
+            let Some(value) = watch.read(world) else {
+                continue;
+            };
+            watch.samples.push_back((time, value));
+            while watch.samples.len() > capacity {
+                watch.samples.pop_front();
+            }
+        }
+    });
+}
+
+/// Evaluate every watch expression, and report new errors.
+fn evaluate_expressions(world: &mut World) {
+    let time = world.resource::<Time<Real>>().elapsed_seconds_f64();
+    world.resource_scope(|world, mut watches: Mut<Watches>| {
+        let capacity = watches.capacity;
+        for watch in watches.expressions.iter_mut() {
+            let value =
+                watch.evaluate(world, |value| (format!("{value:?}"), reflect_to_f64(value)));
+            let value = match value {
+                Ok((debug, number)) => {
+                    if let Some(number) = number {
+                        watch.samples.push_back((time, number));
+                        while watch.samples.len() > capacity {
+                            watch.samples.pop_front();
+                        }
+                    }
+                    Ok(debug)
+                }
+                Err(err) => {
+                    // Errors are reported once, rather than every frame.
+                    if watch.value.as_ref().err() != Some(&err) {
+                        world
+                            .resource_mut::<Popups>()
+                            .error(format!("failed to watch `{}`: {err}", watch.expression));
+                    }
+                    Err(err)
+                }
+            };
+            watch.value = value;
+        }
+    });
+}
+
+/// Pause virtual time when the condition of a breakpoint becomes true. Breakpoints only trigger
+/// when their condition changes from false to true, so the app can be resumed while it holds.
+fn check_breakpoints(world: &mut World) {
+    world.resource_scope(|world, mut watches: Mut<Watches>| {
+        for breakpoint in watches.breakpoints.iter_mut() {
+            if !breakpoint.enabled {
+                breakpoint.satisfied = false;
+                continue;
+            }
+            match breakpoint.check(world) {
+                Ok(satisfied) => {
+                    if satisfied && !breakpoint.satisfied {
+                        breakpoint.hits += 1;
+                        world.resource_mut::<Time<Virtual>>().pause();
+                        world
+                            .resource_mut::<Popups>()
+                            .warn(format!("paused on breakpoint `{}`", breakpoint.condition));
+                    }
+                    breakpoint.satisfied = satisfied;
+                    breakpoint.error = None;
+                }
+                Err(err) => {
+                    if breakpoint.error.as_ref() != Some(&err) {
+                        world.resource_mut::<Popups>().error(format!(
+                            "failed to check breakpoint `{}`: {err}",
+                            breakpoint.condition
+                        ));
+                    }
+                    breakpoint.satisfied = false;
+                    breakpoint.error = Some(err);
+                }
+            }
+        }
+    });
+}
+
+#[derive(Default)]
+struct WatchesTab {
+    expression: String,
+    condition: String,
+}
+
+impl Tab for WatchesTab {
+    fn name(&self) -> &str {
+        "Watches"
+    }
+
+    /// The number of expressions that can't be read.
+    fn badge(&self, world: &World) -> Option<String> {
+        let watches = world.get_resource::<Watches>()?;
+        let failing = watches
+            .expressions
+            .iter()
+            .filter(|e| e.value.is_err())
+            .count();
+        (failing > 0).then(|| failing.to_string())
+    }
+
+    fn draw(&mut self, ui: &mut Ui, world: &mut World) {
+        let mut added = None;
+        ui.horizontal(|ui| {
+            egui::TextEdit::singleline(&mut self.expression)
+                .hint_text("e.g. `Player/Transform.translation.y` or `Res<Score>.points`")
+                .desired_width(300.0)
+                .show(ui);
+            if ui.button("watch").clicked() {
+                added = Some(WatchExpression::parse(&self.expression));
+            }
+        });
+        match added {
+            Some(Ok(expression)) => {
+                world.resource_mut::<Watches>().expressions.push(expression);
+                self.expression.clear();
+            }
+            Some(Err(err)) => world.resource_mut::<Popups>().error(err),
+            None => (),
+        }
+
+        draw_breakpoints(ui, world, &mut self.condition);
+        ui.separator();
+
+        let mut watches = world.resource_mut::<Watches>();
+        let mut removed = None;
+        for (i, watch) in watches.expressions.iter_mut().enumerate() {
+            ui.horizontal(|ui| {
+                ui.label(&watch.expression);
+                match &watch.value {
+                    Ok(value) => ui.monospace(value),
+                    Err(err) => ui.colored_label(ui.visuals().error_fg_color, err),
+                };
+                if ui.small_button("remove").clicked() {
+                    removed = Some(i);
+                }
+            });
+            if !watch.samples.is_empty() {
+                let points = watch.samples.iter().copied().collect::<Vec<_>>();
+                line_plot(ui, &points);
+            }
+        }
+        if let Some(i) = removed {
+            watches.expressions.remove(i);
+        }
+        ui.separator();
+
+        if watches.watches.is_empty() {
+            ui.weak("No fields are watched. Right-click a number in the entities tab to watch it.");
+            return;
+        }
+
+        let mut removed = None;
+        for (i, watch) in watches.watches.iter_mut().enumerate() {
+            egui::CollapsingHeader::new(watch.label())
+                .id_source(("watch", i))
+                .default_open(true)
+                .show(ui, |ui| {
+                    ui.horizontal(|ui| {
+                        match watch.samples.back() {
+                            Some((_, value)) => ui.label(format!("{value:.3}")),
+                            None => ui.weak("no samples"),
+                        };
+                        if ui.small_button("clear").clicked() {
+                            watch.samples.clear();
+                        }
+                        if ui.small_button("remove").clicked() {
+                            removed = Some(i);
+                        }
+                    });
+                    let points = watch.samples.iter().copied().collect::<Vec<_>>();
+                    line_plot(ui, &points);
+                });
+        }
+        if let Some(i) = removed {
+            watches.watches.remove(i);
+        }
+    }
+}
+
+fn draw_breakpoints(ui: &mut Ui, world: &mut World, condition: &mut String) {
+    let mut added = None;
+    ui.horizontal(|ui| {
+        egui::TextEdit::singleline(condition)
+            .hint_text("Pause when, e.g. `Health.current < 10`")
+            .desired_width(300.0)
+            .show(ui);
+        if ui.button("add breakpoint").clicked() {
+            added = Some(Breakpoint::parse(condition));
+        }
+    });
+    match added {
+        Some(Ok(breakpoint)) => {
+            world.resource_mut::<Watches>().breakpoints.push(breakpoint);
+            condition.clear();
+        }
+        Some(Err(err)) => world.resource_mut::<Popups>().error(err),
+        None => (),
+    }
+
+    let mut watches = world.resource_mut::<Watches>();
+    let mut removed = None;
+    for (i, breakpoint) in watches.breakpoints.iter_mut().enumerate() {
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut breakpoint.enabled, &breakpoint.condition);
+            ui.weak(format!("{} hits", breakpoint.hits));
+            if let Some(err) = &breakpoint.error {
+                ui.colored_label(ui.visuals().error_fg_color, err);
+            }
+            if ui.small_button("remove").clicked() {
+                removed = Some(i);
+            }
+        });
+    }
+    if let Some(i) = removed {
+        watches.breakpoints.remove(i);
+    }
+}
@@ -0,0 +1,472 @@
+//! The world stats tab module. Displays statistics about the archetypes and tables of the world,
+//! which helps to find archetype fragmentation and runaway spawners. Can also track how often
+//! entities move between archetypes, as component insertion and removal churn is costly, and
+//! estimates the memory used by every component type, to find component types that keep growing.
+
+use bevy::ecs::archetype::{Archetype, ArchetypeId};
+use bevy::ecs::component::ComponentId;
+use bevy::prelude::*;
+use bevy::utils::{get_short_name, HashMap, HashSet};
+
+use crate::compat::egui::{self, Ui};
+use crate::{Spyglass, Tab};
+
+/// The plugin that adds the world stats tab to the end of the [`Spyglass`] tab list.
+pub struct WorldStatsTabPlugin;
+
+impl Plugin for WorldStatsTabPlugin {
+    fn build(&self, app: &mut App) {
+        if cfg!(feature = "disabled") {
+            return;
+        }
+
+        let mut spyglass = app.world.resource_mut::<Spyglass>();
+        spyglass.add_tab(Box::new(WorldStatsTab::default()));
+
+        app.init_resource::<ArchetypeChurn>()
+            .add_systems(Last, track_archetype_moves);
+    }
+}
+
+/// How often a component type was involved in entities moving between archetypes.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ComponentChurn {
+    /// The number of times the component was added to an existing entity.
+    pub added: usize,
+    /// The number of times the component was removed from an entity that was not despawned.
+    pub removed: usize,
+}
+
+/// The resource that tracks entities moving between archetypes. Tracking compares the archetype
+/// of every entity each frame, so it is disabled by default.
+#[derive(Default, Resource)]
+pub struct ArchetypeChurn {
+    /// Whether archetype moves are tracked.
+    pub enabled: bool,
+    /// The number of entities that moved archetype in the last frame.
+    pub last_frame: usize,
+    /// The number of archetype moves since tracking started.
+    pub total: usize,
+    /// The number of frames tracked.
+    pub frames: usize,
+    /// The churn of each component type since tracking started.
+    pub components: HashMap<ComponentId, ComponentChurn>,
+    locations: HashMap<Entity, ArchetypeId>,
+}
+
+impl ArchetypeChurn {
+    /// Clear the tracked statistics.
+    pub fn reset(&mut self) {
+        self.last_frame = 0;
+        self.total = 0;
+        self.frames = 0;
+        self.components.clear();
+    }
+}
+
+fn track_archetype_moves(world: &mut World) {
+    world.resource_scope(|world, mut churn: Mut<ArchetypeChurn>| {
+        if !churn.enabled {
+            churn.locations.clear();
+            return;
+        }
+        // Moves between the frames the tab skips are only seen as one move.
+        if !Spyglass::tab_refreshes(world, "World Stats") {
+            return;
+        }
+
+        let churn = &mut *churn;
+        let archetypes = world.archetypes();
+        let mut locations = HashMap::with_capacity(churn.locations.len());
+        let mut moves = 0;
+        for archetype in archetypes.iter() {
+            for entity in archetype.entities() {
+                let entity = entity.entity();
+                locations.insert(entity, archetype.id());
+
+                let Some(&previous) = churn.locations.get(&entity) else {
+                    continue;
+                };
+                if previous == archetype.id() {
+                    continue;
+                }
+                moves += 1;
+
+                let before = archetypes[previous].components().collect::<HashSet<_>>();
+                let after = archetype.components().collect::<HashSet<_>>();
+                for &comp in after.difference(&before) {
+                    churn.components.entry(comp).or_default().added += 1;
+                }
+                for &comp in before.difference(&after) {
+                    churn.components.entry(comp).or_default().removed += 1;
+                }
+            }
+        }
+
+        churn.frames += 1;
+        churn.locations = locations;
+        churn.last_frame = moves;
+        churn.total += moves;
+    });
+}
+
+/// The estimated memory used by a component type, see [`component_memory`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ComponentMemory {
+    /// The component type.
+    pub id: ComponentId,
+    /// The type name of the component.
+    pub name: String,
+    /// The number of entities with the component.
+    pub count: usize,
+    /// The size of one component, in bytes.
+    pub size: usize,
+}
+
+impl ComponentMemory {
+    /// The estimated memory used by all components of the type, in bytes.
+    pub fn bytes(&self) -> usize {
+        self.size * self.count
+    }
+}
+
+/// Estimate the memory used by every component type that at least one entity has, as the size of
+/// its layout times the number of entities with it across all archetypes. Only the inline size is
+/// counted, so memory that components allocate themselves, such as the contents of a `Vec`, is
+/// not included, and neither is spare table capacity.
+pub fn component_memory(world: &World) -> Vec<ComponentMemory> {
+    let mut counts = HashMap::<ComponentId, usize>::new();
+    for archetype in world.archetypes().iter() {
+        for comp in archetype.components() {
+            *counts.entry(comp).or_default() += archetype.len();
+        }
+    }
+    counts
+        .into_iter()
+        .filter(|&(_, count)| count > 0)
+        .filter_map(|(id, count)| {
+            let info = world.components().get_info(id)?;
+            Some(ComponentMemory {
+                id,
+                name: info.name().to_string(),
+                count,
+                size: info.layout().size(),
+            })
+        })
+        .collect()
+}
+
+/// Format a number of bytes with a binary unit.
+fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit + 1 < UNITS.len() {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{value:.1} {}", UNITS[unit])
+    }
+}
+
+/// The column the component memory table is sorted by.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum MemorySort {
+    Name,
+    Count,
+    Size,
+    #[default]
+    Total,
+    Delta,
+}
+
+/// A row of the component memory table.
+struct MemoryRow {
+    memory: ComponentMemory,
+    /// The change in bytes since the baseline.
+    delta: i64,
+}
+
+/// The totals of the world, collected by the world stats tab.
+#[derive(Default)]
+struct WorldTotals {
+    entities: u32,
+    component_types: usize,
+    resources: usize,
+    archetypes: usize,
+    tables: usize,
+}
+
+/// The statistics of an archetype, collected by the world stats tab.
+struct ArchetypeStats {
+    id: ArchetypeId,
+    entities: usize,
+    table: usize,
+    table_rows: usize,
+    table_capacity: usize,
+    /// The sorted type names of the components.
+    components: Vec<String>,
+}
+
+impl ArchetypeStats {
+    fn new(world: &World, archetype: &Archetype) -> Self {
+        let table = &world.storages().tables[archetype.table_id()];
+        let mut components = archetype
+            .components()
+            .map(|comp| {
+                world
+                    .components()
+                    .get_name(comp)
+                    .map(str::to_string)
+                    .unwrap_or_else(|| format!("ComponentId({comp:?})"))
+            })
+            .collect::<Vec<_>>();
+        components.sort_unstable();
+        Self {
+            id: archetype.id(),
+            entities: archetype.len(),
+            table: archetype.table_id().index(),
+            table_rows: table.entity_count(),
+            table_capacity: table.entity_capacity(),
+            components,
+        }
+    }
+}
+
+#[derive(Default)]
+struct WorldStatsTab {
+    show_empty: bool,
+    totals: WorldTotals,
+    /// The archetypes, largest first.
+    archetypes: Vec<ArchetypeStats>,
+    memory: Vec<MemoryRow>,
+    memory_sort: MemorySort,
+    /// The bytes of every component type when the baseline was set, which the deltas are
+    /// relative to. Set on the first collection.
+    memory_baseline: Option<HashMap<ComponentId, usize>>,
+}
+
+impl Tab for WorldStatsTab {
+    fn name(&self) -> &str {
+        "World Stats"
+    }
+
+    fn collect(&mut self, world: &mut World) {
+        let resources = world
+            .storages()
+            .resources
+            .iter()
+            .filter(|(_, data)| data.is_present())
+            .count()
+            + world
+                .storages()
+                .non_send_resources
+                .iter()
+                .filter(|(_, data)| data.is_present())
+                .count();
+        self.totals = WorldTotals {
+            entities: world.entities().len(),
+            component_types: world.components().len(),
+            resources,
+            archetypes: world.archetypes().len(),
+            tables: world.storages().tables.len(),
+        };
+
+        self.archetypes = world
+            .archetypes()
+            .iter()
+            .filter(|archetype| self.show_empty || !archetype.is_empty())
+            .map(|archetype| ArchetypeStats::new(world, archetype))
+            .collect();
+        self.archetypes
+            .sort_by_key(|archetype| std::cmp::Reverse(archetype.entities));
+
+        let memory = component_memory(world);
+        let baseline = self.memory_baseline.get_or_insert_with(|| {
+            memory
+                .iter()
+                .map(|memory| (memory.id, memory.bytes()))
+                .collect()
+        });
+        self.memory = memory
+            .into_iter()
+            .map(|memory| MemoryRow {
+                delta: memory.bytes() as i64
+                    - baseline.get(&memory.id).copied().unwrap_or_default() as i64,
+                memory,
+            })
+            .collect();
+    }
+
+    fn draw(&mut self, ui: &mut Ui, world: &mut World) {
+        let totals = &self.totals;
+        ui.group(|ui| {
+            egui::Grid::new("world_stats_totals").show(ui, |ui| {
+                ui.label("entities");
+                ui.label(totals.entities.to_string());
+                ui.end_row();
+                ui.label("component types");
+                ui.label(totals.component_types.to_string());
+                ui.end_row();
+                ui.label("resources");
+                ui.label(totals.resources.to_string());
+                ui.end_row();
+                ui.label("archetypes");
+                ui.label(totals.archetypes.to_string());
+                ui.end_row();
+                ui.label("tables");
+                ui.label(totals.tables.to_string());
+                ui.end_row();
+            });
+        });
+
+        draw_churn(ui, world);
+        self.draw_memory(ui);
+
+        ui.checkbox(&mut self.show_empty, "show empty archetypes");
+
+        for archetype in &self.archetypes {
+            draw_archetype(ui, archetype);
+        }
+    }
+}
+
+impl WorldStatsTab {
+    fn draw_memory(&mut self, ui: &mut Ui) {
+        ui.collapsing("component memory", |ui| {
+            ui.horizontal(|ui| {
+                let total = self.memory.iter().map(|row| row.memory.bytes()).sum();
+                ui.label(format!("{} estimated in components", format_bytes(total)));
+                if ui
+                    .button("set baseline")
+                    .on_hover_text("Measure the deltas from the current memory use")
+                    .clicked()
+                {
+                    self.memory_baseline = Some(
+                        self.memory
+                            .iter()
+                            .map(|row| (row.memory.id, row.memory.bytes()))
+                            .collect(),
+                    );
+                    for row in &mut self.memory {
+                        row.delta = 0;
+                    }
+                }
+            });
+
+            match self.memory_sort {
+                MemorySort::Name => self.memory.sort_by(|a, b| {
+                    get_short_name(&a.memory.name).cmp(&get_short_name(&b.memory.name))
+                }),
+                MemorySort::Count => self
+                    .memory
+                    .sort_by_key(|row| std::cmp::Reverse(row.memory.count)),
+                MemorySort::Size => self
+                    .memory
+                    .sort_by_key(|row| std::cmp::Reverse(row.memory.size)),
+                MemorySort::Total => self
+                    .memory
+                    .sort_by_key(|row| std::cmp::Reverse(row.memory.bytes())),
+                MemorySort::Delta => self.memory.sort_by_key(|row| std::cmp::Reverse(row.delta)),
+            }
+            egui::Grid::new("component_memory")
+                .striped(true)
+                .show(ui, |ui| {
+                    for (sort, label) in [
+                        (MemorySort::Name, "component"),
+                        (MemorySort::Count, "count"),
+                        (MemorySort::Size, "size"),
+                        (MemorySort::Total, "total"),
+                        (MemorySort::Delta, "delta"),
+                    ] {
+                        ui.selectable_value(&mut self.memory_sort, sort, label);
+                    }
+                    ui.end_row();
+                    for row in &self.memory {
+                        let memory = &row.memory;
+                        ui.label(get_short_name(&memory.name))
+                            .on_hover_text(&memory.name);
+                        ui.monospace(memory.count.to_string());
+                        ui.monospace(format_bytes(memory.size));
+                        ui.monospace(format_bytes(memory.bytes()));
+                        let delta = format_bytes(row.delta.unsigned_abs() as usize);
+                        match row.delta {
+                            0 => ui.weak("-"),
+                            d if d > 0 => {
+                                ui.colored_label(ui.visuals().warn_fg_color, format!("+{delta}"))
+                            }
+                            _ => ui.monospace(format!("-{delta}")),
+                        };
+                        ui.end_row();
+                    }
+                });
+        });
+    }
+}
+
+fn draw_archetype(ui: &mut Ui, archetype: &ArchetypeStats) {
+    egui::CollapsingHeader::new(format!(
+        "Archetype {} - {} entities, {} components",
+        archetype.id.index(),
+        archetype.entities,
+        archetype.components.len(),
+    ))
+    .id_source(archetype.id)
+    .show(ui, |ui| {
+        ui.label(format!(
+            "table {}: {} rows, capacity {}",
+            archetype.table, archetype.table_rows, archetype.table_capacity,
+        ));
+        ui.separator();
+
+        for name in &archetype.components {
+            ui.label(get_short_name(name)).on_hover_text(name);
+        }
+    });
+}
+
+fn draw_churn(ui: &mut Ui, world: &mut World) {
+    world.resource_scope(|world, mut churn: Mut<ArchetypeChurn>| {
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut churn.enabled, "track archetype moves");
+            if ui.button("reset").clicked() {
+                churn.reset();
+            }
+        });
+        if !churn.enabled {
+            return;
+        }
+
+        let average = churn.total as f32 / churn.frames.max(1) as f32;
+        ui.label(format!(
+            "{} moves last frame, {average:.1} per frame over {} frames",
+            churn.last_frame, churn.frames
+        ));
+
+        let mut offenders = churn.components.iter().collect::<Vec<_>>();
+        offenders.sort_by_key(|(_, c)| std::cmp::Reverse(c.added + c.removed));
+
+        egui::Grid::new("archetype_churn")
+            .striped(true)
+            .show(ui, |ui| {
+                ui.strong("component");
+                ui.strong("added");
+                ui.strong("removed");
+                ui.end_row();
+                for (&comp, c) in offenders.into_iter().take(10) {
+                    let name = world
+                        .components()
+                        .get_name(comp)
+                        .map(str::to_string)
+                        .unwrap_or_else(|| format!("ComponentId({comp:?})"));
+                    ui.label(get_short_name(&name)).on_hover_text(name);
+                    ui.label(c.added.to_string());
+                    ui.label(c.removed.to_string());
+                    ui.end_row();
+                }
+            });
+    });
+}
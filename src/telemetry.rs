@@ -0,0 +1,241 @@
+//! The telemetry module. Samples the data the inspector shows, such as diagnostics and world
+//! statistics, at a fixed interval and periodically flushes it to disk. [`TelemetryPlugin`] does
+//! not need the inspector UI, so it can run in shipped playtests; the resulting files can be
+//! viewed later in the telemetry tab of the full inspector.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use bevy::app::AppExit;
+use bevy::core::FrameCount;
+use bevy::diagnostic::DiagnosticsStore;
+use bevy::prelude::*;
+use bevy::tasks::AsyncComputeTaskPool;
+use bevy::time::Real;
+use serde::{Deserialize, Serialize};
+
+use crate::export::write_export;
+use crate::formats::{FormatError, SpyglassFormat, SpyglassFormats};
+use crate::tabs::watches::{reflect_to_f64, Watches};
+
+/// The plugin that samples telemetry. Can be added with or without the
+/// [`SpyglassPlugin`](crate::SpyglassPlugin).
+pub struct TelemetryPlugin;
+
+impl Plugin for TelemetryPlugin {
+    fn build(&self, app: &mut App) {
+        if cfg!(feature = "disabled") {
+            return;
+        }
+
+        app.init_resource::<Telemetry>()
+            .init_resource::<SpyglassFormats>()
+            .add_systems(Last, (sample_telemetry, flush_telemetry).chain());
+    }
+}
+
+/// A sample of telemetry, taken at the end of a frame.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct TelemetrySample {
+    /// The frame the sample was taken on.
+    pub frame: u32,
+    /// The real time since startup, in seconds.
+    pub time: f64,
+    /// The number of entities.
+    pub entities: u32,
+    /// The number of archetypes.
+    pub archetypes: u32,
+    /// The smoothed value of every diagnostic, by name, and the value of every numeric watch and
+    /// watch expression as `watch:<label>`.
+    pub diagnostics: BTreeMap<String, f64>,
+}
+
+impl TelemetrySample {
+    /// Get the value of a series by name: `entities`, `archetypes`, the name of a diagnostic or
+    /// `watch:<label>`.
+    pub fn get(&self, series: &str) -> Option<f64> {
+        match series {
+            "entities" => Some(self.entities as f64),
+            "archetypes" => Some(self.archetypes as f64),
+            _ => self.diagnostics.get(series).copied(),
+        }
+    }
+}
+
+/// A log of telemetry samples, as saved to disk.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct TelemetryLog {
+    /// The samples, in order.
+    pub samples: Vec<TelemetrySample>,
+}
+
+impl TelemetryLog {
+    /// Get the names of every series in the log.
+    pub fn series(&self) -> Vec<String> {
+        let mut series = vec!["entities".to_string(), "archetypes".to_string()];
+        for sample in self.samples.iter() {
+            for name in sample.diagnostics.keys() {
+                if !series.contains(name) {
+                    series.push(name.clone());
+                }
+            }
+        }
+        series
+    }
+
+    /// Serialize the log with the given format.
+    pub fn serialize(&self, format: &dyn SpyglassFormat) -> Result<Vec<u8>, FormatError> {
+        format.serialize(self)
+    }
+
+    /// Deserialize a log with the given format.
+    pub fn deserialize(format: &dyn SpyglassFormat, bytes: &[u8]) -> Result<Self, FormatError> {
+        let mut log = None;
+        format.deserialize(bytes, &mut |de| {
+            log = Some(erased_serde::deserialize(de)?);
+            Ok(())
+        })?;
+        log.ok_or_else(|| FormatError::new("no telemetry was deserialized"))
+    }
+}
+
+/// The resource that configures telemetry, and stores the samples taken so far.
+#[derive(Resource)]
+pub struct Telemetry {
+    /// The real time between samples.
+    pub interval: Duration,
+    /// The real time between flushes to disk. The log is also flushed when the app exits.
+    pub flush_interval: Duration,
+    /// The path the log is flushed to, without an extension. The extension of the current
+    /// [`SpyglassFormats`] format is added.
+    pub path: PathBuf,
+    /// The samples taken so far.
+    pub log: TelemetryLog,
+    last_sample: Option<Duration>,
+    last_flush: Duration,
+}
+
+impl Default for Telemetry {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(1),
+            flush_interval: Duration::from_secs(30),
+            path: PathBuf::from("telemetry"),
+            log: TelemetryLog::default(),
+            last_sample: None,
+            last_flush: Duration::ZERO,
+        }
+    }
+}
+
+impl Telemetry {
+    /// Write the log to disk now.
+    pub fn flush(&self, formats: &SpyglassFormats) -> Result<PathBuf, String> {
+        let format = formats.current();
+        let bytes = self.log.serialize(format).map_err(|e| e.to_string())?;
+        let path = self.path.with_extension(format.extension());
+        write_export(&path, &bytes)?;
+        Ok(path)
+    }
+
+    /// Write a copy of the log to disk on the [`AsyncComputeTaskPool`], so that large logs do not
+    /// block the frame. Errors are logged. Flushes immediately if there is no task pool.
+    pub fn flush_in_background(&self, formats: &SpyglassFormats) {
+        let Some(pool) = AsyncComputeTaskPool::try_get() else {
+            if let Err(err) = self.flush(formats) {
+                error!("failed to flush spyglass telemetry: {err}");
+            }
+            return;
+        };
+
+        let format = formats.current_shared();
+        let log = self.log.clone();
+        let path = self.path.with_extension(format.extension());
+        pool.spawn(async move {
+            let result = log
+                .serialize(&*format)
+                .map_err(|e| e.to_string())
+                .and_then(|bytes| write_export(&path, &bytes));
+            if let Err(err) = result {
+                error!("failed to flush spyglass telemetry: {err}");
+            }
+        })
+        .detach();
+    }
+}
+
+fn sample_telemetry(world: &mut World) {
+    let now = world.resource::<Time<Real>>().elapsed();
+    let mut telemetry = world.resource_mut::<Telemetry>();
+    if telemetry
+        .last_sample
+        .is_some_and(|last| now - last < telemetry.interval)
+    {
+        return;
+    }
+    telemetry.last_sample = Some(now);
+
+    let mut diagnostics: BTreeMap<_, _> = world
+        .get_resource::<DiagnosticsStore>()
+        .map(|store| {
+            store
+                .iter()
+                .filter_map(|diagnostic| {
+                    Some((diagnostic.name.to_string(), diagnostic.smoothed()?))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    diagnostics.extend(sample_watches(world));
+    let sample = TelemetrySample {
+        frame: world
+            .get_resource::<FrameCount>()
+            .map_or(0, |frame| frame.0),
+        time: now.as_secs_f64(),
+        entities: world.entities().len(),
+        archetypes: world.archetypes().len() as u32,
+        diagnostics,
+    };
+    world.resource_mut::<Telemetry>().log.samples.push(sample);
+}
+
+/// The current value of every numeric watch and watch expression, as `watch:<label>` series.
+fn sample_watches(world: &World) -> Vec<(String, f64)> {
+    let Some(watches) = world.get_resource::<Watches>() else {
+        return vec![];
+    };
+    let watched = watches
+        .watches
+        .iter()
+        .filter_map(|watch| Some((watch.label(), watch.read(world)?)));
+    let expressions = watches.expressions.iter().filter_map(|watch| {
+        let value = watch.evaluate(world, reflect_to_f64).ok()??;
+        Some((watch.expression.clone(), value))
+    });
+    watched
+        .chain(expressions)
+        .map(|(label, value)| (format!("watch:{label}"), value))
+        .collect()
+}
+
+fn flush_telemetry(
+    mut telemetry: ResMut<Telemetry>,
+    formats: Res<SpyglassFormats>,
+    time: Res<Time<Real>>,
+    mut exit: EventReader<AppExit>,
+) {
+    let exiting = exit.read().next().is_some();
+    let now = time.elapsed();
+    if !exiting && now - telemetry.last_flush < telemetry.flush_interval {
+        return;
+    }
+    telemetry.last_flush = now;
+
+    // The app does not wait for background tasks before exiting.
+    if !exiting {
+        telemetry.flush_in_background(&formats);
+    } else if let Err(err) = telemetry.flush(&formats) {
+        error!("failed to flush spyglass telemetry: {err}");
+    }
+}
@@ -0,0 +1,401 @@
+//! Reusable widgets for tabs and editors.
+
+use std::any::TypeId;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use bevy::prelude::*;
+use bevy::utils::get_short_name;
+
+use crate::compat::egui::{self, ScrollArea, Ui};
+use crate::compat::{Egui, EguiBackend};
+use crate::SpyglassContext;
+
+/// The folder that asset path pickers list files from. Defaults to the same `assets` folder
+/// the [`AssetPlugin`] loads from by default, and should be changed if that is configured.
+#[derive(Resource)]
+pub struct AssetFolder(pub PathBuf);
+
+impl Default for AssetFolder {
+    fn default() -> Self {
+        #[cfg(not(target_arch = "wasm32"))]
+        let base = bevy::asset::io::file::FileAssetReader::get_base_path();
+        #[cfg(target_arch = "wasm32")]
+        let base = PathBuf::new();
+        Self(base.join("assets"))
+    }
+}
+
+impl AssetFolder {
+    /// List the paths of all files under the asset folder, relative to it and separated by `/`,
+    /// which have one of the given extensions. An empty list of extensions matches every file.
+    pub fn files(&self, extensions: &[&str]) -> Vec<String> {
+        let mut files = vec![];
+        collect_files(&self.0, &self.0, extensions, &mut files);
+        files.sort_unstable();
+        files
+    }
+}
+
+fn collect_files(root: &Path, dir: &Path, extensions: &[&str], files: &mut Vec<String>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_files(root, &path, extensions, files);
+            continue;
+        }
+
+        let extension = path.extension().and_then(|ext| ext.to_str()).unwrap_or("");
+        if !extensions.is_empty()
+            && !extensions
+                .iter()
+                .any(|ext| ext.eq_ignore_ascii_case(extension))
+        {
+            continue;
+        }
+
+        if let Ok(relative) = path.strip_prefix(root) {
+            let relative = relative
+                .components()
+                .map(|comp| comp.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            files.push(relative);
+        }
+    }
+}
+
+/// A button that opens a menu listing the files under the [`AssetFolder`] with one of the given
+/// extensions, filterable by name. Picking a file writes its path, relative to the asset folder,
+/// to `path` and returns `true`. The folder is listed when the menu opens, rather than every frame.
+pub fn asset_path_picker(
+    ui: &mut Ui,
+    path: &mut String,
+    folder: &AssetFolder,
+    extensions: &[&str],
+) -> bool {
+    let filter_id = ui.id().with("asset_path_filter");
+    let files_id = ui.id().with("asset_path_files");
+    let mut picked = false;
+
+    let open = ui.menu_button("browse", |ui| {
+        let mut filter = ui.data_mut(|data| data.get_temp::<String>(filter_id).unwrap_or_default());
+        egui::TextEdit::singleline(&mut filter)
+            .hint_text("Filter")
+            .show(ui);
+
+        let files = ui.data_mut(|data| data.get_temp::<Arc<Vec<String>>>(files_id));
+        let files = files.unwrap_or_else(|| {
+            let files = Arc::new(folder.files(extensions));
+            ui.data_mut(|data| data.insert_temp(files_id, files.clone()));
+            files
+        });
+        if files.is_empty() {
+            ui.weak(format!("no matching files in `{}`", folder.0.display()));
+        }
+
+        ScrollArea::new([false, true])
+            .max_height(300.0)
+            .show(ui, |ui| {
+                for file in files.iter().filter(|file| file.contains(filter.as_str())) {
+                    if ui.selectable_label(*path == *file, file).clicked() {
+                        *path = file.clone();
+                        picked = true;
+                        ui.close_menu();
+                    }
+                }
+            });
+
+        ui.data_mut(|data| data.insert_temp(filter_id, filter));
+    });
+
+    // Forget the listing once the menu closes, so it is up to date when the menu opens again.
+    if open.inner.is_none() {
+        ui.data_mut(|data| data.remove::<Arc<Vec<String>>>(files_id));
+    }
+    picked
+}
+
+/// The most entities an [`entity_picker`] lists at once.
+const MAX_PICKER_ENTITIES: usize = 100;
+
+/// A button that opens a menu listing the entities of the world by name, filterable by name.
+/// Entities for which `exclude` returns `true` are not listed. Returns the picked entity.
+pub fn entity_picker(
+    ui: &mut Ui,
+    label: &str,
+    world: &World,
+    exclude: impl Fn(Entity) -> bool,
+) -> Option<Entity> {
+    let filter_id = ui.id().with(("entity_picker_filter", label));
+    let mut picked = None;
+
+    ui.menu_button(label, |ui| {
+        let mut filter = ui.data_mut(|data| data.get_temp::<String>(filter_id).unwrap_or_default());
+        egui::TextEdit::singleline(&mut filter)
+            .hint_text("Filter")
+            .show(ui);
+
+        let mut entities = world
+            .iter_entities()
+            .map(|entity| entity.id())
+            .filter(|&entity| !exclude(entity))
+            .map(|entity| match world.get::<Name>(entity) {
+                Some(name) => (entity, format!("{name} ({entity:?})")),
+                None => (entity, format!("{entity:?}")),
+            })
+            .filter(|(_, label)| label.contains(filter.as_str()))
+            .collect::<Vec<_>>();
+        entities.sort_unstable_by_key(|&(entity, _)| entity);
+        if entities.len() > MAX_PICKER_ENTITIES {
+            ui.weak(format!(
+                "showing {MAX_PICKER_ENTITIES} of {} entities",
+                entities.len()
+            ));
+            entities.truncate(MAX_PICKER_ENTITIES);
+        }
+
+        ScrollArea::new([false, true])
+            .max_height(300.0)
+            .show(ui, |ui| {
+                for (entity, label) in entities {
+                    if ui.selectable_label(false, label).clicked() {
+                        picked = Some(entity);
+                        ui.close_menu();
+                    }
+                }
+            });
+
+        ui.data_mut(|data| data.insert_temp(filter_id, filter));
+    });
+
+    picked
+}
+
+/// The component types of the app: those registered with [`ReflectComponent`], which can be
+/// picked before any entity has them, and those of the world. Returned as their type ids, short
+/// names and full names, sorted by short name.
+pub fn component_types(world: &World) -> Vec<(TypeId, String, String)> {
+    let registry = world.resource::<AppTypeRegistry>().read();
+    let registered = registry
+        .iter()
+        .filter(|registration| registration.data::<ReflectComponent>().is_some())
+        .map(|registration| {
+            let table = registration.type_info().type_path_table();
+            (registration.type_id(), table.path().to_string())
+        });
+    let live = world
+        .components()
+        .iter()
+        .filter_map(|info| Some((info.type_id()?, info.name().to_string())));
+    let mut components = registered
+        .chain(live)
+        .map(|(id, name)| (id, get_short_name(&name), name))
+        .collect::<Vec<_>>();
+    components.sort_unstable_by(|a, b| a.1.cmp(&b.1).then(a.2.cmp(&b.2)));
+    components.dedup_by_key(|(id, ..)| *id);
+    components
+}
+
+/// A button that opens a menu listing the [`component_types`] by short name, filterable by name.
+/// Components for which `exclude` returns `true` are not listed. Returns the picked component.
+pub fn component_picker(
+    ui: &mut Ui,
+    label: &str,
+    world: &World,
+    exclude: impl Fn(TypeId) -> bool,
+) -> Option<TypeId> {
+    let filter_id = ui.id().with(("component_picker_filter", label));
+    let mut picked = None;
+
+    ui.menu_button(label, |ui| {
+        let mut filter = ui.data_mut(|data| data.get_temp::<String>(filter_id).unwrap_or_default());
+        egui::TextEdit::singleline(&mut filter)
+            .hint_text("Filter")
+            .show(ui);
+
+        let needle = filter.to_lowercase();
+        let components = component_types(world)
+            .into_iter()
+            .filter(|(id, short, _)| !exclude(*id) && short.to_lowercase().contains(&needle));
+
+        ScrollArea::new([false, true])
+            .max_height(300.0)
+            .show(ui, |ui| {
+                for (id, short, name) in components {
+                    if ui
+                        .selectable_label(false, short)
+                        .on_hover_text(name)
+                        .clicked()
+                    {
+                        picked = Some(id);
+                        ui.close_menu();
+                    }
+                }
+            });
+
+        ui.data_mut(|data| data.insert_temp(filter_id, filter));
+    });
+
+    picked
+}
+
+/// Plot `(time, value)` points as a line, with the value range labelled.
+pub fn line_plot(ui: &mut Ui, points: &[(f64, f64)]) {
+    let Some(&(start, _)) = points.first() else {
+        ui.weak("The series has no samples.");
+        return;
+    };
+    let end = points.last().map_or(start, |&(time, _)| time);
+    let (min, max) = points.iter().fold(
+        (f64::INFINITY, f64::NEG_INFINITY),
+        |(min, max), &(_, value)| (min.min(value), max.max(value)),
+    );
+    ui.weak(format!("min {min:.3}, max {max:.3}"));
+
+    let (response, painter) = ui.allocate_painter(
+        egui::vec2(ui.available_width(), 160.0),
+        egui::Sense::hover(),
+    );
+    let rect = response.rect;
+    painter.rect_stroke(rect, 0.0, ui.visuals().widgets.noninteractive.bg_stroke);
+
+    let x = |time: f64| {
+        let t = if end > start {
+            (time - start) / (end - start)
+        } else {
+            0.5
+        };
+        rect.left() + t as f32 * rect.width()
+    };
+    let y = |value: f64| {
+        let t = if max > min {
+            (value - min) / (max - min)
+        } else {
+            0.5
+        };
+        rect.bottom() - t as f32 * rect.height()
+    };
+    let line = points
+        .iter()
+        .map(|&(time, value)| egui::pos2(x(time), y(value)))
+        .collect();
+    painter.add(egui::Shape::line(
+        line,
+        egui::Stroke::new(1.5, ui.visuals().selection.bg_fill),
+    ));
+}
+
+/// The resource that holds the image shown in the zoomable image preview window, which
+/// [`image_preview`] opens.
+#[derive(Default, Resource)]
+pub struct ImagePreview {
+    /// The previewed image, or `None` if the window is closed.
+    pub image: Option<AssetId<Image>>,
+    /// The scale the image is drawn at.
+    pub zoom: f32,
+}
+
+/// The size of the longest side of [`image_preview`] thumbnails.
+const THUMBNAIL_SIZE: f32 = 96.0;
+
+/// Draw a thumbnail of `image` with its size and format, and a button that opens the image in
+/// the [`ImagePreview`] window.
+pub fn image_preview(ui: &mut Ui, world: &mut World, image: AssetId<Image>) {
+    let Some((size, info)) = world
+        .get_resource::<Assets<Image>>()
+        .and_then(|images| images.get(image))
+        .map(|image| (image.size_f32(), image_info(image)))
+    else {
+        ui.weak("the image is not loaded");
+        return;
+    };
+    ui.horizontal(|ui| {
+        if let Some(texture) = Egui::image_texture(world, image) {
+            let scale = THUMBNAIL_SIZE / size.max_element().max(1.0);
+            ui.image((texture, egui::vec2(size.x, size.y) * scale.min(1.0)));
+        }
+        ui.vertical(|ui| {
+            ui.label(info);
+            if ui.button("open preview").clicked() {
+                world.insert_resource(ImagePreview {
+                    image: Some(image),
+                    zoom: 1.0,
+                });
+            }
+        });
+    });
+}
+
+/// The size, format and mip levels of an image.
+fn image_info(image: &Image) -> String {
+    let descriptor = &image.texture_descriptor;
+    format!(
+        "{}x{} {:?}, {} mip levels",
+        descriptor.size.width,
+        descriptor.size.height,
+        descriptor.format,
+        descriptor.mip_level_count
+    )
+}
+
+/// Draw the [`ImagePreview`] window, which scrolls over the image at an adjustable zoom.
+pub(crate) fn image_preview_window(world: &mut World) {
+    let Some(image) = world.get_resource::<ImagePreview>().and_then(|p| p.image) else {
+        return;
+    };
+    let Some(window) = SpyglassContext::window(world) else {
+        return;
+    };
+    let Some(ctx) = Egui::context(world, window) else {
+        return;
+    };
+    let Some((size, info)) = world
+        .get_resource::<Assets<Image>>()
+        .and_then(|images| images.get(image))
+        .map(|image| (image.size_f32(), image_info(image)))
+    else {
+        world.resource_mut::<ImagePreview>().image = None;
+        return;
+    };
+    let texture = Egui::image_texture(world, image);
+
+    let mut preview = world.resource_mut::<ImagePreview>();
+    let mut open = true;
+    egui::Window::new("image preview")
+        .open(&mut open)
+        .default_size(egui::vec2(512.0, 512.0))
+        .show(&ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.label(info);
+                ui.add(
+                    egui::Slider::new(&mut preview.zoom, 0.1..=16.0)
+                        .logarithmic(true)
+                        .text("zoom"),
+                );
+                if ui.button("fit").clicked() {
+                    let available = ui.available_width().max(1.0);
+                    preview.zoom = (available / size.x.max(1.0)).clamp(0.1, 16.0);
+                }
+            });
+            let hovered = ui.ui_contains_pointer();
+            let scroll = ui.input(|input| input.zoom_delta());
+            if hovered && scroll != 1.0 {
+                preview.zoom = (preview.zoom * scroll).clamp(0.1, 16.0);
+            }
+            let Some(texture) = texture else {
+                ui.weak("the image can't be displayed");
+                return;
+            };
+            ScrollArea::both().show(ui, |ui| {
+                ui.image((texture, egui::vec2(size.x, size.y) * preview.zoom));
+            });
+        });
+    if !open {
+        preview.image = None;
+    }
+}
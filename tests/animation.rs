@@ -0,0 +1,35 @@
+//! Checks the animation tab. Only built with the `animation` feature.
+#![cfg(feature = "animation")]
+
+mod common;
+
+use aether_spyglass::tabs::animation::AnimationTabPlugin;
+use aether_spyglass::SpyglassPlugin;
+use bevy::prelude::*;
+
+use common::{cycle_tabs, headless_app};
+
+#[test]
+fn animation_tab() {
+    let mut app = headless_app((SpyglassPlugin::default(), AnimationTabPlugin));
+    let mut clip = AnimationClip::default();
+    clip.add_curve_to_path(
+        EntityPath {
+            parts: vec![Name::new("Bone")],
+        },
+        VariableCurve {
+            keyframe_timestamps: vec![0.0, 1.0],
+            keyframes: Keyframes::Translation(vec![Vec3::ZERO, Vec3::X]),
+        },
+    );
+    let clip = app.world.resource_mut::<Assets<AnimationClip>>().add(clip);
+    let mut player = AnimationPlayer::default();
+    player.play(clip.clone()).repeat();
+    let entity = app.world.spawn((Name::new("Bone"), player)).id();
+    cycle_tabs(&mut app);
+
+    // Drawing the tab does not disturb the player.
+    let player = app.world.get::<AnimationPlayer>(entity).unwrap();
+    assert!(player.is_playing_clip(&clip));
+    assert!(!player.is_paused());
+}
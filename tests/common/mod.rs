@@ -0,0 +1,73 @@
+//! Helpers shared by the integration tests: a headless app to run the inspector in, and the
+//! custom editor example, whose `Tint` component most tests inspect.
+#![allow(dead_code)]
+
+#[path = "../../examples/custom_editor.rs"]
+pub mod custom_editor;
+
+use aether_spyglass::dump::SpyglassDump;
+use aether_spyglass::Spyglass;
+use bevy::app::Plugins;
+use bevy::log::LogPlugin;
+use bevy::prelude::*;
+use bevy::render::settings::WgpuSettings;
+use bevy::render::RenderPlugin;
+use bevy::window::PrimaryWindow;
+use bevy::winit::WinitPlugin;
+use bevy_egui::{egui, EguiContext};
+
+/// Create an app with the default plugins, but without a window backend or a renderer. The
+/// primary window entity is still spawned, so the inspector has a context to draw into.
+pub fn headless_app<M>(plugins: impl Plugins<M>) -> App {
+    let mut app = App::new();
+    app.add_plugins(
+        DefaultPlugins
+            .build()
+            .disable::<WinitPlugin>()
+            .disable::<LogPlugin>()
+            .set(RenderPlugin {
+                render_creation: WgpuSettings {
+                    backends: None,
+                    ..default()
+                }
+                .into(),
+            }),
+    )
+    .add_plugins(plugins);
+    app.finish();
+    app.cleanup();
+    app
+}
+
+pub fn run_frames(app: &mut App, frames: usize) {
+    for _ in 0..frames {
+        app.update();
+    }
+}
+
+/// Select each tab in turn for a few frames, checking that it is the one drawn.
+pub fn cycle_tabs(app: &mut App) {
+    run_frames(app, 3);
+
+    let names = app
+        .world
+        .resource::<Spyglass>()
+        .tabs
+        .iter()
+        .map(|tab| tab.name().to_string())
+        .collect::<Vec<_>>();
+    for (i, name) in names.iter().enumerate() {
+        app.world.resource_mut::<Spyglass>().selected = Some(i);
+        run_frames(app, 3);
+        assert_eq!(SpyglassDump::capture(&app.world).tab.as_ref(), Some(name));
+    }
+
+    let mut q = app
+        .world
+        .query_filtered::<&mut EguiContext, With<PrimaryWindow>>();
+    let mut ctx = q.single_mut(&mut app.world);
+    let window = ctx
+        .get_mut()
+        .memory(|memory| memory.area_rect(egui::Id::new("Spyglass")));
+    assert!(window.is_some(), "the inspector window was not drawn");
+}
@@ -0,0 +1,194 @@
+//! Checks that container types which reflect as lists or maps are edited with the list and map
+//! editors, rather than degrading to the debug-print fallback, and that their items can be added
+//! and removed.
+
+use std::collections::VecDeque;
+
+use aether_spyglass::tabs::entities::editors::EditorStates;
+use aether_spyglass::tabs::entities::ReprEditors;
+use bevy::prelude::*;
+use bevy::reflect::GetTypeRegistration;
+use bevy::utils::HashMap;
+use bevy_egui::egui;
+use smallvec::SmallVec;
+
+#[derive(Component, Default, Reflect)]
+#[reflect(Component, Default)]
+struct Containers {
+    vec: Vec<i32>,
+    deque: VecDeque<i32>,
+    small: SmallVec<[i32; 4]>,
+}
+
+/// Draws a value with its editor in a headless egui context, over several frames, so that the
+/// editor's buttons can be clicked.
+struct Harness {
+    ctx: egui::Context,
+    editors: ReprEditors,
+    states: EditorStates,
+    root: &'static str,
+    events: Vec<egui::Event>,
+    shapes: Vec<egui::epaint::ClippedShape>,
+}
+
+impl Harness {
+    /// Create a harness that draws values under the path `root`, with the collapsing headers at
+    /// `paths` requested open.
+    fn new(root: &'static str, paths: &[&str]) -> Self {
+        let mut states = EditorStates::default();
+        for path in paths {
+            states.request_expand(*path);
+        }
+        Self {
+            ctx: egui::Context::default(),
+            editors: ReprEditors::default(),
+            states,
+            root,
+            events: vec![],
+            shapes: vec![],
+        }
+    }
+
+    /// Draw a frame, and return the paths that were drawn expanded so far.
+    fn draw(&mut self, world: &mut World, value: &mut dyn Reflect) -> Vec<String> {
+        let input = egui::RawInput {
+            events: std::mem::take(&mut self.events),
+            ..default()
+        };
+        let Self {
+            editors,
+            states,
+            root,
+            ..
+        } = self;
+        let output = self.ctx.run(input, |ctx| {
+            egui::CentralPanel::default().show(ctx, |ui| {
+                states.push_path(*root);
+                let editor = editors.get(value);
+                editor(ui, value, world, editors, states);
+                states.pop_path();
+            });
+        });
+        self.shapes = output.shapes;
+        self.states.expanded().to_vec()
+    }
+
+    /// Click the `nth` button labelled `text` in the last frame, from the top, and draw the
+    /// frames of the click.
+    fn click(&mut self, world: &mut World, value: &mut dyn Reflect, text: &str, nth: usize) {
+        let mut rects = self
+            .shapes
+            .iter()
+            .filter_map(|clipped| match &clipped.shape {
+                egui::Shape::Text(shape) if shape.galley.text() == text => {
+                    Some(shape.galley.rect.translate(shape.pos.to_vec2()))
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        rects.sort_by(|a, b| a.top().total_cmp(&b.top()));
+        let pos = rects
+            .get(nth)
+            .unwrap_or_else(|| panic!("no button `{text}` number {nth}"))
+            .center();
+
+        let button = |pressed| egui::Event::PointerButton {
+            pos,
+            button: egui::PointerButton::Primary,
+            pressed,
+            modifiers: egui::Modifiers::NONE,
+        };
+        self.events = vec![egui::Event::PointerMoved(pos)];
+        self.draw(world, value);
+        self.events = vec![button(true)];
+        self.draw(world, value);
+        self.events = vec![button(false)];
+        self.draw(world, value);
+        self.draw(world, value);
+    }
+}
+
+fn world_with<T: GetTypeRegistration>() -> World {
+    let mut world = World::new();
+    world.init_resource::<AppTypeRegistry>();
+    world.resource::<AppTypeRegistry>().write().register::<T>();
+    world
+}
+
+#[test]
+fn list_like_containers_use_the_list_editor() {
+    let mut world = world_with::<Containers>();
+
+    let containers = Containers {
+        vec: vec![1, 2],
+        deque: VecDeque::from([3, 4]),
+        small: SmallVec::from_slice(&[5, 6]),
+    };
+    // Components are edited through dynamic clones, like in the entities tab.
+    let mut repr = containers.clone_value();
+
+    let paths = [
+        "Containers",
+        "Containers.vec",
+        "Containers.deque",
+        "Containers.small",
+    ];
+    let mut harness = Harness::new("Containers", &paths);
+    let expanded = harness.draw(&mut world, repr.as_mut());
+    for path in paths {
+        assert!(
+            expanded.iter().any(|expanded| expanded == path),
+            "`{path}` was not drawn as an expandable list: {expanded:?}"
+        );
+    }
+
+    // The "+" buttons of the lists, from the top, push a default item.
+    harness.click(&mut world, repr.as_mut(), "+", 1);
+    harness.click(&mut world, repr.as_mut(), "+", 2);
+
+    let mut applied = Containers::default();
+    applied.apply(repr.as_ref());
+    assert_eq!(applied.vec, vec![1, 2]);
+    assert_eq!(applied.deque, VecDeque::from([3, 4, 0]));
+    assert_eq!(applied.small.as_slice(), &[5, 6, 0]);
+}
+
+/// Bevy 0.12 implements `Reflect` for `HashMap` but not yet for `BTreeMap`, so a `BTreeMap` can't
+/// be a reflected field; maps are covered through `HashMap`, which reflects as the same `Map`.
+#[derive(Component, Default, Reflect)]
+#[reflect(Component, Default)]
+struct Scores {
+    by_level: HashMap<u32, i32>,
+}
+
+#[test]
+fn maps_use_the_map_editor() {
+    let mut world = world_with::<Scores>();
+    let scores = Scores {
+        by_level: HashMap::from([(1, 10)]),
+    };
+    let mut repr = scores.clone_value();
+
+    let paths = ["Scores", "Scores.by_level"];
+    let mut harness = Harness::new("Scores", &paths);
+    let expanded = harness.draw(&mut world, repr.as_mut());
+    assert!(
+        expanded
+            .iter()
+            .any(|expanded| expanded == "Scores.by_level"),
+        "the map was not drawn as an expandable map: {expanded:?}"
+    );
+
+    // "+" constructs the key of a new entry, whose value is the default.
+    harness.click(&mut world, repr.as_mut(), "+", 0);
+    harness.click(&mut world, repr.as_mut(), "apply", 0);
+    let mut applied = Scores::default();
+    applied.apply(repr.as_ref());
+    assert_eq!(applied.by_level, HashMap::from([(0, 0), (1, 10)]));
+
+    // "-" removes an entry, in the order the entries are listed.
+    harness.click(&mut world, repr.as_mut(), "-", 0);
+    let mut applied = Scores::default();
+    applied.apply(repr.as_ref());
+    assert_eq!(applied.by_level, HashMap::from([(0, 0)]));
+}
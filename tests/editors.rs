@@ -0,0 +1,280 @@
+//! Checks the component editors: which edits are applied back to the world and when, the state
+//! they keep between frames, and the previews of assets.
+
+mod common;
+
+use aether_spyglass::dump::SpyglassDump;
+use aether_spyglass::script::{
+    ScriptAction, SelectEntityByName, SetField, SpyglassScript, WaitFrames,
+};
+use aether_spyglass::tabs::entities::editors::{EditorState, EditorStates, STATE_IDLE_FRAMES};
+use aether_spyglass::tabs::entities::reflect_commands::ReflectCommands;
+use aether_spyglass::tabs::entities::selection::SelectEntity;
+use aether_spyglass::tabs::entities::{ApplyMode, ComponentRefresh};
+use aether_spyglass::widgets::ImagePreview;
+use aether_spyglass::{Spyglass, SpyglassSettings, SpyglassWindow};
+use bevy::core::FrameCount;
+use bevy::prelude::*;
+use bevy::reflect::GetPath;
+use bevy_egui::egui;
+
+use common::{custom_editor, headless_app, run_frames};
+
+#[test]
+fn material_asset_editor() {
+    let mut app = headless_app(custom_editor::CustomEditorPlugin);
+    let material = app
+        .world
+        .resource_mut::<Assets<StandardMaterial>>()
+        .add(StandardMaterial::default());
+    let entity = app.world.spawn(material).id();
+    app.world.resource_mut::<Spyglass>().selected = Some(0);
+    app.world.send_event(SelectEntity(Some(entity)));
+    run_frames(&mut app, 3);
+
+    let states = app.world.resource::<EditorStates>();
+    assert!(
+        states
+            .headers()
+            .iter()
+            .any(|header| header == "Handle<StandardMaterial>(asset)"),
+        "{:?}",
+        states.headers()
+    );
+}
+
+#[test]
+fn image_handle_preview() {
+    let mut app = headless_app(custom_editor::CustomEditorPlugin);
+    let mut images = app.world.resource_mut::<Assets<Image>>();
+    let image = images.add(Image::default());
+    let texture = images.add(Image::default());
+    let material = app
+        .world
+        .resource_mut::<Assets<StandardMaterial>>()
+        .add(StandardMaterial {
+            base_color_texture: Some(texture.clone()),
+            ..default()
+        });
+    let entity = app.world.spawn((image.clone(), material)).id();
+    app.world.resource_mut::<Spyglass>().selected = Some(0);
+    app.world.send_event(SelectEntity(Some(entity)));
+    app.world
+        .resource_mut::<EditorStates>()
+        .request_expand("Handle<StandardMaterial>(asset)");
+    run_frames(&mut app, 3);
+    // The image handle component, and the image handle in the material, are previewed.
+    let textures = app.world.resource::<bevy_egui::EguiUserTextures>();
+    assert!(textures.image_id(&image).is_some());
+    assert!(textures.image_id(&texture).is_some());
+
+    app.world.resource_mut::<ImagePreview>().image = Some(image.id());
+    run_frames(&mut app, 1);
+    assert_eq!(app.world.resource::<ImagePreview>().image, Some(image.id()));
+    app.world.resource_mut::<Assets<Image>>().remove(&image);
+    run_frames(&mut app, 1);
+    assert_eq!(app.world.resource::<ImagePreview>().image, None);
+}
+
+#[test]
+fn recent_values_are_cleared_with_the_states() {
+    let mut states = EditorStates::default();
+    states.push_path("Tint.strength");
+    states.record_value("1");
+    states.record_value("2");
+    assert_eq!(states.recent_values(), ["1", "2"]);
+    states.clear();
+    assert!(states.recent_values().is_empty());
+}
+
+#[derive(Default, Resource)]
+struct TintChanges(usize);
+
+fn count_tint_changes(
+    mut changes: ResMut<TintChanges>,
+    q: Query<(), Changed<custom_editor::Tint>>,
+) {
+    changes.0 += q.iter().count();
+}
+
+#[test]
+fn unedited_components_are_not_changed() {
+    let mut app = headless_app(custom_editor::CustomEditorPlugin);
+    app.init_resource::<TintChanges>()
+        .add_systems(Last, count_tint_changes);
+    app.world
+        .resource_mut::<SpyglassScript>()
+        .push(SelectEntityByName("Tinted"))
+        .push(ScriptAction::ExpandComponent("Tint".to_string()));
+    run_frames(&mut app, 3);
+
+    app.world.resource_mut::<TintChanges>().0 = 0;
+    run_frames(&mut app, 5);
+    assert_eq!(app.world.resource::<TintChanges>().0, 0);
+
+    // Edits are applied back once.
+    app.world
+        .resource_mut::<SpyglassScript>()
+        .push(SetField("Tint.strength", 0.25f32));
+    run_frames(&mut app, 3);
+    assert_eq!(app.world.resource::<TintChanges>().0, 1);
+    let mut q = app.world.query::<&custom_editor::Tint>();
+    let tint = q.single(&app.world);
+    assert_eq!(tint.path::<f32>("strength"), Ok(&0.25));
+}
+
+#[test]
+fn editor_states_gc() {
+    let mut app = headless_app(custom_editor::CustomEditorPlugin);
+    let first = app.world.spawn(Name::new("First")).id();
+    let second = app.world.spawn(Name::new("Second")).id();
+    app.world.send_event(SelectEntity(Some(first)));
+    run_frames(&mut app, 2);
+    let stale = egui::Id::new("stale");
+    let fresh = egui::Id::new("fresh");
+    let mut states = app.world.resource_mut::<EditorStates>();
+    states.insert(stale, EditorState::Composite);
+    states.insert(fresh, EditorState::Composite);
+    assert_eq!(states.len(), 2);
+
+    // States that are not accessed for long enough are dropped.
+    app.world.resource_mut::<FrameCount>().0 += STATE_IDLE_FRAMES;
+    for _ in 0..61 {
+        app.world.resource_mut::<EditorStates>().get(fresh);
+        run_frames(&mut app, 1);
+    }
+    let mut states = app.world.resource_mut::<EditorStates>();
+    assert!(states.get(stale).is_none());
+    assert!(states.get(fresh).is_some());
+
+    // Selecting another entity drops every state.
+    app.world.send_event(SelectEntity(Some(second)));
+    run_frames(&mut app, 1);
+    assert!(app.world.resource::<EditorStates>().is_empty());
+}
+
+#[test]
+fn editor_state_scopes() {
+    let ctx = egui::Context::default();
+    let mut states = EditorStates::default();
+    // Draw a scope with a child text editor, returning the ids of both.
+    let draw = |states: &mut EditorStates| {
+        let mut ids = None;
+        let _ = ctx.run(default(), |ctx| {
+            egui::CentralPanel::default().show(ctx, |ui| {
+                let parent = ui.id();
+                let child = states.scope(ui, |ui, states| {
+                    ui.push_id(0, |ui| {
+                        states.get_or(ui.id(), || EditorState::TextEdit {
+                            temp_value: String::new(),
+                        });
+                        ui.id()
+                    })
+                    .inner
+                });
+                ids = Some((parent, child));
+            });
+        });
+        ids.unwrap()
+    };
+    let edit = |states: &mut EditorStates, id| {
+        states.get(id).unwrap().text_edit().push_str("edited");
+    };
+
+    let (parent, child) = draw(&mut states);
+    edit(&mut states, child);
+    draw(&mut states);
+    assert_eq!(states.get(child).unwrap().text_edit(), "edited");
+
+    // Children are cleared explicitly, or when the state of their scope is fresh.
+    states.clear_children(parent);
+    assert!(states.get(child).is_none());
+    draw(&mut states);
+    edit(&mut states, child);
+    states.remove(parent);
+    draw(&mut states);
+    assert_eq!(states.get(child).unwrap().text_edit(), "");
+}
+
+#[test]
+fn mismatched_editor_states() {
+    let mut states = EditorStates::default();
+    let id = egui::Id::new("reused");
+    states.insert(id, EditorState::Composite);
+    assert!(states.get(id).unwrap().try_text_edit().is_none());
+    assert_eq!(states.text_edit(id, || "text".to_string()), "text");
+    assert_eq!(states.text_edit(id, String::new), "text");
+    assert!(states.get(id).unwrap().try_composite().is_none());
+
+    // A scope whose id holds a text edit is reset instead of panicking.
+    let ctx = egui::Context::default();
+    let _ = ctx.run(default(), |ctx| {
+        egui::CentralPanel::default().show(ctx, |ui| {
+            states.text_edit(ui.id(), String::new);
+            states.scope(ui, |_, _| ());
+            assert!(states.get(ui.id()).unwrap().try_composite().is_some());
+        });
+    });
+}
+
+#[derive(Component)]
+struct Mover;
+
+fn move_right(mut q: Query<&mut Transform, With<Mover>>) {
+    for mut transform in q.iter_mut() {
+        transform.translation.x += 1.0;
+    }
+}
+
+#[test]
+fn apply_edited_fields() {
+    let mut app = headless_app(custom_editor::CustomEditorPlugin);
+    app.add_systems(Update, move_right.after(SpyglassWindow));
+    app.world.resource_mut::<SpyglassSettings>().apply_mode = ApplyMode::Fields;
+    let entity = app
+        .world
+        .spawn((Name::new("Mover"), Mover, Transform::default()))
+        .id();
+    app.world.resource_mut::<Spyglass>().selected = Some(0);
+    app.world
+        .resource_mut::<SpyglassScript>()
+        .push(SelectEntityByName("Mover"))
+        .push(WaitFrames(2))
+        .push(SetField("Transform.scale.y", 2.0f32));
+    run_frames(&mut app, 6);
+
+    // Only the edited field is written, so the game's changes to the others are kept.
+    let transform = app.world.get::<Transform>(entity).unwrap();
+    assert_eq!(transform.scale.y, 2.0);
+    assert_eq!(transform.translation.x, 6.0);
+    assert_eq!(app.world.resource::<ReflectCommands>().iter().count(), 0);
+}
+
+#[test]
+fn conflicting_edits_are_held_back() {
+    let mut app = headless_app(custom_editor::CustomEditorPlugin);
+    app.add_systems(PreUpdate, move_right);
+    // The repr is kept while the game moves the entity, like an edit that takes several frames.
+    app.world
+        .resource_mut::<SpyglassSettings>()
+        .set_component_refresh::<Transform>(ComponentRefresh::Manual);
+    let entity = app
+        .world
+        .spawn((Name::new("Mover"), Mover, Transform::default()))
+        .id();
+    app.world.resource_mut::<Spyglass>().selected = Some(0);
+    app.world
+        .resource_mut::<SpyglassScript>()
+        .push(SelectEntityByName("Mover"))
+        .push(WaitFrames(2))
+        .push(SetField("Transform.scale.y", 2.0f32));
+    run_frames(&mut app, 6);
+
+    let transform = app.world.get::<Transform>(entity).unwrap();
+    assert_eq!(transform.scale.y, 1.0);
+    assert_eq!(transform.translation.x, 6.0);
+    assert_eq!(
+        SpyglassDump::capture(&app.world).conflicts,
+        [std::any::type_name::<Transform>()]
+    );
+}
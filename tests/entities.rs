@@ -0,0 +1,333 @@
+//! Checks the entities tab: selection, search, grouping, comparison, duplication, labels and
+//! bundle presets.
+
+mod common;
+
+use aether_spyglass::dump::SpyglassDump;
+use aether_spyglass::script::{SetField, SpyglassScript};
+use aether_spyglass::tabs::console::{ConsoleCommands, ConsoleTabPlugin};
+use aether_spyglass::tabs::entities::bundles::SpyglassBundles;
+use aether_spyglass::tabs::entities::compare::EntityComparison;
+use aether_spyglass::tabs::entities::duplicate::duplicate_entity;
+use aether_spyglass::tabs::entities::groups::EntityGroupBy;
+use aether_spyglass::tabs::entities::labels::EntityLabels;
+use aether_spyglass::tabs::entities::search::{entities_by_path, entity_by_id, resolve_search};
+use aether_spyglass::tabs::entities::selection::{EntitySelected, SelectEntity, SelectionHistory};
+use aether_spyglass::tabs::entities::{Popups, Severity};
+use aether_spyglass::Spyglass;
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use bevy_egui::{egui, EguiContext};
+
+use common::{custom_editor, headless_app, run_frames};
+
+#[test]
+fn compare_entities() {
+    let mut app = headless_app(custom_editor::CustomEditorPlugin);
+    let left = app
+        .world
+        .spawn((Transform::from_xyz(1.0, 2.0, 3.0), Visibility::Hidden))
+        .id();
+    let right = app
+        .world
+        .spawn((Transform::from_xyz(1.0, 5.0, 3.0), Name::new("Right")))
+        .id();
+
+    let comparison = EntityComparison::new(&app.world, left, right);
+    let (_, transform) = comparison
+        .shared
+        .iter()
+        .find(|(name, _)| name.ends_with("Transform"))
+        .unwrap();
+    let differing = transform
+        .iter()
+        .filter(|field| field.differs())
+        .map(|field| field.path.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(differing, [".translation.y"]);
+    assert!(transform.iter().any(|field| field.path == ".translation.x"));
+    assert!(comparison
+        .only_left
+        .iter()
+        .any(|c| c.ends_with("Visibility")));
+    assert!(comparison.only_right.iter().any(|c| c.ends_with("Name")));
+}
+
+#[test]
+fn group_entities() {
+    let mut app = headless_app(custom_editor::CustomEditorPlugin);
+    let a = app.world.spawn(Transform::default()).id();
+    let b = app
+        .world
+        .spawn((Transform::default(), Visibility::Hidden))
+        .id();
+    let c = app.world.spawn(Transform::default()).id();
+
+    let buckets = EntityGroupBy::Archetype.bucket(&app.world, &[a, b, c]);
+    assert_eq!(
+        buckets,
+        [
+            ("Transform".to_string(), vec![a, c]),
+            ("Transform, Visibility".to_string(), vec![b]),
+        ]
+    );
+
+    let buckets = EntityGroupBy::Marker("Visibility".into()).bucket(&app.world, &[a, b, c]);
+    assert_eq!(
+        buckets,
+        [
+            ("without Visibility".to_string(), vec![a, c]),
+            ("with Visibility".to_string(), vec![b]),
+        ]
+    );
+}
+
+#[test]
+fn duplicate_with_children() {
+    #[derive(Component)]
+    struct Unreflected;
+
+    let mut app = headless_app(custom_editor::CustomEditorPlugin);
+    let parent = app.world.spawn(Name::new("Root")).id();
+    let original = app
+        .world
+        .spawn((Name::new("Original"), Transform::from_xyz(1.0, 0.0, 0.0)))
+        .with_children(|children| {
+            children.spawn((Name::new("Child"), Unreflected));
+        })
+        .set_parent(parent)
+        .id();
+
+    let duplicate = duplicate_entity(&mut app.world, original, true);
+    let copy = app.world.entity(duplicate.entity);
+    assert_eq!(copy.get::<Name>().unwrap().as_str(), "Original");
+    assert_eq!(copy.get::<Transform>().unwrap().translation.x, 1.0);
+    assert_eq!(copy.get::<Parent>().unwrap().get(), parent);
+    let children = copy.get::<Children>().unwrap();
+    assert_eq!(children.len(), 1);
+    assert_eq!(
+        app.world.get::<Name>(children[0]).unwrap().as_str(),
+        "Child"
+    );
+    assert_eq!(duplicate.skipped, ["Unreflected"]);
+}
+
+#[test]
+fn selection_events() {
+    let mut app = headless_app(custom_editor::CustomEditorPlugin);
+    run_frames(&mut app, 2);
+    let mut reader = app
+        .world
+        .resource_mut::<Events<EntitySelected>>()
+        .get_reader();
+
+    let mut q = app
+        .world
+        .query_filtered::<Entity, With<custom_editor::Tint>>();
+    let tinted = q.single(&app.world);
+    app.world.send_event(SelectEntity(Some(tinted)));
+    run_frames(&mut app, 2);
+    assert_eq!(
+        SpyglassDump::capture(&app.world).selected_entity,
+        Some(tinted)
+    );
+
+    app.world.send_event(SelectEntity(None));
+    run_frames(&mut app, 2);
+    assert_eq!(SpyglassDump::capture(&app.world).selected_entity, None);
+
+    let events = app.world.resource::<Events<EntitySelected>>();
+    let selections = reader.read(events).copied().collect::<Vec<_>>();
+    assert_eq!(
+        selections,
+        [EntitySelected(Some(tinted)), EntitySelected(None)]
+    );
+}
+
+#[test]
+fn selection_history() {
+    let mut app = headless_app(custom_editor::CustomEditorPlugin);
+    let a = app.world.spawn_empty().id();
+    let b = app.world.spawn_empty().id();
+    let despawned = app.world.spawn_empty().id();
+    for entity in [a, despawned, b] {
+        app.world.send_event(SelectEntity(Some(entity)));
+        run_frames(&mut app, 1);
+    }
+    app.world.despawn(despawned);
+
+    let history = app.world.resource::<SelectionHistory>();
+    assert_eq!(history.back(), [None, Some(a), Some(despawned)]);
+    assert_eq!(history.forward().count(), 0);
+
+    // Going back skips the despawned entity.
+    assert!(SelectionHistory::go_back(&mut app.world));
+    run_frames(&mut app, 1);
+    assert_eq!(SpyglassDump::capture(&app.world).selected_entity, Some(a));
+    let history = app.world.resource::<SelectionHistory>();
+    assert_eq!(history.back(), [None]);
+    assert_eq!(history.forward().collect::<Vec<_>>(), [Some(b)]);
+
+    assert!(SelectionHistory::go_forward(&mut app.world));
+    run_frames(&mut app, 1);
+    assert_eq!(SpyglassDump::capture(&app.world).selected_entity, Some(b));
+}
+
+#[test]
+fn entity_labels() {
+    let mut app = headless_app(custom_editor::CustomEditorPlugin);
+    app.world.resource_mut::<EntityLabels>().enabled = true;
+    app.world.spawn(Camera3dBundle {
+        transform: Transform::from_xyz(0.0, 0.0, 10.0).looking_at(Vec3::ZERO, Vec3::Y),
+        ..default()
+    });
+    let labelled = app
+        .world
+        .spawn((Name::new("Landmark"), TransformBundle::default()))
+        .id();
+    let behind = app
+        .world
+        .spawn((
+            Name::new("Behind"),
+            TransformBundle::from_transform(Transform::from_xyz(0.0, 0.0, 20.0)),
+        ))
+        .id();
+    run_frames(&mut app, 3);
+
+    let mut q = app
+        .world
+        .query_filtered::<&mut EguiContext, With<PrimaryWindow>>();
+    let ctx = q.single_mut(&mut app.world).get_mut().clone();
+    let label = |entity| {
+        ctx.memory(|memory| memory.area_rect(egui::Id::new(("spyglass_entity_label", entity))))
+    };
+    assert!(label(labelled).is_some());
+    assert!(label(behind).is_none());
+}
+
+#[test]
+fn entity_search_by_id_and_path() {
+    let mut app = headless_app((custom_editor::CustomEditorPlugin, ConsoleTabPlugin));
+    let boss = app.world.spawn(Name::new("Boss")).id();
+    let enemies = app.world.spawn(Name::new("Enemies")).add_child(boss).id();
+    app.world.spawn(Name::new("Level1")).add_child(enemies);
+    let elsewhere = app.world.spawn(Name::new("Boss")).id();
+    run_frames(&mut app, 1);
+
+    let world = &app.world;
+    assert_eq!(entity_by_id(world, &format!("{boss:?}")), Some(boss));
+    assert_eq!(entity_by_id(world, &boss.index().to_string()), Some(boss));
+    let stale = format!("{}v{}", boss.index(), boss.generation() + 1);
+    assert_eq!(entity_by_id(world, &stale), None);
+    assert_eq!(entity_by_id(world, "Boss"), None);
+
+    assert_eq!(entities_by_path(world, "Level1/Enemies/Boss"), [boss]);
+    assert_eq!(entities_by_path(world, "Enemies/Boss"), [boss]);
+    assert!(entities_by_path(world, "Level1/Boss").is_empty());
+    let mut bosses = entities_by_path(world, "Boss");
+    bosses.sort();
+    assert_eq!(bosses, [boss, elsewhere]);
+
+    assert_eq!(resolve_search(world, "Bo"), None);
+    assert_eq!(resolve_search(world, "Level1/Enemies"), Some(vec![enemies]));
+    assert_eq!(
+        ConsoleCommands::execute(&mut app.world, "get Level1/Enemies/Boss Name"),
+        Ok("\"Boss\"".to_string())
+    );
+}
+
+#[test]
+fn despawned_selection() {
+    let mut app = headless_app(custom_editor::CustomEditorPlugin);
+    let entity = app.world.spawn(Name::new("Doomed")).id();
+    app.world.resource_mut::<Spyglass>().selected = Some(0);
+    app.world.send_event(SelectEntity(Some(entity)));
+    run_frames(&mut app, 2);
+    assert_eq!(
+        SpyglassDump::capture(&app.world).selected_entity,
+        Some(entity)
+    );
+
+    // The game despawning the selected entity deselects it instead of panicking.
+    app.world.despawn(entity);
+    run_frames(&mut app, 2);
+    assert_eq!(SpyglassDump::capture(&app.world).selected_entity, None);
+    let popups = app.world.resource::<Popups>();
+    let popup = popups
+        .iter()
+        .find(|popup| popup.severity() == Severity::Warning);
+    assert!(popup.is_some_and(|popup| popup.message().contains("entity despawned")));
+}
+
+#[derive(Component)]
+struct Enemy;
+
+#[test]
+fn bundle_presets() {
+    let mut app = headless_app(custom_editor::CustomEditorPlugin);
+    app.world
+        .resource_mut::<SpyglassBundles>()
+        .add("Enemy base", || {
+            (
+                Enemy,
+                TransformBundle::from_transform(Transform::from_xyz(1.0, 2.0, 3.0)),
+            )
+        });
+    let entity = app.world.spawn(Name::new("Enemy")).id();
+
+    assert!(!SpyglassBundles::insert(
+        &mut app.world,
+        entity,
+        "Boss base"
+    ));
+    assert!(SpyglassBundles::insert(
+        &mut app.world,
+        entity,
+        "Enemy base"
+    ));
+    run_frames(&mut app, 2);
+
+    let entity = app.world.entity(entity);
+    assert!(entity.contains::<Enemy>());
+    assert!(entity.contains::<GlobalTransform>());
+    assert_eq!(entity.get::<Transform>().unwrap().translation.y, 2.0);
+}
+
+mod player {
+    use bevy::prelude::*;
+
+    #[derive(Component, Default, Reflect)]
+    #[reflect(Component)]
+    pub struct Health(pub f32);
+}
+
+mod enemy {
+    use bevy::prelude::*;
+
+    #[derive(Component, Default, Reflect)]
+    #[reflect(Component)]
+    pub struct Health(pub f32);
+}
+
+#[test]
+fn components_with_the_same_short_name() {
+    let mut app = headless_app(custom_editor::CustomEditorPlugin);
+    app.register_type::<player::Health>()
+        .register_type::<enemy::Health>();
+    let entity = app
+        .world
+        .spawn((Name::new("Both"), player::Health(1.0), enemy::Health(2.0)))
+        .id();
+    app.world.resource_mut::<Spyglass>().selected = Some(0);
+    app.world.send_event(SelectEntity(Some(entity)));
+    run_frames(&mut app, 2);
+
+    // Each is edited as itself, labelled with its full type name.
+    let player = format!("Health ({})", std::any::type_name::<player::Health>());
+    app.world
+        .resource_mut::<SpyglassScript>()
+        .push(SetField(format!("{player}.0"), 5.0f32));
+    run_frames(&mut app, 3);
+    assert_eq!(app.world.get::<player::Health>(entity).unwrap().0, 5.0);
+    assert_eq!(app.world.get::<enemy::Health>(entity).unwrap().0, 2.0);
+}
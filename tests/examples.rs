@@ -0,0 +1,86 @@
+//! Drives the examples headlessly, frame by frame, cycling through every tab of the inspector.
+
+mod common;
+#[path = "../examples/custom_tab.rs"]
+#[allow(dead_code)]
+mod custom_tab;
+#[path = "../examples/multi_window.rs"]
+#[allow(dead_code)]
+mod multi_window;
+#[path = "../examples/stress.rs"]
+#[allow(dead_code)]
+mod stress;
+
+use aether_spyglass::dump::SpyglassDump;
+use aether_spyglass::script::{SelectEntityByName, SpyglassScript, WaitFrames};
+use aether_spyglass::tabs::assets::AssetsTabPlugin;
+use aether_spyglass::tabs::camera::CameraTabPlugin;
+use aether_spyglass::tabs::console::ConsoleTabPlugin;
+use aether_spyglass::tabs::input::InputTabPlugin;
+use aether_spyglass::tabs::query::QueryTabPlugin;
+use aether_spyglass::tabs::render_errors::{RenderErrors, RenderErrorsTabPlugin};
+use aether_spyglass::tabs::spikes::SpikesTabPlugin;
+use aether_spyglass::tabs::time::TimeTabPlugin;
+use aether_spyglass::tabs::visibility::VisibilityTabPlugin;
+use bevy::prelude::*;
+
+use common::{custom_editor, cycle_tabs, headless_app, run_frames};
+
+#[test]
+fn stress() {
+    let mut app = headless_app((
+        stress::StressPlugin,
+        AssetsTabPlugin,
+        CameraTabPlugin,
+        ConsoleTabPlugin,
+        InputTabPlugin,
+        QueryTabPlugin,
+        RenderErrorsTabPlugin,
+        SpikesTabPlugin,
+        TimeTabPlugin,
+        VisibilityTabPlugin,
+    ));
+    cycle_tabs(&mut app);
+
+    let mut q = app.world.query::<&Name>();
+    assert!(q.iter(&app.world).count() >= stress::ENTITIES);
+    assert!(app.world.resource::<RenderErrors>().errors().is_empty());
+}
+
+#[test]
+fn custom_tab() {
+    let mut app = headless_app(custom_tab::CustomTabPlugin);
+    cycle_tabs(&mut app);
+
+    let dump = SpyglassDump::capture(&app.world);
+    assert_eq!(dump.tab.as_deref(), Some("Score"));
+}
+
+#[test]
+fn custom_editor() {
+    let mut app = headless_app(custom_editor::CustomEditorPlugin);
+    cycle_tabs(&mut app);
+
+    app.world
+        .resource_mut::<SpyglassScript>()
+        .push(SelectEntityByName("Tinted"))
+        .push(WaitFrames(2));
+    run_frames(&mut app, 4);
+    assert!(app.world.resource::<SpyglassScript>().is_finished());
+
+    let dump = SpyglassDump::capture(&app.world);
+    assert_eq!(dump.tab.as_deref(), Some("Entities"));
+    let mut q = app
+        .world
+        .query_filtered::<Entity, With<custom_editor::Tint>>();
+    assert_eq!(dump.selected_entity, Some(q.single(&app.world)));
+}
+
+#[test]
+fn multi_window() {
+    let mut app = headless_app(multi_window::MultiWindowPlugin);
+    cycle_tabs(&mut app);
+
+    let mut q = app.world.query::<&Window>();
+    assert_eq!(q.iter(&app.world).count(), 2);
+}
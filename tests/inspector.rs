@@ -0,0 +1,404 @@
+//! Checks the inspector itself: its window and layout, the tab list, settings, commands, popups
+//! and script recording.
+
+mod common;
+#[path = "../examples/multi_window.rs"]
+#[allow(dead_code)]
+mod multi_window;
+
+use aether_spyglass::commands::{fuzzy_score, SpyglassCommands};
+use aether_spyglass::dump::SpyglassDump;
+use aether_spyglass::script::record::{ScriptRecorder, ScriptRecorderPlugin};
+use aether_spyglass::script::{
+    ScriptAction, SelectEntityByName, SelectTab, SpyglassScript, WaitFrames,
+};
+use aether_spyglass::tabs::entities::editors::EditorStates;
+use aether_spyglass::tabs::entities::gizmo::TransformGizmo;
+use aether_spyglass::tabs::entities::{ComponentRefresh, Popup, Popups, Severity};
+use aether_spyglass::tabs::watches::{Watches, WatchesTabPlugin};
+use aether_spyglass::{
+    InspectorLayout, Spyglass, SpyglassContext, SpyglassPlugin, SpyglassSettings, SpyglassTheme,
+    Tab,
+};
+use bevy::input::keyboard::KeyboardInput;
+use bevy::input::ButtonState;
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use bevy_egui::{egui, EguiContext};
+
+use common::{custom_editor, cycle_tabs, headless_app, run_frames};
+
+#[test]
+fn dedicated_context() {
+    let mut app = headless_app(multi_window::MultiWindowPlugin);
+    app.insert_resource(SpyglassContext::DedicatedWindow);
+    run_frames(&mut app, 3);
+
+    let mut q = app.world.query::<&Window>();
+    let titles = q
+        .iter(&app.world)
+        .map(|window| window.title.clone())
+        .collect::<Vec<_>>();
+    assert_eq!(titles.len(), 3);
+    assert!(titles.iter().any(|title| title == "Spyglass"));
+
+    // The inspector no longer draws a window into the primary context.
+    let mut q = app
+        .world
+        .query_filtered::<&mut EguiContext, With<PrimaryWindow>>();
+    let mut ctx = q.single_mut(&mut app.world);
+    let window = ctx
+        .get_mut()
+        .memory(|memory| memory.area_rect(egui::Id::new("Spyglass")));
+    assert!(window.is_none());
+
+    app.world.resource_mut::<Spyglass>().selected = Some(0);
+    run_frames(&mut app, 3);
+    let dump = SpyglassDump::capture(&app.world);
+    assert_eq!(dump.tab.as_deref(), Some("Entities"));
+}
+
+#[test]
+fn inspector_leaves_archetypes_alone() {
+    let mut app = headless_app(custom_editor::CustomEditorPlugin);
+    run_frames(&mut app, 2);
+
+    let mut q = app
+        .world
+        .query_filtered::<Entity, With<custom_editor::Tint>>();
+    let tinted = q.single(&app.world);
+    let before = app.world.entity(tinted).archetype().id();
+
+    cycle_tabs(&mut app);
+    let entity = app.world.entity(tinted);
+    let archetype = entity.archetype();
+    assert_eq!(archetype.id(), before);
+    assert!(archetype.components().all(|comp| {
+        let name = app.world.components().get_name(comp).unwrap();
+        !name.starts_with("aether_spyglass::")
+    }));
+}
+
+#[test]
+fn popup_queue() {
+    let mut app = headless_app(custom_editor::CustomEditorPlugin);
+    let mut popups = app.world.resource_mut::<Popups>();
+    popups.add(Popup::new("gone").with_timeout(std::time::Duration::ZERO));
+    for i in 0..7 {
+        popups.error(format!("error {i}"));
+    }
+    popups.warn("warning");
+    popups.recoverable_error("bad edit");
+    run_frames(&mut app, 3);
+
+    // Errors and warnings stay until dismissed, while popups that timed out are removed.
+    let popups = app.world.resource::<Popups>();
+    let severities = popups.iter().map(Popup::severity).collect::<Vec<_>>();
+    assert_eq!(severities.len(), 9);
+    assert_eq!(severities[0], Severity::Error);
+    assert_eq!(severities[7], Severity::Warning);
+    assert!(popups.iter().last().unwrap().is_recoverable());
+}
+
+#[test]
+fn commands() {
+    assert!(fuzzy_score("ent tab", "open Entities tab").is_some());
+    assert!(fuzzy_score("tab ent", "open Entities tab").is_none());
+    assert!(fuzzy_score("pt", "pause time") > fuzzy_score("pt", "take screenshot"));
+
+    let mut app = headless_app(custom_editor::CustomEditorPlugin);
+    assert!(SpyglassCommands::run(
+        &mut app.world,
+        "toggle transform gizmo"
+    ));
+    assert!(app.world.resource::<TransformGizmo>().enabled);
+    assert!(!SpyglassCommands::run(&mut app.world, "no such command"));
+}
+
+#[test]
+fn read_only() {
+    let mut app = headless_app(custom_editor::CustomEditorPlugin);
+    assert!(SpyglassCommands::run(&mut app.world, "toggle read-only"));
+    app.world.resource_mut::<SpyglassSettings>().theme = SpyglassTheme::Light;
+    app.world
+        .resource_mut::<SpyglassScript>()
+        .push(SelectEntityByName("Tinted"))
+        .push(ScriptAction::ExpandComponent("Tint".to_string()));
+    run_frames(&mut app, 3);
+    assert!(app.world.resource::<EditorStates>().read_only());
+
+    assert!(SpyglassCommands::run(&mut app.world, "toggle read-only"));
+    run_frames(&mut app, 1);
+    assert!(!app.world.resource::<EditorStates>().read_only());
+}
+
+#[test]
+fn record_script() {
+    let mut app = headless_app((custom_editor::CustomEditorPlugin, ScriptRecorderPlugin));
+    run_frames(&mut app, 2);
+
+    app.world
+        .resource_scope(|world, mut recorder: Mut<ScriptRecorder>| recorder.start(world));
+    app.world
+        .resource_mut::<SpyglassScript>()
+        .push(SelectEntityByName("Tinted"))
+        .push(WaitFrames(3))
+        .push(SelectTab("World Stats"));
+    run_frames(&mut app, 6);
+    app.world.resource_mut::<ScriptRecorder>().stop();
+
+    let recorder = app.world.resource::<ScriptRecorder>();
+    let actions = recorder.actions();
+    assert!(matches!(&actions[0], ScriptAction::SelectEntityByName(name) if name == "Tinted"));
+    assert!(matches!(&actions[1], ScriptAction::Wait(3)));
+    assert!(matches!(&actions[2], ScriptAction::SelectTab(name) if name == "World Stats"));
+    assert!(recorder
+        .to_rust()
+        .contains(".push(SelectEntityByName(\"Tinted\"))"));
+}
+
+#[test]
+fn settings_tab() {
+    let mut app = headless_app(custom_editor::CustomEditorPlugin);
+    app.world
+        .resource_mut::<SpyglassScript>()
+        .push(SelectTab("Settings"));
+    run_frames(&mut app, 3);
+    assert_eq!(
+        SpyglassDump::capture(&app.world).tab.as_deref(),
+        Some("Settings")
+    );
+
+    // The editors create new values through the registry, so the settings must be registered.
+    let registry = app.world.resource::<AppTypeRegistry>().clone();
+    let registry = registry.read();
+    let reflect = registry
+        .get(std::any::TypeId::of::<SpyglassSettings>())
+        .and_then(|registration| registration.data::<ReflectResource>())
+        .unwrap();
+    let mut settings = reflect.reflect_mut(&mut app.world).unwrap();
+    *settings
+        .reflect_path_mut("hide_internal_components")
+        .unwrap()
+        .downcast_mut::<bool>()
+        .unwrap() = false;
+    assert!(
+        !app.world
+            .resource::<SpyglassSettings>()
+            .hide_internal_components
+    );
+}
+
+#[test]
+fn tab_refresh_rate() {
+    let mut app = headless_app((custom_editor::CustomEditorPlugin, WatchesTabPlugin));
+    run_frames(&mut app, 2);
+
+    let mut q = app
+        .world
+        .query_filtered::<Entity, With<custom_editor::Tint>>();
+    let tinted = q.single(&app.world);
+    app.world.resource_mut::<Watches>().watch(
+        tinted,
+        std::any::type_name::<custom_editor::Tint>(),
+        ".strength",
+    );
+    let samples = |app: &App| app.world.resource::<Watches>().watches[0].samples.len();
+
+    app.world
+        .resource_mut::<Spyglass>()
+        .refresh
+        .insert("Watches".to_string(), ComponentRefresh::Manual);
+    run_frames(&mut app, 5);
+    assert_eq!(samples(&app), 0);
+
+    app.world
+        .resource_mut::<Spyglass>()
+        .request_refresh("Watches");
+    run_frames(&mut app, 5);
+    assert_eq!(samples(&app), 1);
+
+    app.world
+        .resource_mut::<Spyglass>()
+        .refresh
+        .insert("Watches".to_string(), ComponentRefresh::EveryNFrames(2));
+    run_frames(&mut app, 10);
+    assert_eq!(samples(&app), 6);
+}
+
+#[test]
+fn egui_plugin_added_by_app() {
+    let mut app = headless_app((bevy_egui::EguiPlugin, custom_editor::CustomEditorPlugin));
+    cycle_tabs(&mut app);
+}
+
+#[test]
+fn egui_plugin_added_after_spyglass() {
+    let mut app = headless_app((SpyglassPlugin::without_egui_plugin(), bevy_egui::EguiPlugin));
+    cycle_tabs(&mut app);
+}
+
+struct OrderedTab(&'static str, i32);
+
+impl Tab for OrderedTab {
+    fn name(&self) -> &str {
+        self.0
+    }
+
+    fn draw(&mut self, ui: &mut egui::Ui, _: &mut World) {
+        ui.label(self.0);
+    }
+
+    fn order(&self) -> i32 {
+        self.1
+    }
+}
+
+#[test]
+fn tab_order() {
+    let mut app = headless_app(custom_editor::CustomEditorPlugin);
+    let mut spyglass = app.world.resource_mut::<Spyglass>();
+    spyglass.selected = Some(0);
+    spyglass.add_tab(Box::new(OrderedTab("Last", 10)));
+    spyglass.add_tab(Box::new(OrderedTab("First", -10)));
+    spyglass.add_tab(Box::new(OrderedTab("Second", -10)));
+    let names = spyglass
+        .tabs
+        .iter()
+        .map(|tab| tab.name())
+        .collect::<Vec<_>>();
+    assert_eq!(names[..3], ["First", "Second", "Entities"]);
+    assert_eq!(names.last(), Some(&"Last"));
+    assert_eq!(spyglass.selected, Some(2));
+    cycle_tabs(&mut app);
+}
+
+#[test]
+fn tab_hotkeys() {
+    let mut app = headless_app(custom_editor::CustomEditorPlugin);
+    let mut spyglass = app.world.resource_mut::<Spyglass>();
+    assert!(spyglass.select_tab_by_name("Settings"));
+    assert!(!spyglass.select_tab_by_name("Nothing"));
+    assert_eq!(spyglass.tabs[spyglass.selected.unwrap()].name(), "Settings");
+
+    app.world
+        .resource_mut::<SpyglassSettings>()
+        .tab_hotkeys
+        .push((KeyCode::F2, "World Stats".to_string()));
+    let mut q = app.world.query_filtered::<Entity, With<PrimaryWindow>>();
+    let window = q.single(&app.world);
+    app.world.send_event(KeyboardInput {
+        scan_code: 0,
+        key_code: Some(KeyCode::F2),
+        state: ButtonState::Pressed,
+        window,
+    });
+    run_frames(&mut app, 2);
+    assert_eq!(
+        SpyglassDump::capture(&app.world).tab.as_deref(),
+        Some("World Stats")
+    );
+}
+
+struct CollectingTab(std::sync::Arc<std::sync::atomic::AtomicUsize>);
+
+impl Tab for CollectingTab {
+    fn name(&self) -> &str {
+        "Collecting"
+    }
+
+    fn collect(&mut self, _: &mut World) {
+        self.0.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+    }
+
+    fn draw(&mut self, _: &mut egui::Ui, _: &mut World) {}
+}
+
+#[test]
+fn tab_collect() {
+    let collected = std::sync::Arc::default();
+    let count = |collected: &std::sync::atomic::AtomicUsize| {
+        collected.load(std::sync::atomic::Ordering::Relaxed)
+    };
+    let mut app = headless_app(custom_editor::CustomEditorPlugin);
+    let mut spyglass = app.world.resource_mut::<Spyglass>();
+    spyglass.add_tab(Box::new(CollectingTab(std::sync::Arc::clone(&collected))));
+    spyglass
+        .refresh
+        .insert("Collecting".to_string(), ComponentRefresh::Manual);
+    run_frames(&mut app, 3);
+    assert_eq!(count(&collected), 0, "only the selected tab collects");
+
+    app.world
+        .resource_mut::<Spyglass>()
+        .select_tab_by_name("Collecting");
+    run_frames(&mut app, 3);
+    assert_eq!(count(&collected), 1, "a newly selected tab collects once");
+
+    app.world
+        .resource_mut::<Spyglass>()
+        .request_refresh("Collecting");
+    run_frames(&mut app, 3);
+    assert_eq!(count(&collected), 2);
+    cycle_tabs(&mut app);
+}
+
+#[test]
+fn side_panel_layout() {
+    let mut app = headless_app(custom_editor::CustomEditorPlugin);
+    app.world.resource_mut::<SpyglassSettings>().layout = InspectorLayout::RightPanel;
+    app.world.resource_mut::<Spyglass>().selected = Some(0);
+    run_frames(&mut app, 3);
+
+    let mut q = app
+        .world
+        .query_filtered::<&mut EguiContext, With<PrimaryWindow>>();
+    let ctx = q.single_mut(&mut app.world).get_mut().clone();
+    let panel = egui::containers::panel::PanelState::load(&ctx, egui::Id::new("spyglass_panel"));
+    assert!(panel.is_some(), "the inspector panel was not drawn");
+    assert!(ctx
+        .memory(|memory| memory.area_rect(egui::Id::new("Spyglass")))
+        .is_none());
+}
+
+/// A tab that records the id of the ui it is drawn in.
+struct IdTab(
+    &'static str,
+    std::sync::Arc<std::sync::Mutex<Option<egui::Id>>>,
+);
+
+impl Tab for IdTab {
+    fn name(&self) -> &str {
+        self.0
+    }
+
+    fn draw(&mut self, ui: &mut egui::Ui, _: &mut World) {
+        *self.1.lock().unwrap() = Some(ui.id());
+    }
+}
+
+#[test]
+fn tab_id_salt() {
+    let first = std::sync::Arc::default();
+    let second = std::sync::Arc::default();
+    let mut app = headless_app(custom_editor::CustomEditorPlugin);
+    let mut spyglass = app.world.resource_mut::<Spyglass>();
+    spyglass.add_tab(Box::new(IdTab("First", std::sync::Arc::clone(&first))));
+    spyglass.add_tab(Box::new(IdTab("Second", std::sync::Arc::clone(&second))));
+    let draw = |app: &mut App, name: &str| {
+        app.world
+            .resource_mut::<Spyglass>()
+            .select_tab_by_name(name);
+        run_frames(app, 1);
+    };
+    draw(&mut app, "First");
+    draw(&mut app, "Second");
+    let id = *first.lock().unwrap();
+    assert!(id.is_some());
+    assert_ne!(id, *second.lock().unwrap());
+
+    // Reordering the tabs keeps their ids.
+    app.world.resource_mut::<Spyglass>().tabs.reverse();
+    draw(&mut app, "First");
+    assert_eq!(*first.lock().unwrap(), id);
+}
@@ -0,0 +1,85 @@
+//! Checks the physics tabs, which record the collisions of the bodies they list. Only built with
+//! the `rapier` or `xpbd` feature.
+#![cfg(any(feature = "rapier", feature = "xpbd"))]
+
+mod common;
+
+#[cfg(feature = "rapier")]
+use aether_spyglass::tabs::physics::rapier::{RapierCollisions, RapierTabPlugin};
+#[cfg(feature = "xpbd")]
+use aether_spyglass::tabs::physics::xpbd::{XpbdCollisions, XpbdTabPlugin};
+use aether_spyglass::SpyglassPlugin;
+use bevy::prelude::*;
+
+use common::{cycle_tabs, headless_app};
+
+#[cfg(feature = "rapier")]
+#[test]
+fn rapier_tab() {
+    use bevy_rapier3d::prelude::*;
+
+    let mut app = headless_app((
+        SpyglassPlugin::default(),
+        RapierPhysicsPlugin::<NoUserData>::default(),
+        RapierTabPlugin,
+    ));
+    app.world.spawn((
+        Name::new("Floor"),
+        RigidBody::Fixed,
+        Collider::cuboid(5.0, 0.5, 5.0),
+        TransformBundle::default(),
+    ));
+    let ball = app
+        .world
+        .spawn((
+            Name::new("Ball"),
+            RigidBody::Dynamic,
+            Collider::ball(0.5),
+            ActiveEvents::COLLISION_EVENTS,
+            TransformBundle::from_transform(Transform::from_xyz(0.0, 0.8, 0.0)),
+        ))
+        .id();
+    cycle_tabs(&mut app);
+
+    // The ball landed on the floor, which the tab lists as an event.
+    let log = app.world.resource::<RapierCollisions>();
+    assert!(log
+        .events
+        .iter()
+        .any(|record| record.started && [record.entities.0, record.entities.1].contains(&ball)));
+}
+
+#[cfg(feature = "xpbd")]
+#[test]
+fn xpbd_tab() {
+    use bevy_xpbd_3d::prelude::*;
+
+    let mut app = headless_app((
+        SpyglassPlugin::default(),
+        PhysicsPlugins::default(),
+        XpbdTabPlugin,
+    ));
+    app.world.spawn((
+        Name::new("Floor"),
+        RigidBody::Static,
+        Collider::cuboid(10.0, 1.0, 10.0),
+        TransformBundle::default(),
+    ));
+    let ball = app
+        .world
+        .spawn((
+            Name::new("Ball"),
+            RigidBody::Dynamic,
+            Collider::ball(0.5),
+            TransformBundle::from_transform(Transform::from_xyz(0.0, 0.8, 0.0)),
+        ))
+        .id();
+    cycle_tabs(&mut app);
+
+    // The ball landed on the floor, which the tab lists as an event.
+    let log = app.world.resource::<XpbdCollisions>();
+    assert!(log
+        .events
+        .iter()
+        .any(|record| record.started && [record.entities.0, record.entities.1].contains(&ball)));
+}
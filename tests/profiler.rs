@@ -0,0 +1,62 @@
+//! Checks that the profiler times systems and schedules. Only built with the `profiler`
+//! feature.
+#![cfg(feature = "profiler")]
+
+use aether_spyglass::profiler::Profiler;
+use aether_spyglass::tabs::profiler::ProfilerTabPlugin;
+use aether_spyglass::{Spyglass, SpyglassPlugin};
+use bevy::log::LogPlugin;
+use bevy::prelude::*;
+use bevy::render::settings::WgpuSettings;
+use bevy::render::RenderPlugin;
+use bevy::winit::WinitPlugin;
+
+fn busy_system() {
+    std::thread::sleep(std::time::Duration::from_millis(2));
+}
+
+#[test]
+fn profiler_times_systems() {
+    let mut app = App::new();
+    // The log plugin would install its own tracing subscriber.
+    app.add_plugins(
+        DefaultPlugins
+            .build()
+            .disable::<WinitPlugin>()
+            .disable::<LogPlugin>()
+            .set(RenderPlugin {
+                render_creation: WgpuSettings {
+                    backends: None,
+                    ..default()
+                }
+                .into(),
+            }),
+    )
+    .add_plugins((SpyglassPlugin::default(), ProfilerTabPlugin))
+    .add_systems(Update, busy_system);
+    app.finish();
+    app.cleanup();
+    for _ in 0..3 {
+        app.update();
+    }
+
+    let profiler = app.world.resource::<Profiler>();
+    assert!(profiler.installed());
+    let busy = profiler
+        .last_frame
+        .systems
+        .iter()
+        .find(|(name, _)| name.ends_with("busy_system"))
+        .map(|(_, time)| *time)
+        .unwrap();
+    assert!(busy >= std::time::Duration::from_millis(2));
+    assert!(profiler
+        .last_frame
+        .schedules
+        .iter()
+        .any(|(name, _)| name == "Update"));
+
+    let mut spyglass = app.world.resource_mut::<Spyglass>();
+    assert!(spyglass.select_tab_by_name("Profiler"));
+    app.update();
+}
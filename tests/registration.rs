@@ -0,0 +1,28 @@
+//! Checks that editors exported with `spyglass_editors!` are collected. Only built with the
+//! `inventory` feature.
+#![cfg(feature = "inventory")]
+
+use aether_spyglass::registration::register_exported_editors;
+use aether_spyglass::tabs::entities::ReprEditors;
+use bevy::prelude::*;
+use bevy::reflect::Typed;
+use bevy_egui::egui;
+
+#[derive(Default, Reflect)]
+struct Health(f32);
+
+fn register(editors: &mut ReprEditors) {
+    editors.insert_typed::<Health>(|ui, health, _, _, _| {
+        ui.add(egui::Slider::new(&mut health.0, 0.0..=100.0));
+    });
+}
+
+aether_spyglass::spyglass_editors!(register);
+
+#[test]
+fn exported_editors_are_registered() {
+    let mut editors = ReprEditors::default();
+    assert!(!editors.has_custom(Health::type_info()));
+    register_exported_editors(&mut editors);
+    assert!(editors.has_custom(Health::type_info()));
+}
@@ -0,0 +1,166 @@
+//! Checks that a remote client inspects and edits a game over the remote protocol, and that the
+//! server turns bad requests into errors.
+
+mod common;
+#[path = "../examples/remote.rs"]
+#[allow(dead_code)]
+mod remote;
+
+use aether_spyglass::formats::{serialize_reflect, SpyglassFormats};
+use aether_spyglass::remote::{
+    handle_request, RemoteRequest, RemoteResponse, SpyglassServer, MAX_MESSAGE_LEN,
+};
+use aether_spyglass::tabs::remote::RemoteClient;
+use aether_spyglass::Spyglass;
+use bevy::prelude::*;
+
+use common::{cycle_tabs, headless_app};
+
+/// Update the server and the client in lockstep until `done` holds for the client.
+fn pump(server: &mut App, client: &mut App, done: impl Fn(&RemoteClient) -> bool) {
+    for _ in 0..200 {
+        server.update();
+        client.update();
+        if done(client.world.resource::<RemoteClient>()) {
+            return;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(5));
+    }
+    panic!("the remote world did not respond");
+}
+
+fn send(client: &mut App, request: RemoteRequest) {
+    client
+        .world
+        .resource_scope(|world, mut remote: Mut<RemoteClient>| {
+            remote
+                .send(world.resource::<SpyglassFormats>(), &request)
+                .unwrap();
+        });
+}
+
+#[test]
+fn remote() {
+    let mut server = App::new();
+    server.add_plugins((
+        MinimalPlugins,
+        remote::RemoteGamePlugin {
+            address: "127.0.0.1:0".to_string(),
+        },
+    ));
+    server.update();
+    let address = server
+        .world
+        .resource::<SpyglassServer>()
+        .local_addr()
+        .unwrap();
+
+    let mut client = headless_app(remote::RemoteClientPlugin);
+    cycle_tabs(&mut client);
+    client
+        .world
+        .resource_mut::<RemoteClient>()
+        .connect(address)
+        .unwrap();
+
+    send(&mut client, RemoteRequest::ListEntities);
+    pump(&mut server, &mut client, |remote| {
+        !remote.entities.is_empty()
+    });
+    let spinner = client
+        .world
+        .resource::<RemoteClient>()
+        .entities
+        .iter()
+        .find(|remote| remote.name.as_deref() == Some("Spinner"))
+        .unwrap()
+        .entity;
+
+    client
+        .world
+        .resource_mut::<RemoteClient>()
+        .select(Some(spinner));
+    send(&mut client, RemoteRequest::GetEntity(spinner));
+    pump(&mut server, &mut client, |remote| {
+        !remote.components.is_empty()
+    });
+
+    // Edit the received value as an editor would; the remote tab sends the change when drawn.
+    let mut remote = client.world.resource_mut::<RemoteClient>();
+    let spin = remote
+        .components
+        .iter_mut()
+        .find(|comp| comp.type_path.ends_with("::Spin"))
+        .and_then(|comp| comp.value.as_mut())
+        .unwrap();
+    *spin
+        .reflect_path_mut("speed")
+        .unwrap()
+        .downcast_mut::<f32>()
+        .unwrap() = 10.0;
+
+    let tab = client
+        .world
+        .resource::<Spyglass>()
+        .tabs
+        .iter()
+        .position(|tab| tab.name() == "Remote");
+    client.world.resource_mut::<Spyglass>().selected = tab;
+    for _ in 0..10 {
+        client.update();
+        server.update();
+        std::thread::sleep(std::time::Duration::from_millis(5));
+    }
+
+    let mut q = server.world.query::<&remote::Spin>();
+    assert_eq!(q.single(&server.world).speed, 10.0);
+}
+
+#[test]
+fn remote_rejects_bad_requests() {
+    let mut server = App::new();
+    server.add_plugins((
+        MinimalPlugins,
+        remote::RemoteGamePlugin {
+            address: "127.0.0.1:0".to_string(),
+        },
+    ));
+    server.update();
+    let spinner = server
+        .world
+        .query_filtered::<Entity, With<remote::Spin>>()
+        .single(&server.world);
+
+    // A value of another type is refused instead of panicking.
+    let format = server.world.resource::<SpyglassFormats>().current();
+    let registry = server.world.resource::<AppTypeRegistry>().read();
+    let name = serialize_reflect(format, &Name::new("Spinner"), &registry).unwrap();
+    drop(registry);
+    let request = RemoteRequest::SetComponent {
+        entity: spinner,
+        component: std::any::type_name::<remote::Spin>().to_string(),
+        value: String::from_utf8(name).unwrap(),
+    };
+    let response = handle_request(&mut server.world, request);
+    assert!(matches!(response, RemoteResponse::Error(_)), "{response:?}");
+
+    // A frame longer than the limit closes the connection.
+    let address = server
+        .world
+        .resource::<SpyglassServer>()
+        .local_addr()
+        .unwrap();
+    let mut stream = std::net::TcpStream::connect(address).unwrap();
+    server.update();
+    assert_eq!(server.world.resource::<SpyglassServer>().connections(), 1);
+    let len = MAX_MESSAGE_LEN as u32 + 1;
+    std::io::Write::write_all(&mut stream, &len.to_be_bytes()).unwrap();
+    for _ in 0..50 {
+        server.update();
+        if server.world.resource::<SpyglassServer>().connections() == 0 {
+            return;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(5));
+    }
+    panic!("the connection was not closed");
+}
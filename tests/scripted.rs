@@ -0,0 +1,48 @@
+//! Checks that scripted tabs are loaded and drawn. Only built with the `rhai` feature.
+#![cfg(feature = "rhai")]
+
+mod common;
+
+use aether_spyglass::tabs::scripted::{ScriptedTab, ScriptedTabsPlugin};
+use aether_spyglass::{Spyglass, Tab};
+use bevy::prelude::*;
+
+use common::{custom_editor, headless_app, run_frames};
+
+#[test]
+fn scripted_tab() {
+    let source = r#"
+        // Tuning for the player
+        const NAME = "Player tuning";
+        label("Movement");
+        for player in query("Transform") {
+            label(`x: ${get(player, "Transform.translation.x")}`);
+            set(player, "Transform.translation.x", get(player, "Transform.translation.x") + 1.0);
+        }
+        separator();
+        if button("Move") {
+            set("Player", "Transform.translation.y", 3);
+        }
+    "#;
+    let tab = ScriptedTab::parse("player", source);
+    assert!(tab.is_valid());
+    assert_eq!(tab.name(), "Player tuning");
+    assert_eq!(ScriptedTab::parse("player", "label(1").name(), "player");
+    assert!(!ScriptedTab::parse("broken", "label(1").is_valid());
+
+    let mut app = headless_app((custom_editor::CustomEditorPlugin, ScriptedTabsPlugin));
+    let player = app
+        .world
+        .spawn((Name::new("Player"), Transform::default()))
+        .id();
+    let mut spyglass = app.world.resource_mut::<Spyglass>();
+    spyglass.tabs.push(Box::new(tab));
+    spyglass.selected = Some(spyglass.tabs.len() - 1);
+    run_frames(&mut app, 3);
+
+    // The script ran once per frame, and its world API reached the player.
+    assert_eq!(
+        app.world.get::<Transform>(player).unwrap().translation.x,
+        3.0
+    );
+}
@@ -0,0 +1,367 @@
+//! Checks the builtin tabs other than the entities tab and the ones behind features.
+
+mod common;
+
+use std::any::TypeId;
+
+use aether_spyglass::capture::Capture;
+use aether_spyglass::export::Exports;
+use aether_spyglass::formats::CsvColumn;
+use aether_spyglass::tabs::assets::{failure_reason, spawn_scene, AssetsTabPlugin, SceneAsset};
+use aether_spyglass::tabs::camera::{frame_entity, CameraTabPlugin, DebugCamera};
+use aether_spyglass::tabs::console::{ConsoleCommands, ConsoleTabPlugin};
+use aether_spyglass::tabs::query::{export_csv, ComponentQuery};
+use aether_spyglass::tabs::spikes::{FrameSpikes, SpikesTabPlugin};
+use aether_spyglass::tabs::watches::{
+    Breakpoint, Comparison, WatchExpression, Watches, WatchesTabPlugin,
+};
+use aether_spyglass::tabs::world_stats::component_memory;
+use aether_spyglass::telemetry::{Telemetry, TelemetryPlugin};
+use aether_spyglass::widgets::component_types;
+use aether_spyglass::Spyglass;
+use bevy::prelude::*;
+
+use common::{custom_editor, cycle_tabs, headless_app, run_frames};
+
+#[test]
+fn watch_field() {
+    let mut app = headless_app((
+        custom_editor::CustomEditorPlugin,
+        WatchesTabPlugin,
+        TelemetryPlugin,
+    ));
+    app.world.resource_mut::<Telemetry>().interval = std::time::Duration::ZERO;
+    run_frames(&mut app, 2);
+
+    let mut q = app
+        .world
+        .query_filtered::<Entity, With<custom_editor::Tint>>();
+    let tinted = q.single(&app.world);
+    app.world.resource_mut::<Watches>().watch(
+        tinted,
+        std::any::type_name::<custom_editor::Tint>(),
+        ".strength",
+    );
+    let expression = WatchExpression::parse("Tinted/Tint.strength").unwrap();
+    let missing = WatchExpression::parse("Nobody/Tint.strength").unwrap();
+    app.world
+        .resource_mut::<Watches>()
+        .expressions
+        .extend([expression, missing]);
+    cycle_tabs(&mut app);
+
+    let watches = app.world.resource::<Watches>();
+    let samples = &watches.watches[0].samples;
+    assert!(!samples.is_empty());
+    assert!(samples.iter().all(|&(_, value)| value == 0.5));
+    assert_eq!(watches.expressions[0].value.as_deref(), Ok("0.5"));
+    assert!(watches.expressions[1].value.is_err());
+    assert!(WatchExpression::parse("Res<Score.points").is_err());
+
+    // Screenshots are annotated with the latest values.
+    let capture = app.world.resource::<Capture>();
+    let annotations = capture
+        .annotators
+        .iter()
+        .filter_map(|annotator| annotator(&app.world))
+        .collect::<Vec<_>>();
+    let label = watches.watches[0].label();
+    assert_eq!(
+        annotations,
+        [format!("{label}: 0.5\nTinted/Tint.strength: 0.5")]
+    );
+
+    // Telemetry samples them too.
+    let telemetry = app.world.resource::<Telemetry>();
+    let sample = telemetry.log.samples.last().unwrap();
+    assert_eq!(sample.get(&format!("watch:{label}")), Some(0.5));
+    assert_eq!(sample.get("watch:Tinted/Tint.strength"), Some(0.5));
+    assert_eq!(sample.get("watch:Nobody/Tint.strength"), None);
+
+    let spyglass = app.world.resource::<Spyglass>();
+    let tab = spyglass.tabs.iter().find(|tab| tab.name() == "Watches");
+    assert_eq!(tab.unwrap().badge(&app.world).as_deref(), Some("1"));
+}
+
+#[test]
+fn breakpoint_pauses_time() {
+    let mut app = headless_app((custom_editor::CustomEditorPlugin, WatchesTabPlugin));
+    run_frames(&mut app, 2);
+
+    let never = Breakpoint::parse("Tint.strength > 1").unwrap();
+    app.world.resource_mut::<Watches>().breakpoints.push(never);
+    run_frames(&mut app, 2);
+    assert!(!app.world.resource::<Time<Virtual>>().is_paused());
+
+    let breakpoint = Breakpoint::parse("Tint.strength <= 0.5").unwrap();
+    app.world
+        .resource_mut::<Watches>()
+        .breakpoints
+        .push(breakpoint);
+    run_frames(&mut app, 2);
+    assert!(app.world.resource::<Time<Virtual>>().is_paused());
+
+    // The breakpoint only triggers again once its condition was false.
+    app.world.resource_mut::<Time<Virtual>>().unpause();
+    run_frames(&mut app, 2);
+    assert!(!app.world.resource::<Time<Virtual>>().is_paused());
+    assert_eq!(app.world.resource::<Watches>().breakpoints[1].hits, 1);
+    assert!(Breakpoint::parse("Tint.strength").is_err());
+}
+
+#[derive(Default, Reflect, Resource)]
+#[reflect(Resource)]
+struct Score {
+    points: u32,
+}
+
+#[test]
+fn resource_breakpoint() {
+    let mut app = headless_app((custom_editor::CustomEditorPlugin, WatchesTabPlugin));
+    app.init_resource::<Score>().register_type::<Score>();
+
+    // The brackets of `Res<…>` are not taken for the comparison.
+    let breakpoint = Breakpoint::parse("Res<Score>.points >= 10").unwrap();
+    assert_eq!(breakpoint.comparison, Comparison::GreaterEqual);
+    assert_eq!(breakpoint.value, 10.0);
+    assert!(Breakpoint::parse("Handle<Image>.id < 3").is_ok());
+    app.world
+        .resource_mut::<Watches>()
+        .breakpoints
+        .push(breakpoint);
+    run_frames(&mut app, 2);
+    assert!(!app.world.resource::<Time<Virtual>>().is_paused());
+
+    app.world.resource_mut::<Score>().points = 10;
+    run_frames(&mut app, 2);
+    assert!(app.world.resource::<Time<Virtual>>().is_paused());
+}
+
+#[test]
+fn frame_selected_entity() {
+    let mut app = headless_app((custom_editor::CustomEditorPlugin, CameraTabPlugin));
+    let target = app
+        .world
+        .spawn(TransformBundle::from_transform(Transform::from_xyz(
+            4.0, 1.0, -2.0,
+        )))
+        .id();
+    let camera = app
+        .world
+        .spawn((TransformBundle::default(), DebugCamera))
+        .id();
+    run_frames(&mut app, 2);
+
+    frame_entity(&mut app.world, target).unwrap();
+    let transform = *app.world.get::<Transform>(camera).unwrap();
+    let to_target = Vec3::new(4.0, 1.0, -2.0) - transform.translation;
+    assert!(to_target.length() > 0.5);
+    assert!(transform.forward().dot(to_target.normalize()) > 0.99);
+
+    app.world.entity_mut(camera).remove::<DebugCamera>();
+    assert!(frame_entity(&mut app.world, target).is_err());
+}
+
+#[test]
+fn console() {
+    let mut app = headless_app((custom_editor::CustomEditorPlugin, ConsoleTabPlugin));
+    let mut run = |line: &str| ConsoleCommands::execute(&mut app.world, line);
+
+    run(r#"spawn "Big Rock""#).unwrap();
+    run(r#"despawn "Big Rock""#).unwrap();
+    assert!(run(r#"despawn "Big Rock""#).is_err());
+
+    let player = app
+        .world
+        .spawn((Name::new("Player"), Transform::default()))
+        .id();
+    let mut run = |line: &str| ConsoleCommands::execute(&mut app.world, line);
+    run("set Player Transform.translation.x 5").unwrap();
+    assert_eq!(run("get Player Transform.translation.x").unwrap(), "5.0");
+    assert!(run("set Player Transform.translation.x five").is_err());
+    assert!(run("frobnicate").is_err());
+    assert_eq!(
+        app.world.get::<Transform>(player).unwrap().translation.x,
+        5.0
+    );
+}
+
+#[test]
+fn query_csv_export() {
+    let mut app = headless_app(custom_editor::CustomEditorPlugin);
+    let entity = app
+        .world
+        .spawn((Name::new("Marker"), Transform::from_xyz(0.0, 4.0, 0.0)))
+        .id();
+    let path = std::env::temp_dir().join(format!("spyglass_query_{}.csv", std::process::id()));
+    let columns = [CsvColumn {
+        component: std::any::type_name::<Transform>().to_string(),
+        path: "translation.y".to_string(),
+    }];
+    export_csv(&mut app.world, &[entity], &columns, path.clone());
+    for _ in 0..100 {
+        if app.world.resource::<Exports>().running().count() == 0 {
+            break;
+        }
+        run_frames(&mut app, 1);
+    }
+
+    let csv = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(
+        csv.lines().nth(1),
+        Some(format!("{entity:?},Marker,4.0").as_str())
+    );
+}
+
+#[derive(Component, Default, Reflect)]
+#[reflect(Component)]
+struct Unspawned;
+
+#[test]
+fn component_query() {
+    let mut app = headless_app(custom_editor::CustomEditorPlugin);
+    run_frames(&mut app, 2);
+    let tint = TypeId::of::<custom_editor::Tint>();
+    let name = TypeId::of::<Name>();
+    let tinted = app
+        .world
+        .query_filtered::<Entity, With<custom_editor::Tint>>()
+        .single(&app.world);
+    let anonymous = app.world.spawn(custom_editor::Tint::default()).id();
+
+    let mut query = ComponentQuery {
+        with: vec![tint],
+        ..default()
+    };
+    let since_start = bevy::ecs::component::Tick::new(0);
+    assert_eq!(query.run(&app.world, since_start), [tinted, anonymous]);
+    assert_eq!(query.to_rust(&app.world), "Query<Entity, With<Tint>>");
+
+    query.without.push(name);
+    assert_eq!(query.run(&app.world, since_start), [anonymous]);
+    assert_eq!(
+        query.to_rust(&app.world),
+        "Query<Entity, (With<Tint>, Without<Name>)>"
+    );
+
+    let query = ComponentQuery {
+        changed: vec![tint],
+        ..default()
+    };
+    let now = app.world.read_change_tick();
+    app.world.increment_change_tick();
+    assert!(query.run(&app.world, now).is_empty());
+    app.world
+        .get_mut::<custom_editor::Tint>(tinted)
+        .unwrap()
+        .set_changed();
+    assert_eq!(query.run(&app.world, now), [tinted]);
+
+    // Registered components no entity had yet can be picked and filtered on.
+    app.register_type::<Unspawned>();
+    let unused = TypeId::of::<Unspawned>();
+    assert!(app.world.components().get_id(unused).is_none());
+    let types = component_types(&app.world);
+    assert!(types
+        .iter()
+        .any(|(id, short, _)| *id == unused && short == "Unspawned"));
+    let mut query = ComponentQuery {
+        with: vec![tint],
+        without: vec![unused],
+        ..default()
+    };
+    assert_eq!(query.run(&app.world, since_start), [tinted, anonymous]);
+    query.with.push(unused);
+    assert!(query.run(&app.world, since_start).is_empty());
+}
+
+#[test]
+fn frame_spikes() {
+    let mut app = headless_app((custom_editor::CustomEditorPlugin, SpikesTabPlugin));
+    run_frames(&mut app, 2);
+    assert!(app.world.resource::<FrameSpikes>().spikes.is_empty());
+
+    let mut spikes = app.world.resource_mut::<FrameSpikes>();
+    spikes.threshold = Some(std::time::Duration::ZERO);
+    spikes.capacity = 3;
+    run_frames(&mut app, 5);
+    let entities = app.world.entities().len();
+    let spikes = &app.world.resource::<FrameSpikes>().spikes;
+    assert_eq!(spikes.len(), 3);
+    assert!(spikes
+        .iter()
+        .all(|spike| spike.frame_time > std::time::Duration::ZERO));
+    assert_eq!(spikes.back().unwrap().entities, entities);
+}
+
+#[test]
+fn component_memory_estimate() {
+    let mut app = headless_app(custom_editor::CustomEditorPlugin);
+    run_frames(&mut app, 2);
+    let tint = |app: &App| {
+        component_memory(&app.world)
+            .into_iter()
+            .find(|memory| memory.name.ends_with("::Tint"))
+            .unwrap()
+    };
+    let before = tint(&app);
+    assert_eq!(before.size, std::mem::size_of::<custom_editor::Tint>());
+
+    app.world
+        .spawn_batch((0..10).map(|_| custom_editor::Tint::default()));
+    let after = tint(&app);
+    assert_eq!(after.count, before.count + 10);
+    assert_eq!(after.bytes(), after.size * after.count);
+}
+
+#[test]
+fn spawn_scene_instance() {
+    let mut app = headless_app(custom_editor::CustomEditorPlugin);
+    let mut scene_world = World::new();
+    scene_world.spawn(custom_editor::Tint::default());
+    scene_world.spawn(custom_editor::Tint::default());
+    let scene = app
+        .world
+        .resource_mut::<Assets<Scene>>()
+        .add(Scene::new(scene_world));
+    let tints = |app: &mut App| {
+        app.world
+            .query::<&custom_editor::Tint>()
+            .iter(&app.world)
+            .count()
+    };
+    run_frames(&mut app, 1);
+    let before = tints(&mut app);
+
+    let transform = Transform::from_xyz(1.0, 2.0, 3.0);
+    let root = spawn_scene(&mut app.world, SceneAsset::Scene(scene.id()), transform);
+    run_frames(&mut app, 2);
+    assert_eq!(tints(&mut app), before + 2);
+    assert_eq!(*app.world.get::<Transform>(root).unwrap(), transform);
+    assert_eq!(app.world.get::<Children>(root).map(|c| c.len()), Some(2));
+}
+
+#[test]
+fn asset_failure_reason() {
+    let mut app = headless_app((custom_editor::CustomEditorPlugin, AssetsTabPlugin));
+    let server = app.world.resource::<AssetServer>().clone();
+    let missing: Handle<DynamicScene> = server.load("missing.scn.ron");
+    app.world.spawn(missing.clone());
+    app.world
+        .resource_mut::<Spyglass>()
+        .select_tab_by_name("Assets");
+    for _ in 0..100 {
+        run_frames(&mut app, 1);
+        if server.load_state(&missing) == bevy::asset::LoadState::Failed {
+            break;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    }
+    assert_eq!(server.load_state(&missing), bevy::asset::LoadState::Failed);
+    run_frames(&mut app, 1);
+
+    let path = server.get_path(&missing).unwrap();
+    let reason = failure_reason(&server, &path);
+    assert!(reason.contains("not found"), "{reason}");
+}
@@ -0,0 +1,39 @@
+//! Checks the UI tab. Only built with the `ui` feature.
+#![cfg(feature = "ui")]
+
+mod common;
+
+use aether_spyglass::tabs::ui::UiTabPlugin;
+use aether_spyglass::SpyglassPlugin;
+use bevy::prelude::*;
+
+use common::{cycle_tabs, headless_app};
+
+#[test]
+fn ui_tab() {
+    let mut app = headless_app((SpyglassPlugin::default(), UiTabPlugin));
+    let root = NodeBundle {
+        style: Style {
+            width: Val::Px(200.0),
+            height: Val::Px(100.0),
+            ..default()
+        },
+        ..default()
+    };
+    let child = NodeBundle {
+        style: Style {
+            width: Val::Percent(50.0),
+            ..default()
+        },
+        ..default()
+    };
+    let mut child_id = None;
+    app.world
+        .spawn((Name::new("Panel"), root))
+        .with_children(|parent| child_id = Some(parent.spawn(child).id()));
+    cycle_tabs(&mut app);
+
+    // The layout the tab shows was computed.
+    let child = app.world.get::<Node>(child_id.unwrap()).unwrap();
+    assert_eq!(child.size(), Vec2::new(100.0, 100.0));
+}